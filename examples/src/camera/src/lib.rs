@@ -1,5 +1,5 @@
 #![allow(unused_imports)]
-use pax_lang::api::{ArgsClick, EasingCurve, RuntimeContext, Property};
+use pax_lang::api::{ArgsClick, EasingCurve, Event, RuntimeContext, Property};
 use pax_lang::Pax;
 use pax_std::primitives::{Rectangle, Group, Frame, Text, Ellipse};
 
@@ -27,7 +27,7 @@ impl Camera {
         self.pan_y.set(0.0);
     }
 
-    pub fn handle_click(&mut self, _: RuntimeContext, args: ArgsClick) {
+    pub fn handle_click(&mut self, _: RuntimeContext, args: Event<ArgsClick>) {
         let delta_pan = (args.mouse.x - self.pan_x.get(), args.mouse.y - self.pan_y.get());
         self.pan_x.ease_to(self.pan_x.get() + delta_pan.0, 200, EasingCurve::Linear);
         self.pan_y.ease_to(self.pan_y.get() + delta_pan.1, 200, EasingCurve::Linear);
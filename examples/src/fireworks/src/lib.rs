@@ -1,4 +1,4 @@
-use pax_lang::api::{ArgsClick, ArgsWheel, EasingCurve, RuntimeContext};
+use pax_lang::api::{ArgsClick, ArgsWheel, EasingCurve, Event, RuntimeContext};
 use pax_lang::*;
 use pax_std::primitives::{Ellipse, Frame, Group, Path, Rectangle, Text};
 
@@ -14,7 +14,7 @@ const ROTATION_COEFFICIENT: f64 = 0.00010;
 
 impl Fireworks {
 
-    pub fn handle_scroll(&mut self, ctx: RuntimeContext, args: ArgsWheel) {
+    pub fn handle_scroll(&mut self, ctx: RuntimeContext, args: Event<ArgsWheel>) {
         let old_t = self.rotation.get();
         let new_t = old_t - args.delta_y * ROTATION_COEFFICIENT;
         self.rotation.set(f64::max(0.0,new_t));
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 
+use pax_core::hot_reload::HotReloadCartridge;
 use pax_core::pax_properties_coproduct::TypesCoproduct;
 use pax_core::{ComponentInstance, ExpressionContext, InstanceRegistry, InstantiationArgs};
 use piet_common::RenderContext;
@@ -25,3 +26,15 @@ pub fn instantiate_main_component<R: 'static + RenderContext>(
 ) -> Rc<RefCell<ComponentInstance<R>>> {
     unreachable!("{}", PLACEHOLDER_ERROR)
 }
+
+pub fn instantiate_component_by_type_id<R: 'static + RenderContext>(
+    _instance_registry: Rc<RefCell<InstanceRegistry<R>>>,
+    _type_id: &str,
+) -> Option<Rc<RefCell<ComponentInstance<R>>>> {
+    unreachable!("{}", PLACEHOLDER_ERROR)
+}
+
+pub fn get_hot_reload_cartridge<R: 'static + RenderContext>(
+) -> Option<Rc<dyn HotReloadCartridge<R>>> {
+    unreachable!("{}", PLACEHOLDER_ERROR)
+}
@@ -95,6 +95,8 @@ public class TextElement {
     public var textStyle: TextStyle
     public var depth: UInt?
     public var style_link: TextStyle?
+    public var opacity: Float = 1.0
+    public var blendMode: BlendMode = .normal
     
     public init(id_chain: [UInt64], clipping_ids: [[UInt64]], content: String, transform: [Float], size_x: Float, size_y: Float, textStyle: TextStyle, depth: UInt?, style_link: TextStyle?) {
         self.id_chain = id_chain
@@ -131,6 +133,12 @@ public class TextElement {
         if let depth = patch.depth {
             self.depth = depth
         }
+        if let opacity = patch.opacity {
+            self.opacity = opacity
+        }
+        if let blendMode = patch.blend_mode {
+            self.blendMode = toBlendMode(cssName: blendMode)
+        }
         
         // Apply new TextStyle
         if let styleBuffer = patch.style {
@@ -194,6 +202,44 @@ public func toAlignment(horizontalAlignment: TextAlignHorizontal, verticalAlignm
     return Alignment(horizontal: horizontal, vertical: vertical)
 }
 
+/// Maps the CSS name of a blend mode, as sent in `blend_mode` patch fields, to SwiftUI's `BlendMode`
+public func toBlendMode(cssName: String) -> BlendMode {
+    switch cssName {
+    case "multiply":
+        return .multiply
+    case "screen":
+        return .screen
+    case "overlay":
+        return .overlay
+    case "darken":
+        return .darken
+    case "lighten":
+        return .lighten
+    case "color-dodge":
+        return .colorDodge
+    case "color-burn":
+        return .colorBurn
+    case "hard-light":
+        return .hardLight
+    case "soft-light":
+        return .softLight
+    case "difference":
+        return .difference
+    case "exclusion":
+        return .exclusion
+    case "hue":
+        return .hue
+    case "saturation":
+        return .saturation
+    case "color":
+        return .color
+    case "luminosity":
+        return .luminosity
+    default:
+        return .normal
+    }
+}
+
 
 /// A patch representing an image load request from a given id_chain
 public class ImageLoadPatch {
@@ -279,6 +325,8 @@ public class TextUpdatePatch {
     public var depth: UInt?
     public var style: TextStyleMessage?
     public var style_link: TextStyleMessage?
+    public var opacity: Float?
+    public var blend_mode: String?
 
     public init(fb: FlxbReference) {
         self.id_chain = fb["id_chain"]!.asVector!.makeIterator().map({ fb in
@@ -291,6 +339,8 @@ public class TextUpdatePatch {
         self.size_x = fb["size_x"]?.asFloat
         self.size_y = fb["size_y"]?.asFloat
         self.depth = fb["depth"]?.asUInt
+        self.opacity = fb["opacity"]?.asFloat
+        self.blend_mode = fb["blend_mode"]?.asString
         
         if let styleBuffer = fb["style"], !styleBuffer.isNull {
             self.style = TextStyleMessage(styleBuffer)
@@ -654,6 +704,8 @@ public class FrameElement {
     public var transform: [Float]
     public var size_x: Float
     public var size_y: Float
+    public var opacity: Float = 1.0
+    public var blendMode: BlendMode = .normal
     
     public init(id_chain: [UInt64], transform: [Float], size_x: Float, size_y: Float) {
         self.id_chain = id_chain
@@ -678,6 +730,12 @@ public class FrameElement {
         if patch.size_y != nil {
             self.size_y = patch.size_y!
         }
+        if patch.opacity != nil {
+            self.opacity = patch.opacity!
+        }
+        if patch.blend_mode != nil {
+            self.blendMode = toBlendMode(cssName: patch.blend_mode!)
+        }
     }
 }
 
@@ -689,6 +747,8 @@ public class FrameUpdatePatch {
     public var transform: [Float]?
     public var size_x: Float?
     public var size_y: Float?
+    public var opacity: Float?
+    public var blend_mode: String?
     
     public init(fb: FlxbReference) {
         self.id_chain = fb["id_chain"]!.asVector!.makeIterator().map({ fb in
@@ -699,6 +759,8 @@ public class FrameUpdatePatch {
         })
         self.size_x = fb["size_x"]?.asFloat
         self.size_y = fb["size_y"]?.asFloat
+        self.opacity = fb["opacity"]?.asFloat
+        self.blend_mode = fb["blend_mode"]?.asString
     }
 }
 
@@ -10,7 +10,9 @@ use std::rc::Rc;
 use std::mem::{transmute, ManuallyDrop};
 use std::os::raw::c_char;
 
-use core_graphics::context::CGContext;
+use core_graphics::base::CGFloat;
+use core_graphics::context::{CGBlendMode, CGContext};
+use foreign_types::ForeignType;
 use piet_coregraphics::CoreGraphicsContext;
 
 use flexbuffers;
@@ -24,7 +26,34 @@ use pax_core::{InstanceRegistry, PaxEngine};
 //Note that any types exposed by pax_message must ALSO be added to `PaxCartridge.h`
 //in order to be visible to Swift
 pub use pax_message::*;
-use pax_runtime_api::{ArgsClick, ArgsScroll, ModifierKey, MouseButton, MouseEventArgs};
+use pax_runtime_api::{ArgsClick, ArgsScroll, BlendMode, ModifierKey, MouseButton, MouseEventArgs};
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    //not (yet) wrapped by the `core-graphics` crate
+    fn CGContextSetAlpha(c: core_graphics::sys::CGContextRef, alpha: CGFloat);
+}
+
+fn to_cg_blend_mode(blend_mode: BlendMode) -> CGBlendMode {
+    match blend_mode {
+        BlendMode::Normal => CGBlendMode::Normal,
+        BlendMode::Multiply => CGBlendMode::Multiply,
+        BlendMode::Screen => CGBlendMode::Screen,
+        BlendMode::Overlay => CGBlendMode::Overlay,
+        BlendMode::Darken => CGBlendMode::Darken,
+        BlendMode::Lighten => CGBlendMode::Lighten,
+        BlendMode::ColorDodge => CGBlendMode::ColorDodge,
+        BlendMode::ColorBurn => CGBlendMode::ColorBurn,
+        BlendMode::HardLight => CGBlendMode::HardLight,
+        BlendMode::SoftLight => CGBlendMode::SoftLight,
+        BlendMode::Difference => CGBlendMode::Difference,
+        BlendMode::Exclusion => CGBlendMode::Exclusion,
+        BlendMode::Hue => CGBlendMode::Hue,
+        BlendMode::Saturation => CGBlendMode::Saturation,
+        BlendMode::Color => CGBlendMode::Color,
+        BlendMode::Luminosity => CGBlendMode::Luminosity,
+    }
+}
 
 /// Container data structure for PaxEngine, aggregated to support passing across C bridge
 #[repr(C)] //Exposed to Swift via PaxCartridge.h
@@ -119,7 +148,7 @@ pub extern "C" fn pax_interrupt(
             };
         }
         NativeInterrupt::Scroll(args) => {
-            let prospective_hit = engine.get_focused_element();
+            let prospective_hit = engine.get_topmost_element_beneath_viewport_center();
             match prospective_hit {
                 Some(topmost_node) => {
                     let args_scroll = ArgsScroll {
@@ -167,7 +196,18 @@ pub extern "C" fn pax_tick(
     let will_cast_cgContext = cgContext as *mut CGContext;
     let ctx = unsafe { &mut *will_cast_cgContext };
     let render_context = CoreGraphicsContext::new_y_up(ctx, height as f64, None);
+    //alpha and blend mode are part of the CGContext's graphics state, so the engine's
+    //save / restore scopes them to each composited subtree.  Only valid for this tick's CGContext.
+    (*engine).set_compositor(Box::new(
+        move |_canvas_id: &str, opacity: f64, blend_mode: BlendMode| {
+            let ctx = unsafe { &*will_cast_cgContext };
+            unsafe { CGContextSetAlpha(ctx.as_ptr(), opacity) };
+            ctx.set_blend_mode(to_cg_blend_mode(blend_mode));
+        },
+    ));
     (*engine).set_viewport_size((width as f64, height as f64));
+    //Each `draw` receives a fresh CGContext, so the previous frame can't be retained across idle ticks
+    (*engine).invalidate();
 
     let mut render_contexts = HashMap::new();
     render_contexts.insert(format!("{}", 0), render_context);
@@ -0,0 +1,261 @@
+//! Canvas layers, as drawn by `PaxChassisHeadless::tick`.  Where piet draws with cairo, each layer is drawn
+//! through a cairo context of its own, which `LayerCompositor` redirects into an offscreen group for each
+//! subtree that sets `opacity` or `blend_mode`, then paints that group back onto the layer.  Elsewhere, layers
+//! are piet bitmap targets, and `opacity` and `blend_mode` are not applied.
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "openbsd",
+    target_os = "freebsd",
+    target_os = "netbsd"
+))]
+pub(crate) use cairo_layers::{draw_layers, LayerCompositor};
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "openbsd",
+    target_os = "freebsd",
+    target_os = "netbsd"
+)))]
+pub(crate) use bitmap_layers::{draw_layers, LayerCompositor};
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "openbsd",
+    target_os = "freebsd",
+    target_os = "netbsd"
+))]
+mod cairo_layers {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use cairo::{Context, Format, ImageSurface, Operator};
+    use piet_common::{CairoRenderContext, ImageBuf, ImageFormat, Piet, RenderContext};
+
+    use pax_core::Compositor;
+    use pax_runtime_api::{BlendMode, ZIndex};
+
+    /// Composites the subtrees drawn to the layers of `draw_layers` by drawing each into a group, then painting
+    /// that group onto its layer.  Groups nest, so each is painted with its own share of the accumulated opacity,
+    /// and with its blend mode only where that differs from the enclosing group's, which applies it already.
+    #[derive(Clone, Default)]
+    pub(crate) struct LayerCompositor {
+        /// The cairo context of each layer being drawn, by canvas id
+        contexts: Rc<RefCell<HashMap<String, Context>>>,
+        /// The accumulated opacity and blend mode of the groups open on each layer, innermost last
+        groups: Rc<RefCell<HashMap<String, Vec<(f64, BlendMode)>>>>,
+    }
+
+    impl Compositor for LayerCompositor {
+        fn begin(&self, canvas_id: &str, opacity: f64, blend_mode: BlendMode) {
+            if let Some(context) = self.contexts.borrow().get(canvas_id) {
+                context.push_group();
+                self.groups
+                    .borrow_mut()
+                    .entry(canvas_id.to_string())
+                    .or_default()
+                    .push((opacity, blend_mode));
+            }
+        }
+
+        fn end(&self, canvas_id: &str) {
+            let contexts = self.contexts.borrow();
+            let mut groups = self.groups.borrow_mut();
+            let groups = groups.entry(canvas_id.to_string()).or_default();
+            if let (Some(context), Some((opacity, blend_mode))) =
+                (contexts.get(canvas_id), groups.pop())
+            {
+                let (enclosing_opacity, enclosing_blend_mode) =
+                    groups.last().copied().unwrap_or((1.0, BlendMode::Normal));
+                let own_opacity = if enclosing_opacity > 0.0 {
+                    opacity / enclosing_opacity
+                } else {
+                    0.0
+                };
+                let operator = if blend_mode == enclosing_blend_mode {
+                    Operator::Over
+                } else {
+                    to_cairo_operator(blend_mode)
+                };
+                let painted = context.pop_group_to_source().and_then(|_| {
+                    context.set_operator(operator);
+                    context.paint_with_alpha(own_opacity)
+                });
+                if let Err(err) = painted {
+                    pax_runtime_api::log(&format!(
+                        "Failed to composite layer {}: {}",
+                        canvas_id, err
+                    ));
+                }
+            }
+        }
+    }
+
+    fn to_cairo_operator(blend_mode: BlendMode) -> Operator {
+        match blend_mode {
+            BlendMode::Normal => Operator::Over,
+            BlendMode::Multiply => Operator::Multiply,
+            BlendMode::Screen => Operator::Screen,
+            BlendMode::Overlay => Operator::Overlay,
+            BlendMode::Darken => Operator::Darken,
+            BlendMode::Lighten => Operator::Lighten,
+            BlendMode::ColorDodge => Operator::ColorDodge,
+            BlendMode::ColorBurn => Operator::ColorBurn,
+            BlendMode::HardLight => Operator::HardLight,
+            BlendMode::SoftLight => Operator::SoftLight,
+            BlendMode::Difference => Operator::Difference,
+            BlendMode::Exclusion => Operator::Exclusion,
+            BlendMode::Hue => Operator::HslHue,
+            BlendMode::Saturation => Operator::HslSaturation,
+            BlendMode::Color => Operator::HslColor,
+            BlendMode::Luminosity => Operator::HslLuminosity,
+        }
+    }
+
+    fn backend_error<E: std::error::Error + 'static>(err: E) -> piet::Error {
+        piet::Error::BackendError(Box::new(err))
+    }
+
+    /// Draws `layer_count` layers of `pixel_size` pixels with `draw`, which receives their drawing contexts by
+    /// canvas id, then reads each layer back as premultiplied RGBA
+    pub(crate) fn draw_layers<T>(
+        compositor: &LayerCompositor,
+        layer_count: usize,
+        pixel_size: (usize, usize),
+        pix_scale: f64,
+        draw: impl FnOnce(&mut HashMap<String, Piet<'static>>) -> T,
+    ) -> Result<(T, Vec<ImageBuf>), piet::Error> {
+        let (pixel_width, pixel_height) = pixel_size;
+        let mut surfaces = Vec::with_capacity(layer_count);
+        let mut contexts = HashMap::new();
+        for i in 0..layer_count {
+            let surface =
+                ImageSurface::create(Format::ARgb32, pixel_width as i32, pixel_height as i32)
+                    .map_err(backend_error)?;
+            let context = Context::new(&surface).map_err(backend_error)?;
+            context.scale(pix_scale, pix_scale);
+            contexts.insert(ZIndex::generate_location_id(None, i as u32), context);
+            surfaces.push(surface);
+        }
+
+        *compositor.contexts.borrow_mut() = contexts.clone();
+        let mut render_contexts: HashMap<String, Piet<'static>> = HashMap::new();
+        for (canvas_id, context) in contexts.iter() {
+            //Safety: the engine only borrows render contexts for the duration of `draw`, and every
+            //context is dropped below, before `contexts`.
+            let render_context: Piet<'static> =
+                unsafe { std::mem::transmute(CairoRenderContext::new(context)) };
+            render_contexts.insert(canvas_id.clone(), render_context);
+        }
+        let drawn = draw(&mut render_contexts);
+        compositor.contexts.borrow_mut().clear();
+        compositor.groups.borrow_mut().clear();
+        for (_, mut render_context) in render_contexts.drain() {
+            render_context.finish()?;
+        }
+        drop(contexts);
+
+        let layers = surfaces
+            .iter()
+            .map(to_image_buf)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((drawn, layers))
+    }
+
+    /// Copies `surface`, whose pixels are native-endian premultiplied ARGB, into premultiplied RGBA
+    fn to_image_buf(surface: &ImageSurface) -> Result<ImageBuf, piet::Error> {
+        let stride = surface.stride() as usize;
+        let width = surface.width() as usize;
+        let height = surface.height() as usize;
+        let mut pixels = vec![0; width * height * 4];
+        surface
+            .with_data(|data| {
+                for y in 0..height {
+                    for x in 0..width {
+                        let argb = u32::from_ne_bytes(
+                            data[y * stride + x * 4..y * stride + x * 4 + 4]
+                                .try_into()
+                                .unwrap(),
+                        );
+                        let [a, r, g, b] = argb.to_be_bytes();
+                        pixels[(y * width + x) * 4..(y * width + x) * 4 + 4]
+                            .copy_from_slice(&[r, g, b, a]);
+                    }
+                }
+            })
+            .map_err(backend_error)?;
+        Ok(ImageBuf::from_raw(
+            pixels,
+            ImageFormat::RgbaPremul,
+            width,
+            height,
+        ))
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "openbsd",
+    target_os = "freebsd",
+    target_os = "netbsd"
+)))]
+mod bitmap_layers {
+    use std::collections::HashMap;
+
+    use piet_common::{BitmapTarget, Device, ImageBuf, ImageFormat, Piet, RenderContext};
+
+    use pax_core::Compositor;
+    use pax_runtime_api::{BlendMode, ZIndex};
+
+    /// Leaves `opacity` and `blend_mode` unapplied, as piet offers no means of compositing a bitmap target
+    #[derive(Clone, Default)]
+    pub(crate) struct LayerCompositor;
+
+    impl Compositor for LayerCompositor {
+        fn begin(&self, _canvas_id: &str, _opacity: f64, _blend_mode: BlendMode) {}
+    }
+
+    /// Draws `layer_count` layers of `pixel_size` pixels with `draw`, which receives their drawing contexts by
+    /// canvas id, then reads each layer back as premultiplied RGBA
+    pub(crate) fn draw_layers<T>(
+        _compositor: &LayerCompositor,
+        layer_count: usize,
+        pixel_size: (usize, usize),
+        pix_scale: f64,
+        draw: impl FnOnce(&mut HashMap<String, Piet<'static>>) -> T,
+    ) -> Result<(T, Vec<ImageBuf>), piet::Error> {
+        let (pixel_width, pixel_height) = pixel_size;
+        // `bitmap_target` borrows its `Device` for the lifetime of the target, so each layer gets its own
+        let mut devices = (0..layer_count)
+            .map(|_| Device::new())
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut targets = devices
+            .iter_mut()
+            .map(|device| device.bitmap_target(pixel_width, pixel_height, pix_scale))
+            .collect::<Result<Vec<BitmapTarget>, _>>()?;
+
+        let drawn = {
+            let mut render_contexts: HashMap<String, Piet<'static>> = HashMap::new();
+            for (i, target) in targets.iter_mut().enumerate() {
+                let render_context = target.render_context();
+                //Safety: the engine only borrows render contexts for the duration of `draw`, and every
+                //context is dropped below, before its target is read back.
+                let render_context: Piet<'static> = unsafe { std::mem::transmute(render_context) };
+                render_contexts
+                    .insert(ZIndex::generate_location_id(None, i as u32), render_context);
+            }
+            let drawn = draw(&mut render_contexts);
+            for (_, mut render_context) in render_contexts.drain() {
+                render_context.finish()?;
+            }
+            drawn
+        };
+
+        let layers = targets
+            .iter_mut()
+            .map(|target| target.to_image_buf(ImageFormat::RgbaPremul))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((drawn, layers))
+    }
+}
@@ -0,0 +1,1019 @@
+//! Offscreen chassis, rendering Pax cartridges without a browser or native window.
+//! Canvas layers are rasterized by piet's software backend (cairo on Linux), and native
+//! elements (e.g. `Text`) are rasterized on top of their layers, so that each tick produces a
+//! single composited frame.  Useful for CI, visual regression tests, and rendering on servers.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use piet_common::kurbo::{Affine, Point, Rect, Shape};
+use piet_common::{
+    Color, Device, FontFamily, ImageBuf, ImageFormat, InterpolationMode, Piet, RenderContext, Text,
+    TextAlignment, TextLayout, TextLayoutBuilder,
+};
+
+use pax_core::{InstanceRegistry, PaxEngine};
+
+use pax_message::{
+    AnyCreatePatch, ColorVariantMessage, FontPatch, FramePatch, ImagePatch, NativeInterrupt,
+    NativeMessage, TextAlignHorizontalMessage, TextAlignVerticalMessage, TextPatch,
+};
+use pax_runtime_api::{
+    ArgsButtonClick, ArgsClick, ArgsContextMenu, ArgsDoubleClick, ArgsDropdownChange, ArgsJab,
+    ArgsKeyDown, ArgsKeyPress, ArgsKeyUp, ArgsMouseDown, ArgsMouseMove, ArgsMouseOut,
+    ArgsMouseOver, ArgsMouseUp, ArgsScroll, ArgsSliderChange, ArgsTextboxChange, ArgsTouchEnd,
+    ArgsTouchMove, ArgsTouchStart, ArgsWheel, KeyboardEventArgs, ModifierKey, MouseButton,
+    MouseEventArgs, Touch,
+};
+
+use crate::layers::{draw_layers, LayerCompositor};
+
+mod layers;
+pub mod snapshot;
+
+const DEFAULT_FONT_SIZE: f64 = 14.0;
+
+pub fn log_wrapper(msg: &str) {
+    println!("{}", msg);
+}
+
+/// A single composited frame, as straight (non-premultiplied) RGBA8 pixels in row-major order.
+#[derive(Clone)]
+pub struct HeadlessFrame {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl HeadlessFrame {
+    /// Encode this frame as an RGBA PNG at `path`
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), piet::Error> {
+        let file = BufWriter::new(File::create(path).map_err(Into::<Box<_>>::into)?);
+        let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .map_err(Into::<Box<_>>::into)?
+            .write_image_data(&self.pixels)
+            .map_err(Into::<Box<_>>::into)?;
+        Ok(())
+    }
+
+    /// Decode the image file at `path` (e.g. a checked-in golden PNG) into a frame
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<HeadlessFrame, piet::Error> {
+        let image_buf = ImageBuf::from_file(path).map_err(|err| piet::Error::BackendError(err))?;
+        Ok(HeadlessFrame {
+            width: image_buf.width(),
+            height: image_buf.height(),
+            pixels: image_buf
+                .pixel_colors()
+                .flatten()
+                .flat_map(|color| {
+                    let (r, g, b, a) = color.as_rgba8();
+                    [r, g, b, a]
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns the RGBA value of the pixel at (x, y)
+    pub fn get_pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        let offset = (y * self.width + x) * 4;
+        [
+            self.pixels[offset],
+            self.pixels[offset + 1],
+            self.pixels[offset + 2],
+            self.pixels[offset + 3],
+        ]
+    }
+}
+
+/// Last-known state of a native `Text` element, accumulated from `TextCreate` / `TextUpdate` patches
+struct NativeTextNode {
+    z_index: u32,
+    clipping_ids: Vec<Vec<u32>>,
+    content: String,
+    transform: Affine,
+    size: (f64, f64),
+    font_family: Option<String>,
+    font_size: f64,
+    fill: Color,
+    align_horizontal: TextAlignment,
+    /// 0.0 for top, 0.5 for center, 1.0 for bottom
+    align_vertical: f64,
+}
+
+impl NativeTextNode {
+    fn new(patch: AnyCreatePatch) -> Self {
+        NativeTextNode {
+            z_index: patch.z_index,
+            clipping_ids: patch.clipping_ids,
+            content: "".to_string(),
+            transform: Affine::default(),
+            size: (0.0, 0.0),
+            font_family: None,
+            font_size: DEFAULT_FONT_SIZE,
+            fill: Color::BLACK,
+            align_horizontal: TextAlignment::Start,
+            align_vertical: 0.0,
+        }
+    }
+
+    fn apply_patch(&mut self, patch: TextPatch) {
+        if let Some(content) = patch.content {
+            self.content = content;
+        }
+        if let Some(transform) = patch.transform {
+            self.transform = affine_from_coeffs(&transform);
+        }
+        if let Some(size_x) = patch.size_x {
+            self.size.0 = size_x;
+        }
+        if let Some(size_y) = patch.size_y {
+            self.size.1 = size_y;
+        }
+        if let Some(style) = patch.style {
+            if let Some(font) = style.font {
+                let family = match font {
+                    FontPatch::System(font) => font.family,
+                    FontPatch::Web(font) => font.family,
+                    FontPatch::Local(font) => font.family,
+                };
+                if family.is_some() {
+                    self.font_family = family;
+                }
+            }
+            if let Some(font_size) = style.font_size {
+                self.font_size = font_size;
+            }
+            if let Some(fill) = style.fill {
+                self.fill = color_from_message(&fill);
+            }
+            if let Some(align_horizontal) = style.align_horizontal {
+                self.align_horizontal = match align_horizontal {
+                    TextAlignHorizontalMessage::Left => TextAlignment::Start,
+                    TextAlignHorizontalMessage::Center => TextAlignment::Center,
+                    TextAlignHorizontalMessage::Right => TextAlignment::End,
+                };
+            }
+            if let Some(align_vertical) = style.align_vertical {
+                self.align_vertical = match align_vertical {
+                    TextAlignVerticalMessage::Top => 0.0,
+                    TextAlignVerticalMessage::Center => 0.5,
+                    TextAlignVerticalMessage::Bottom => 1.0,
+                };
+            }
+        }
+    }
+}
+
+/// Last-known state of a native `Frame`, used to clip the native elements it contains
+struct NativeFrameNode {
+    transform: Affine,
+    size: (f64, f64),
+}
+
+impl NativeFrameNode {
+    fn apply_patch(&mut self, patch: FramePatch) {
+        if let Some(transform) = patch.transform {
+            self.transform = affine_from_coeffs(&transform);
+        }
+        if let Some(size_x) = patch.size_x {
+            self.size.0 = size_x;
+        }
+        if let Some(size_y) = patch.size_y {
+            self.size.1 = size_y;
+        }
+    }
+}
+
+fn affine_from_coeffs(coeffs: &Vec<f64>) -> Affine {
+    Affine::new([
+        coeffs[0], coeffs[1], coeffs[2], coeffs[3], coeffs[4], coeffs[5],
+    ])
+}
+
+fn color_from_message(message: &ColorVariantMessage) -> Color {
+    match message {
+        ColorVariantMessage::Hlca(c) => Color::hlca(c[0], c[1], c[2], c[3]),
+        ColorVariantMessage::Hlc(c) => Color::hlc(c[0], c[1], c[2]),
+        ColorVariantMessage::Rgba(c) => Color::rgba(c[0], c[1], c[2], c[3]),
+        ColorVariantMessage::Rgb(c) => Color::rgb(c[0], c[1], c[2]),
+    }
+}
+
+fn unpremultiply_rgba(data: &mut [u8]) {
+    for pixel in data.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u32;
+        if alpha != 0 && alpha != 255 {
+            for channel in pixel.iter_mut().take(3) {
+                *channel = ((*channel as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+            }
+        }
+    }
+}
+
+pub struct PaxChassisHeadless {
+    engine: PaxEngine<Piet<'static>>,
+    width: usize,
+    height: usize,
+    pix_scale: f64,
+    /// Number of canvas layers to allocate per tick; grows as native elements report higher z-indices
+    layer_count: usize,
+    /// Directory against which `Image` paths are resolved
+    assets_dir: PathBuf,
+    text_nodes: HashMap<Vec<u32>, NativeTextNode>,
+    frame_nodes: HashMap<Vec<u32>, NativeFrameNode>,
+    frames_rendered: usize,
+    /// Returned as-is by ticks for which the engine has nothing new to render
+    last_frame: Option<HeadlessFrame>,
+    /// Applies `opacity` and `blend_mode` to the layers drawn each tick, on the engine's behalf
+    compositor: LayerCompositor,
+}
+
+impl PaxChassisHeadless {
+    /// Instantiate the main component from the attached `pax_cartridge`, rendering
+    /// into a viewport of `width` x `height` logical pixels
+    pub fn new(width: usize, height: usize) -> Self {
+        let instance_registry: Rc<RefCell<InstanceRegistry<Piet<'static>>>> =
+            Rc::new(RefCell::new(InstanceRegistry::new()));
+        let main_component_instance =
+            pax_cartridge::instantiate_main_component(Rc::clone(&instance_registry));
+        let expression_table = pax_cartridge::instantiate_expression_table();
+
+        let engine = PaxEngine::new(
+            main_component_instance,
+            expression_table,
+            pax_runtime_api::PlatformSpecificLogger::Headless(log_wrapper),
+            (width as f64, height as f64),
+            instance_registry,
+        );
+
+        Self::with_engine(engine, width, height)
+    }
+
+    /// Wrap an already-instantiated engine, e.g. one mounted with a non-main root component
+    pub fn with_engine(mut engine: PaxEngine<Piet<'static>>, width: usize, height: usize) -> Self {
+        let compositor = LayerCompositor::default();
+        engine.set_compositor(Box::new(compositor.clone()));
+        PaxChassisHeadless {
+            engine,
+            width,
+            height,
+            pix_scale: 1.0,
+            layer_count: 1,
+            assets_dir: PathBuf::from("."),
+            text_nodes: HashMap::new(),
+            frame_nodes: HashMap::new(),
+            frames_rendered: 0,
+            last_frame: None,
+            compositor,
+        }
+    }
+
+    /// Render at `pix_scale` physical pixels per logical pixel, akin to a device pixel ratio
+    pub fn set_pix_scale(&mut self, pix_scale: f64) {
+        self.pix_scale = pix_scale;
+        self.engine.invalidate();
+    }
+
+    /// Set the directory against which paths for `Image`s are resolved
+    pub fn set_assets_dir<P: AsRef<Path>>(&mut self, assets_dir: P) {
+        self.assets_dir = assets_dir.as_ref().to_path_buf();
+    }
+
+    pub fn send_viewport_update(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.engine.set_viewport_size((width as f64, height as f64));
+    }
+
+    pub fn get_engine(&self) -> &PaxEngine<Piet<'static>> {
+        &self.engine
+    }
+
+    pub fn get_engine_mut(&mut self) -> &mut PaxEngine<Piet<'static>> {
+        &mut self.engine
+    }
+
+    pub fn get_frames_rendered(&self) -> usize {
+        self.frames_rendered
+    }
+
+    fn get_pixel_size(&self) -> (usize, usize) {
+        (
+            (self.width as f64 * self.pix_scale).ceil() as usize,
+            (self.height as f64 * self.pix_scale).ceil() as usize,
+        )
+    }
+
+    /// Advance the engine by one tick, returning the composited frame
+    pub fn tick(&mut self) -> Result<HeadlessFrame, piet::Error> {
+        //Idle ticks don't draw, so the previous frame still stands
+        if let (false, Some(last_frame)) = (self.engine.needs_render(), &self.last_frame) {
+            let frame = last_frame.clone();
+            let messages = self.engine.tick(&mut HashMap::new());
+            self.process_messages(messages);
+            self.frames_rendered += 1;
+            return Ok(frame);
+        }
+
+        let pixel_size = self.get_pixel_size();
+        let engine = &mut self.engine;
+        let (messages, layers) = draw_layers(
+            &self.compositor,
+            self.layer_count,
+            pixel_size,
+            self.pix_scale,
+            |render_contexts| engine.tick(render_contexts),
+        )?;
+        self.process_messages(messages);
+
+        let frame = self.composite(&layers)?;
+        self.frames_rendered += 1;
+        self.last_frame = Some(frame.clone());
+        Ok(frame)
+    }
+
+    /// Advance the engine by one tick, writing the composited frame to `path` as a PNG
+    pub fn tick_to_png<P: AsRef<Path>>(&mut self, path: P) -> Result<(), piet::Error> {
+        self.tick()?.save_png(path)
+    }
+
+    /// Tick `frame_count` times, writing each frame into `output_dir` as `frame_00000.png`, `frame_00001.png`, etc.
+    pub fn render_frames_to_dir<P: AsRef<Path>>(
+        &mut self,
+        output_dir: P,
+        frame_count: usize,
+    ) -> Result<(), piet::Error> {
+        std::fs::create_dir_all(&output_dir).map_err(Into::<Box<_>>::into)?;
+        for _ in 0..frame_count {
+            let path = output_dir
+                .as_ref()
+                .join(format!("frame_{:05}.png", self.frames_rendered));
+            self.tick_to_png(path)?;
+        }
+        Ok(())
+    }
+
+    /// Apply native messages emitted by the engine to our retained native elements, standing in
+    /// for the DOM / AppKit views that a windowed chassis would manage
+    fn process_messages(&mut self, messages: Vec<NativeMessage>) {
+        for message in messages {
+            match message {
+                NativeMessage::TextCreate(patch) => {
+                    self.ensure_layer_count(patch.z_index);
+                    self.text_nodes
+                        .insert(patch.id_chain.clone(), NativeTextNode::new(patch));
+                }
+                NativeMessage::TextUpdate(patch) => {
+                    if let Some(text_node) = self.text_nodes.get_mut(&patch.id_chain) {
+                        text_node.apply_patch(*patch);
+                    }
+                }
+                NativeMessage::TextDelete(id_chain) => {
+                    self.text_nodes.remove(&id_chain);
+                }
+                NativeMessage::FrameCreate(patch) => {
+                    self.ensure_layer_count(patch.z_index);
+                    self.frame_nodes.insert(
+                        patch.id_chain,
+                        NativeFrameNode {
+                            transform: Affine::default(),
+                            size: (0.0, 0.0),
+                        },
+                    );
+                }
+                NativeMessage::FrameUpdate(patch) => {
+                    if let Some(frame_node) = self.frame_nodes.get_mut(&patch.id_chain) {
+                        frame_node.apply_patch(patch);
+                    }
+                }
+                NativeMessage::FrameDelete(id_chain) => {
+                    self.frame_nodes.remove(&id_chain);
+                }
+                NativeMessage::ImageLoad(patch) => {
+                    self.load_image(patch);
+                }
+                //Native scrolling is not simulated; scroller contents render into the root layers
+                NativeMessage::ScrollerCreate(_)
+                | NativeMessage::ScrollerUpdate(_)
+                | NativeMessage::ScrollerDelete(_) => {}
+                NativeMessage::LayerAdd(_) => {}
+                //Native form controls are not simulated; their events may be sent with `interrupt`
+                NativeMessage::ButtonCreate(_)
+                | NativeMessage::ButtonUpdate(_)
+                | NativeMessage::ButtonDelete(_)
+                | NativeMessage::SliderCreate(_)
+                | NativeMessage::SliderUpdate(_)
+                | NativeMessage::SliderDelete(_)
+                | NativeMessage::DropdownCreate(_)
+                | NativeMessage::DropdownUpdate(_)
+                | NativeMessage::DropdownDelete(_)
+                | NativeMessage::TextboxCreate(_)
+                | NativeMessage::TextboxUpdate(_)
+                | NativeMessage::TextboxDelete(_) => {}
+                //Focus is tracked by the engine; there are no native elements for it to move between
+                NativeMessage::Focus(_) | NativeMessage::Blur(_) => {}
+            }
+        }
+    }
+
+    /// Layers are allocated before each tick, so content on a newly discovered layer
+    /// falls back to layer "0" for the tick during which it was discovered
+    fn ensure_layer_count(&mut self, z_index: u32) {
+        if z_index as usize >= self.layer_count {
+            //newly allocated layers must be drawn to
+            self.engine.invalidate();
+        }
+        self.layer_count = self.layer_count.max(z_index as usize + 1);
+    }
+
+    /// Stands in for the browser's image loading: decode the referenced file and hand its pixels to the engine
+    fn load_image(&mut self, patch: ImagePatch) {
+        if let Some(path) = patch.path {
+            let full_path = self.assets_dir.join(&path);
+            match HeadlessFrame::from_file(&full_path) {
+                Ok(image) => {
+                    self.engine
+                        .load_image(patch.id_chain, image.pixels, image.width, image.height);
+                }
+                Err(err) => {
+                    pax_runtime_api::log(&format!(
+                        "Failed to load image {}: {}",
+                        full_path.display(),
+                        err
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Stack layers and native elements the way the web chassis does: higher layers sit beneath lower
+    /// layers, and each layer's native elements sit above that layer's canvas
+    fn composite(&self, layers: &Vec<ImageBuf>) -> Result<HeadlessFrame, piet::Error> {
+        let (pixel_width, pixel_height) = self.get_pixel_size();
+        let viewport = Rect::new(0.0, 0.0, self.width as f64, self.height as f64);
+
+        let mut device = Device::new()?;
+        let mut target = device.bitmap_target(pixel_width, pixel_height, self.pix_scale)?;
+        {
+            let mut rc = target.render_context();
+            let layer_count = self.layer_count.max(layers.len());
+            for z_index in (0..layer_count).rev() {
+                if let Some(layer) = layers.get(z_index) {
+                    let image = layer.to_image(&mut rc);
+                    rc.draw_image(&image, viewport, InterpolationMode::Bilinear);
+                }
+                self.draw_text_nodes(&mut rc, z_index as u32)?;
+            }
+            rc.finish()?;
+        }
+
+        let mut pixels = target
+            .to_image_buf(ImageFormat::RgbaPremul)?
+            .raw_pixels()
+            .to_vec();
+        unpremultiply_rgba(&mut pixels);
+
+        Ok(HeadlessFrame {
+            width: pixel_width,
+            height: pixel_height,
+            pixels,
+        })
+    }
+
+    fn draw_text_nodes(&self, rc: &mut Piet, z_index: u32) -> Result<(), piet::Error> {
+        //Sort by id_chain so that overlapping text renders deterministically
+        let mut text_nodes: Vec<_> = self
+            .text_nodes
+            .iter()
+            .filter(|(_, text_node)| text_node.z_index == z_index)
+            .collect();
+        text_nodes.sort_by(|(id_chain_a, _), (id_chain_b, _)| id_chain_a.cmp(id_chain_b));
+
+        for (_, text_node) in text_nodes {
+            rc.with_save(|rc| {
+                for clipping_id in text_node.clipping_ids.iter() {
+                    if let Some(frame_node) = self.frame_nodes.get(clipping_id) {
+                        let clip_rect = Rect::new(0.0, 0.0, frame_node.size.0, frame_node.size.1);
+                        rc.clip(frame_node.transform * clip_rect.to_path(0.1));
+                    }
+                }
+                rc.transform(text_node.transform);
+
+                let font_family = text_node
+                    .font_family
+                    .as_ref()
+                    .and_then(|family| rc.text().font_family(family))
+                    .unwrap_or(FontFamily::SANS_SERIF);
+                let layout = rc
+                    .text()
+                    .new_text_layout(text_node.content.clone())
+                    .font(font_family, text_node.font_size)
+                    .text_color(text_node.fill.clone())
+                    .alignment(text_node.align_horizontal)
+                    .max_width(text_node.size.0)
+                    .build()?;
+                let offset_y = (text_node.size.1 - layout.size().height) * text_node.align_vertical;
+                rc.draw_text(&layout, Point::new(0.0, offset_y));
+                Ok(())
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Send `interrupt`s programmatically, standing in for user input.
+    /// Returns whether a handler called `prevent_default` on the resulting event.
+    pub fn interrupt(&mut self, native_interrupt: NativeInterrupt) -> bool {
+        let mut default_prevented = false;
+        match native_interrupt {
+            //Images are loaded by the chassis itself, upon `ImageLoad` messages; see `load_image`
+            NativeInterrupt::Image(_args) => {}
+            NativeInterrupt::AddedLayer(_args) => {}
+            NativeInterrupt::Click(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let args_click = ArgsClick {
+                        mouse: MouseEventArgs {
+                            x: args.x,
+                            y: args.y,
+                            button: MouseButton::from(args.button),
+                            modifiers: args
+                                .modifiers
+                                .iter()
+                                .map(|x| ModifierKey::from(x))
+                                .collect(),
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_click(args_click);
+                }
+            }
+            NativeInterrupt::Scroll(args) => {
+                let prospective_hit = self.engine.get_topmost_element_beneath_viewport_center();
+                if let Some(topmost_node) = prospective_hit {
+                    let args_scroll = ArgsScroll {
+                        delta_x: args.delta_x,
+                        delta_y: args.delta_y,
+                    };
+                    default_prevented = topmost_node.dispatch_scroll(args_scroll);
+                }
+            }
+            NativeInterrupt::Jab(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let args_jab = ArgsJab {
+                        x: args.x,
+                        y: args.y,
+                    };
+                    default_prevented = topmost_node.dispatch_jab(args_jab);
+                }
+            }
+            NativeInterrupt::TouchStart(args) => {
+                //an empty touch list, as may be built programmatically, has no point to hit test
+                let first_touch = match args.touches.get(0) {
+                    Some(first_touch) => first_touch,
+                    None => return false,
+                };
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((first_touch.x, first_touch.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let touches = args.touches.iter().map(|x| Touch::from(x)).collect();
+                    let args_touch_start = ArgsTouchStart { touches };
+                    default_prevented = topmost_node.dispatch_touch_start(args_touch_start);
+                }
+            }
+            NativeInterrupt::TouchMove(args) => {
+                //an empty touch list, as may be built programmatically, has no point to hit test
+                let first_touch = match args.touches.get(0) {
+                    Some(first_touch) => first_touch,
+                    None => return false,
+                };
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((first_touch.x, first_touch.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let touches = args.touches.iter().map(|x| Touch::from(x)).collect();
+                    let args_touch_move = ArgsTouchMove { touches };
+                    default_prevented = topmost_node.dispatch_touch_move(args_touch_move);
+                }
+            }
+            NativeInterrupt::TouchEnd(args) => {
+                //an empty touch list, as may be built programmatically, has no point to hit test
+                let first_touch = match args.touches.get(0) {
+                    Some(first_touch) => first_touch,
+                    None => return false,
+                };
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((first_touch.x, first_touch.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let touches = args.touches.iter().map(|x| Touch::from(x)).collect();
+                    let args_touch_end = ArgsTouchEnd { touches };
+                    default_prevented = topmost_node.dispatch_touch_end(args_touch_end);
+                }
+            }
+            NativeInterrupt::KeyDown(args) => {
+                let modifiers: Vec<ModifierKey> = args
+                    .modifiers
+                    .iter()
+                    .map(|x| ModifierKey::from(x))
+                    .collect();
+                let prospective_hit = self.engine.get_keyboard_event_target();
+                if let Some(topmost_node) = prospective_hit {
+                    let args_key_down = ArgsKeyDown {
+                        keyboard: KeyboardEventArgs {
+                            key: args.key.clone(),
+                            modifiers: modifiers.clone(),
+                            is_repeat: args.is_repeat,
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_key_down(args_key_down);
+                }
+                if !default_prevented {
+                    self.engine
+                        .handle_focus_traversal_key(&args.key, &modifiers);
+                }
+            }
+            NativeInterrupt::KeyUp(args) => {
+                let prospective_hit = self.engine.get_keyboard_event_target();
+                if let Some(topmost_node) = prospective_hit {
+                    let modifiers = args
+                        .modifiers
+                        .iter()
+                        .map(|x| ModifierKey::from(x))
+                        .collect();
+                    let args_key_up = ArgsKeyUp {
+                        keyboard: KeyboardEventArgs {
+                            key: args.key,
+                            modifiers,
+                            is_repeat: args.is_repeat,
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_key_up(args_key_up);
+                }
+            }
+            NativeInterrupt::KeyPress(args) => {
+                let prospective_hit = self.engine.get_keyboard_event_target();
+                if let Some(topmost_node) = prospective_hit {
+                    let modifiers = args
+                        .modifiers
+                        .iter()
+                        .map(|x| ModifierKey::from(x))
+                        .collect();
+                    let args_key_press = ArgsKeyPress {
+                        keyboard: KeyboardEventArgs {
+                            key: args.key,
+                            modifiers,
+                            is_repeat: args.is_repeat,
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_key_press(args_key_press);
+                }
+            }
+            NativeInterrupt::DoubleClick(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let args_double_click = ArgsDoubleClick {
+                        mouse: MouseEventArgs {
+                            x: args.x,
+                            y: args.y,
+                            button: MouseButton::from(args.button),
+                            modifiers: args
+                                .modifiers
+                                .iter()
+                                .map(|x| ModifierKey::from(x))
+                                .collect(),
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_double_click(args_double_click);
+                }
+            }
+            NativeInterrupt::MouseMove(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let args_mouse_move = ArgsMouseMove {
+                        mouse: MouseEventArgs {
+                            x: args.x,
+                            y: args.y,
+                            button: MouseButton::from(args.button),
+                            modifiers: args
+                                .modifiers
+                                .iter()
+                                .map(|x| ModifierKey::from(x))
+                                .collect(),
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_mouse_move(args_mouse_move);
+                }
+            }
+            NativeInterrupt::Wheel(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let modifiers = args
+                        .modifiers
+                        .iter()
+                        .map(|x| ModifierKey::from(x))
+                        .collect();
+                    let args_wheel = ArgsWheel {
+                        x: args.x,
+                        y: args.y,
+                        delta_x: args.delta_x,
+                        delta_y: args.delta_y,
+                        modifiers,
+                    };
+                    default_prevented = topmost_node.dispatch_wheel(args_wheel);
+                }
+            }
+            NativeInterrupt::MouseDown(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = &prospective_hit {
+                    let args_mouse_down = ArgsMouseDown {
+                        mouse: MouseEventArgs {
+                            x: args.x,
+                            y: args.y,
+                            button: MouseButton::from(args.button),
+                            modifiers: args
+                                .modifiers
+                                .iter()
+                                .map(|x| ModifierKey::from(x))
+                                .collect(),
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_mouse_down(args_mouse_down);
+                }
+                //As in browsers, pressing an element focuses it (or its nearest focusable ancestor), unless prevented
+                if !default_prevented {
+                    self.engine.focus_pointed_element(prospective_hit);
+                }
+            }
+            NativeInterrupt::MouseUp(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let args_mouse_up = ArgsMouseUp {
+                        mouse: MouseEventArgs {
+                            x: args.x,
+                            y: args.y,
+                            button: MouseButton::from(args.button),
+                            modifiers: args
+                                .modifiers
+                                .iter()
+                                .map(|x| ModifierKey::from(x))
+                                .collect(),
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_mouse_up(args_mouse_up);
+                }
+            }
+            NativeInterrupt::MouseOver(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let args_mouse_over = ArgsMouseOver {
+                        mouse: MouseEventArgs {
+                            x: args.x,
+                            y: args.y,
+                            button: MouseButton::from(args.button),
+                            modifiers: args
+                                .modifiers
+                                .iter()
+                                .map(|x| ModifierKey::from(x))
+                                .collect(),
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_mouse_over(args_mouse_over);
+                }
+            }
+            NativeInterrupt::MouseOut(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let args_mouse_out = ArgsMouseOut {
+                        mouse: MouseEventArgs {
+                            x: args.x,
+                            y: args.y,
+                            button: MouseButton::from(args.button),
+                            modifiers: args
+                                .modifiers
+                                .iter()
+                                .map(|x| ModifierKey::from(x))
+                                .collect(),
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_mouse_out(args_mouse_out);
+                }
+            }
+            NativeInterrupt::ContextMenu(args) => {
+                let prospective_hit = self
+                    .engine
+                    .get_topmost_element_beneath_ray((args.x, args.y));
+                if let Some(topmost_node) = prospective_hit {
+                    let args_context_menu = ArgsContextMenu {
+                        mouse: MouseEventArgs {
+                            x: args.x,
+                            y: args.y,
+                            button: MouseButton::from(args.button),
+                            modifiers: args
+                                .modifiers
+                                .iter()
+                                .map(|x| ModifierKey::from(x))
+                                .collect(),
+                        },
+                    };
+                    default_prevented = topmost_node.dispatch_context_menu(args_context_menu);
+                }
+            }
+            NativeInterrupt::FormButtonClick(args) => {
+                let node = self.engine.get_repeat_expanded_node(&args.id_chain);
+                if let Some(node) = node {
+                    default_prevented = node.dispatch_button_click(ArgsButtonClick {});
+                }
+            }
+            NativeInterrupt::FormSliderChange(args) => {
+                let node = self.engine.get_repeat_expanded_node(&args.id_chain);
+                if let Some(node) = node {
+                    default_prevented =
+                        node.dispatch_slider_change(ArgsSliderChange { value: args.value });
+                }
+            }
+            NativeInterrupt::FormDropdownChange(args) => {
+                let node = self.engine.get_repeat_expanded_node(&args.id_chain);
+                if let Some(node) = node {
+                    default_prevented = node.dispatch_dropdown_change(ArgsDropdownChange {
+                        selected_index: args.selected_index,
+                    });
+                }
+            }
+            NativeInterrupt::FormTextboxChange(args) => {
+                let node = self.engine.get_repeat_expanded_node(&args.id_chain);
+                if let Some(node) = node {
+                    default_prevented =
+                        node.dispatch_textbox_change(ArgsTextboxChange { text: args.text });
+                }
+            }
+            NativeInterrupt::FormFocus(args) => {
+                self.engine.focus(&args.id_chain);
+            }
+            NativeInterrupt::FormBlur(args) => {
+                self.engine.blur(&args.id_chain);
+            }
+        };
+        default_prevented
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pax_core::{
+        ComponentInstance, InstantiationArgs, RenderNode, RenderNodePtr, RenderNodePtrList,
+        RenderTreeContext,
+    };
+    use pax_properties_coproduct::PropertiesCoproduct;
+    use pax_runtime_api::{CommonProperties, PropertyInstance, PropertyLiteral};
+    use std::sync::Mutex;
+
+    /// `register_logger` can't be raced, and tests run on parallel threads
+    static ENGINE_CONSTRUCTION: Mutex<()> = Mutex::new(());
+
+    /// Fills its bounds with opaque red, if `fills`, beneath its children
+    struct FillNode {
+        instance_id: u32,
+        common_properties: CommonProperties,
+        fills: bool,
+        children: RenderNodePtrList<Piet<'static>>,
+    }
+
+    impl RenderNode<Piet<'static>> for FillNode {
+        fn instantiate(_args: InstantiationArgs<Piet<'static>>) -> Rc<RefCell<Self>> {
+            unimplemented!()
+        }
+
+        fn get_rendering_children(&self) -> RenderNodePtrList<Piet<'static>> {
+            Rc::clone(&self.children)
+        }
+
+        fn get_common_properties(&self) -> &CommonProperties {
+            &self.common_properties
+        }
+
+        fn get_instance_id(&self) -> u32 {
+            self.instance_id
+        }
+
+        fn handle_render(
+            &mut self,
+            rtc: &mut RenderTreeContext<Piet<'static>>,
+            rc: &mut Piet<'static>,
+        ) {
+            if self.fills {
+                let (width, height) = rtc.bounds;
+                let shape = rtc.transform_global * Rect::new(0.0, 0.0, width, height).to_path(0.1);
+                rc.fill(shape, &Color::RED);
+            }
+        }
+    }
+
+    fn build_fill_node(
+        instance_registry: &mut InstanceRegistry<Piet<'static>>,
+        opacity: f64,
+        fills: bool,
+        children: Vec<RenderNodePtr<Piet<'static>>>,
+    ) -> RenderNodePtr<Piet<'static>> {
+        let mut common_properties = CommonProperties::default();
+        common_properties.opacity = Some(Rc::new(RefCell::new(PropertyLiteral::new(opacity)))
+            as Rc<RefCell<dyn PropertyInstance<f64>>>);
+        Rc::new(RefCell::new(FillNode {
+            instance_id: instance_registry.mint_id(),
+            common_properties,
+            fills,
+            children: Rc::new(RefCell::new(children)),
+        }))
+    }
+
+    fn build_chassis(
+        build_template: impl FnOnce(
+            &mut InstanceRegistry<Piet<'static>>,
+        ) -> Vec<RenderNodePtr<Piet<'static>>>,
+    ) -> PaxChassisHeadless {
+        let instance_registry = Rc::new(RefCell::new(InstanceRegistry::new()));
+        let template = build_template(&mut instance_registry.borrow_mut());
+        let main_component = ComponentInstance::instantiate(InstantiationArgs {
+            common_properties: CommonProperties::default(),
+            properties: PropertiesCoproduct::None,
+            handler_registry: None,
+            instance_registry: Rc::clone(&instance_registry),
+            children: None,
+            component_template: Some(Rc::new(RefCell::new(template))),
+            scroller_args: None,
+            slot_index: None,
+            repeat_source_expression_vec: None,
+            repeat_source_expression_range: None,
+            repeat_key_expression: None,
+            conditional_boolean_expression: None,
+            conditional_else_children: None,
+            compute_properties_fn: Some(Box::new(|_, _| {})),
+        });
+        let _guard = ENGINE_CONSTRUCTION.lock().unwrap();
+        let engine = PaxEngine::new(
+            main_component,
+            HashMap::new(),
+            pax_runtime_api::PlatformSpecificLogger::Headless(log_wrapper),
+            (4.0, 4.0),
+            instance_registry,
+        );
+        PaxChassisHeadless::with_engine(engine, 4, 4)
+    }
+
+    #[test]
+    fn test_nested_opacity_compounds() {
+        let mut chassis = build_chassis(|instance_registry| {
+            let child = build_fill_node(instance_registry, 0.5, true, vec![]);
+            vec![build_fill_node(instance_registry, 0.5, false, vec![child])]
+        });
+        let [r, g, b, a] = chassis.tick().unwrap().get_pixel(1, 1);
+        assert_eq!((r, g, b), (255, 0, 0));
+        assert!(
+            (63..=65).contains(&a),
+            "expected alpha of about 0.25, got {}",
+            a
+        );
+    }
+
+    #[test]
+    fn test_opacity_applies_to_subtrees_as_a_whole() {
+        //the opaque child covers its parent within their group, which is then composited at half opacity
+        let mut chassis = build_chassis(|instance_registry| {
+            let child = build_fill_node(instance_registry, 1.0, true, vec![]);
+            vec![build_fill_node(instance_registry, 0.5, true, vec![child])]
+        });
+        let [_, _, _, a] = chassis.tick().unwrap().get_pixel(1, 1);
+        assert!(
+            (127..=129).contains(&a),
+            "expected alpha of about 0.5, got {}",
+            a
+        );
+    }
+}
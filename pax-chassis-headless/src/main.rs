@@ -0,0 +1,78 @@
+//! Executable harness for `pax build --target headless`: ticks the attached cartridge a scripted
+//! number of times, optionally writing each composited frame to a directory as PNG.
+//!
+//! Usage: `pax-chassis-headless [--frames N] [--output-dir DIR] [--width W] [--height H] [--assets-dir DIR]`
+
+use std::path::PathBuf;
+
+use pax_chassis_headless::PaxChassisHeadless;
+
+const DEFAULT_FRAME_COUNT: usize = 1;
+const DEFAULT_WIDTH: usize = 1280;
+const DEFAULT_HEIGHT: usize = 800;
+
+const USAGE: &str = "Usage: pax-chassis-headless [--frames N] [--output-dir DIR] [--width W] [--height H] [--assets-dir DIR]";
+
+fn parse_usize(flag: &str, value: Option<String>) -> usize {
+    value
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or_else(|| {
+            eprintln!("Expected a positive integer for `{}`.\n{}", flag, USAGE);
+            std::process::exit(1);
+        })
+}
+
+fn main() {
+    let mut frame_count = DEFAULT_FRAME_COUNT;
+    let mut width = DEFAULT_WIDTH;
+    let mut height = DEFAULT_HEIGHT;
+    let mut output_dir: Option<PathBuf> = None;
+    //By default, resolve assets alongside the executable, mirroring the layout of `.pax/build/*/headless`
+    let mut assets_dir: Option<PathBuf> = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|p| p.to_path_buf()));
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--frames" => frame_count = parse_usize(&arg, args.next()),
+            "--width" => width = parse_usize(&arg, args.next()),
+            "--height" => height = parse_usize(&arg, args.next()),
+            "--output-dir" => output_dir = args.next().map(PathBuf::from),
+            "--assets-dir" => assets_dir = args.next().map(PathBuf::from),
+            _ => {
+                eprintln!("Unrecognized argument `{}`.\n{}", arg, USAGE);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let mut chassis = PaxChassisHeadless::new(width, height);
+    if let Some(assets_dir) = assets_dir {
+        chassis.set_assets_dir(assets_dir);
+    }
+
+    let result = if let Some(output_dir) = &output_dir {
+        chassis.render_frames_to_dir(output_dir, frame_count)
+    } else {
+        (0..frame_count).try_for_each(|_| chassis.tick().map(|_| ()))
+    };
+
+    match result {
+        Ok(_) => {
+            if let Some(output_dir) = output_dir {
+                println!(
+                    "Rendered {} frame(s) to {}",
+                    frame_count,
+                    output_dir.to_str().unwrap()
+                );
+            } else {
+                println!("Ticked {} frame(s)", frame_count);
+            }
+        }
+        Err(err) => {
+            eprintln!("Headless rendering failed: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,365 @@
+//! Golden-image snapshot testing for Pax components.
+//!
+//! A `SnapshotTest` mounts a single component from the attached cartridge as the root of a fresh
+//! `PaxEngine`, advances it a number of ticks through the regular render pipeline, and compares the
+//! composited frame against a checked-in golden PNG.  On mismatch, the actual frame and a diff image
+//! are written next to the golden.  Set `PAX_UPDATE_SNAPSHOTS=1` to (re)write goldens instead of comparing.
+//!
+//! Because the component tree is provided by the compiled cartridge, tests should depend on the
+//! cartridge-attached chassis emitted by `pax build --target=headless`, e.g. in `Cargo.toml`:
+//! `[dev-dependencies] pax-chassis-headless = { path = ".pax/pkg/pax-chassis-headless" }`
+//!
+//! ```ignore
+//! #[test]
+//! fn hello_rgb_renders() {
+//!     SnapshotTest::new("crate::HelloRGB")
+//!         .with_viewport(400, 300)
+//!         .with_ticks(10)
+//!         .with_tolerance(2)
+//!         .assert_matches_golden("tests/snapshots/hello_rgb.png");
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use piet_common::Piet;
+
+use pax_core::{InstanceRegistry, PaxEngine};
+use pax_properties_coproduct::PropertiesCoproduct;
+
+use crate::{log_wrapper, HeadlessFrame, PaxChassisHeadless};
+
+/// When set (to any value), `assert_matches_golden` overwrites goldens with the actual output
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "PAX_UPDATE_SNAPSHOTS";
+
+const DEFAULT_SNAPSHOT_WIDTH: usize = 800;
+const DEFAULT_SNAPSHOT_HEIGHT: usize = 600;
+const DEFAULT_SNAPSHOT_TICKS: usize = 1;
+
+/// Mounts a component by type id and renders it offscreen for comparison against a golden image.
+pub struct SnapshotTest {
+    type_id: String,
+    width: usize,
+    height: usize,
+    pix_scale: f64,
+    ticks: usize,
+    /// Maximum allowed absolute difference per RGBA channel before a pixel is considered mismatched
+    tolerance: u8,
+    /// Number of mismatched pixels allowed before the comparison fails
+    max_mismatched_pixels: usize,
+    assets_dir: Option<PathBuf>,
+    properties_setter: Option<Box<dyn FnOnce(&mut PropertiesCoproduct)>>,
+}
+
+impl SnapshotTest {
+    /// Mount the component with the given type id, e.g. `crate::HelloRGB`.  Any non-primitive
+    /// component in the attached cartridge may be mounted, not only the main component.
+    pub fn new(type_id: &str) -> Self {
+        SnapshotTest {
+            type_id: type_id.to_string(),
+            width: DEFAULT_SNAPSHOT_WIDTH,
+            height: DEFAULT_SNAPSHOT_HEIGHT,
+            pix_scale: 1.0,
+            ticks: DEFAULT_SNAPSHOT_TICKS,
+            tolerance: 0,
+            max_mismatched_pixels: 0,
+            assets_dir: None,
+            properties_setter: None,
+        }
+    }
+
+    pub fn with_viewport(mut self, width: usize, height: usize) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn with_pix_scale(mut self, pix_scale: f64) -> Self {
+        self.pix_scale = pix_scale;
+        self
+    }
+
+    /// Number of engine ticks to advance before capturing the frame; the last tick is the one compared
+    pub fn with_ticks(mut self, ticks: usize) -> Self {
+        self.ticks = ticks.max(1);
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_mismatched_pixels(mut self, max_mismatched_pixels: usize) -> Self {
+        self.max_mismatched_pixels = max_mismatched_pixels;
+        self
+    }
+
+    pub fn with_assets_dir<P: AsRef<Path>>(mut self, assets_dir: P) -> Self {
+        self.assets_dir = Some(assets_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Mutate the mounted component's properties before the first tick.
+    /// The closure receives the root's `PropertiesCoproduct`, whose variant is the component's escaped type id.
+    pub fn with_properties<F: FnOnce(&mut PropertiesCoproduct) + 'static>(
+        mut self,
+        setter: F,
+    ) -> Self {
+        self.properties_setter = Some(Box::new(setter));
+        self
+    }
+
+    /// Mount the component, advance the configured number of ticks, and return the final frame.
+    /// Panics if the attached cartridge has no component with this type id.
+    pub fn render(self) -> HeadlessFrame {
+        let instance_registry: Rc<RefCell<InstanceRegistry<Piet<'static>>>> =
+            Rc::new(RefCell::new(InstanceRegistry::new()));
+        let root_component_instance = pax_cartridge::instantiate_component_by_type_id(
+            Rc::clone(&instance_registry),
+            &self.type_id,
+        )
+        .unwrap_or_else(|| {
+            panic!(
+                "No mountable component with type id `{}` in the attached cartridge",
+                self.type_id
+            )
+        });
+
+        if let Some(setter) = self.properties_setter {
+            setter(&mut *root_component_instance.borrow().properties.borrow_mut());
+        }
+
+        let engine = PaxEngine::new(
+            root_component_instance,
+            pax_cartridge::instantiate_expression_table(),
+            pax_runtime_api::PlatformSpecificLogger::Headless(log_wrapper),
+            (self.width as f64, self.height as f64),
+            instance_registry,
+        );
+
+        let mut chassis = PaxChassisHeadless::with_engine(engine, self.width, self.height);
+        chassis.set_pix_scale(self.pix_scale);
+        if let Some(assets_dir) = &self.assets_dir {
+            chassis.set_assets_dir(assets_dir);
+        }
+
+        let mut frame = None;
+        for _ in 0..self.ticks {
+            frame = Some(
+                chassis
+                    .tick()
+                    .unwrap_or_else(|err| panic!("Failed to render `{}`: {}", self.type_id, err)),
+            );
+        }
+        frame.unwrap()
+    }
+
+    /// Render and compare against the golden PNG at `golden_path`, panicking with a summary on mismatch.
+    /// `<golden>.actual.png` and `<golden>.diff.png` are written alongside the golden when the comparison fails.
+    pub fn assert_matches_golden<P: AsRef<Path>>(self, golden_path: P) {
+        let golden_path = golden_path.as_ref();
+        let tolerance = self.tolerance;
+        let max_mismatched_pixels = self.max_mismatched_pixels;
+        let actual = self.render();
+
+        if std::env::var_os(UPDATE_SNAPSHOTS_ENV_VAR).is_some() {
+            if let Some(parent) = golden_path.parent() {
+                std::fs::create_dir_all(parent).unwrap();
+            }
+            actual.save_png(golden_path).unwrap();
+            return;
+        }
+
+        if !golden_path.exists() {
+            panic!(
+                "Golden image {} does not exist.  Re-run with {}=1 to create it.",
+                golden_path.display(),
+                UPDATE_SNAPSHOTS_ENV_VAR
+            );
+        }
+
+        let golden = HeadlessFrame::from_file(golden_path).unwrap_or_else(|err| {
+            panic!(
+                "Failed to read golden image {}: {}",
+                golden_path.display(),
+                err
+            )
+        });
+
+        let comparison = compare_frames(&golden, &actual, tolerance);
+        if !comparison.is_within(max_mismatched_pixels) {
+            let actual_path = sibling_path(golden_path, "actual");
+            let diff_path = sibling_path(golden_path, "diff");
+            actual.save_png(&actual_path).unwrap();
+            if let Some(diff) = &comparison.diff {
+                diff.save_png(&diff_path).unwrap();
+            }
+            panic!(
+                "Snapshot {} does not match: {}.  Actual frame written to {}{}",
+                golden_path.display(),
+                comparison.describe(),
+                actual_path.display(),
+                if comparison.diff.is_some() {
+                    format!(", diff written to {}", diff_path.display())
+                } else {
+                    "".to_string()
+                }
+            );
+        }
+    }
+}
+
+/// Result of comparing two frames pixel-by-pixel
+pub struct FrameComparison {
+    pub mismatched_pixels: usize,
+    pub total_pixels: usize,
+    /// Largest absolute difference seen on any single channel
+    pub max_channel_difference: u8,
+    /// `Some((expected, actual))` when the frames' dimensions differ, in which case no pixels are compared
+    pub size_mismatch: Option<((usize, usize), (usize, usize))>,
+    /// Mismatched pixels in opaque red over a faded copy of the actual frame; `None` on size mismatch
+    pub diff: Option<HeadlessFrame>,
+}
+
+impl FrameComparison {
+    pub fn is_match(&self) -> bool {
+        self.is_within(0)
+    }
+
+    /// Whether at most `max_mismatched_pixels` differ.  Frames of different sizes never match, however lenient the limit.
+    pub fn is_within(&self, max_mismatched_pixels: usize) -> bool {
+        self.size_mismatch.is_none() && self.mismatched_pixels <= max_mismatched_pixels
+    }
+
+    fn describe(&self) -> String {
+        if let Some((expected, actual)) = self.size_mismatch {
+            format!(
+                "expected {}x{} pixels but rendered {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            )
+        } else {
+            format!(
+                "{} of {} pixels differ (max channel difference {})",
+                self.mismatched_pixels, self.total_pixels, self.max_channel_difference
+            )
+        }
+    }
+}
+
+/// Compare `actual` against `expected`, treating a pixel as mismatched if any RGBA channel differs by more than `tolerance`
+pub fn compare_frames(
+    expected: &HeadlessFrame,
+    actual: &HeadlessFrame,
+    tolerance: u8,
+) -> FrameComparison {
+    if expected.width != actual.width || expected.height != actual.height {
+        return FrameComparison {
+            mismatched_pixels: actual.width * actual.height,
+            total_pixels: actual.width * actual.height,
+            max_channel_difference: u8::MAX,
+            size_mismatch: Some((
+                (expected.width, expected.height),
+                (actual.width, actual.height),
+            )),
+            diff: None,
+        };
+    }
+
+    let mut mismatched_pixels = 0;
+    let mut max_channel_difference = 0;
+    let mut diff_pixels = Vec::with_capacity(actual.pixels.len());
+    for (expected_pixel, actual_pixel) in expected
+        .pixels
+        .chunks_exact(4)
+        .zip(actual.pixels.chunks_exact(4))
+    {
+        let pixel_difference = expected_pixel
+            .iter()
+            .zip(actual_pixel.iter())
+            .map(|(e, a)| e.abs_diff(*a))
+            .max()
+            .unwrap();
+        max_channel_difference = max_channel_difference.max(pixel_difference);
+        if pixel_difference > tolerance {
+            mismatched_pixels += 1;
+            diff_pixels.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            //Fade matching pixels toward white so that mismatches stand out
+            diff_pixels.extend(
+                actual_pixel
+                    .iter()
+                    .take(3)
+                    .map(|channel| 191 + channel / 4)
+                    .chain(std::iter::once(255)),
+            );
+        }
+    }
+
+    FrameComparison {
+        mismatched_pixels,
+        total_pixels: actual.width * actual.height,
+        max_channel_difference,
+        size_mismatch: None,
+        diff: Some(HeadlessFrame {
+            width: actual.width,
+            height: actual.height,
+            pixels: diff_pixels,
+        }),
+    }
+}
+
+/// e.g. `snapshots/button.png` -> `snapshots/button.diff.png`
+fn sibling_path(golden_path: &Path, suffix: &str) -> PathBuf {
+    let stem = golden_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    golden_path.with_file_name(format!("{}.{}.png", stem, suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_frame(width: usize, height: usize, rgba: [u8; 4]) -> HeadlessFrame {
+        HeadlessFrame {
+            width,
+            height,
+            pixels: rgba.repeat(width * height),
+        }
+    }
+
+    #[test]
+    fn test_compare_frames_tolerance() {
+        let expected = solid_frame(2, 2, [100, 100, 100, 255]);
+        let mut actual = solid_frame(2, 2, [102, 100, 100, 255]);
+        actual.pixels[12] = 200;
+
+        let comparison = compare_frames(&expected, &actual, 2);
+        assert_eq!(comparison.mismatched_pixels, 1);
+        assert_eq!(comparison.max_channel_difference, 100);
+        assert_eq!(comparison.diff.unwrap().get_pixel(1, 1), [255, 0, 0, 255]);
+
+        assert!(compare_frames(&expected, &expected, 0).is_match());
+        assert!(!compare_frames(&expected, &actual, 1).is_match());
+    }
+
+    #[test]
+    fn test_compare_frames_size_mismatch() {
+        let comparison = compare_frames(
+            &solid_frame(2, 2, [0, 0, 0, 255]),
+            &solid_frame(3, 2, [0, 0, 0, 255]),
+            255,
+        );
+        assert!(!comparison.is_match());
+        assert!(!comparison.is_within(usize::MAX));
+        assert!(comparison.diff.is_none());
+        assert_eq!(
+            sibling_path(Path::new("a/b.png"), "diff"),
+            Path::new("a/b.diff.png")
+        );
+    }
+}
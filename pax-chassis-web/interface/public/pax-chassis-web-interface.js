@@ -95,6 +95,8 @@ var Pax = (() => {
         this.size_x = jsonMessage["size_x"];
         this.size_y = jsonMessage["size_y"];
         this.transform = jsonMessage["transform"];
+        this.opacity = jsonMessage["opacity"];
+        this.blend_mode = jsonMessage["blend_mode"];
       }
     }
     cleanUp() {
@@ -102,6 +104,8 @@ var Pax = (() => {
       this.size_x = 0;
       this.size_y = 0;
       this.transform = [];
+      this.opacity = void 0;
+      this.blend_mode = void 0;
     }
   };
 
@@ -117,6 +121,8 @@ var Pax = (() => {
       this.size_y = jsonMessage["size_y"];
       this.transform = jsonMessage["transform"];
       this.depth = jsonMessage["depth"];
+      this.opacity = jsonMessage["opacity"];
+      this.blend_mode = jsonMessage["blend_mode"];
       const styleMessage = jsonMessage["style"];
       if (styleMessage) {
         this.style = this.objectManager.getFromPool(TEXT_STYLE, this.objectManager);
@@ -134,6 +140,8 @@ var Pax = (() => {
       this.size_x = 0;
       this.size_y = 0;
       this.transform = [];
+      this.opacity = void 0;
+      this.blend_mode = void 0;
       this.objectManager.returnToPool(TEXT_STYLE, this.style);
       this.style = void 0;
       this.objectManager.returnToPool(TEXT_STYLE, this.style_link);
@@ -179,12 +187,103 @@ var Pax = (() => {
     }
   };
 
+  // src/classes/messages/button-update-patch.ts
+  var ButtonUpdatePatch = class {
+    fromPatch(jsonMessage) {
+      this.idChain = jsonMessage["id_chain"];
+      this.sizeX = jsonMessage["size_x"];
+      this.sizeY = jsonMessage["size_y"];
+      this.transform = jsonMessage["transform"];
+      this.label = jsonMessage["label"];
+      this.disabled = jsonMessage["disabled"];
+    }
+    cleanUp() {
+      this.idChain = [];
+      this.sizeX = void 0;
+      this.sizeY = void 0;
+      this.transform = void 0;
+      this.label = void 0;
+      this.disabled = void 0;
+    }
+  };
+
+  // src/classes/messages/slider-update-patch.ts
+  var SliderUpdatePatch = class {
+    fromPatch(jsonMessage) {
+      this.idChain = jsonMessage["id_chain"];
+      this.sizeX = jsonMessage["size_x"];
+      this.sizeY = jsonMessage["size_y"];
+      this.transform = jsonMessage["transform"];
+      this.value = jsonMessage["value"];
+      this.min = jsonMessage["min"];
+      this.max = jsonMessage["max"];
+      this.step = jsonMessage["step"];
+      this.disabled = jsonMessage["disabled"];
+    }
+    cleanUp() {
+      this.idChain = [];
+      this.sizeX = void 0;
+      this.sizeY = void 0;
+      this.transform = void 0;
+      this.value = void 0;
+      this.min = void 0;
+      this.max = void 0;
+      this.step = void 0;
+      this.disabled = void 0;
+    }
+  };
+
+  // src/classes/messages/dropdown-update-patch.ts
+  var DropdownUpdatePatch = class {
+    fromPatch(jsonMessage) {
+      this.idChain = jsonMessage["id_chain"];
+      this.sizeX = jsonMessage["size_x"];
+      this.sizeY = jsonMessage["size_y"];
+      this.transform = jsonMessage["transform"];
+      this.options = jsonMessage["options"];
+      this.selectedIndex = jsonMessage["selected_index"];
+      this.disabled = jsonMessage["disabled"];
+    }
+    cleanUp() {
+      this.idChain = [];
+      this.sizeX = void 0;
+      this.sizeY = void 0;
+      this.transform = void 0;
+      this.options = void 0;
+      this.selectedIndex = void 0;
+      this.disabled = void 0;
+    }
+  };
+
+  // src/classes/messages/textbox-update-patch.ts
+  var TextboxUpdatePatch = class {
+    fromPatch(jsonMessage) {
+      this.idChain = jsonMessage["id_chain"];
+      this.sizeX = jsonMessage["size_x"];
+      this.sizeY = jsonMessage["size_y"];
+      this.transform = jsonMessage["transform"];
+      this.text = jsonMessage["text"];
+      this.placeholder = jsonMessage["placeholder"];
+      this.disabled = jsonMessage["disabled"];
+    }
+    cleanUp() {
+      this.idChain = [];
+      this.sizeX = void 0;
+      this.sizeY = void 0;
+      this.transform = void 0;
+      this.text = void 0;
+      this.placeholder = void 0;
+      this.disabled = void 0;
+    }
+  };
+
   // src/utils/constants.ts
   var NATIVE_OVERLAY_CLASS = "native-overlay";
   var CANVAS_CLASS = "canvas";
   var SCROLLER_CONTAINER = "scroller-container";
   var INNER_PANE = "inner-pane";
   var NATIVE_LEAF_CLASS = "native-leaf";
+  var NATIVE_FORM_CONTROL_CLASS = "native-form-control";
 
   // src/utils/helpers.ts
   async function readImageToByteBuffer(imagePath) {
@@ -610,6 +709,7 @@ var Pax = (() => {
       this.objectManager = objectManager2;
       this.canvases = /* @__PURE__ */ new Map();
       this.scrollers = /* @__PURE__ */ new Map();
+      this.formControls = /* @__PURE__ */ new Map();
       this.baseOcclusionContext = objectManager2.getFromPool(OCCLUSION_CONTEXT, objectManager2);
       this.registeredFontFaces = /* @__PURE__ */ new Set();
     }
@@ -783,6 +883,12 @@ var Pax = (() => {
       if (patch.transform != null) {
         leaf.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
       }
+      if (patch.opacity != null) {
+        leaf.style.opacity = String(patch.opacity);
+      }
+      if (patch.blend_mode != null) {
+        leaf.style.mixBlendMode = patch.blend_mode;
+      }
     }
     textDelete(id_chain) {
       let oldNode = this.textNodes[id_chain];
@@ -827,6 +933,162 @@ var Pax = (() => {
         this.scrollers.delete(arrayToKey(idChain));
       }
     }
+    //Form controls are interactive leaves: each reports its own value changes, focus and blur
+    //as interrupts addressed by its id_chain, rather than by ray-casting
+    sendFormInterrupt(interrupt, args) {
+      this.chassis.interrupt(JSON.stringify({ [interrupt]: args }), []);
+    }
+    formControlCreate(patch, control) {
+      console.assert(patch.idChain != null);
+      console.assert(patch.zIndex != null);
+      let idChain = patch.idChain;
+      control.setAttribute("class", NATIVE_LEAF_CLASS);
+      control.classList.add(NATIVE_FORM_CONTROL_CLASS);
+      control.setAttribute("id_chain", String(idChain));
+      control.addEventListener("focus", () => {
+        this.sendFormInterrupt("FormFocus", { "id_chain": idChain });
+      });
+      control.addEventListener("blur", () => {
+        this.sendFormInterrupt("FormBlur", { "id_chain": idChain });
+      });
+      let scroller_id;
+      if (patch.scrollerIds != null) {
+        let length = patch.scrollerIds.length;
+        if (length != 0) {
+          scroller_id = patch.scrollerIds[length - 1];
+        }
+      }
+      _NativeElementPool.addNativeElement(
+        control,
+        this.baseOcclusionContext,
+        this.scrollers,
+        idChain,
+        scroller_id,
+        patch.zIndex
+      );
+      this.formControls.set(arrayToKey(idChain), control);
+    }
+    formControlUpdate(control, patch) {
+      if (patch.sizeX != null) {
+        control.style.width = patch.sizeX + "px";
+      }
+      if (patch.sizeY != null) {
+        control.style.height = patch.sizeY + "px";
+      }
+      if (patch.transform != null) {
+        control.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
+      }
+      if (patch.disabled != null) {
+        control.disabled = patch.disabled;
+      }
+    }
+    formControlDelete(idChain) {
+      let control = this.formControls.get(arrayToKey(idChain));
+      if (control) {
+        control.parentElement?.removeChild(control);
+        this.formControls.delete(arrayToKey(idChain));
+      }
+    }
+    //Keyboard focus is tracked by the engine, which tells us when to move DOM focus to or from a form control
+    focus(idChain) {
+      let control = this.formControls.get(arrayToKey(idChain));
+      if (control && document.activeElement !== control) {
+        control.focus();
+      }
+    }
+    blur(idChain) {
+      let control = this.formControls.get(arrayToKey(idChain));
+      if (control && document.activeElement === control) {
+        control.blur();
+      }
+    }
+    buttonCreate(patch) {
+      let button = document.createElement("button");
+      let idChain = patch.idChain;
+      button.addEventListener("click", () => {
+        this.sendFormInterrupt("FormButtonClick", { "id_chain": idChain });
+      });
+      this.formControlCreate(patch, button);
+    }
+    buttonUpdate(patch) {
+      let button = this.formControls.get(arrayToKey(patch.idChain));
+      console.assert(button !== void 0);
+      this.formControlUpdate(button, patch);
+      if (patch.label != null) {
+        button.textContent = patch.label;
+      }
+    }
+    sliderCreate(patch) {
+      let slider = document.createElement("input");
+      slider.type = "range";
+      let idChain = patch.idChain;
+      slider.addEventListener("input", () => {
+        this.sendFormInterrupt("FormSliderChange", { "id_chain": idChain, "value": slider.valueAsNumber });
+      });
+      this.formControlCreate(patch, slider);
+    }
+    sliderUpdate(patch) {
+      let slider = this.formControls.get(arrayToKey(patch.idChain));
+      console.assert(slider !== void 0);
+      this.formControlUpdate(slider, patch);
+      if (patch.min != null) {
+        slider.min = String(patch.min);
+      }
+      if (patch.max != null) {
+        slider.max = String(patch.max);
+      }
+      if (patch.step != null) {
+        slider.step = String(patch.step);
+      }
+      if (patch.value != null && slider.valueAsNumber != patch.value) {
+        slider.value = String(patch.value);
+      }
+    }
+    dropdownCreate(patch) {
+      let dropdown = document.createElement("select");
+      let idChain = patch.idChain;
+      dropdown.addEventListener("change", () => {
+        this.sendFormInterrupt("FormDropdownChange", { "id_chain": idChain, "selected_index": dropdown.selectedIndex });
+      });
+      this.formControlCreate(patch, dropdown);
+    }
+    dropdownUpdate(patch) {
+      let dropdown = this.formControls.get(arrayToKey(patch.idChain));
+      console.assert(dropdown !== void 0);
+      this.formControlUpdate(dropdown, patch);
+      let selectedIndex = patch.selectedIndex ?? dropdown.selectedIndex;
+      if (patch.options != null) {
+        dropdown.innerHTML = "";
+        patch.options.forEach((label) => {
+          let option = document.createElement("option");
+          option.textContent = label;
+          dropdown.appendChild(option);
+        });
+      }
+      if (dropdown.selectedIndex != selectedIndex) {
+        dropdown.selectedIndex = selectedIndex;
+      }
+    }
+    textboxCreate(patch) {
+      let textbox = document.createElement("input");
+      textbox.type = "text";
+      let idChain = patch.idChain;
+      textbox.addEventListener("input", () => {
+        this.sendFormInterrupt("FormTextboxChange", { "id_chain": idChain, "text": textbox.value });
+      });
+      this.formControlCreate(patch, textbox);
+    }
+    textboxUpdate(patch) {
+      let textbox = this.formControls.get(arrayToKey(patch.idChain));
+      console.assert(textbox !== void 0);
+      this.formControlUpdate(textbox, patch);
+      if (patch.text != null && textbox.value != patch.text) {
+        textbox.value = patch.text;
+      }
+      if (patch.placeholder != null) {
+        textbox.placeholder = patch.placeholder;
+      }
+    }
     async imageLoad(patch, chassis) {
       function getScriptBasePath(scriptName) {
         const scripts = document.getElementsByTagName("script");
@@ -1075,6 +1337,10 @@ var Pax = (() => {
   var IMAGE_LOAD_PATCH = "IMAGE LOAD PATCH";
   var SCROLLER_UPDATE_PATCH = "Scroller Update Patch";
   var TEXT_UPDATE_PATCH = "Text Update Patch";
+  var BUTTON_UPDATE_PATCH = "Button Update Patch";
+  var SLIDER_UPDATE_PATCH = "Slider Update Patch";
+  var DROPDOWN_UPDATE_PATCH = "Dropdown Update Patch";
+  var TEXTBOX_UPDATE_PATCH = "Textbox Update Patch";
   var LAYER = "LAYER";
   var OCCLUSION_CONTEXT = "Occlusion Context";
   var SCROLLER = "Scroller";
@@ -1159,6 +1425,34 @@ var Pax = (() => {
         patch.cleanUp();
       }
     },
+    {
+      name: BUTTON_UPDATE_PATCH,
+      factory: () => new ButtonUpdatePatch(),
+      cleanUp: (patch) => {
+        patch.cleanUp();
+      }
+    },
+    {
+      name: SLIDER_UPDATE_PATCH,
+      factory: () => new SliderUpdatePatch(),
+      cleanUp: (patch) => {
+        patch.cleanUp();
+      }
+    },
+    {
+      name: DROPDOWN_UPDATE_PATCH,
+      factory: () => new DropdownUpdatePatch(),
+      cleanUp: (patch) => {
+        patch.cleanUp();
+      }
+    },
+    {
+      name: TEXTBOX_UPDATE_PATCH,
+      factory: () => new TextboxUpdatePatch(),
+      cleanUp: (patch) => {
+        patch.cleanUp();
+      }
+    },
     {
       name: LAYER,
       factory: (objectManager2) => new Layer(objectManager2),
@@ -1254,7 +1548,9 @@ var Pax = (() => {
           "modifiers": convertModifiers(evt)
         }
       };
-      chassis.interrupt(JSON.stringify(clickEvent), []);
+      if (chassis.interrupt(JSON.stringify(clickEvent), [])) {
+        evt.preventDefault();
+      }
       let jabEvent = {
         "Jab": {
           "x": evt.clientX,
@@ -1272,7 +1568,9 @@ var Pax = (() => {
           "modifiers": convertModifiers(evt)
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
     layer.addEventListener("mousemove", (evt) => {
       let event = {
@@ -1283,7 +1581,9 @@ var Pax = (() => {
           "modifiers": convertModifiers(evt)
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
     layer.addEventListener("wheel", (evt) => {
       let event = {
@@ -1295,8 +1595,10 @@ var Pax = (() => {
           "modifiers": convertModifiers(evt)
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
-    }, { "passive": true, "capture": true });
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
+    }, { "passive": false, "capture": true });
     layer.addEventListener("mousedown", (evt) => {
       let event = {
         "MouseDown": {
@@ -1306,7 +1608,9 @@ var Pax = (() => {
           "modifiers": convertModifiers(evt)
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
     layer.addEventListener("mouseup", (evt) => {
       let event = {
@@ -1317,7 +1621,9 @@ var Pax = (() => {
           "modifiers": convertModifiers(evt)
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
     layer.addEventListener("mouseover", (evt) => {
       let event = {
@@ -1328,7 +1634,9 @@ var Pax = (() => {
           "modifiers": convertModifiers(evt)
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
     layer.addEventListener("mouseout", (evt) => {
       let event = {
@@ -1339,7 +1647,9 @@ var Pax = (() => {
           "modifiers": convertModifiers(evt)
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
     layer.addEventListener("contextmenu", (evt) => {
       let event = {
@@ -1350,7 +1660,9 @@ var Pax = (() => {
           "modifiers": convertModifiers(evt)
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
     layer.addEventListener("touchstart", (evt) => {
       let event = {
@@ -1391,6 +1703,9 @@ var Pax = (() => {
       });
     }, { "passive": true, "capture": true });
     layer.addEventListener("keydown", (evt) => {
+      if (evt.key === "Tab") {
+        evt.preventDefault();
+      }
       let event = {
         "KeyDown": {
           "key": evt.key,
@@ -1398,7 +1713,9 @@ var Pax = (() => {
           "is_repeat": evt.repeat
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
     layer.addEventListener("keyup", (evt) => {
       let event = {
@@ -1408,7 +1725,9 @@ var Pax = (() => {
           "is_repeat": evt.repeat
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
     layer.addEventListener("keypress", (evt) => {
       let event = {
@@ -1418,7 +1737,9 @@ var Pax = (() => {
           "is_repeat": evt.repeat
         }
       };
-      chassis.interrupt(JSON.stringify(event), []);
+      if (chassis.interrupt(JSON.stringify(event), [])) {
+        evt.preventDefault();
+      }
     }, true);
   }
 
@@ -1464,14 +1785,33 @@ var Pax = (() => {
       let { chassis, get_latest_memory } = await loadWasmModule(extensionlessUrl);
       isMobile = /Android|webOS|iPhone|iPad|iPod|BlackBerry|IEMobile|Opera Mini/i.test(navigator.userAgent);
       nativePool.build(chassis, isMobile, mount2);
+      connectHotReload(chassis).then();
       requestAnimationFrame(renderLoop.bind(renderLoop, chassis, mount2, get_latest_memory));
     } catch (error) {
       console.error("Failed to load or instantiate Wasm module:", error);
     }
   }
+  async function connectHotReload(chassis) {
+    let config;
+    try {
+      const response = await fetch("hot-reload.json");
+      if (!response.ok) {
+        return;
+      }
+      config = await response.json();
+    } catch {
+      return;
+    }
+    const socket = new WebSocket(`ws://127.0.0.1:${config.port}`);
+    socket.onmessage = (event) => {
+      chassis.hot_reload(event.data);
+    };
+  }
   function renderLoop(chassis, mount2, get_latest_memory) {
     nativePool.sendScrollerValues();
-    nativePool.clearCanvases();
+    if (chassis.needs_render()) {
+      nativePool.clearCanvases();
+    }
     const memorySliceSpec = chassis.tick();
     const latestMemory = get_latest_memory();
     const memoryBuffer = new Uint8Array(latestMemory.buffer);
@@ -1539,6 +1879,68 @@ var Pax = (() => {
       } else if (unwrapped_msg["ScrollerDelete"]) {
         let msg = unwrapped_msg["ScrollerDelete"];
         nativePool.scrollerDelete(msg);
+      } else if (unwrapped_msg["ButtonCreate"]) {
+        let msg = unwrapped_msg["ButtonCreate"];
+        let patch = objectManager2.getFromPool(ANY_CREATE_PATCH);
+        patch.fromPatch(msg);
+        nativePool.buttonCreate(patch);
+      } else if (unwrapped_msg["ButtonUpdate"]) {
+        let msg = unwrapped_msg["ButtonUpdate"];
+        let patch = objectManager2.getFromPool(BUTTON_UPDATE_PATCH);
+        patch.fromPatch(msg);
+        nativePool.buttonUpdate(patch);
+        objectManager2.returnToPool(BUTTON_UPDATE_PATCH, patch);
+      } else if (unwrapped_msg["ButtonDelete"]) {
+        let msg = unwrapped_msg["ButtonDelete"];
+        nativePool.formControlDelete(msg);
+      } else if (unwrapped_msg["SliderCreate"]) {
+        let msg = unwrapped_msg["SliderCreate"];
+        let patch = objectManager2.getFromPool(ANY_CREATE_PATCH);
+        patch.fromPatch(msg);
+        nativePool.sliderCreate(patch);
+      } else if (unwrapped_msg["SliderUpdate"]) {
+        let msg = unwrapped_msg["SliderUpdate"];
+        let patch = objectManager2.getFromPool(SLIDER_UPDATE_PATCH);
+        patch.fromPatch(msg);
+        nativePool.sliderUpdate(patch);
+        objectManager2.returnToPool(SLIDER_UPDATE_PATCH, patch);
+      } else if (unwrapped_msg["SliderDelete"]) {
+        let msg = unwrapped_msg["SliderDelete"];
+        nativePool.formControlDelete(msg);
+      } else if (unwrapped_msg["DropdownCreate"]) {
+        let msg = unwrapped_msg["DropdownCreate"];
+        let patch = objectManager2.getFromPool(ANY_CREATE_PATCH);
+        patch.fromPatch(msg);
+        nativePool.dropdownCreate(patch);
+      } else if (unwrapped_msg["DropdownUpdate"]) {
+        let msg = unwrapped_msg["DropdownUpdate"];
+        let patch = objectManager2.getFromPool(DROPDOWN_UPDATE_PATCH);
+        patch.fromPatch(msg);
+        nativePool.dropdownUpdate(patch);
+        objectManager2.returnToPool(DROPDOWN_UPDATE_PATCH, patch);
+      } else if (unwrapped_msg["DropdownDelete"]) {
+        let msg = unwrapped_msg["DropdownDelete"];
+        nativePool.formControlDelete(msg);
+      } else if (unwrapped_msg["TextboxCreate"]) {
+        let msg = unwrapped_msg["TextboxCreate"];
+        let patch = objectManager2.getFromPool(ANY_CREATE_PATCH);
+        patch.fromPatch(msg);
+        nativePool.textboxCreate(patch);
+      } else if (unwrapped_msg["TextboxUpdate"]) {
+        let msg = unwrapped_msg["TextboxUpdate"];
+        let patch = objectManager2.getFromPool(TEXTBOX_UPDATE_PATCH);
+        patch.fromPatch(msg);
+        nativePool.textboxUpdate(patch);
+        objectManager2.returnToPool(TEXTBOX_UPDATE_PATCH, patch);
+      } else if (unwrapped_msg["TextboxDelete"]) {
+        let msg = unwrapped_msg["TextboxDelete"];
+        nativePool.formControlDelete(msg);
+      } else if (unwrapped_msg["Focus"]) {
+        let msg = unwrapped_msg["Focus"];
+        nativePool.focus(msg);
+      } else if (unwrapped_msg["Blur"]) {
+        let msg = unwrapped_msg["Blur"];
+        nativePool.blur(msg);
       }
     });
   }
@@ -0,0 +1,26 @@
+export class ButtonUpdatePatch {
+    public idChain?: number[];
+    public sizeX?: number;
+    public sizeY?: number;
+    public transform?: number[];
+    public label?: string;
+    public disabled?: boolean;
+
+    fromPatch(jsonMessage: any) {
+        this.idChain = jsonMessage["id_chain"];
+        this.sizeX = jsonMessage["size_x"];
+        this.sizeY = jsonMessage["size_y"];
+        this.transform = jsonMessage["transform"];
+        this.label = jsonMessage["label"];
+        this.disabled = jsonMessage["disabled"];
+    }
+
+    cleanUp(){
+        this.idChain = [];
+        this.sizeX = undefined;
+        this.sizeY = undefined;
+        this.transform = undefined;
+        this.label = undefined;
+        this.disabled = undefined;
+    }
+}
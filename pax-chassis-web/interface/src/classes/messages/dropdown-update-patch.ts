@@ -0,0 +1,29 @@
+export class DropdownUpdatePatch {
+    public idChain?: number[];
+    public sizeX?: number;
+    public sizeY?: number;
+    public transform?: number[];
+    public options?: string[];
+    public selectedIndex?: number;
+    public disabled?: boolean;
+
+    fromPatch(jsonMessage: any) {
+        this.idChain = jsonMessage["id_chain"];
+        this.sizeX = jsonMessage["size_x"];
+        this.sizeY = jsonMessage["size_y"];
+        this.transform = jsonMessage["transform"];
+        this.options = jsonMessage["options"];
+        this.selectedIndex = jsonMessage["selected_index"];
+        this.disabled = jsonMessage["disabled"];
+    }
+
+    cleanUp(){
+        this.idChain = [];
+        this.sizeX = undefined;
+        this.sizeY = undefined;
+        this.transform = undefined;
+        this.options = undefined;
+        this.selectedIndex = undefined;
+        this.disabled = undefined;
+    }
+}
@@ -3,12 +3,16 @@ export class FrameUpdatePatch {
     public size_x?: number;
     public size_y?: number;
     public transform?: number[];
+    public opacity?: number;
+    public blend_mode?: string;
     fromPatch(jsonMessage: any) {
         if(jsonMessage != null) {
             this.id_chain = jsonMessage["id_chain"];
             this.size_x = jsonMessage["size_x"];
             this.size_y = jsonMessage["size_y"];
             this.transform = jsonMessage["transform"];
+            this.opacity = jsonMessage["opacity"];
+            this.blend_mode = jsonMessage["blend_mode"];
         }
     }
 
@@ -17,5 +21,7 @@ export class FrameUpdatePatch {
         this.size_x = 0;
         this.size_y = 0;
         this.transform = [];
+        this.opacity = undefined;
+        this.blend_mode = undefined;
     }
 }
\ No newline at end of file
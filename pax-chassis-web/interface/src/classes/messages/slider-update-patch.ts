@@ -0,0 +1,35 @@
+export class SliderUpdatePatch {
+    public idChain?: number[];
+    public sizeX?: number;
+    public sizeY?: number;
+    public transform?: number[];
+    public value?: number;
+    public min?: number;
+    public max?: number;
+    public step?: number;
+    public disabled?: boolean;
+
+    fromPatch(jsonMessage: any) {
+        this.idChain = jsonMessage["id_chain"];
+        this.sizeX = jsonMessage["size_x"];
+        this.sizeY = jsonMessage["size_y"];
+        this.transform = jsonMessage["transform"];
+        this.value = jsonMessage["value"];
+        this.min = jsonMessage["min"];
+        this.max = jsonMessage["max"];
+        this.step = jsonMessage["step"];
+        this.disabled = jsonMessage["disabled"];
+    }
+
+    cleanUp(){
+        this.idChain = [];
+        this.sizeX = undefined;
+        this.sizeY = undefined;
+        this.transform = undefined;
+        this.value = undefined;
+        this.min = undefined;
+        this.max = undefined;
+        this.step = undefined;
+        this.disabled = undefined;
+    }
+}
@@ -11,6 +11,8 @@ export class TextUpdatePatch {
     public style?: TextStyle;
     public style_link?: TextStyle;
     public depth?: number;
+    public opacity?: number;
+    public blend_mode?: string;
     objectManager: ObjectManager;
 
     constructor(objectManager: ObjectManager) {
@@ -24,6 +26,8 @@ export class TextUpdatePatch {
         this.size_y = jsonMessage["size_y"];
         this.transform = jsonMessage["transform"];
         this.depth = jsonMessage["depth"];
+        this.opacity = jsonMessage["opacity"];
+        this.blend_mode = jsonMessage["blend_mode"];
 
         const styleMessage = jsonMessage["style"];
         if (styleMessage) {
@@ -44,6 +48,8 @@ export class TextUpdatePatch {
         this.size_x = 0;
         this.size_y = 0;
         this.transform = [];
+        this.opacity = undefined;
+        this.blend_mode = undefined;
         this.objectManager.returnToPool(TEXT_STYLE, this.style);
         this.style = undefined;
         this.objectManager.returnToPool(TEXT_STYLE, this.style_link);
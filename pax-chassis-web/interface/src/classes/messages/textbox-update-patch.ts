@@ -0,0 +1,29 @@
+export class TextboxUpdatePatch {
+    public idChain?: number[];
+    public sizeX?: number;
+    public sizeY?: number;
+    public transform?: number[];
+    public text?: string;
+    public placeholder?: string;
+    public disabled?: boolean;
+
+    fromPatch(jsonMessage: any) {
+        this.idChain = jsonMessage["id_chain"];
+        this.sizeX = jsonMessage["size_x"];
+        this.sizeY = jsonMessage["size_y"];
+        this.transform = jsonMessage["transform"];
+        this.text = jsonMessage["text"];
+        this.placeholder = jsonMessage["placeholder"];
+        this.disabled = jsonMessage["disabled"];
+    }
+
+    cleanUp(){
+        this.idChain = [];
+        this.sizeX = undefined;
+        this.sizeY = undefined;
+        this.transform = undefined;
+        this.text = undefined;
+        this.placeholder = undefined;
+        this.disabled = undefined;
+    }
+}
@@ -1,6 +1,6 @@
 // @ts-ignore
 import {Scroller} from "./scroller";
-import {NATIVE_LEAF_CLASS} from "../utils/constants";
+import {NATIVE_FORM_CONTROL_CLASS, NATIVE_LEAF_CLASS} from "../utils/constants";
 import {AnyCreatePatch} from "./messages/any-create-patch";
 // @ts-ignore
 import snarkdown from 'snarkdown';
@@ -8,6 +8,10 @@ import {TextUpdatePatch} from "./messages/text-update-patch";
 import {FrameUpdatePatch} from "./messages/frame-update-patch";
 import {ScrollerUpdatePatch} from "./messages/scroller-update-patch";
 import {ImageLoadPatch} from "./messages/image-load-patch";
+import {ButtonUpdatePatch} from "./messages/button-update-patch";
+import {SliderUpdatePatch} from "./messages/slider-update-patch";
+import {DropdownUpdatePatch} from "./messages/dropdown-update-patch";
+import {TextboxUpdatePatch} from "./messages/textbox-update-patch";
 import {OcclusionContext} from "./occlusion-context";
 import {ObjectManager} from "../pools/object-manager";
 import {DIV, OBJECT, OCCLUSION_CONTEXT, SCROLLER} from "../pools/supported-objects";
@@ -20,6 +24,7 @@ export class NativeElementPool {
     private scrollers: Map<string, Scroller>;
     baseOcclusionContext: OcclusionContext;
     private textNodes = {};
+    private formControls: Map<string, HTMLElement>;
     private chassis? : PaxChassisWeb;
     private objectManager: ObjectManager;
     registeredFontFaces: Set<string>;
@@ -30,6 +35,7 @@ export class NativeElementPool {
         this.objectManager = objectManager;
         this.canvases = new Map();
         this.scrollers = new Map();
+        this.formControls = new Map();
         this.baseOcclusionContext = objectManager.getFromPool(OCCLUSION_CONTEXT, objectManager);
         this.registeredFontFaces = new Set<string>();
     }
@@ -232,6 +238,14 @@ export class NativeElementPool {
         if (patch.transform != null) {
             leaf.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
         }
+
+        // Handle opacity and blend_mode, already accumulated from ancestors
+        if (patch.opacity != null) {
+            leaf.style.opacity = String(patch.opacity);
+        }
+        if (patch.blend_mode != null) {
+            leaf.style.mixBlendMode = patch.blend_mode;
+        }
     }
 
     textDelete(id_chain: number[]) {
@@ -334,6 +348,174 @@ export class NativeElementPool {
 
 
 
+    //Form controls are interactive leaves: each reports its own value changes, focus and blur
+    //as interrupts addressed by its id_chain, rather than by ray-casting
+    private sendFormInterrupt(interrupt: string, args: object) {
+        this.chassis!.interrupt(JSON.stringify({[interrupt]: args}), []);
+    }
+
+    private formControlCreate(patch: AnyCreatePatch, control: HTMLElement) {
+        console.assert(patch.idChain != null);
+        console.assert(patch.zIndex != null);
+
+        let idChain = patch.idChain!;
+        control.setAttribute("class", NATIVE_LEAF_CLASS);
+        control.classList.add(NATIVE_FORM_CONTROL_CLASS);
+        control.setAttribute("id_chain", String(idChain));
+        control.addEventListener("focus", () => {
+            this.sendFormInterrupt("FormFocus", {"id_chain": idChain});
+        });
+        control.addEventListener("blur", () => {
+            this.sendFormInterrupt("FormBlur", {"id_chain": idChain});
+        });
+
+        let scroller_id;
+        if(patch.scrollerIds != null){
+            let length = patch.scrollerIds.length;
+            if(length != 0) {
+                scroller_id = patch.scrollerIds[length-1];
+            }
+        }
+        NativeElementPool.addNativeElement(control, this.baseOcclusionContext,
+            this.scrollers, idChain, scroller_id, patch.zIndex!);
+        this.formControls.set(arrayToKey(idChain), control);
+    }
+
+    private formControlUpdate(control: HTMLElement, patch: {sizeX?: number, sizeY?: number, transform?: number[], disabled?: boolean}) {
+        if (patch.sizeX != null) {
+            control.style.width = patch.sizeX + "px";
+        }
+        if (patch.sizeY != null) {
+            control.style.height = patch.sizeY + "px";
+        }
+        if (patch.transform != null) {
+            control.style.transform = packAffineCoeffsIntoMatrix3DString(patch.transform);
+        }
+        if (patch.disabled != null) {
+            (control as HTMLButtonElement | HTMLInputElement | HTMLSelectElement).disabled = patch.disabled;
+        }
+    }
+
+    formControlDelete(idChain: number[]) {
+        let control = this.formControls.get(arrayToKey(idChain));
+        if (control) {
+            control.parentElement?.removeChild(control);
+            this.formControls.delete(arrayToKey(idChain));
+        }
+    }
+
+    //Keyboard focus is tracked by the engine, which tells us when to move DOM focus to or from a form control
+    focus(idChain: number[]) {
+        let control = this.formControls.get(arrayToKey(idChain));
+        if (control && document.activeElement !== control) {
+            control.focus();
+        }
+    }
+
+    blur(idChain: number[]) {
+        let control = this.formControls.get(arrayToKey(idChain));
+        if (control && document.activeElement === control) {
+            control.blur();
+        }
+    }
+
+    buttonCreate(patch: AnyCreatePatch) {
+        let button = document.createElement("button");
+        let idChain = patch.idChain!;
+        button.addEventListener("click", () => {
+            this.sendFormInterrupt("FormButtonClick", {"id_chain": idChain});
+        });
+        this.formControlCreate(patch, button);
+    }
+
+    buttonUpdate(patch: ButtonUpdatePatch) {
+        let button = this.formControls.get(arrayToKey(patch.idChain!)) as HTMLButtonElement;
+        console.assert(button !== undefined);
+        this.formControlUpdate(button, patch);
+        if (patch.label != null) {
+            button.textContent = patch.label;
+        }
+    }
+
+    sliderCreate(patch: AnyCreatePatch) {
+        let slider = document.createElement("input");
+        slider.type = "range";
+        let idChain = patch.idChain!;
+        slider.addEventListener("input", () => {
+            this.sendFormInterrupt("FormSliderChange", {"id_chain": idChain, "value": slider.valueAsNumber});
+        });
+        this.formControlCreate(patch, slider);
+    }
+
+    sliderUpdate(patch: SliderUpdatePatch) {
+        let slider = this.formControls.get(arrayToKey(patch.idChain!)) as HTMLInputElement;
+        console.assert(slider !== undefined);
+        this.formControlUpdate(slider, patch);
+        //bounds first, so that the browser doesn't clamp `value` to stale bounds
+        if (patch.min != null) {
+            slider.min = String(patch.min);
+        }
+        if (patch.max != null) {
+            slider.max = String(patch.max);
+        }
+        if (patch.step != null) {
+            slider.step = String(patch.step);
+        }
+        if (patch.value != null && slider.valueAsNumber != patch.value) {
+            slider.value = String(patch.value);
+        }
+    }
+
+    dropdownCreate(patch: AnyCreatePatch) {
+        let dropdown = document.createElement("select");
+        let idChain = patch.idChain!;
+        dropdown.addEventListener("change", () => {
+            this.sendFormInterrupt("FormDropdownChange", {"id_chain": idChain, "selected_index": dropdown.selectedIndex});
+        });
+        this.formControlCreate(patch, dropdown);
+    }
+
+    dropdownUpdate(patch: DropdownUpdatePatch) {
+        let dropdown = this.formControls.get(arrayToKey(patch.idChain!)) as HTMLSelectElement;
+        console.assert(dropdown !== undefined);
+        this.formControlUpdate(dropdown, patch);
+        let selectedIndex = patch.selectedIndex ?? dropdown.selectedIndex;
+        if (patch.options != null) {
+            dropdown.innerHTML = "";
+            patch.options.forEach((label) => {
+                let option = document.createElement("option");
+                option.textContent = label;
+                dropdown.appendChild(option);
+            });
+        }
+        if (dropdown.selectedIndex != selectedIndex) {
+            dropdown.selectedIndex = selectedIndex;
+        }
+    }
+
+    textboxCreate(patch: AnyCreatePatch) {
+        let textbox = document.createElement("input");
+        textbox.type = "text";
+        let idChain = patch.idChain!;
+        textbox.addEventListener("input", () => {
+            this.sendFormInterrupt("FormTextboxChange", {"id_chain": idChain, "text": textbox.value});
+        });
+        this.formControlCreate(patch, textbox);
+    }
+
+    textboxUpdate(patch: TextboxUpdatePatch) {
+        let textbox = this.formControls.get(arrayToKey(patch.idChain!)) as HTMLInputElement;
+        console.assert(textbox !== undefined);
+        this.formControlUpdate(textbox, patch);
+        //leave the caret alone when echoing back what the user just typed
+        if (patch.text != null && textbox.value != patch.text) {
+            textbox.value = patch.text;
+        }
+        if (patch.placeholder != null) {
+            textbox.placeholder = patch.placeholder;
+        }
+    }
+
     async imageLoad(patch: ImageLoadPatch, chassis: PaxChassisWeb) {
 
         //Check the full path of our index.js; use the prefix of this path also for our image assets
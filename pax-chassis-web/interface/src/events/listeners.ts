@@ -49,7 +49,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "modifiers": convertModifiers(evt)
             }
         };
-        chassis.interrupt(JSON.stringify(clickEvent), []);
+        if (chassis.interrupt(JSON.stringify(clickEvent), [])) {
+            evt.preventDefault();
+        }
         let jabEvent = {
             "Jab": {
                 "x": evt.clientX,
@@ -68,7 +70,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "modifiers": convertModifiers(evt)
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     layer.addEventListener('mousemove', (evt) => {
@@ -80,7 +84,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "modifiers": convertModifiers(evt)
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     layer.addEventListener('wheel', (evt) => {
@@ -93,8 +99,10 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "modifiers": convertModifiers(evt)
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
-    }, {"passive": true, "capture": true});
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
+    }, {"passive": false, "capture": true});
     // @ts-ignore
     layer.addEventListener('mousedown', (evt) => {
         let event = {
@@ -105,7 +113,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "modifiers": convertModifiers(evt)
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     layer.addEventListener('mouseup', (evt) => {
@@ -117,7 +127,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "modifiers": convertModifiers(evt)
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     layer.addEventListener('mouseover', (evt) => {
@@ -129,7 +141,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "modifiers": convertModifiers(evt)
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     layer.addEventListener('mouseout', (evt) => {
@@ -141,7 +155,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "modifiers": convertModifiers(evt)
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     layer.addEventListener('contextmenu', (evt) => {
@@ -153,7 +169,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "modifiers": convertModifiers(evt)
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     layer.addEventListener('touchstart', (evt) => {
@@ -200,6 +218,10 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
     }, {"passive": true, "capture": true});
     // @ts-ignore
     layer.addEventListener('keydown', (evt) => {
+        if (evt.key === "Tab") {
+            //The engine owns tab order, and moves DOM focus to form controls itself
+            evt.preventDefault();
+        }
         let event = {
             "KeyDown": {
                 "key": evt.key,
@@ -207,7 +229,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "is_repeat": evt.repeat
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     layer.addEventListener('keyup', (evt) => {
@@ -218,7 +242,9 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "is_repeat": evt.repeat
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
     // @ts-ignore
     layer.addEventListener('keypress', (evt) => {
@@ -229,6 +255,8 @@ export function setupEventListeners(chassis: PaxChassisWeb, layer: any) {
                 "is_repeat": evt.repeat
             }
         };
-        chassis.interrupt(JSON.stringify(event), []);
+        if (chassis.interrupt(JSON.stringify(event), [])) {
+            evt.preventDefault();
+        }
     }, true);
 }
\ No newline at end of file
@@ -7,7 +7,8 @@ import {
     FRAME_UPDATE_PATCH,
     IMAGE_LOAD_PATCH, SCROLLER_UPDATE_PATCH,
     SUPPORTED_OBJECTS,
-    TEXT_UPDATE_PATCH
+    TEXT_UPDATE_PATCH,
+    BUTTON_UPDATE_PATCH, SLIDER_UPDATE_PATCH, DROPDOWN_UPDATE_PATCH, TEXTBOX_UPDATE_PATCH
 } from "./pools/supported-objects";
 import {NativeElementPool} from "./classes/native-element-pool";
 import {AnyCreatePatch} from "./classes/messages/any-create-patch";
@@ -15,6 +16,10 @@ import {TextUpdatePatch} from "./classes/messages/text-update-patch";
 import {FrameUpdatePatch} from "./classes/messages/frame-update-patch";
 import {ImageLoadPatch} from "./classes/messages/image-load-patch";
 import {ScrollerUpdatePatch} from "./classes/messages/scroller-update-patch";
+import {ButtonUpdatePatch} from "./classes/messages/button-update-patch";
+import {SliderUpdatePatch} from "./classes/messages/slider-update-patch";
+import {DropdownUpdatePatch} from "./classes/messages/dropdown-update-patch";
+import {TextboxUpdatePatch} from "./classes/messages/textbox-update-patch";
 import {setupEventListeners} from "./events/listeners";
 import "./styles/pax-web.css";
 
@@ -70,15 +75,37 @@ async function startRenderLoop(extensionlessUrl: string, mount: Element) {
         let {chassis, get_latest_memory} = await loadWasmModule(extensionlessUrl);
         isMobile = /Android|webOS|iPhone|iPad|iPod|BlackBerry|IEMobile|Opera Mini/i.test(navigator.userAgent);
         nativePool.build(chassis, isMobile, mount);
+        connectHotReload(chassis).then();
         requestAnimationFrame(renderLoop.bind(renderLoop, chassis, mount, get_latest_memory));
     } catch (error) {
         console.error("Failed to load or instantiate Wasm module:", error);
     }
 }
 
+//Under `pax run --hot-reload`, the CLI writes the port of its hot reload server alongside the app
+async function connectHotReload(chassis: PaxChassisWeb) {
+    let config: { port: number };
+    try {
+        const response = await fetch("hot-reload.json");
+        if (!response.ok) {
+            return;
+        }
+        config = await response.json();
+    } catch {
+        return;
+    }
+    const socket = new WebSocket(`ws://127.0.0.1:${config.port}`);
+    socket.onmessage = (event: MessageEvent) => {
+        chassis.hot_reload(event.data);
+    };
+}
+
 function renderLoop (chassis: PaxChassisWeb, mount: Element, get_latest_memory: ()=>any) {
     nativePool.sendScrollerValues();
-    nativePool.clearCanvases();
+    //Idle frames leave the previous frame's canvases untouched
+    if (chassis.needs_render()) {
+        nativePool.clearCanvases();
+    }
 
     const memorySliceSpec = chassis.tick();
     const latestMemory : WebAssembly.Memory = get_latest_memory();
@@ -155,6 +182,68 @@ export function processMessages(messages: any[], chassis: PaxChassisWeb, objectM
         }else if (unwrapped_msg["ScrollerDelete"]) {
             let msg = unwrapped_msg["ScrollerDelete"];
             nativePool.scrollerDelete(msg)
+        }else if(unwrapped_msg["ButtonCreate"]) {
+            let msg = unwrapped_msg["ButtonCreate"]
+            let patch: AnyCreatePatch = objectManager.getFromPool(ANY_CREATE_PATCH);
+            patch.fromPatch(msg);
+            nativePool.buttonCreate(patch);
+        }else if (unwrapped_msg["ButtonUpdate"]){
+            let msg = unwrapped_msg["ButtonUpdate"]
+            let patch: ButtonUpdatePatch = objectManager.getFromPool(BUTTON_UPDATE_PATCH);
+            patch.fromPatch(msg);
+            nativePool.buttonUpdate(patch);
+            objectManager.returnToPool(BUTTON_UPDATE_PATCH, patch);
+        }else if (unwrapped_msg["ButtonDelete"]) {
+            let msg = unwrapped_msg["ButtonDelete"];
+            nativePool.formControlDelete(msg)
+        }else if(unwrapped_msg["SliderCreate"]) {
+            let msg = unwrapped_msg["SliderCreate"]
+            let patch: AnyCreatePatch = objectManager.getFromPool(ANY_CREATE_PATCH);
+            patch.fromPatch(msg);
+            nativePool.sliderCreate(patch);
+        }else if (unwrapped_msg["SliderUpdate"]){
+            let msg = unwrapped_msg["SliderUpdate"]
+            let patch: SliderUpdatePatch = objectManager.getFromPool(SLIDER_UPDATE_PATCH);
+            patch.fromPatch(msg);
+            nativePool.sliderUpdate(patch);
+            objectManager.returnToPool(SLIDER_UPDATE_PATCH, patch);
+        }else if (unwrapped_msg["SliderDelete"]) {
+            let msg = unwrapped_msg["SliderDelete"];
+            nativePool.formControlDelete(msg)
+        }else if(unwrapped_msg["DropdownCreate"]) {
+            let msg = unwrapped_msg["DropdownCreate"]
+            let patch: AnyCreatePatch = objectManager.getFromPool(ANY_CREATE_PATCH);
+            patch.fromPatch(msg);
+            nativePool.dropdownCreate(patch);
+        }else if (unwrapped_msg["DropdownUpdate"]){
+            let msg = unwrapped_msg["DropdownUpdate"]
+            let patch: DropdownUpdatePatch = objectManager.getFromPool(DROPDOWN_UPDATE_PATCH);
+            patch.fromPatch(msg);
+            nativePool.dropdownUpdate(patch);
+            objectManager.returnToPool(DROPDOWN_UPDATE_PATCH, patch);
+        }else if (unwrapped_msg["DropdownDelete"]) {
+            let msg = unwrapped_msg["DropdownDelete"];
+            nativePool.formControlDelete(msg)
+        }else if(unwrapped_msg["TextboxCreate"]) {
+            let msg = unwrapped_msg["TextboxCreate"]
+            let patch: AnyCreatePatch = objectManager.getFromPool(ANY_CREATE_PATCH);
+            patch.fromPatch(msg);
+            nativePool.textboxCreate(patch);
+        }else if (unwrapped_msg["TextboxUpdate"]){
+            let msg = unwrapped_msg["TextboxUpdate"]
+            let patch: TextboxUpdatePatch = objectManager.getFromPool(TEXTBOX_UPDATE_PATCH);
+            patch.fromPatch(msg);
+            nativePool.textboxUpdate(patch);
+            objectManager.returnToPool(TEXTBOX_UPDATE_PATCH, patch);
+        }else if (unwrapped_msg["TextboxDelete"]) {
+            let msg = unwrapped_msg["TextboxDelete"];
+            nativePool.formControlDelete(msg)
+        }else if (unwrapped_msg["Focus"]) {
+            let msg = unwrapped_msg["Focus"];
+            nativePool.focus(msg)
+        }else if (unwrapped_msg["Blur"]) {
+            let msg = unwrapped_msg["Blur"];
+            nativePool.blur(msg)
         }
     })
 }
@@ -3,6 +3,10 @@ import {FrameUpdatePatch} from "../classes/messages/frame-update-patch";
 import {TextUpdatePatch} from "../classes/messages/text-update-patch";
 import {ScrollerUpdatePatch} from "../classes/messages/scroller-update-patch";
 import {ImageLoadPatch} from "../classes/messages/image-load-patch";
+import {ButtonUpdatePatch} from "../classes/messages/button-update-patch";
+import {SliderUpdatePatch} from "../classes/messages/slider-update-patch";
+import {DropdownUpdatePatch} from "../classes/messages/dropdown-update-patch";
+import {TextboxUpdatePatch} from "../classes/messages/textbox-update-patch";
 import {CANVAS_CLASS} from "../utils/constants";
 import {ObjectManager} from "./object-manager";
 import {Layer} from "../classes/layer";
@@ -19,6 +23,10 @@ export const FRAME_UPDATE_PATCH = "Frame Update Patch";
 export const IMAGE_LOAD_PATCH = "IMAGE LOAD PATCH";
 export const SCROLLER_UPDATE_PATCH = "Scroller Update Patch";
 export const TEXT_UPDATE_PATCH = "Text Update Patch";
+export const BUTTON_UPDATE_PATCH = "Button Update Patch";
+export const SLIDER_UPDATE_PATCH = "Slider Update Patch";
+export const DROPDOWN_UPDATE_PATCH = "Dropdown Update Patch";
+export const TEXTBOX_UPDATE_PATCH = "Textbox Update Patch";
 
 export const LAYER = "LAYER";
 export const OCCLUSION_CONTEXT = "Occlusion Context";
@@ -99,6 +107,26 @@ export let SUPPORTED_OBJECTS = [{
         factory: () => new ScrollerUpdatePatch(),
         cleanUp: (patch: ScrollerUpdatePatch) => {patch.cleanUp()},
     },
+    {
+        name: BUTTON_UPDATE_PATCH,
+        factory: () => new ButtonUpdatePatch(),
+        cleanUp: (patch: ButtonUpdatePatch) => {patch.cleanUp()},
+    },
+    {
+        name: SLIDER_UPDATE_PATCH,
+        factory: () => new SliderUpdatePatch(),
+        cleanUp: (patch: SliderUpdatePatch) => {patch.cleanUp()},
+    },
+    {
+        name: DROPDOWN_UPDATE_PATCH,
+        factory: () => new DropdownUpdatePatch(),
+        cleanUp: (patch: DropdownUpdatePatch) => {patch.cleanUp()},
+    },
+    {
+        name: TEXTBOX_UPDATE_PATCH,
+        factory: () => new TextboxUpdatePatch(),
+        cleanUp: (patch: TextboxUpdatePatch) => {patch.cleanUp()},
+    },
     {
         name: LAYER,
         factory: (objectManager: ObjectManager) => new Layer(objectManager),
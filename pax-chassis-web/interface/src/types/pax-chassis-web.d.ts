@@ -41,13 +41,24 @@ export class PaxChassisWeb {
 /**
 * @param {string} native_interrupt
 * @param {any} additional_payload
+* @returns {boolean}
 */
-  interrupt(native_interrupt: string, additional_payload: any): void;
+  interrupt(native_interrupt: string, additional_payload: any): boolean;
 /**
 * @param {MemorySlice} slice
 */
   deallocate(slice: MemorySlice): void;
 /**
+* Apply a `ComponentTemplateReload` sent by `pax run --hot-reload`
+* @param {string} payload
+*/
+  hot_reload(payload: string): void;
+/**
+* Whether the next `tick` will draw; when `false`, canvases should be left as-is rather than cleared
+* @returns {boolean}
+*/
+  needs_render(): boolean;
+/**
 * @returns {MemorySlice}
 */
   tick(): MemorySlice;
@@ -63,8 +74,10 @@ export interface InitOutput {
   readonly paxchassisweb_add_context: (a: number, b: number, c: number) => void;
   readonly paxchassisweb_send_viewport_update: (a: number, b: number, c: number) => void;
   readonly paxchassisweb_remove_context: (a: number, b: number, c: number) => void;
-  readonly paxchassisweb_interrupt: (a: number, b: number, c: number, d: number) => void;
+  readonly paxchassisweb_interrupt: (a: number, b: number, c: number, d: number) => number;
   readonly paxchassisweb_deallocate: (a: number, b: number) => void;
+  readonly paxchassisweb_hot_reload: (a: number, b: number, c: number) => void;
+  readonly paxchassisweb_needs_render: (a: number) => number;
   readonly paxchassisweb_tick: (a: number) => number;
   readonly __wbg_memoryslice_free: (a: number) => void;
   readonly memoryslice_ptr: (a: number) => number;
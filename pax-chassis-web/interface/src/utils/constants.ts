@@ -3,5 +3,6 @@ export const CANVAS_CLASS = "canvas";
 export const SCROLLER_CONTAINER = "scroller-container"
 export const INNER_PANE = "inner-pane"
 export const NATIVE_LEAF_CLASS = "native-leaf";
+export const NATIVE_FORM_CONTROL_CLASS = "native-form-control";
 export const NATIVE_CLIPPING_CLASS = "native-clipping";
 export const CLIP_PREFIX = "clip"
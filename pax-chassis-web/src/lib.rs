@@ -6,17 +6,19 @@ use std::collections::HashMap;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{window, HtmlCanvasElement};
+use web_sys::{window, CanvasRenderingContext2d, HtmlCanvasElement};
 
 use piet_web::WebRenderContext;
 
 use pax_core::{InstanceRegistry, PaxEngine};
 
+use pax_message::hot_reload::ComponentTemplateReload;
 use pax_message::{ImageLoadInterruptArgs, NativeInterrupt};
 use pax_runtime_api::{
-    ArgsClick, ArgsContextMenu, ArgsDoubleClick, ArgsJab, ArgsKeyDown, ArgsKeyPress, ArgsKeyUp,
-    ArgsMouseDown, ArgsMouseMove, ArgsMouseOut, ArgsMouseOver, ArgsMouseUp, ArgsScroll,
-    ArgsTouchEnd, ArgsTouchMove, ArgsTouchStart, ArgsWheel, KeyboardEventArgs, ModifierKey,
+    ArgsButtonClick, ArgsClick, ArgsContextMenu, ArgsDoubleClick, ArgsDropdownChange, ArgsJab,
+    ArgsKeyDown, ArgsKeyPress, ArgsKeyUp, ArgsMouseDown, ArgsMouseMove, ArgsMouseOut,
+    ArgsMouseOver, ArgsMouseUp, ArgsScroll, ArgsSliderChange, ArgsTextboxChange, ArgsTouchEnd,
+    ArgsTouchMove, ArgsTouchStart, ArgsWheel, BlendMode, KeyboardEventArgs, ModifierKey,
     MouseButton, MouseEventArgs, Touch,
 };
 use serde_json;
@@ -57,6 +59,8 @@ pub fn wasm_memory() -> JsValue {
 pub struct PaxChassisWeb {
     engine: Rc<RefCell<PaxEngine<WebRenderContext<'static>>>>,
     drawing_contexts: HashMap<String, WebRenderContext<'static>>,
+    /// The canvas contexts underlying `drawing_contexts`, shared with the engine's compositor
+    canvas_contexts: Rc<RefCell<HashMap<String, CanvasRenderingContext2d>>>,
 }
 
 #[wasm_bindgen]
@@ -75,7 +79,7 @@ impl PaxChassisWeb {
             pax_cartridge::instantiate_main_component(Rc::clone(&instance_registry));
         let expression_table = pax_cartridge::instantiate_expression_table();
 
-        let engine = pax_core::PaxEngine::new(
+        let mut engine = pax_core::PaxEngine::new(
             main_component_instance,
             expression_table,
             pax_runtime_api::PlatformSpecificLogger::Web(log_wrapper),
@@ -83,12 +87,30 @@ impl PaxChassisWeb {
             instance_registry,
         );
 
+        //canvas `save` / `restore` cover `globalAlpha` and `globalCompositeOperation`, so the engine's
+        //layer save/restore scopes these to each composited subtree
+        let canvas_contexts: Rc<RefCell<HashMap<String, CanvasRenderingContext2d>>> =
+            Rc::new(RefCell::new(HashMap::new()));
+        let compositor_canvas_contexts = Rc::clone(&canvas_contexts);
+        engine.set_compositor(Box::new(
+            move |canvas_id: &str, opacity: f64, blend_mode: BlendMode| {
+                if let Some(context) = compositor_canvas_contexts.borrow().get(canvas_id) {
+                    context.set_global_alpha(opacity);
+                    let _ = context.set_global_composite_operation(match blend_mode {
+                        BlendMode::Normal => "source-over",
+                        _ => blend_mode.css_name(),
+                    });
+                }
+            },
+        ));
+
         let engine_container: Rc<RefCell<PaxEngine<WebRenderContext>>> =
             Rc::new(RefCell::new(engine));
 
         Self {
             engine: engine_container,
             drawing_contexts: HashMap::new(),
+            canvas_contexts,
         }
     }
 
@@ -115,9 +137,13 @@ impl PaxChassisWeb {
         canvas.set_height(height as u32);
         let _ = context.scale(dpr, dpr);
 
+        self.canvas_contexts
+            .borrow_mut()
+            .insert(id.clone(), context.clone());
         let render_context = WebRenderContext::new(context, window.clone());
 
         self.drawing_contexts.insert(id, render_context);
+        self.engine.borrow_mut().invalidate();
     }
 
     pub fn send_viewport_update(&mut self, width: f64, height: f64) {
@@ -125,10 +151,14 @@ impl PaxChassisWeb {
     }
     pub fn remove_context(&mut self, id: String) {
         self.drawing_contexts.remove(&id);
+        self.canvas_contexts.borrow_mut().remove(&id);
+        self.engine.borrow_mut().invalidate();
     }
 
-    pub fn interrupt(&mut self, native_interrupt: String, additional_payload: &JsValue) {
+    /// Returns whether a handler called `prevent_default`, in which case the browser's default action should be suppressed
+    pub fn interrupt(&mut self, native_interrupt: String, additional_payload: &JsValue) -> bool {
         let x: NativeInterrupt = serde_json::from_str(&native_interrupt).unwrap();
+        let mut default_prevented = false;
         match x {
             NativeInterrupt::Image(args) => match args {
                 ImageLoadInterruptArgs::Reference(_ref_args) => {}
@@ -160,17 +190,19 @@ impl PaxChassisWeb {
                                 .collect(),
                         },
                     };
-                    topmost_node.dispatch_click(args_click);
+                    default_prevented = topmost_node.dispatch_click(args_click);
                 }
             }
             NativeInterrupt::Scroll(args) => {
-                let prospective_hit = (*self.engine).borrow().get_focused_element();
+                let prospective_hit = (*self.engine)
+                    .borrow()
+                    .get_topmost_element_beneath_viewport_center();
                 if let Some(topmost_node) = prospective_hit {
                     let args_scroll = ArgsScroll {
                         delta_x: args.delta_x,
                         delta_y: args.delta_y,
                     };
-                    topmost_node.dispatch_scroll(args_scroll);
+                    default_prevented = topmost_node.dispatch_scroll(args_scroll);
                 }
             }
             NativeInterrupt::Jab(args) => {
@@ -182,7 +214,7 @@ impl PaxChassisWeb {
                         x: args.x,
                         y: args.y,
                     };
-                    topmost_node.dispatch_jab(args_jab);
+                    default_prevented = topmost_node.dispatch_jab(args_jab);
                 }
             }
             NativeInterrupt::TouchStart(args) => {
@@ -193,7 +225,7 @@ impl PaxChassisWeb {
                 if let Some(topmost_node) = prospective_hit {
                     let touches = args.touches.iter().map(|x| Touch::from(x)).collect();
                     let args_touch_start = ArgsTouchStart { touches };
-                    topmost_node.dispatch_touch_start(args_touch_start);
+                    default_prevented = topmost_node.dispatch_touch_start(args_touch_start);
                 }
             }
             NativeInterrupt::TouchMove(args) => {
@@ -204,7 +236,7 @@ impl PaxChassisWeb {
                 if let Some(topmost_node) = prospective_hit {
                     let touches = args.touches.iter().map(|x| Touch::from(x)).collect();
                     let args_touch_move = ArgsTouchMove { touches };
-                    topmost_node.dispatch_touch_move(args_touch_move);
+                    default_prevented = topmost_node.dispatch_touch_move(args_touch_move);
                 }
             }
             NativeInterrupt::TouchEnd(args) => {
@@ -215,29 +247,34 @@ impl PaxChassisWeb {
                 if let Some(topmost_node) = prospective_hit {
                     let touches = args.touches.iter().map(|x| Touch::from(x)).collect();
                     let args_touch_end = ArgsTouchEnd { touches };
-                    topmost_node.dispatch_touch_end(args_touch_end);
+                    default_prevented = topmost_node.dispatch_touch_end(args_touch_end);
                 }
             }
             NativeInterrupt::KeyDown(args) => {
-                let prospective_hit = (*self.engine).borrow().get_focused_element();
+                let modifiers: Vec<ModifierKey> = args
+                    .modifiers
+                    .iter()
+                    .map(|x| ModifierKey::from(x))
+                    .collect();
+                let prospective_hit = (*self.engine).borrow().get_keyboard_event_target();
                 if let Some(topmost_node) = prospective_hit {
-                    let modifiers = args
-                        .modifiers
-                        .iter()
-                        .map(|x| ModifierKey::from(x))
-                        .collect();
                     let args_key_down = ArgsKeyDown {
                         keyboard: KeyboardEventArgs {
-                            key: args.key,
-                            modifiers,
+                            key: args.key.clone(),
+                            modifiers: modifiers.clone(),
                             is_repeat: args.is_repeat,
                         },
                     };
-                    topmost_node.dispatch_key_down(args_key_down);
+                    default_prevented = topmost_node.dispatch_key_down(args_key_down);
+                }
+                if !default_prevented {
+                    (*self.engine)
+                        .borrow_mut()
+                        .handle_focus_traversal_key(&args.key, &modifiers);
                 }
             }
             NativeInterrupt::KeyUp(args) => {
-                let prospective_hit = (*self.engine).borrow().get_focused_element();
+                let prospective_hit = (*self.engine).borrow().get_keyboard_event_target();
                 if let Some(topmost_node) = prospective_hit {
                     let modifiers = args
                         .modifiers
@@ -251,11 +288,11 @@ impl PaxChassisWeb {
                             is_repeat: args.is_repeat,
                         },
                     };
-                    topmost_node.dispatch_key_up(args_key_up);
+                    default_prevented = topmost_node.dispatch_key_up(args_key_up);
                 }
             }
             NativeInterrupt::KeyPress(args) => {
-                let prospective_hit = (*self.engine).borrow().get_focused_element();
+                let prospective_hit = (*self.engine).borrow().get_keyboard_event_target();
                 if let Some(topmost_node) = prospective_hit {
                     let modifiers = args
                         .modifiers
@@ -269,7 +306,7 @@ impl PaxChassisWeb {
                             is_repeat: args.is_repeat,
                         },
                     };
-                    topmost_node.dispatch_key_press(args_key_press);
+                    default_prevented = topmost_node.dispatch_key_press(args_key_press);
                 }
             }
             NativeInterrupt::DoubleClick(args) => {
@@ -289,7 +326,7 @@ impl PaxChassisWeb {
                                 .collect(),
                         },
                     };
-                    topmost_node.dispatch_double_click(args_double_click);
+                    default_prevented = topmost_node.dispatch_double_click(args_double_click);
                 }
             }
             NativeInterrupt::MouseMove(args) => {
@@ -309,7 +346,7 @@ impl PaxChassisWeb {
                                 .collect(),
                         },
                     };
-                    topmost_node.dispatch_mouse_move(args_mouse_move);
+                    default_prevented = topmost_node.dispatch_mouse_move(args_mouse_move);
                 }
             }
             NativeInterrupt::Wheel(args) => {
@@ -329,14 +366,14 @@ impl PaxChassisWeb {
                         delta_y: args.delta_y,
                         modifiers,
                     };
-                    topmost_node.dispatch_wheel(args_wheel);
+                    default_prevented = topmost_node.dispatch_wheel(args_wheel);
                 }
             }
             NativeInterrupt::MouseDown(args) => {
                 let prospective_hit = (*self.engine)
                     .borrow()
                     .get_topmost_element_beneath_ray((args.x, args.y));
-                if let Some(topmost_node) = prospective_hit {
+                if let Some(topmost_node) = &prospective_hit {
                     let args_mouse_down = ArgsMouseDown {
                         mouse: MouseEventArgs {
                             x: args.x,
@@ -349,7 +386,13 @@ impl PaxChassisWeb {
                                 .collect(),
                         },
                     };
-                    topmost_node.dispatch_mouse_down(args_mouse_down);
+                    default_prevented = topmost_node.dispatch_mouse_down(args_mouse_down);
+                }
+                //As in browsers, pressing an element focuses it (or its nearest focusable ancestor), unless prevented
+                if !default_prevented {
+                    (*self.engine)
+                        .borrow_mut()
+                        .focus_pointed_element(prospective_hit);
                 }
             }
             NativeInterrupt::MouseUp(args) => {
@@ -369,7 +412,7 @@ impl PaxChassisWeb {
                                 .collect(),
                         },
                     };
-                    topmost_node.dispatch_mouse_up(args_mouse_up);
+                    default_prevented = topmost_node.dispatch_mouse_up(args_mouse_up);
                 }
             }
             NativeInterrupt::MouseOver(args) => {
@@ -389,7 +432,7 @@ impl PaxChassisWeb {
                                 .collect(),
                         },
                     };
-                    topmost_node.dispatch_mouse_over(args_mouse_over);
+                    default_prevented = topmost_node.dispatch_mouse_over(args_mouse_over);
                 }
             }
             NativeInterrupt::MouseOut(args) => {
@@ -409,7 +452,7 @@ impl PaxChassisWeb {
                                 .collect(),
                         },
                     };
-                    topmost_node.dispatch_mouse_out(args_mouse_out);
+                    default_prevented = topmost_node.dispatch_mouse_out(args_mouse_out);
                 }
             }
             NativeInterrupt::ContextMenu(args) => {
@@ -429,10 +472,53 @@ impl PaxChassisWeb {
                                 .collect(),
                         },
                     };
-                    topmost_node.dispatch_context_menu(args_context_menu);
+                    default_prevented = topmost_node.dispatch_context_menu(args_context_menu);
+                }
+            }
+            NativeInterrupt::FormButtonClick(args) => {
+                let node = (*self.engine)
+                    .borrow()
+                    .get_repeat_expanded_node(&args.id_chain);
+                if let Some(node) = node {
+                    default_prevented = node.dispatch_button_click(ArgsButtonClick {});
                 }
             }
+            NativeInterrupt::FormSliderChange(args) => {
+                let node = (*self.engine)
+                    .borrow()
+                    .get_repeat_expanded_node(&args.id_chain);
+                if let Some(node) = node {
+                    default_prevented =
+                        node.dispatch_slider_change(ArgsSliderChange { value: args.value });
+                }
+            }
+            NativeInterrupt::FormDropdownChange(args) => {
+                let node = (*self.engine)
+                    .borrow()
+                    .get_repeat_expanded_node(&args.id_chain);
+                if let Some(node) = node {
+                    default_prevented = node.dispatch_dropdown_change(ArgsDropdownChange {
+                        selected_index: args.selected_index,
+                    });
+                }
+            }
+            NativeInterrupt::FormTextboxChange(args) => {
+                let node = (*self.engine)
+                    .borrow()
+                    .get_repeat_expanded_node(&args.id_chain);
+                if let Some(node) = node {
+                    default_prevented =
+                        node.dispatch_textbox_change(ArgsTextboxChange { text: args.text });
+                }
+            }
+            NativeInterrupt::FormFocus(args) => {
+                (*self.engine).borrow_mut().focus(&args.id_chain);
+            }
+            NativeInterrupt::FormBlur(args) => {
+                (*self.engine).borrow_mut().blur(&args.id_chain);
+            }
         };
+        default_prevented
     }
 
     pub fn deallocate(&mut self, slice: MemorySlice) {
@@ -442,6 +528,26 @@ impl PaxChassisWeb {
         }
     }
 
+    /// Apply a `ComponentTemplateReload` sent by `pax run --hot-reload`
+    pub fn hot_reload(&mut self, payload: String) {
+        let reload: ComponentTemplateReload = match serde_json::from_str(&payload) {
+            Ok(reload) => reload,
+            Err(err) => return console_log!("Malformed hot reload: {}", err),
+        };
+        let cartridge = match pax_cartridge::get_hot_reload_cartridge() {
+            Some(cartridge) => cartridge,
+            None => return console_log!("This app was not built with `pax run --hot-reload`"),
+        };
+        if let Err(err) = self.engine.borrow_mut().hot_reload(reload, cartridge) {
+            console_log!("Failed to hot reload: {}", err);
+        }
+    }
+
+    /// Whether the next `tick` will draw; when `false`, canvases should be left as-is rather than cleared
+    pub fn needs_render(&self) -> bool {
+        self.engine.borrow().needs_render()
+    }
+
     pub fn tick(&mut self) -> MemorySlice {
         let message_queue = self.engine.borrow_mut().tick(&mut self.drawing_contexts);
 
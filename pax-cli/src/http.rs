@@ -14,7 +14,7 @@ pub fn check_for_update(new_version_info: Arc<Mutex<Option<String>>>) {
 
     let client = reqwest::blocking::Client::new();
     if let Ok(response) = client
-        .get(&url)
+        .get(url)
         .header(reqwest::header::USER_AGENT, user_agent)
         .send()
     {
@@ -11,8 +11,8 @@ extern crate pax_language_server;
 
 mod http;
 
-use color_eyre::eyre::Report;
 use color_eyre::eyre::Result;
+use color_eyre::eyre::{eyre, Report};
 use signal_hook::consts::{SIGINT, SIGTERM};
 use signal_hook::iterator::Signals;
 
@@ -55,12 +55,36 @@ fn main() -> Result<(), Report> {
         .help("Specify the target platform on which to run.  Will run in platform-specific demo harness.")
         .takes_value(true);
 
+    #[allow(non_snake_case)]
+    let ARG_FRAMES = Arg::with_name("frames")
+        .long("frames")
+        .takes_value(true)
+        .help("For `--target=headless`: number of ticks to run before exiting.  Defaults to 1.");
+
+    #[allow(non_snake_case)]
+    let ARG_OUTPUT_DIR = Arg::with_name("output-dir")
+        .long("output-dir")
+        .takes_value(true)
+        .help("For `--target=headless`: directory into which each rendered frame is written as a PNG.  Defaults to `.pax/build/debug/headless/frames`.");
+
     #[allow(non_snake_case)]
     let ARG_RELEASE = Arg::with_name("release")
         .long("release")
         .takes_value(false)
         .help("Build in Release mode, with appropriate platform-specific optimizations.");
 
+    #[allow(non_snake_case)]
+    let ARG_INTERPRET_EXPRESSIONS = Arg::with_name("interpret-expressions")
+        .long("interpret-expressions")
+        .takes_value(false)
+        .help("Evaluate expressions with a runtime interpreter instead of compiling them to Rust, so that they may be changed without a rebuild.  Slower than compiled expressions.");
+
+    #[allow(non_snake_case)]
+    let ARG_HOT_RELOAD = Arg::with_name("hot-reload")
+        .long("hot-reload")
+        .takes_value(false)
+        .help("Watch the project for changes and hot reload changed templates into the running app, keeping its state.  Supported for `web` only; implies --interpret-expressions.");
+
     #[allow(non_snake_case)]
     let ARG_LIBDEV = Arg::with_name("libdev")
         .long("libdev")
@@ -85,6 +109,10 @@ fn main() -> Result<(), Report> {
                 .arg( ARG_TARGET.clone() )
                 .arg( ARG_VERBOSE.clone() )
                 .arg( ARG_LIBDEV.clone() )
+                .arg( ARG_FRAMES.clone() )
+                .arg( ARG_OUTPUT_DIR.clone() )
+                .arg( ARG_INTERPRET_EXPRESSIONS.clone() )
+                .arg( ARG_HOT_RELOAD.clone() )
         )
         .subcommand(
             App::new("build")
@@ -94,6 +122,16 @@ fn main() -> Result<(), Report> {
                 .arg( ARG_VERBOSE.clone() )
                 .arg( ARG_LIBDEV.clone() )
                 .arg( ARG_RELEASE.clone() )
+                .arg( ARG_INTERPRET_EXPRESSIONS.clone() )
+        )
+        .subcommand(
+            App::new("watch")
+                .about("Builds the Pax project from the current working directory, then rebuilds it whenever its sources change, regenerating only what each change affects.  Web builds are served throughout.")
+                .arg( ARG_PATH.clone() )
+                .arg( ARG_TARGET.clone() )
+                .arg( ARG_VERBOSE.clone() )
+                .arg( ARG_LIBDEV.clone() )
+                .arg( ARG_INTERPRET_EXPRESSIONS.clone() )
         )
         .subcommand(
             App::new("clean")
@@ -129,7 +167,7 @@ fn main() -> Result<(), Report> {
     let is_libdev_mode = args.contains(&"--libdev".to_string());
 
     // Create a separate thread to handle signals e.g. via CTRL+C
-    let mut signals = Signals::new(&[SIGINT, SIGTERM]).unwrap();
+    let mut signals = Signals::new([SIGINT, SIGTERM]).unwrap();
     let cloned_version_info = Arc::clone(&new_version_info);
     let cloned_process_child_ids = Arc::clone(&process_child_ids);
     thread::spawn(move || {
@@ -159,15 +197,32 @@ fn perform_nominal_action(
             let path = args.value_of("path").unwrap().to_string(); //default value "."
             let verbose = args.is_present("verbose");
             let is_libdev_mode = args.is_present("libdev");
+            let headless_frame_count = match args.value_of("frames") {
+                Some(frames) => Some(
+                    frames
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|frames| *frames > 0)
+                        .ok_or_else(|| eyre!("`--frames` must be a positive integer"))?,
+                ),
+                None => None,
+            };
+            let headless_output_dir = args.value_of("output-dir").map(|s| s.to_string());
+            let interpret_expressions = args.is_present("interpret-expressions");
+            let hot_reload = args.is_present("hot-reload");
 
             pax_compiler::perform_build(&RunContext {
-                target: RunTarget::from(target.as_str()),
+                target: target.parse::<RunTarget>()?,
                 path,
                 verbose,
                 should_also_run: true,
                 is_libdev_mode,
                 process_child_ids,
                 is_release: false,
+                headless_frame_count,
+                headless_output_dir,
+                interpret_expressions,
+                hot_reload,
             })
         }
         ("build", Some(args)) => {
@@ -176,15 +231,42 @@ fn perform_nominal_action(
             let verbose = args.is_present("verbose");
             let is_libdev_mode = args.is_present("libdev");
             let is_release = args.is_present("release");
+            let interpret_expressions = args.is_present("interpret-expressions");
 
             pax_compiler::perform_build(&RunContext {
-                target: RunTarget::from(target.as_str()),
+                target: target.parse::<RunTarget>()?,
                 path,
                 should_also_run: false,
                 verbose,
                 is_libdev_mode,
                 process_child_ids,
                 is_release,
+                headless_frame_count: None,
+                headless_output_dir: None,
+                interpret_expressions,
+                hot_reload: false,
+            })
+        }
+        ("watch", Some(args)) => {
+            let target = args.value_of("target").unwrap().to_lowercase();
+            let path = args.value_of("path").unwrap().to_string(); //default value "."
+            let verbose = args.is_present("verbose");
+            let is_libdev_mode = args.is_present("libdev");
+            let interpret_expressions = args.is_present("interpret-expressions");
+
+            pax_compiler::perform_watch(&RunContext {
+                target: target.parse::<RunTarget>()?,
+                path,
+                //`perform_watch` serves web builds itself, once rather than after every rebuild
+                should_also_run: false,
+                verbose,
+                is_libdev_mode,
+                process_child_ids,
+                is_release: false,
+                headless_frame_count: None,
+                headless_output_dir: None,
+                interpret_expressions,
+                hot_reload: false,
             })
         }
         ("clean", Some(args)) => {
@@ -250,7 +332,7 @@ fn perform_cleanup(
     if let Ok(process_child_ids_lock) = process_child_ids.lock() {
         process_child_ids_lock.iter().for_each(|child_id| {
             kill_process(*child_id as u32)
-                .expect(&format!("Failed to kill process with ID: {}", child_id));
+                .unwrap_or_else(|_| panic!("Failed to kill process with ID: {}", child_id));
         });
     }
 
@@ -258,7 +340,7 @@ fn perform_cleanup(
     if let Ok(new_version_lock) = new_version_info.lock() {
         if !is_libdev_mode {
             if let Some(new_version) = new_version_lock.as_ref() {
-                if new_version != "" {
+                if !new_version.is_empty() {
                     //Print our banner if we have a concrete value stored in the new version mutex
                     const TOTAL_LENGTH: usize = 60;
                     let stars_line: ColoredString =
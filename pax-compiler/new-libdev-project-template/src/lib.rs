@@ -26,7 +26,7 @@ impl Example {
         self.ticks.set(old_ticks + 1);
     }
 
-    pub fn increment(&mut self, ctx: RuntimeContext, args: ArgsClick){
+    pub fn increment(&mut self, ctx: RuntimeContext, args: Event<ArgsClick>){
         let old_num_clicks = self.num_clicks.get();
         self.num_clicks.set(old_num_clicks + 1);
         self.message.set(format!("{} clicks", self.num_clicks.get()));
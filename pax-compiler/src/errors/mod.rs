@@ -26,6 +26,7 @@ pub struct PaxTemplateError {
 }
 
 impl PaxTemplateError {
+    #[allow(clippy::new_ret_no_self)]
     pub fn new(message: Option<String>, token: Token) -> eyre::Report {
         let err = PaxTemplateError { message, token };
         eyre!(format!("{}", err))
@@ -42,12 +43,16 @@ impl fmt::Display for PaxTemplateError {
             TokenType::IfExpression => "Invalid 'if' expression.",
             TokenType::ForPredicate => "Invalid 'for' predicate.",
             TokenType::ForSource => "Invalid 'for' source.",
+            TokenType::ForKey => "Invalid 'for' key.",
             TokenType::SlotExpression => "Invalid slot expression.",
             TokenType::EventId => "Invalid event ID.",
             TokenType::Handler => "Invalid handler.",
             TokenType::SettingKey => "Invalid setting key.",
             TokenType::Selector => "Invalid selector.",
             TokenType::PascalIdentifier => "Invalid type",
+            TokenType::KeyframeFrame => "Invalid keyframe frame.",
+            TokenType::KeyframeName => "Invalid keyframe name.",
+            TokenType::EasingCurve => "Invalid easing curve.",
             TokenType::Unknown => "Unknown token error.",
         };
 
@@ -94,8 +99,8 @@ pub fn process_messages(output: Output, source_map: &SourceMap) -> Result<(), Re
 
     let mut has_errors = false;
 
-    for message in Message::parse_stream(reader) {
-        if let Ok(Message::CompilerMessage(msg)) = message {
+    for message in Message::parse_stream(reader).flatten() {
+        if let Message::CompilerMessage(msg) = message {
             if msg.message.level == DiagnosticLevel::Error && !msg.message.spans.is_empty() {
                 let line = msg.message.spans[0].line_start;
                 if let Some(range_data) = source_map.get_range_for_line(line) {
@@ -22,6 +22,12 @@ pub struct SourceMap {
     pub next_id: usize,
 }
 
+impl Default for SourceMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SourceMap {
     pub fn new() -> Self {
         SourceMap {
@@ -87,7 +93,7 @@ impl SourceMap {
                         .sources
                         .get(&id)
                         .cloned()
-                        .unwrap_or_else(|| Token::default());
+                        .unwrap_or_else(Token::default);
                     let range_data = RangeData {
                         end: end_pos,
                         id,
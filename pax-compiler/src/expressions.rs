@@ -1,7 +1,7 @@
 use super::manifest::{
-    ComponentDefinition, ControlFlowRepeatPredicateDefinition, ExpressionSpec,
-    ExpressionSpecInvocation, PaxManifest, PropertyDefinition, SettingsSelectorBlockDefinition,
-    TemplateNodeDefinition, ValueDefinition,
+    ComponentDefinition, ControlFlowRepeatPattern, ControlFlowRepeatPredicateDefinition,
+    ExpressionScopeFrame, ExpressionSpec, ExpressionSpecInvocation, PaxManifest,
+    PropertyDefinition, SettingsSelectorBlockDefinition, TemplateNodeDefinition, ValueDefinition,
 };
 use std::collections::HashMap;
 use std::ops::{IndexMut, RangeFrom};
@@ -10,14 +10,15 @@ use std::slice::IterMut;
 use crate::errors::source_map::SourceMap;
 use crate::errors::PaxTemplateError;
 use crate::manifest::{PropertyDefinitionFlags, Token, TypeDefinition, TypeTable};
-use crate::parsing::escape_identifier;
+use crate::parsing::{escape_identifier, PaxelMethodCallArg, TIMELINE_EASING_CURVES};
+use crate::type_checking::{check_expression_type, PaxelType};
 use color_eyre::eyre;
-use color_eyre::eyre::eyre;
+
 use color_eyre::eyre::Report;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 
-const BUILTIN_TYPES: &'static [(&str, &str); 12] = &[
+const BUILTIN_TYPES: &[(&str, &str); 16] = &[
     ("transform", "Transform2D"),
     ("width", "Size"),
     ("height", "Size"),
@@ -30,6 +31,10 @@ const BUILTIN_TYPES: &'static [(&str, &str); 12] = &[
     ("scale_x", "Size"),
     ("scale_y", "Size"),
     ("rotate", "Rotation"),
+    ("focusable", "bool"),
+    ("tab_index", "isize"),
+    ("opacity", "f64"),
+    ("blend_mode", "BlendMode"),
 ];
 
 pub fn compile_all_expressions<'a>(
@@ -37,41 +42,19 @@ pub fn compile_all_expressions<'a>(
     source_map: &'a mut SourceMap,
 ) -> eyre::Result<(), Report> {
     let mut swap_expression_specs: HashMap<usize, ExpressionSpec> = HashMap::new();
-    let mut all_expression_specs: HashMap<usize, ExpressionSpec> = HashMap::new();
 
     let mut new_components = manifest.components.clone();
     let mut uid_track = 0;
 
     for component_def in new_components.values_mut() {
-        let mut new_component_def = component_def.clone();
-        let read_only_component_def = component_def.clone();
-
-        if let Some(ref mut template) = new_component_def.template {
-            let mut active_node_def = TemplateNodeDefinition::default();
-            std::mem::swap(&mut active_node_def, template.index_mut(0));
-
-            let mut ctx = ExpressionCompilationContext {
-                template,
-                active_node_def,
-                scope_stack: vec![component_def
-                    .get_property_definitions(&manifest.type_table)
-                    .iter()
-                    .map(|pd| (pd.name.clone(), pd.clone()))
-                    .collect()],
-                uid_gen: uid_track..,
-                all_components: manifest.components.clone(),
-                expression_specs: &mut swap_expression_specs,
-                component_def: &read_only_component_def,
-                type_table: &manifest.type_table,
-            };
-
-            ctx = recurse_compile_expressions(ctx, source_map)?;
-            uid_track = ctx.uid_gen.next().unwrap();
-            all_expression_specs.extend(ctx.expression_specs.to_owned());
-            std::mem::swap(&mut ctx.active_node_def, template.index_mut(0));
-        }
-
-        std::mem::swap(component_def, &mut new_component_def);
+        uid_track = compile_component_expressions(
+            component_def,
+            &manifest.components,
+            &manifest.type_table,
+            &mut swap_expression_specs,
+            uid_track,
+            source_map,
+        )?;
     }
 
     manifest.components = new_components;
@@ -79,6 +62,46 @@ pub fn compile_all_expressions<'a>(
     Ok(())
 }
 
+/// Compiles the expressions in a single component's template into `expression_specs`, writing their vtable ids
+/// (minted from `uid_start`) back into the template.  Returns the next id to mint.
+pub fn compile_component_expressions(
+    component_def: &mut ComponentDefinition,
+    all_components: &HashMap<String, ComponentDefinition>,
+    type_table: &TypeTable,
+    expression_specs: &mut HashMap<usize, ExpressionSpec>,
+    uid_start: usize,
+    source_map: &mut SourceMap,
+) -> eyre::Result<usize, Report> {
+    let read_only_component_def = component_def.clone();
+    let mut uid_track = uid_start;
+
+    if let Some(ref mut template) = component_def.template {
+        let mut active_node_def = TemplateNodeDefinition::default();
+        std::mem::swap(&mut active_node_def, template.index_mut(0));
+
+        let mut ctx = ExpressionCompilationContext {
+            template,
+            active_node_def,
+            scope_stack: vec![read_only_component_def
+                .get_property_definitions(type_table)
+                .iter()
+                .map(|pd| (pd.name.clone(), pd.clone()))
+                .collect()],
+            uid_gen: uid_track..,
+            all_components: all_components.clone(),
+            expression_specs,
+            component_def: &read_only_component_def,
+            type_table,
+        };
+
+        ctx = recurse_compile_expressions(ctx, source_map)?;
+        uid_track = ctx.uid_gen.next().unwrap();
+        std::mem::swap(&mut ctx.active_node_def, template.index_mut(0));
+    }
+
+    Ok(uid_track)
+}
+
 fn pull_matched_identifiers_from_inline(
     inline_settings: &Option<Vec<(Token, ValueDefinition)>>,
     s: String,
@@ -86,10 +109,9 @@ fn pull_matched_identifiers_from_inline(
     let mut ret = Vec::new();
     if let Some(val) = inline_settings {
         for (_, matched) in val.iter().filter(|avd| avd.0.token_value == s.as_str()) {
-            match matched {
-                ValueDefinition::Identifier(s, _) => ret.push(s.clone()),
-                _ => {}
-            };
+            if let ValueDefinition::Identifier(s, _) = matched {
+                ret.push(s.clone());
+            }
         }
     }
     ret
@@ -105,7 +127,7 @@ fn pull_settings_with_selector(
             .filter(|block| block.selector.token_value == selector)
             .flat_map(|block| block.value_block.settings_key_value_pairs.clone())
             .collect();
-        (!merged_settings.is_empty()).then(|| merged_settings)
+        (!merged_settings.is_empty()).then_some(merged_settings)
     })
 }
 
@@ -114,26 +136,28 @@ fn merge_inline_settings_with_settings_block(
     settings_block: &Option<Vec<SettingsSelectorBlockDefinition>>,
 ) -> Option<Vec<(Token, ValueDefinition)>> {
     // collect id settings
-    let ids = pull_matched_identifiers_from_inline(&inline_settings, "id".to_string());
+    let ids = pull_matched_identifiers_from_inline(inline_settings, "id".to_string());
 
     let mut id_settings = Vec::new();
-    if ids.len() == 1 {
-        if let Some(settings) =
-            pull_settings_with_selector(&settings_block, format!("#{}", ids[0].token_value))
-        {
-            id_settings.extend(settings.clone());
+    match ids.len() {
+        0 => {}
+        1 => {
+            if let Some(settings) =
+                pull_settings_with_selector(settings_block, format!("#{}", ids[0].token_value))
+            {
+                id_settings.extend(settings.clone());
+            }
         }
-    } else if ids.len() > 1 {
-        panic!("Specified more than one id inline!");
+        _ => panic!("Specified more than one id inline!"),
     }
 
     // collect all class settings
-    let classes = pull_matched_identifiers_from_inline(&inline_settings, "class".to_string());
+    let classes = pull_matched_identifiers_from_inline(inline_settings, "class".to_string());
 
     let mut class_settings = Vec::new();
     for class in classes {
         if let Some(settings) =
-            pull_settings_with_selector(&settings_block, format!(".{}", class.token_value))
+            pull_settings_with_selector(settings_block, format!(".{}", class.token_value))
         {
             class_settings.extend(settings.clone());
         }
@@ -157,14 +181,55 @@ fn merge_inline_settings_with_settings_block(
     }
 
     let merged: Vec<(Token, ValueDefinition)> = map.into_iter().collect();
-    if merged.len() > 0 {
+    if !merged.is_empty() {
         Some(merged)
     } else {
         None
     }
 }
 
-fn recurse_compile_literal_block<'a>(
+/// Overlays any keyframes from a component's `@timeline` block (selected by the node's `id` or `class`)
+/// onto its already-merged settings; a timeline-bound property takes precedence over any other value
+fn merge_timeline_with_settings(
+    inline_settings: &Option<Vec<(Token, ValueDefinition)>>,
+    merged_settings: Option<Vec<(Token, ValueDefinition)>>,
+    timeline_block: &Option<Vec<SettingsSelectorBlockDefinition>>,
+) -> Option<Vec<(Token, ValueDefinition)>> {
+    let timeline_settings: Vec<(Token, ValueDefinition)> =
+        merge_inline_settings_with_settings_block(inline_settings, timeline_block)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, value)| matches!(value, ValueDefinition::Timeline(_)))
+            .collect();
+
+    if timeline_settings.is_empty() {
+        return merged_settings;
+    }
+
+    let mut merged = merged_settings.unwrap_or_default();
+    merged.retain(|(key, _)| !timeline_settings.iter().any(|(k, _)| k == key));
+    merged.extend(timeline_settings);
+    Some(merged)
+}
+
+/// Returns the type_id of the property named `property_name`, whether a built-in like `width` or one of `property_definitions`
+fn get_property_type_id(
+    property_name: &str,
+    property_definitions: &[PropertyDefinition],
+) -> Option<String> {
+    BUILTIN_TYPES
+        .iter()
+        .find(|type_str| type_str.0 == property_name)
+        .map(|type_str| type_str.1.to_string())
+        .or_else(|| {
+            property_definitions
+                .iter()
+                .find(|property_def| property_def.name == property_name)
+                .map(|property_def| property_def.type_id.clone())
+        })
+}
+
+fn recurse_compile_literal_block(
     settings_pairs: &mut IterMut<(Token, ValueDefinition)>,
     ctx: &mut ExpressionCompilationContext,
     current_property_definitions: Vec<PropertyDefinition>,
@@ -176,6 +241,61 @@ fn recurse_compile_literal_block<'a>(
             // LiteralValue:       no need to compile literal values
             // EventBindingTarget: event bindings are handled on a separate compiler pass; no-op here
             ValueDefinition::LiteralValue(_) | ValueDefinition::EventBindingTarget(_) => {}
+            ValueDefinition::Timeline(keyframes) => {
+                // Keyframe values are literals, so there is nothing to compile; just make sure the
+                // animated property exists so that codegen can bind it to the component's timeline
+                let is_known_property = BUILTIN_TYPES
+                    .iter()
+                    .any(|type_str| type_str.0 == &*pair.0.token_value)
+                    || current_property_definitions
+                        .iter()
+                        .any(|property_def| property_def.name == pair.0.token_value);
+                if !is_known_property {
+                    return Err(PaxTemplateError::new(
+                        Some(format!(
+                            "Property `{}` not found on `{}`",
+                            &pair.0.token_value, type_id
+                        )),
+                        pair.0.clone(),
+                    ));
+                }
+                if keyframes.is_empty() {
+                    return Err(PaxTemplateError::new(
+                        Some(format!(
+                            "`{}` must be given at least one keyframe",
+                            &pair.0.token_value
+                        )),
+                        pair.0.clone(),
+                    ));
+                }
+                if let Some(keyframe) = keyframes
+                    .iter()
+                    .find(|keyframe| keyframe.get_frame().is_none())
+                {
+                    return Err(PaxTemplateError::new(
+                        Some(format!(
+                            "Keyframe frame `{}` is out of range; frames may be at most {}",
+                            keyframe.frame.token_value,
+                            usize::MAX
+                        )),
+                        keyframe.frame.clone(),
+                    ));
+                }
+                if let Some(curve) = keyframes
+                    .iter()
+                    .filter_map(|keyframe| keyframe.curve.as_ref())
+                    .find(|curve| !TIMELINE_EASING_CURVES.contains(&curve.token_value.as_str()))
+                {
+                    return Err(PaxTemplateError::new(
+                        Some(format!(
+                            "Unknown easing curve `{}`; expected one of: {}",
+                            curve.token_value,
+                            TIMELINE_EASING_CURVES.join(", ")
+                        )),
+                        curve.clone(),
+                    ));
+                }
+            }
             ValueDefinition::Block(block) => {
                 let type_def = (current_property_definitions
                     .iter()
@@ -200,7 +320,13 @@ fn recurse_compile_literal_block<'a>(
                 // e.g. the `self.num_clicks + 5` in `<SomeNode some_property={self.num_clicks + 5} />`
                 let id = ctx.uid_gen.next().unwrap();
 
-                let (output_statement, invocations) = compile_paxel_to_ril(input.clone(), &ctx)?;
+                check_expression_type(
+                    input,
+                    get_property_type_id(&pair.0.token_value, &current_property_definitions)
+                        .as_deref(),
+                    ctx,
+                )?;
+                let (output_statement, invocations) = compile_paxel_to_ril(input.clone(), ctx)?;
 
                 let pascalized_return_type = if let Some(type_string) = BUILTIN_TYPES
                     .iter()
@@ -241,6 +367,8 @@ fn recurse_compile_literal_block<'a>(
                         input_statement,
                         is_repeat_source_iterable_expression: false,
                         repeat_source_iterable_type_id_escaped: "".to_string(),
+                        interpreted_paxel: Some(input.token_value.clone()),
+                        scope_frames: ctx.get_scope_frames(),
                     },
                 );
 
@@ -263,21 +391,27 @@ fn recurse_compile_literal_block<'a>(
 
                     //a single identifier binding is the same as an expression returning that identifier, `{self.some_identifier}`
                     //thus, we can compile it as PAXEL and make use of any shared logic, e.g. `self`/`this` handling
+                    check_expression_type(
+                        identifier,
+                        get_property_type_id(&pair.0.token_value, &current_property_definitions)
+                            .as_deref(),
+                        ctx,
+                    )?;
                     let (output_statement, invocations) =
-                        compile_paxel_to_ril(identifier.clone(), &ctx)?;
+                        compile_paxel_to_ril(identifier.clone(), ctx)?;
 
                     let source_map_id = source_map.insert(identifier.clone());
                     let input_statement = source_map
                         .generate_mapped_string(identifier.token_value.clone(), source_map_id);
 
-                    let pascalized_return_type = (&ctx
+                    let pascalized_return_type = ctx
                         .component_def
                         .get_property_definitions(ctx.type_table)
                         .iter()
                         .find(|property_def| property_def.name == pair.0.token_value)
                         .unwrap()
                         .get_type_definition(ctx.type_table)
-                        .type_id_escaped)
+                        .type_id_escaped
                         .clone();
 
                     ctx.expression_specs.insert(
@@ -290,6 +424,8 @@ fn recurse_compile_literal_block<'a>(
                             input_statement,
                             is_repeat_source_iterable_expression: false,
                             repeat_source_iterable_type_id_escaped: "".to_string(),
+                            interpreted_paxel: Some(identifier.token_value.clone()),
+                            scope_frames: ctx.get_scope_frames(),
                         },
                     );
                 }
@@ -305,14 +441,17 @@ fn recurse_compile_literal_block<'a>(
 
 fn recurse_compile_expressions<'a>(
     mut ctx: ExpressionCompilationContext<'a>,
-    mut source_map: &mut SourceMap,
+    source_map: &mut SourceMap,
 ) -> eyre::Result<ExpressionCompilationContext<'a>, Report> {
     let incremented = false;
 
     let cloned_settings_block = ctx.component_def.settings.clone();
     let cloned_inline_settings = ctx.active_node_def.settings.clone();
-    let mut merged_settings =
-        merge_inline_settings_with_settings_block(&cloned_inline_settings, &cloned_settings_block);
+    let mut merged_settings = merge_timeline_with_settings(
+        &cloned_inline_settings,
+        merge_inline_settings_with_settings_block(&cloned_inline_settings, &cloned_settings_block),
+        &ctx.component_def.timeline,
+    );
     let mut cloned_control_flow_settings = ctx.active_node_def.control_flow_settings.clone();
 
     if let Some(ref mut inline_settings) = merged_settings {
@@ -324,10 +463,10 @@ fn recurse_compile_expressions<'a>(
         // Scope created to limit the borrow of ctx
         {
             let active_node_component = ctx.all_components.get(&type_id)
-                .expect(&format!("No known component with identifier {}.  Try importing or defining a component named {}", &type_id, &type_id));
+                .unwrap_or_else(|| panic!("No known component with identifier {}.  Try importing or defining a component named {}", &type_id, &type_id));
 
             pascal_identifier = active_node_component.pascal_identifier.clone();
-            property_def = active_node_component.get_property_definitions(&mut ctx.type_table);
+            property_def = active_node_component.get_property_definitions(ctx.type_table);
         }
 
         recurse_compile_literal_block(
@@ -335,7 +474,7 @@ fn recurse_compile_expressions<'a>(
             &mut ctx,
             property_def.clone(),
             pascal_identifier,
-            &mut source_map,
+            source_map,
         )?;
     } else if let Some(ref mut cfa) = cloned_control_flow_settings {
         //Handle attributes for control flow
@@ -349,9 +488,12 @@ fn recurse_compile_expressions<'a>(
             // Examples:
             // for (elem, i) in self.elements
             //  - must be a symbolic identifier, such as `elements` or `self.elements`
+            //  - may call collection methods on that symbol, like `self.elements.filter(|e| e.visible)`
             // for i in 0..max_elems
             //  - may use an integer literal or symbolic identifier in either position
-            //  - must use an exclusive (..) range operator (inclusive could be supported; effort required)
+            //  - may use an exclusive (..) or inclusive (..=) range operator
+            //  - counts down when the end is less than the start, e.g. `10..0`
+            //  - may declare a step with `step`, e.g. `0..100 step 5`
 
             let id = ctx.uid_gen.next().unwrap();
             repeat_source_definition.vtable_id = Some(id);
@@ -366,23 +508,44 @@ fn recurse_compile_expressions<'a>(
             let (paxel, return_type) = if let Some(range_expression_paxel) =
                 &repeat_source_definition.range_expression_paxel
             {
+                check_expression_type(range_expression_paxel, None, &ctx)?;
                 (
                     range_expression_paxel.clone(),
-                    TypeDefinition::builtin_range_isize(),
+                    TypeDefinition::builtin_repeat_range(),
                 )
             } else if let Some(symbolic_binding) = &repeat_source_definition.symbolic_binding {
-                let inner_iterable_type_id = ctx
-                    .resolve_symbol_as_prop_def(
+                let source_type_id = if repeat_source_definition.is_method_call {
+                    check_expression_type(symbolic_binding, None, &ctx)?;
+                    let (_, chain_type_ids) = compile_method_calls(symbolic_binding, &ctx)?;
+                    chain_type_ids[0].clone()
+                } else {
+                    ctx.resolve_symbol_as_prop_def(
                         &symbolic_binding.token_value,
                         symbolic_binding.clone(),
                     )?
-                    .unwrap()
+                    .ok_or::<eyre::Report>(PaxTemplateError::new(
+                        Some(format!(
+                            "Property not found: {}",
+                            symbolic_binding.token_value
+                        )),
+                        symbolic_binding.clone(),
+                    ))?
                     .last()
                     .unwrap()
-                    .get_inner_iterable_type_definition(ctx.type_table)
-                    .unwrap()
                     .type_id
-                    .clone();
+                    .clone()
+                };
+                let inner_iterable_type_id = ctx
+                    .type_table
+                    .get(&source_type_id)
+                    .and_then(|td| td.inner_iterable_type_id.clone())
+                    .ok_or::<eyre::Report>(PaxTemplateError::new(
+                        Some(format!(
+                            "`for` can only iterate over collections, but `{}` is a `{}`",
+                            symbolic_binding.raw_value, source_type_id
+                        )),
+                        symbolic_binding.clone(),
+                    ))?;
                 (
                     symbolic_binding.clone(),
                     TypeDefinition::builtin_vec_rc_properties_coproduct(inner_iterable_type_id),
@@ -392,7 +555,7 @@ fn recurse_compile_expressions<'a>(
             };
 
             let repeat_source_iterable_type_id_escaped =
-                if let Some(iiti) = return_type.inner_iterable_type_id {
+                if let Some(iiti) = &return_type.inner_iterable_type_id {
                     escape_identifier(iiti.clone())
                 } else {
                     "".to_string()
@@ -406,100 +569,74 @@ fn recurse_compile_expressions<'a>(
 
             // Attach shadowed property symbols to the scope_stack, so e.g. `elem` can be
             // referred to with the symbol `elem` in PAXEL
-            match cfa.repeat_predicate_definition.as_ref().unwrap() {
+            let (elem_pattern, index_id) = match cfa.repeat_predicate_definition.as_ref().unwrap() {
                 ControlFlowRepeatPredicateDefinition::ElemId(elem_id) => {
-                    //if repeat_source is a range, elem is bound to the element within the range
-                    //if repeat_source is a symbolic binding,
-                    //for i in 0..5
-                    // i describes the element (not the index!), which in this case is a `isize`
-                    // property definition: called `i`
-                    // property_type:isize (the iterable_type)
-
-                    let property_definition = PropertyDefinition {
-                        name: format!("{}", elem_id.token_value),
+                    (ControlFlowRepeatPattern::Binding(elem_id.clone()), None)
+                }
+                ControlFlowRepeatPredicateDefinition::ElemIdIndexId(elem_id, index_id) => (
+                    ControlFlowRepeatPattern::Binding(elem_id.clone()),
+                    Some(index_id),
+                ),
+                ControlFlowRepeatPredicateDefinition::ElemPattern(elem_pattern) => {
+                    (elem_pattern.clone(), None)
+                }
+                ControlFlowRepeatPredicateDefinition::ElemPatternIndexId(
+                    elem_pattern,
+                    index_id,
+                ) => (elem_pattern.clone(), Some(index_id)),
+            };
 
+            //if repeat_source is a range, this is simply isize
+            //if repeat_source is a symbolic binding, then we resolve that symbolic binding and use that resolved type here
+            let iterable_type_id = return_type.inner_iterable_type_id.clone().unwrap();
+
+            // Each symbol bound by the pattern, like `elem` in `for elem in 0..5` or `name` and `score` in
+            // `for ((name, score), i) in self.rows`, is described by the element's type, along with the path of
+            // fields (or tuple indices) leading to its value, which is resolved as if it were a nested symbol
+            let elem_bindings = elem_pattern.get_bindings();
+            let mut scope: HashMap<String, PropertyDefinition> = HashMap::new();
+            for (binding, path) in &elem_bindings {
+                scope.insert(
+                    binding.token_value.clone(),
+                    PropertyDefinition {
+                        name: binding.token_value.clone(),
+                        type_id: iterable_type_id.clone(),
                         flags: PropertyDefinitionFlags {
-                            is_binding_repeat_i: false,
                             is_binding_repeat_elem: true,
+                            is_binding_repeat_i: false,
                             is_repeat_source_range,
                             is_repeat_source_iterable,
                             is_property_wrapped: true,
                         },
-                        type_id: "isize".to_string(),
-                    };
+                        repeat_destructuring_path: path.clone(),
+                    },
+                );
+            }
 
-                    let scope: HashMap<String, PropertyDefinition> = HashMap::from([
-                        //`elem` property (by specified name)
-                        (elem_id.token_value.clone(), property_definition),
-                    ]);
+            if let Some(index_id) = index_id {
+                let mut i_property_definition =
+                    PropertyDefinition::primitive_with_name("usize", &index_id.token_value);
+                i_property_definition.flags = PropertyDefinitionFlags {
+                    is_binding_repeat_i: true,
+                    is_binding_repeat_elem: false,
+                    is_repeat_source_range,
+                    is_repeat_source_iterable,
+                    is_property_wrapped: true,
+                };
+                scope.insert(index_id.token_value.clone(), i_property_definition);
+            }
 
-                    ctx.scope_stack.push(scope);
-                }
-                ControlFlowRepeatPredicateDefinition::ElemIdIndexId(elem_id, index_id) => {
-                    //if repeat_source is a range, this is simply isize
-                    //if repeat_source is a symbolic binding, then we resolve that symbolic binding and use that resolved type here
-                    let iterable_type =
-                        if let Some(_) = &repeat_source_definition.range_expression_paxel {
-                            TypeDefinition::primitive("isize")
-                        } else if let Some(symbolic_binding) =
-                            &repeat_source_definition.symbolic_binding
-                        {
-                            let pd = ctx
-                                .resolve_symbol_as_prop_def(
-                                    &symbolic_binding.token_value,
-                                    symbolic_binding.clone(),
-                                )?
-                                .ok_or::<eyre::Report>(PaxTemplateError::new(
-                                    Some(format!(
-                                        "Property not found: {}",
-                                        symbolic_binding.token_value
-                                    )),
-                                    symbolic_binding.clone(),
-                                ))?
-                                .last()
-                                .unwrap()
-                                .clone();
-                            pd.get_inner_iterable_type_definition(ctx.type_table)
-                                .unwrap()
-                                .clone()
-                        } else {
-                            unreachable!()
-                        };
-
-                    let elem_property_definition = PropertyDefinition {
-                        name: format!("{}", elem_id.token_value),
-                        type_id: iterable_type.type_id,
-                        flags: PropertyDefinitionFlags {
-                            is_binding_repeat_elem: true,
-                            is_binding_repeat_i: false,
-                            is_repeat_source_range,
-                            is_repeat_source_iterable,
-                            is_property_wrapped: true,
-                        },
-                    };
-
-                    let mut i_property_definition =
-                        PropertyDefinition::primitive_with_name("usize", &index_id.token_value);
-                    i_property_definition.flags = PropertyDefinitionFlags {
-                        is_binding_repeat_i: true,
-                        is_binding_repeat_elem: false,
-                        is_repeat_source_range,
-                        is_repeat_source_iterable,
-                        is_property_wrapped: true,
-                    };
-
-                    ctx.scope_stack.push(HashMap::from([
-                        //`elem` property (by specified name)
-                        (elem_id.clone().token_value, elem_property_definition),
-                        //`i` property (by specified name)
-                        (index_id.clone().token_value, i_property_definition),
-                    ]));
-                }
-            };
+            ctx.scope_stack.push(scope);
+
+            // Check that each destructured path exists, e.g. that `score` may be bound by `{score}`
+            for (binding, _) in elem_bindings {
+                ctx.resolve_symbol_as_prop_def(&binding.token_value, binding.clone())?;
+            }
 
             // The return type for a repeat source expression will either be:
             //   1. isize, for ranges (including ranges with direct symbolic references as either operand, like `self.x..10`)
-            //   2. T for a direct symbolic reference to `self.x` for x : Property<Vec<T>>
+            //   2. T for a direct symbolic reference to `self.x` for x : Property<Vec<T>>,
+            //      or for collection methods called on it, like `self.x.rev()`
             // Presumably, we could also support arbitrary expressions as a #3, but
             // we need some way to infer the return type, statically.  This may mean requiring
             // an explicit type declaration by the end-user, or perhaps we can hack something
@@ -522,10 +659,63 @@ fn recurse_compile_expressions<'a>(
                     input_statement,
                     is_repeat_source_iterable_expression: is_repeat_source_iterable,
                     repeat_source_iterable_type_id_escaped,
+                    interpreted_paxel: if is_repeat_source_range {
+                        Some(paxel.token_value.clone())
+                    } else {
+                        None
+                    },
+                    scope_frames: ctx.get_scope_frames(),
                 },
             );
+
+            // Handle the `elem.id` in `for elem in self.elements key elem.id`.  Keys are compiled
+            // with the repeat predicate in scope, and stringified so that keys of any
+            // `Display` type can be compared by Repeat at runtime.
+            if let Some(key_expression_paxel) = &cfa.repeat_key_expression_paxel {
+                let key_type = check_expression_type(key_expression_paxel, None, &ctx)?;
+                if !matches!(
+                    key_type,
+                    PaxelType::String | PaxelType::Numeric | PaxelType::Bool | PaxelType::Unknown
+                ) {
+                    return Err(PaxTemplateError::new(
+                        Some(format!(
+                            "Keys must be strings, numbers or booleans, but found `{}`",
+                            key_type
+                        )),
+                        key_expression_paxel.clone(),
+                    ));
+                }
+                let (output_statement, invocations) =
+                    compile_paxel_to_ril(key_expression_paxel.clone(), &ctx)?;
+                let id = ctx.uid_gen.next().unwrap();
+
+                cfa.repeat_key_expression_vtable_id = Some(id);
+
+                let mut whitespace_removed_input = key_expression_paxel.clone().token_value;
+                whitespace_removed_input.retain(|c| !c.is_whitespace());
+
+                let source_map_id = source_map.insert(key_expression_paxel.clone());
+                let input_statement =
+                    source_map.generate_mapped_string(whitespace_removed_input, source_map_id);
+
+                ctx.expression_specs.insert(
+                    id,
+                    ExpressionSpec {
+                        id,
+                        pascalized_return_type: "String".to_string(),
+                        invocations,
+                        output_statement: format!("({}).to_string()", output_statement),
+                        input_statement,
+                        is_repeat_source_iterable_expression: false,
+                        repeat_source_iterable_type_id_escaped: "".to_string(),
+                        interpreted_paxel: None,
+                        scope_frames: ctx.get_scope_frames(),
+                    },
+                );
+            }
         } else if let Some(condition_expression_paxel) = &cfa.condition_expression_paxel {
             //Handle `if` boolean expression, e.g. the `num_clicks > 5` in `if num_clicks > 5 { ... }`
+            check_expression_type(condition_expression_paxel, Some("bool"), &ctx)?;
             let (output_statement, invocations) =
                 compile_paxel_to_ril(condition_expression_paxel.clone(), &ctx)?;
             let id = ctx.uid_gen.next().unwrap();
@@ -549,10 +739,13 @@ fn recurse_compile_expressions<'a>(
                     input_statement,
                     is_repeat_source_iterable_expression: false,
                     repeat_source_iterable_type_id_escaped: "".to_string(),
+                    interpreted_paxel: Some(condition_expression_paxel.token_value.clone()),
+                    scope_frames: ctx.get_scope_frames(),
                 },
             );
         } else if let Some(slot_index_expression_paxel) = &cfa.slot_index_expression_paxel {
             //Handle `if` boolean expression, e.g. the `num_clicks > 5` in `if num_clicks > 5 { ... }`
+            check_expression_type(slot_index_expression_paxel, Some("Numeric"), &ctx)?;
             let (output_statement, invocations) =
                 compile_paxel_to_ril(slot_index_expression_paxel.clone(), &ctx)?;
             let id = ctx.uid_gen.next().unwrap();
@@ -576,6 +769,8 @@ fn recurse_compile_expressions<'a>(
                     input_statement,
                     is_repeat_source_iterable_expression: false,
                     repeat_source_iterable_type_id_escaped: "".to_string(),
+                    interpreted_paxel: Some(slot_index_expression_paxel.token_value.clone()),
+                    scope_frames: ctx.get_scope_frames(),
                 },
             );
         } else {
@@ -634,7 +829,7 @@ fn resolve_symbol_as_invocation(
         unimplemented!("Built-ins like $bounds are not yet supported")
     } else {
         let prop_def_chain = ctx
-            .resolve_symbol_as_prop_def(&sym, token.clone())?
+            .resolve_symbol_as_prop_def(sym, token.clone())?
             .ok_or::<eyre::Report>(PaxTemplateError::new(
                 Some(format!("symbol not found: {}", &sym)),
                 token.clone(),
@@ -643,7 +838,7 @@ fn resolve_symbol_as_invocation(
         let nested_prop_def = prop_def_chain.last().unwrap();
         let is_nested_numeric = ExpressionSpecInvocation::is_numeric(&nested_prop_def.type_id);
 
-        let split_symbols = clean_and_split_symbols(&sym);
+        let split_symbols = clean_and_split_symbols(sym);
         let escaped_identifier = escape_identifier(split_symbols.join("."));
 
         let mut split_symbols = split_symbols.into_iter();
@@ -663,7 +858,7 @@ fn resolve_symbol_as_invocation(
         let mut found_depth: Option<usize> = None;
         let mut current_depth = 0;
         let mut found_val: Option<PropertyDefinition> = None;
-        while let None = found_depth {
+        while found_depth.is_none() {
             let map = ctx
                 .scope_stack
                 .get((ctx.scope_stack.len() - 1) - current_depth)
@@ -700,18 +895,18 @@ fn resolve_symbol_as_invocation(
                 };
             }
         });
-        if nested_symbol_tail_literal != "" {
+        if !nested_symbol_tail_literal.is_empty() {
             nested_symbol_tail_literal += ".clone()"
         }
 
         Ok(ExpressionSpecInvocation {
             root_identifier,
-            is_numeric: ExpressionSpecInvocation::is_numeric(&property_properties_coproduct_type),
+            is_numeric: ExpressionSpecInvocation::is_numeric(property_properties_coproduct_type),
             is_bool: ExpressionSpecInvocation::is_primitive_bool(
-                &property_properties_coproduct_type,
+                property_properties_coproduct_type,
             ),
             is_string: ExpressionSpecInvocation::is_primitive_string(
-                &property_properties_coproduct_type,
+                property_properties_coproduct_type,
             ),
             escaped_identifier,
             stack_offset,
@@ -724,18 +919,298 @@ fn resolve_symbol_as_invocation(
     }
 }
 
+/// The kinds of values on which PAXEL's standard library methods may be called
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PaxelKind {
+    Numeric,
+    String,
+    Bool,
+    Collection,
+}
+
+impl PaxelKind {
+    pub(crate) fn from_type_id(type_id: &str, type_table: &TypeTable) -> Option<Self> {
+        let is_collection = type_table
+            .get(type_id)
+            .map_or(false, |td| td.inner_iterable_type_id.is_some());
+        let type_name = type_id.split("::").last().unwrap();
+        if is_collection {
+            Some(PaxelKind::Collection)
+        } else if ExpressionSpecInvocation::is_numeric(type_name) || type_name == "Numeric" {
+            Some(PaxelKind::Numeric)
+        } else if ExpressionSpecInvocation::is_primitive_string(type_name)
+            || type_name == "StringBox"
+        {
+            Some(PaxelKind::String)
+        } else if ExpressionSpecInvocation::is_primitive_bool(type_name) {
+            Some(PaxelKind::Bool)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PaxelMethodArg {
+    Value,
+    Lambda,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PaxelMethodReturn {
+    Kind(PaxelKind),
+    /// The same type as the receiver, like the `Vec<T>` returned by `filter`
+    Receiver,
+}
+
+/// PAXEL's standard library, as implemented by `pax_runtime_api::paxel_std`: the methods that may be called
+/// on each kind of value, along with their arguments (after the receiver) and return values
+pub(crate) const PAXEL_STD_METHODS: &[(PaxelKind, &str, &[PaxelMethodArg], PaxelMethodReturn)] = {
+    use PaxelKind::*;
+    use PaxelMethodArg::*;
+    use PaxelMethodReturn::*;
+    &[
+        (Numeric, "abs", &[], Kind(Numeric)),
+        (Numeric, "floor", &[], Kind(Numeric)),
+        (Numeric, "ceil", &[], Kind(Numeric)),
+        (Numeric, "round", &[], Kind(Numeric)),
+        (Numeric, "sqrt", &[], Kind(Numeric)),
+        (Numeric, "pow", &[Value], Kind(Numeric)),
+        (Numeric, "min", &[Value], Kind(Numeric)),
+        (Numeric, "max", &[Value], Kind(Numeric)),
+        (Numeric, "clamp", &[Value, Value], Kind(Numeric)),
+        (String, "len", &[], Kind(Numeric)),
+        (String, "is_empty", &[], Kind(Bool)),
+        (String, "to_uppercase", &[], Kind(String)),
+        (String, "to_lowercase", &[], Kind(String)),
+        (String, "trim", &[], Kind(String)),
+        (String, "contains", &[Value], Kind(Bool)),
+        (String, "starts_with", &[Value], Kind(Bool)),
+        (String, "ends_with", &[Value], Kind(Bool)),
+        (String, "replace", &[Value, Value], Kind(String)),
+        (Collection, "len", &[], Kind(Numeric)),
+        (Collection, "is_empty", &[], Kind(Bool)),
+        (Collection, "filter", &[Lambda], Receiver),
+        (Collection, "any", &[Lambda], Kind(Bool)),
+        (Collection, "all", &[Lambda], Kind(Bool)),
+        (Collection, "count", &[Lambda], Kind(Numeric)),
+        (Collection, "rev", &[], Receiver),
+        (Collection, "take", &[Value], Receiver),
+        (Collection, "skip", &[Value], Receiver),
+    ]
+};
+
+/// Resolves and type-checks the method calls in `paxel`, like the `filter` in `self.items.filter(|x| x.visible)`.
+/// Returns the bindings for each lambda's parameters, by the lambda's offset within `paxel`,
+/// along with the type_id returned by each chain of method calls, outermost chains first.
+fn compile_method_calls(
+    paxel: &Token,
+    ctx: &ExpressionCompilationContext,
+) -> Result<(HashMap<usize, String>, Vec<String>), eyre::Report> {
+    let chains = crate::parsing::collect_paxel_method_calls(&paxel.token_value);
+
+    let mut lambda_params: HashMap<usize, PropertyDefinition> = HashMap::new();
+    let mut lambda_bindings = HashMap::new();
+    let mut chain_type_ids = vec![];
+
+    // Chains are visited from the outermost inwards, so a lambda's parameters are typed before any
+    // chains within that lambda are resolved against them
+    for chain in chains {
+        let split_symbols = clean_and_split_symbols(&chain.receiver);
+        let lambda_param = chain
+            .enclosing_lambdas
+            .iter()
+            .rev()
+            .filter_map(|offset| lambda_params.get(offset))
+            .find(|pd| pd.name == crate::parsing::get_symbol_root(&chain.receiver));
+        let receiver_pd = if let Some(lambda_param) = lambda_param {
+            ctx.resolve_nested_symbols(
+                lambda_param.clone(),
+                split_symbols.iter().skip(1),
+                &chain.receiver,
+                paxel.clone(),
+            )?
+        } else {
+            ctx.resolve_symbol_as_prop_def(&chain.receiver, paxel.clone())?
+                .ok_or::<eyre::Report>(PaxTemplateError::new(
+                    Some(format!("symbol not found: {}", &chain.receiver)),
+                    paxel.clone(),
+                ))?
+        }
+        .last()
+        .unwrap()
+        .clone();
+
+        let mut type_id = receiver_pd.type_id.clone();
+        for call in chain.calls {
+            let kind = PaxelKind::from_type_id(&type_id, ctx.type_table).ok_or::<eyre::Report>(
+                PaxTemplateError::new(
+                    Some(format!(
+                        "Unable to call `{}` on a `{}`; methods may only be called on numbers, strings, bools and collections.",
+                        call.name, type_id
+                    )),
+                    paxel.clone(),
+                ),
+            )?;
+            let (_, _, args, ret) = PAXEL_STD_METHODS
+                .iter()
+                .find(|(k, name, _, _)| *k == kind && *name == call.name)
+                .ok_or::<eyre::Report>(PaxTemplateError::new(
+                    Some(format!(
+                        "Unknown method `{}` for a `{}`.  Available methods are: {}",
+                        call.name,
+                        type_id,
+                        PAXEL_STD_METHODS
+                            .iter()
+                            .filter(|(k, _, _, _)| *k == kind)
+                            .map(|(_, name, _, _)| *name)
+                            .join(", ")
+                    )),
+                    paxel.clone(),
+                ))?;
+            if args.len() != call.args.len() {
+                return Err(PaxTemplateError::new(
+                    Some(format!(
+                        "`{}` expects {} argument(s) but was given {}",
+                        call.name,
+                        args.len(),
+                        call.args.len()
+                    )),
+                    paxel.clone(),
+                ));
+            }
+            for (expected, arg) in args.iter().zip(call.args.iter()) {
+                match (expected, arg) {
+                    (PaxelMethodArg::Value, PaxelMethodCallArg::Expression) => {}
+                    (PaxelMethodArg::Lambda, PaxelMethodCallArg::Lambda(lambda)) => {
+                        if lambda.params.len() != 1 {
+                            return Err(PaxTemplateError::new(
+                                Some(format!(
+                                    "The lambda passed to `{}` must take exactly one parameter, like `|x| x.visible`",
+                                    call.name
+                                )),
+                                paxel.clone(),
+                            ));
+                        }
+                        let elem_type_id = ctx
+                            .type_table
+                            .get(&type_id)
+                            .unwrap()
+                            .inner_iterable_type_id
+                            .clone()
+                            .unwrap();
+                        let param_pd = PropertyDefinition::primitive_with_name(
+                            &elem_type_id,
+                            &lambda.params[0],
+                        );
+                        lambda_bindings.insert(
+                            lambda.offset,
+                            compile_lambda_bindings(&lambda.symbols, &param_pd, ctx, paxel)?,
+                        );
+                        lambda_params.insert(lambda.offset, param_pd);
+                    }
+                    (PaxelMethodArg::Value, PaxelMethodCallArg::Lambda(_)) => {
+                        return Err(PaxTemplateError::new(
+                            Some(format!("`{}` expects a value, not a lambda", call.name)),
+                            paxel.clone(),
+                        ))
+                    }
+                    (PaxelMethodArg::Lambda, PaxelMethodCallArg::Expression) => {
+                        return Err(PaxTemplateError::new(
+                            Some(format!(
+                                "`{}` expects a lambda, like `|x| x.visible`",
+                                call.name
+                            )),
+                            paxel.clone(),
+                        ))
+                    }
+                }
+            }
+            type_id = match ret {
+                PaxelMethodReturn::Kind(PaxelKind::Numeric) => "Numeric".to_string(),
+                PaxelMethodReturn::Kind(PaxelKind::String) => "StringBox".to_string(),
+                PaxelMethodReturn::Kind(PaxelKind::Bool) => "bool".to_string(),
+                PaxelMethodReturn::Kind(PaxelKind::Collection) | PaxelMethodReturn::Receiver => {
+                    type_id
+                }
+            };
+        }
+        chain_type_ids.push(type_id);
+    }
+    Ok((lambda_bindings, chain_type_ids))
+}
+
+/// Binds each symbol used within a lambda's body that refers to its parameter, like `let xPERIvisible = x.visible.get().clone();`
+/// for the `x.visible` in `|x| x.visible`, wrapping numbers and strings as `Numeric`s and `StringBox`es as invocations are.
+fn compile_lambda_bindings(
+    symbols: &[String],
+    param_pd: &PropertyDefinition,
+    ctx: &ExpressionCompilationContext,
+    paxel: &Token,
+) -> Result<String, eyre::Report> {
+    // The parameter itself is bound last, since it shadows the reference from which nested symbols are read
+    let mut symbols = symbols.to_vec();
+    symbols.sort_by_key(|symbol| *symbol == param_pd.name);
+
+    let mut bindings = vec![];
+    for symbol in symbols {
+        let split_symbols = clean_and_split_symbols(&symbol);
+        let prop_def_chain = ctx.resolve_nested_symbols(
+            param_pd.clone(),
+            split_symbols.iter().skip(1),
+            &symbol,
+            paxel.clone(),
+        )?;
+        let mut value = param_pd.name.clone();
+        for pd in prop_def_chain.iter().skip(1) {
+            value += &if pd.flags.is_property_wrapped {
+                format!(".{}.get()", pd.name)
+            } else {
+                format!(".{}", pd.name)
+            };
+        }
+        let type_name = prop_def_chain
+            .last()
+            .unwrap()
+            .type_id
+            .split("::")
+            .last()
+            .unwrap()
+            .to_string();
+        let value = if ExpressionSpecInvocation::is_numeric(&type_name) {
+            format!("Numeric::from({}.clone())", value)
+        } else if ExpressionSpecInvocation::is_primitive_string(&type_name) {
+            format!("StringBox::from({}.clone())", value)
+        } else {
+            format!("{}.clone()", value)
+        };
+        bindings.push(format!(
+            "let {} = {};",
+            escape_identifier(split_symbols.join(".")),
+            value
+        ));
+    }
+    Ok(bindings.join(" "))
+}
+
 /// Returns (RIL string, list of invocation specs for any symbols used)
-fn compile_paxel_to_ril<'a>(
+fn compile_paxel_to_ril(
     paxel: Token,
-    ctx: &ExpressionCompilationContext<'a>,
+    ctx: &ExpressionCompilationContext<'_>,
 ) -> Result<(String, Vec<ExpressionSpecInvocation>), eyre::Report> {
     //1. run Pratt parser; generate output RIL and collected symbolic_ids
-    let (output_string, symbolic_ids) = crate::parsing::run_pratt_parser(&paxel.token_value);
+    let (mut output_string, symbolic_ids) = crate::parsing::run_pratt_parser(&paxel.token_value);
+
+    //  bind the parameters of any lambdas passed to methods, now that their types are known
+    let (lambda_bindings, _) = compile_method_calls(&paxel, ctx)?;
+    for (offset, bindings) in lambda_bindings {
+        output_string = output_string.replace(&format!("/*lambda@{}*/", offset), &bindings);
+    }
 
     //2. for each symbolic id discovered during parsing, resolve that id through scope_stack and populate an ExpressionSpecInvocation
     let invocations_result: Result<Vec<_>, _> = symbolic_ids
         .iter()
-        .map(|sym| resolve_symbol_as_invocation(&sym.trim(), ctx, paxel.clone()))
+        .map(|sym| resolve_symbol_as_invocation(sym.trim(), ctx, paxel.clone()))
         .collect();
 
     let invocations = match invocations_result {
@@ -799,12 +1274,42 @@ pub fn clean_and_split_symbols(possibly_nested_symbols: &str) -> Vec<String> {
     };
 
     entire_symbol
-        .split(".")
+        .split('.')
         .map(|atomic_symbol| atomic_symbol.to_string())
         .collect::<Vec<_>>()
 }
 
 impl<'a> ExpressionCompilationContext<'a> {
+    /// Describe the symbols bound by each scope in `scope_stack`, from the innermost outwards,
+    /// so that the runtime interpreter can resolve symbols against the `StackFrame`s they correspond to
+    pub fn get_scope_frames(&self) -> Vec<ExpressionScopeFrame> {
+        self.scope_stack
+            .iter()
+            .rev()
+            .map(|scope| {
+                let find_binding = |is_binding: fn(&PropertyDefinition) -> bool| {
+                    scope
+                        .values()
+                        .find(|pd| is_binding(pd))
+                        .map(|pd| pd.name.clone())
+                };
+                let mut repeat_elem_destructuring = scope
+                    .values()
+                    .filter(|pd| !pd.repeat_destructuring_path.is_empty())
+                    .map(|pd| (pd.name.clone(), pd.repeat_destructuring_path.clone()))
+                    .collect::<Vec<_>>();
+                repeat_elem_destructuring.sort();
+                ExpressionScopeFrame {
+                    repeat_elem_id: find_binding(|pd| {
+                        pd.flags.is_binding_repeat_elem && pd.repeat_destructuring_path.is_empty()
+                    }),
+                    repeat_index_id: find_binding(|pd| pd.flags.is_binding_repeat_i),
+                    repeat_elem_destructuring,
+                }
+            })
+            .collect()
+    }
+
     /// for an input symbol like `i` or `self.num_clicks`
     /// traverse the self-attached `scope_stack`
     /// and return a copy of the related `PropertyDefinition`, if found.
@@ -823,10 +1328,10 @@ impl<'a> ExpressionCompilationContext<'a> {
             // resolve root symbol through builtin map
             None //FUTURE: support built-ins
         } else {
-            // resolve through scope stack
+            // resolve through scope stack, from the innermost scope outwards, so that e.g. `for` bindings shadow properties
             let mut found = false;
             let mut exhausted = false;
-            let mut iter = self.scope_stack.iter();
+            let mut iter = self.scope_stack.iter().rev();
             let mut current_frame = iter.next();
             let mut ret: Option<PropertyDefinition> = None;
             while !found && !exhausted {
@@ -843,29 +1348,48 @@ impl<'a> ExpressionCompilationContext<'a> {
             ret
         };
 
-        // handle nested symbols like `foo.bar`.
+        // handle nested symbols like `foo.bar`, including those bound by destructuring, like `score` for `(name, score)`,
+        // which are resolved like `elem.1`
         if let Some(root_symbol_pd) = root_symbol_pd {
-            let mut ret = vec![root_symbol_pd];
-            for atomic_symbol in split_symbols {
-                let td = ret.last().unwrap().get_type_definition(self.type_table);
-                // return terminal nested symbol's PropertyDefinition, or root's if there are no nested symbols
-                let next_pd = td
-                    .property_definitions
-                    .iter()
-                    .find(|pd| pd.name == *atomic_symbol)
-                    .ok_or::<eyre::Report>(PaxTemplateError::new(
-                        Some(format!(
-                            "Unable to resolve nested symbol `{}` while evaluating `{}`.",
-                            atomic_symbol, symbol
-                        )),
-                        token.clone(),
-                    ))?
-                    .clone();
-                ret.push(next_pd);
-            }
-            Ok(Some(ret))
+            let destructuring_path = root_symbol_pd.repeat_destructuring_path.clone();
+            Ok(Some(self.resolve_nested_symbols(
+                root_symbol_pd,
+                destructuring_path.iter().chain(split_symbols),
+                symbol,
+                token,
+            )?))
         } else {
             Ok(None)
         }
     }
+
+    /// Starting from `root_symbol_pd`, resolve each of `nested_symbols` as a field of the previous one,
+    /// returning the chain of `PropertyDefinition`s from the root to the terminal nested symbol
+    pub fn resolve_nested_symbols<'b>(
+        &self,
+        root_symbol_pd: PropertyDefinition,
+        nested_symbols: impl Iterator<Item = &'b String>,
+        symbol: &str,
+        token: Token,
+    ) -> Result<Vec<PropertyDefinition>, eyre::Report> {
+        let mut ret = vec![root_symbol_pd];
+        for atomic_symbol in nested_symbols {
+            let td = ret.last().unwrap().get_type_definition(self.type_table);
+            // return terminal nested symbol's PropertyDefinition, or root's if there are no nested symbols
+            let next_pd = td
+                .property_definitions
+                .iter()
+                .find(|pd| pd.name == *atomic_symbol)
+                .ok_or::<eyre::Report>(PaxTemplateError::new(
+                    Some(format!(
+                        "Unable to resolve nested symbol `{}` while evaluating `{}`.",
+                        atomic_symbol, symbol
+                    )),
+                    token.clone(),
+                ))?
+                .clone();
+            ret.push(next_pd);
+        }
+        Ok(ret)
+    }
 }
@@ -1,12 +1,12 @@
 use std::fs;
 use std::path::Path;
 use std::str::FromStr;
-use toml_edit;
+
 use toml_edit::Document;
 
 pub fn set_path_on_pax_dependencies(full_path: &Path) {
     // Read the Cargo.toml
-    let mut doc = fs::read_to_string(&full_path.join("Cargo.toml"))
+    let mut doc = fs::read_to_string(full_path.join("Cargo.toml"))
         .expect("Failed to read Cargo.toml")
         .parse::<toml_edit::Document>()
         .expect("Failed to parse Cargo.toml");
@@ -46,13 +46,13 @@ pub fn set_path_on_pax_dependencies(full_path: &Path) {
     }
 
     // Write the modified Cargo.toml back to disk
-    fs::write(&full_path.join("Cargo.toml"), doc.to_string())
+    fs::write(full_path.join("Cargo.toml"), doc.to_string())
         .expect("Failed to write modified Cargo.toml");
 }
 
 pub fn remove_path_from_pax_dependencies(full_path: &Path) {
     // Read the Cargo.toml
-    let mut doc = fs::read_to_string(&full_path.join("Cargo.toml"))
+    let mut doc = fs::read_to_string(full_path.join("Cargo.toml"))
         .expect("Failed to read Cargo.toml")
         .parse::<toml_edit::Document>()
         .expect("Failed to parse Cargo.toml");
@@ -87,7 +87,7 @@ pub fn remove_path_from_pax_dependencies(full_path: &Path) {
     }
 
     // Write the modified Cargo.toml back to disk
-    fs::write(&full_path.join("Cargo.toml"), doc.to_string())
+    fs::write(full_path.join("Cargo.toml"), doc.to_string())
         .expect("Failed to write modified Cargo.toml");
 }
 
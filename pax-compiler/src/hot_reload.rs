@@ -0,0 +1,555 @@
+//! Template hot reloading for `pax run --hot-reload`.  Watches a project's sources and, whenever a
+//! component's template changes, sends the new template to the running app over a websocket (see
+//! `pax_message::hot_reload`), where the engine swaps it in without losing state.
+//!
+//! Reloaded templates are evaluated by the runtime interpreter, so anything they refer to by name —
+//! handlers, functions, the element types of `for` sources — must have been known when the app was
+//! built.  Changes that can't be hot reloaded, e.g. to Rust sources or to `@timeline`s, print a
+//! prompt to restart `pax run` instead.
+
+use std::collections::{HashMap, HashSet};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use color_eyre::eyre::{self, eyre, Report};
+use pax_message::hot_reload::{
+    ComponentTemplateReload, InterpretedExpression, InterpretedExpressionKind,
+    InterpretedScopeFrame, TemplateNodePatch,
+};
+use tungstenite::{Message, WebSocket};
+
+use crate::errors::source_map::SourceMap;
+use crate::expressions::compile_component_expressions;
+use crate::manifest::{
+    ComponentDefinition, ExpressionSpec, PaxManifest, TemplateNodeDefinition, TokenType,
+    ValueDefinition,
+};
+use crate::watch::{includes_rust_sources, normalize, SourceWatcher};
+use crate::{
+    get_host_crate_info, parsing, run_parser_binary, update_property_prefixes_in_place, PAX_BADGE,
+};
+
+/// Starts watching the project at `path` for changes relative to `baseline`, the (uncompiled) manifest of the
+/// running app, whose expression ids are all below `next_expression_id`.  Returns the port of the websocket
+/// server to which running apps connect to receive reloads.
+pub fn start_hot_reload_session(
+    path: &str,
+    baseline: PaxManifest,
+    next_expression_id: usize,
+    process_child_ids: Arc<Mutex<Vec<u64>>>,
+) -> eyre::Result<u16, Report> {
+    let (port, server) = ReloadServer::start()?;
+
+    let watcher = SourceWatcher::new(path)?;
+
+    let mut session = HotReloadSession {
+        path: path.to_string(),
+        baseline,
+        next_expression_id,
+        process_child_ids,
+        server,
+    };
+    thread::spawn(move || {
+        while let Some(changed_paths) = watcher.next_change() {
+            session.handle_change(&changed_paths);
+        }
+    });
+
+    Ok(port)
+}
+
+struct HotReloadSession {
+    path: String,
+    /// The manifest as of the last successful reload (or the build), before expression compilation
+    baseline: PaxManifest,
+    next_expression_id: usize,
+    process_child_ids: Arc<Mutex<Vec<u64>>>,
+    server: ReloadServer,
+}
+
+impl HotReloadSession {
+    fn handle_change(&mut self, changed_paths: &[PathBuf]) {
+        println!("{} 🔥 Change detected; reparsing", *PAX_BADGE);
+        let output = run_parser_binary(&self.path, Arc::clone(&self.process_child_ids));
+        if !output.status.success() {
+            eprint!("{}", String::from_utf8_lossy(&output.stderr));
+            println!(
+                "{} ❌ Parsing failed; fix the error above to resume hot reloading",
+                *PAX_BADGE
+            );
+            return;
+        }
+        let mut manifest: PaxManifest = match serde_json::from_slice(&output.stdout) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                println!("{} ❌ Malformed JSON from parser: {}", *PAX_BADGE, err);
+                return;
+            }
+        };
+        let host_crate_info = get_host_crate_info(&Path::new(&self.path).join("Cargo.toml"));
+        update_property_prefixes_in_place(&mut manifest, &host_crate_info);
+
+        match self.build_reloads(&manifest) {
+            Ok(reloads) => {
+                for reload in reloads.iter() {
+                    println!(
+                        "{} ♨️  Reloading {}",
+                        *PAX_BADGE,
+                        &manifest.components[&reload.component_type_id].pascal_identifier
+                    );
+                    self.server.broadcast(reload);
+                }
+                self.baseline = manifest;
+            }
+            Err(reason) => println!(
+                "{} ♻️  {}, which can't be hot reloaded; restart `pax run` to apply it",
+                *PAX_BADGE, reason
+            ),
+        }
+
+        if includes_rust_sources(changed_paths) {
+            println!(
+                "{} ♻️  Rust sources changed; restart `pax run` to apply changes outside of templates",
+                *PAX_BADGE
+            );
+        }
+    }
+
+    /// Diffs `manifest` against the baseline, returning a reload for each component whose template changed
+    fn build_reloads(
+        &mut self,
+        manifest: &PaxManifest,
+    ) -> Result<Vec<ComponentTemplateReload>, String> {
+        find_changed_components(&self.baseline, manifest)?
+            .into_iter()
+            .map(|component_def| self.build_reload(manifest, component_def))
+            .collect()
+    }
+
+    fn build_reload(
+        &mut self,
+        manifest: &PaxManifest,
+        component_def: &ComponentDefinition,
+    ) -> Result<ComponentTemplateReload, String> {
+        let mut component_def = component_def.clone();
+        convert_literals_to_expressions(&mut component_def)?;
+
+        let mut expression_specs = HashMap::new();
+        self.next_expression_id = compile_component_expressions(
+            &mut component_def,
+            &manifest.components,
+            &manifest.type_table,
+            &mut expression_specs,
+            self.next_expression_id,
+            &mut SourceMap::new(),
+        )
+        .map_err(|err| {
+            format!(
+                "The template of `{}` failed to compile: {}",
+                component_def.pascal_identifier, err
+            )
+        })?;
+
+        let template = component_def.template.as_ref().unwrap();
+        let mut builder = TemplatePatchBuilder {
+            template,
+            paxel_overrides: HashMap::new(),
+            repeat_key_ids: HashSet::new(),
+        };
+        let template_patches = template[0]
+            .child_ids
+            .iter()
+            .map(|child_id| builder.build(&template[*child_id]))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let mut expression_specs: Vec<&ExpressionSpec> = expression_specs.values().collect();
+        expression_specs.sort();
+        let expressions = expression_specs
+            .into_iter()
+            .map(|spec| builder.build_expression(spec))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let handlers = component_def
+            .events
+            .iter()
+            .flatten()
+            .flat_map(|event| {
+                event
+                    .value
+                    .iter()
+                    .map(|handler| (event.key.token_value.clone(), handler.token_value.clone()))
+            })
+            .collect();
+
+        Ok(ComponentTemplateReload {
+            component_type_id: component_def.type_id.clone(),
+            expressions,
+            template: template_patches,
+            handlers,
+        })
+    }
+}
+
+/// The components of `manifest` whose templates differ from those of `baseline`, or an error describing
+/// a change that can't be hot reloaded
+fn find_changed_components<'a>(
+    baseline: &PaxManifest,
+    manifest: &'a PaxManifest,
+) -> Result<Vec<&'a ComponentDefinition>, String> {
+    if normalize(&manifest.type_table) != normalize(&baseline.type_table) {
+        return Err("Property or type definitions changed".to_string());
+    }
+    let type_ids: HashSet<&String> = manifest.components.keys().collect();
+    if type_ids != baseline.components.keys().collect() {
+        return Err("Components were added or removed".to_string());
+    }
+
+    let mut changed_components: Vec<&ComponentDefinition> = manifest
+        .components
+        .values()
+        .filter(|cd| normalize(cd) != normalize(&baseline.components[&cd.type_id]))
+        .collect();
+    changed_components.sort_by(|a, b| a.type_id.cmp(&b.type_id));
+
+    for component_def in changed_components.iter() {
+        let baseline_component_def = &baseline.components[&component_def.type_id];
+        if component_def.is_primitive || component_def.is_struct_only_component {
+            return Err(format!("`{}` changed", component_def.pascal_identifier));
+        }
+        if normalize(&component_def.timeline) != normalize(&baseline_component_def.timeline) {
+            return Err(format!(
+                "The `@timeline` of `{}` changed",
+                component_def.pascal_identifier
+            ));
+        }
+    }
+    Ok(changed_components)
+}
+
+/// Rewrites literal values (e.g. `100px`) and identifiers (e.g. `fill=color`) as the equivalent expressions,
+/// since a reloaded template's values are all evaluated by the interpreter
+fn convert_literals_to_expressions(component_def: &mut ComponentDefinition) -> Result<(), String> {
+    fn convert(settings: &mut [(crate::manifest::Token, ValueDefinition)]) -> Result<(), String> {
+        for (key, value) in settings.iter_mut() {
+            match value {
+                ValueDefinition::LiteralValue(token) => {
+                    let mut token = token.clone();
+                    token.token_value = token.raw_value.clone();
+                    token.token_type = TokenType::Expression;
+                    *value = ValueDefinition::Expression(token, None);
+                }
+                ValueDefinition::Identifier(token, _)
+                    if key.token_value != "id" && key.token_value != "class" =>
+                {
+                    *value = ValueDefinition::Expression(token.clone(), None);
+                }
+                ValueDefinition::Block(_) => {
+                    return Err(format!("`{}` is bound to a literal block", key.token_value))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    for tnd in component_def.template.iter_mut().flatten() {
+        if let Some(settings) = &mut tnd.settings {
+            convert(settings)?;
+        }
+    }
+    for block in component_def.settings.iter_mut().flatten() {
+        convert(&mut block.value_block.settings_key_value_pairs)?;
+    }
+    Ok(())
+}
+
+struct TemplatePatchBuilder<'a> {
+    template: &'a Vec<TemplateNodeDefinition>,
+    /// PAXEL for the expressions that are always compiled at build time, and so carry no `interpreted_paxel`:
+    /// `for` sources over a `Vec` and `for` keys
+    paxel_overrides: HashMap<usize, String>,
+    repeat_key_ids: HashSet<usize>,
+}
+
+impl<'a> TemplatePatchBuilder<'a> {
+    fn build_children(&mut self, child_ids: &[usize]) -> Result<Vec<TemplateNodePatch>, String> {
+        child_ids
+            .iter()
+            .map(|child_id| self.build(&self.template[*child_id]))
+            .collect()
+    }
+
+    fn build(&mut self, tnd: &TemplateNodeDefinition) -> Result<TemplateNodePatch, String> {
+        let control_flow_settings = tnd.control_flow_settings.as_ref();
+        if tnd.type_id == parsing::TYPE_ID_REPEAT {
+            let cfsd = control_flow_settings.unwrap();
+            let rsd = cfsd.repeat_source_definition.as_ref().unwrap();
+            let source = rsd.vtable_id.unwrap();
+            if let Some(symbolic_binding) = &rsd.symbolic_binding {
+                self.paxel_overrides
+                    .insert(source, symbolic_binding.raw_value.clone());
+            }
+            let key = cfsd.repeat_key_expression_vtable_id;
+            if let (Some(key), Some(key_paxel)) = (key, &cfsd.repeat_key_expression_paxel) {
+                self.paxel_overrides
+                    .insert(key, key_paxel.token_value.clone());
+                self.repeat_key_ids.insert(key);
+            }
+            Ok(TemplateNodePatch::Repeat {
+                source,
+                is_source_range: rsd.range_expression_paxel.is_some(),
+                key,
+                children: self.build_children(&tnd.child_ids)?,
+            })
+        } else if tnd.type_id == parsing::TYPE_ID_IF {
+            let cfsd = control_flow_settings.unwrap();
+            let else_branch_child_ids = cfsd.else_branch_child_ids.clone().unwrap_or_default();
+            let (else_child_ids, child_ids): (Vec<usize>, Vec<usize>) = tnd
+                .child_ids
+                .iter()
+                .partition(|child_id| else_branch_child_ids.contains(child_id));
+            Ok(TemplateNodePatch::Conditional {
+                condition: cfsd.condition_expression_vtable_id.unwrap(),
+                children: self.build_children(&child_ids)?,
+                else_children: self.build_children(&else_child_ids)?,
+            })
+        } else if tnd.type_id == parsing::TYPE_ID_SLOT {
+            Ok(TemplateNodePatch::Slot {
+                index: control_flow_settings
+                    .unwrap()
+                    .slot_index_expression_vtable_id
+                    .unwrap(),
+            })
+        } else {
+            let mut settings = vec![];
+            let mut handlers = vec![];
+            for (key, value) in tnd.settings.iter().flatten() {
+                match value {
+                    ValueDefinition::Expression(_, Some(id))
+                    | ValueDefinition::Identifier(_, Some(id)) => {
+                        settings.push((key.token_value.clone(), *id))
+                    }
+                    ValueDefinition::EventBindingTarget(handler) => {
+                        handlers.push((key.token_value.clone(), handler.token_value.clone()))
+                    }
+                    ValueDefinition::Timeline(_) => {
+                        return Err(format!("`{}` is bound to a `@timeline`", key.token_value))
+                    }
+                    //`id` and `class`
+                    _ => {}
+                }
+            }
+            Ok(TemplateNodePatch::Component {
+                type_id: tnd.type_id.clone(),
+                settings,
+                handlers,
+                children: self.build_children(&tnd.child_ids)?,
+            })
+        }
+    }
+
+    fn build_expression(&self, spec: &ExpressionSpec) -> Result<InterpretedExpression, String> {
+        let paxel = self
+            .paxel_overrides
+            .get(&spec.id)
+            .or(spec.interpreted_paxel.as_ref())
+            .ok_or_else(|| format!("`{}` can't be interpreted", spec.input_statement.content))?
+            .clone();
+        let kind = if spec.is_repeat_source_iterable_expression {
+            InterpretedExpressionKind::RepeatSourceIterable(
+                spec.repeat_source_iterable_type_id_escaped.clone(),
+            )
+        } else if self.repeat_key_ids.contains(&spec.id) {
+            InterpretedExpressionKind::RepeatKey
+        } else {
+            InterpretedExpressionKind::Value
+        };
+        let scope_frames = spec
+            .scope_frames
+            .iter()
+            .map(|frame| {
+                match (
+                    &frame.repeat_elem_id,
+                    &frame.repeat_index_id,
+                    frame.repeat_elem_destructuring.is_empty(),
+                ) {
+                    (None, None, true) => InterpretedScopeFrame::Properties,
+                    (elem, i, _) => InterpretedScopeFrame::RepeatItem {
+                        elem: elem.clone().unwrap_or_default(),
+                        i: i.clone(),
+                        destructured: frame.repeat_elem_destructuring.clone(),
+                    },
+                }
+            })
+            .collect();
+        Ok(InterpretedExpression {
+            id: spec.id,
+            paxel,
+            pascalized_return_type: spec.pascalized_return_type.clone(),
+            scope_frames,
+            kind,
+        })
+    }
+}
+
+/// Sends reloads to every connected app.  The latest reload of each component is replayed to apps
+/// that connect later, e.g. after a page refresh, since they were built from the original templates.
+struct ReloadServer {
+    latest_reloads: Arc<Mutex<HashMap<String, String>>>,
+    clients: Arc<Mutex<Vec<WebSocket<TcpStream>>>>,
+}
+
+impl ReloadServer {
+    fn start() -> eyre::Result<(u16, Self), Report> {
+        let port = portpicker::pick_unused_port()
+            .ok_or_else(|| eyre!("Failed to find a free port for the hot reload server"))?;
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+
+        let server = ReloadServer {
+            latest_reloads: Arc::new(Mutex::new(HashMap::new())),
+            clients: Arc::new(Mutex::new(vec![])),
+        };
+        let latest_reloads = Arc::clone(&server.latest_reloads);
+        let clients = Arc::clone(&server.clients);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Ok(mut websocket) = tungstenite::accept(stream) {
+                    let latest_reloads = latest_reloads.lock().unwrap();
+                    let is_connected = latest_reloads
+                        .values()
+                        .all(|reload| websocket.send(Message::Text(reload.clone())).is_ok());
+                    if is_connected {
+                        clients.lock().unwrap().push(websocket);
+                    }
+                }
+            }
+        });
+
+        Ok((port, server))
+    }
+
+    fn broadcast(&self, reload: &ComponentTemplateReload) {
+        let message = serde_json::to_string(reload).unwrap();
+        let mut latest_reloads = self.latest_reloads.lock().unwrap();
+        latest_reloads.insert(reload.component_type_id.clone(), message.clone());
+        self.clients
+            .lock()
+            .unwrap()
+            .retain_mut(|websocket| websocket.send(Message::Text(message.clone())).is_ok());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::ParsingContext;
+
+    const TEMPLATE: &str = r#"<Rectangle id=rect fill={Color::rgb(1.0, 0.0, 0.0)} width=100px />
+@timeline {
+    #rect {
+        width: [0: 100px, 60: 200px]
+    }
+}"#;
+
+    fn build_component(type_id: &str, pax: &str) -> ComponentDefinition {
+        let template_map = HashMap::from([(
+            "Rectangle".to_string(),
+            "pax_std::primitives::Rectangle".to_string(),
+        )]);
+        let pascal_identifier = type_id.rsplit("::").next().unwrap();
+        parsing::assemble_component_definition(
+            ParsingContext::default(),
+            pax,
+            pascal_identifier,
+            false,
+            template_map,
+            "crate",
+            type_id,
+        )
+        .1
+    }
+
+    fn build_manifest(components: &[(&str, &str)]) -> PaxManifest {
+        PaxManifest {
+            components: components
+                .iter()
+                .map(|(type_id, pax)| (type_id.to_string(), build_component(type_id, pax)))
+                .collect(),
+            main_component_type_id: components[0].0.to_string(),
+            expression_specs: None,
+            type_table: HashMap::new(),
+            import_paths: HashSet::new(),
+        }
+    }
+
+    fn find_changed_type_ids(
+        baseline: &PaxManifest,
+        manifest: &PaxManifest,
+    ) -> Result<Vec<String>, String> {
+        find_changed_components(baseline, manifest).map(|changed| {
+            changed
+                .into_iter()
+                .map(|component_def| component_def.type_id.clone())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_only_components_whose_templates_changed_are_reloaded() {
+        let baseline = build_manifest(&[("crate::A", TEMPLATE), ("crate::B", TEMPLATE)]);
+        assert_eq!(find_changed_type_ids(&baseline, &baseline), Ok(vec![]));
+
+        //moving things around doesn't change a template
+        let indented = TEMPLATE.replace("<Rectangle", "\n\n    <Rectangle");
+        let manifest = build_manifest(&[("crate::A", &indented), ("crate::B", TEMPLATE)]);
+        assert_eq!(find_changed_type_ids(&baseline, &manifest), Ok(vec![]));
+
+        let edited = TEMPLATE.replace("width=100px", "width=50%");
+        let manifest = build_manifest(&[("crate::A", TEMPLATE), ("crate::B", &edited)]);
+        assert_eq!(
+            find_changed_type_ids(&baseline, &manifest),
+            Ok(vec!["crate::B".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_changes_outside_of_templates_are_not_reloaded() {
+        let baseline = build_manifest(&[("crate::A", TEMPLATE), ("crate::B", TEMPLATE)]);
+
+        let retimed = TEMPLATE.replace("60: 200px", "90: 200px");
+        let manifest = build_manifest(&[("crate::A", &retimed), ("crate::B", TEMPLATE)]);
+        assert!(find_changed_type_ids(&baseline, &manifest)
+            .unwrap_err()
+            .contains("`@timeline` of `A`"));
+
+        let manifest = build_manifest(&[("crate::A", TEMPLATE)]);
+        assert!(find_changed_type_ids(&baseline, &manifest).is_err());
+
+        let mut manifest = build_manifest(&[("crate::A", TEMPLATE), ("crate::B", TEMPLATE)]);
+        manifest.type_table.insert(
+            "crate::A".to_string(),
+            crate::manifest::TypeDefinition::primitive("crate::A"),
+        );
+        assert!(find_changed_type_ids(&baseline, &manifest).is_err());
+    }
+
+    #[test]
+    fn test_literals_are_converted_to_expressions() {
+        let mut component_def = build_component("crate::A", TEMPLATE);
+        convert_literals_to_expressions(&mut component_def).unwrap();
+        let rectangle = &component_def.template.as_ref().unwrap()[1];
+        let settings: HashMap<&str, &ValueDefinition> = rectangle
+            .settings
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key.token_value.as_str(), value))
+            .collect();
+        assert!(
+            matches!(settings["width"], ValueDefinition::Expression(token, None) if token.token_value == "100px")
+        );
+        assert!(matches!(settings["fill"], ValueDefinition::Expression(..)));
+        assert!(matches!(settings["id"], ValueDefinition::Identifier(..)));
+    }
+}
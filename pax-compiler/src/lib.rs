@@ -3,22 +3,23 @@ extern crate core;
 pub mod errors;
 pub mod expressions;
 mod helpers;
+mod hot_reload;
 pub mod manifest;
 pub mod parsing;
 pub mod templating;
+pub mod type_checking;
+mod watch;
 
-use cargo_metadata::diagnostic::DiagnosticLevel;
-use cargo_metadata::Message;
 use color_eyre::eyre;
-use log::Level;
+
 use manifest::{PaxManifest, Token};
 use pax_runtime_api::CommonProperties;
-use serde::de::value;
+
 use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::{BufReader, Cursor, Write};
+use std::io::Write;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 use std::thread;
@@ -26,7 +27,7 @@ use templating::MappedString;
 
 use actix_web::middleware::Logger;
 use actix_web::{App, HttpServer};
-use env_logger;
+
 use flate2::read::GzDecoder;
 use fs_extra::dir::{self, CopyOptions};
 use itertools::Itertools;
@@ -43,16 +44,22 @@ use lazy_static::lazy_static;
 use std::os::unix::process::CommandExt;
 
 use crate::manifest::{
-    ComponentDefinition, EventDefinition, ExpressionSpec, LiteralBlockDefinition,
-    TemplateNodeDefinition, TypeDefinition, TypeTable, ValueDefinition,
+    ComponentDefinition, EventDefinition, ExpressionSpec, KeyframeDefinition,
+    LiteralBlockDefinition, PropertyDefinition, TemplateNodeDefinition, TypeDefinition, TypeTable,
+    ValueDefinition,
 };
 
 use crate::errors::source_map::SourceMap;
+use crate::errors::PaxTemplateError;
 use crate::templating::{
     press_template_codegen_cartridge_component_factory,
     press_template_codegen_cartridge_render_node_literal,
-    TemplateArgsCodegenCartridgeComponentFactory, TemplateArgsCodegenCartridgeRenderNodeLiteral,
+    TemplateArgsCodegenCartridgeComponentFactory, TemplateArgsCodegenCartridgeHotReloadComponent,
+    TemplateArgsCodegenCartridgeHotReloadHandler, TemplateArgsCodegenCartridgeMountableComponent,
+    TemplateArgsCodegenCartridgePaxelFunction, TemplateArgsCodegenCartridgePropertyObserver,
+    TemplateArgsCodegenCartridgeReflectedType, TemplateArgsCodegenCartridgeRenderNodeLiteral,
 };
+use crate::watch::{normalize, SourceWatcher};
 
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
@@ -72,10 +79,13 @@ const PAX_CREATE_LIBDEV_TEMPLATE_DIR_NAME: &str = "new-libdev-project-template";
 const PKG_DIR_NAME: &str = "pkg";
 const BUILD_DIR_NAME: &str = "build";
 const PUBLIC_DIR_NAME: &str = "public";
+const HOT_RELOAD_CONFIG_FILE_NAME: &str = "hot-reload.json";
 const ASSETS_DIR_NAME: &str = "assets";
 const REEXPORTS_PARTIAL_FILE_NAME: &str = "reexports.partial.rs";
 const RUST_IOS_DYLIB_FILE_NAME: &str = "libpaxchassisios.dylib";
 const RUST_MACOS_DYLIB_FILE_NAME: &str = "libpaxchassismacos.dylib";
+const HEADLESS_BINARY_NAME: &str = "pax-chassis-headless";
+const HEADLESS_FRAMES_DIR_NAME: &str = "frames";
 const PORTABLE_DYLIB_INSTALL_NAME: &str = "@rpath/PaxCartridge.framework/PaxCartridge";
 
 const XCODE_MACOS_TARGET_DEBUG: &str = "Pax macOS (Development)";
@@ -92,9 +102,10 @@ const ERR_SPAWN: &str = "failed to spawn child";
 
 //whitelist of package ids that are relevant to the compiler, e.g. for cloning & patching, for assembling FS paths,
 //or for looking up package IDs from a userland Cargo.lock.
-const ALL_PKGS: [&'static str; 14] = [
+const ALL_PKGS: [&str; 15] = [
     "pax-cartridge",
     "pax-chassis-common",
+    "pax-chassis-headless",
     "pax-chassis-ios",
     "pax-chassis-macos",
     "pax-chassis-web",
@@ -116,7 +127,7 @@ const ALL_PKGS: [&'static str; 14] = [
 /// The packages in `.pax/pkg` are both where we write our codegen (into pax-cartridge and pax-properties-coproduct)
 /// and where we build chassis and chassis-interfaces. (for example, running `wasm-pack` inside `.pax/pkg/pax-chassis-web`.
 /// This assumes that you are in the examples/src directory in the monorepo
-fn clone_all_to_pkg_dir(pax_dir: &PathBuf, pax_version: &Option<String>, ctx: &RunContext) {
+fn clone_all_to_pkg_dir(pax_dir: &Path, pax_version: &Option<String>, ctx: &RunContext) {
     let dest_pkg_root = pax_dir.join(PKG_DIR_NAME);
     for pkg in ALL_PKGS {
         if ctx.is_libdev_mode {
@@ -135,7 +146,7 @@ fn clone_all_to_pkg_dir(pax_dir: &PathBuf, pax_version: &Option<String>, ctx: &R
             let dest = dest_pkg_root.join(pkg);
 
             copy_dir_recursively(&src, &dest, &DIR_IGNORE_LIST_MACOS)
-                .expect(&format!("Failed to copy from {:?} to {:?}", src, dest));
+                .unwrap_or_else(|_| panic!("Failed to copy from {:?} to {:?}", src, dest));
         } else {
             let dest = dest_pkg_root.join(pkg);
             if !dest.exists() {
@@ -146,10 +157,12 @@ fn clone_all_to_pkg_dir(pax_dir: &PathBuf, pax_version: &Option<String>, ctx: &R
                     "https://crates.io/api/v1/crates/{}/{}/download",
                     pkg, pax_version
                 );
-                let resp = reqwest::blocking::get(&tarball_url).expect(&format!(
-                    "Failed to fetch tarball for {} at version {}",
-                    pkg, pax_version
-                ));
+                let resp = reqwest::blocking::get(&tarball_url).unwrap_or_else(|_| {
+                    panic!(
+                        "Failed to fetch tarball for {} at version {}",
+                        pkg, pax_version
+                    )
+                });
 
                 let tarball_bytes = resp.bytes().expect("Failed to read tarball bytes");
 
@@ -180,7 +193,7 @@ fn clone_all_to_pkg_dir(pax_dir: &PathBuf, pax_version: &Option<String>, ctx: &R
                         fs::create_dir_all(&path).expect("Failed to create directory");
                     } else {
                         if let Some(parent) = path.parent() {
-                            fs::create_dir_all(&parent).expect("Failed to create parent directory");
+                            fs::create_dir_all(parent).expect("Failed to create parent directory");
                         }
                         entry.unpack(&path).expect("Failed to unpack file");
                     }
@@ -191,13 +204,22 @@ fn clone_all_to_pkg_dir(pax_dir: &PathBuf, pax_version: &Option<String>, ctx: &R
 }
 
 /// Returns a sorted and de-duped list of combined_reexports.
-fn generate_reexports_partial_rs(pax_dir: &PathBuf, manifest: &PaxManifest) {
+fn generate_reexports_partial_rs(pax_dir: &Path, manifest: &PaxManifest) {
     let imports = manifest.import_paths.clone().into_iter().sorted().collect();
 
     let file_contents = &bundle_reexports_into_namespace_string(&imports);
 
     let path = pax_dir.join(Path::new(REEXPORTS_PARTIAL_FILE_NAME));
-    fs::write(path, file_contents).unwrap();
+    write_if_changed(&path, file_contents).unwrap();
+}
+
+/// Writes `contents` to `path`, unless the file already holds exactly `contents`.  Leaving unchanged files
+/// untouched preserves their mtimes, so that cargo's fingerprinting can skip rebuilding them.
+fn write_if_changed(path: &Path, contents: &str) -> std::io::Result<()> {
+    match fs::read_to_string(path) {
+        Ok(existing_contents) if existing_contents == contents => Ok(()),
+        _ => fs::write(path, contents),
+    }
 }
 
 fn bundle_reexports_into_namespace_string(sorted_reexports: &Vec<String>) -> String {
@@ -220,6 +242,10 @@ fn update_property_prefixes_in_place(manifest: &mut PaxManifest, host_crate_info
         t.1.type_id =
             t.1.type_id
                 .replace("{PREFIX}", &host_crate_info.import_prefix);
+        t.1.inner_iterable_type_id =
+            t.1.inner_iterable_type_id
+                .as_ref()
+                .map(|iiti| iiti.replace("{PREFIX}", &host_crate_info.import_prefix));
         t.1.property_definitions.iter_mut().for_each(|pd| {
             pd.type_id = pd
                 .type_id
@@ -233,33 +259,34 @@ fn update_property_prefixes_in_place(manifest: &mut PaxManifest, host_crate_info
     std::mem::swap(&mut manifest.type_table, &mut updated_type_table);
 }
 
-fn generate_and_overwrite_properties_coproduct(
-    pax_dir: &PathBuf,
-    manifest: &PaxManifest,
-    host_crate_info: &HostCrateInfo,
-) {
-    let target_dir = pax_dir.join(PKG_DIR_NAME).join("pax-properties-coproduct");
-
-    let target_cargo_full_path = fs::canonicalize(target_dir.join("Cargo.toml")).unwrap();
-    let mut target_cargo_toml_contents =
-        toml_edit::Document::from_str(&fs::read_to_string(&target_cargo_full_path).unwrap())
-            .unwrap();
+/// The path by which generated code refers to a type, through the host crate's reexports.  Tuples aren't
+/// reexported themselves, but the type_ids of their elements are already prefixed.
+fn get_type_path(type_id: &str, host_crate_info: &HostCrateInfo) -> String {
+    let type_id = type_id.replace("crate::", "");
+    if type_id.starts_with('(') {
+        type_id
+    } else {
+        host_crate_info.import_prefix.to_string() + &type_id
+    }
+}
 
-    //insert new entry pointing to userland crate, where `pax_app` is defined
-    std::mem::swap(
-        target_cargo_toml_contents["dependencies"]
-            .get_mut(&host_crate_info.name)
-            .unwrap(),
-        &mut Item::from_str("{ path=\"../../..\" }").unwrap(),
-    );
+/// Returns the TypeDefinition of each T such that T is the iterator type for some Property<Vec<T>>
+fn get_iterable_type_definitions(manifest: &PaxManifest) -> impl Iterator<Item = &TypeDefinition> {
+    manifest
+        .components
+        .values()
+        .flat_map(|cd| cd.get_property_definitions(&manifest.type_table))
+        .filter_map(|pd| pd.get_inner_iterable_type_definition(&manifest.type_table))
+}
 
-    //write patched Cargo.toml
-    fs::write(
-        &target_cargo_full_path,
-        &target_cargo_toml_contents.to_string(),
-    )
-    .unwrap();
+/// The (variant, type) tuples of a coproduct
+type CoproductTuples = Vec<(String, String)>;
 
+/// Returns the (variant, type) tuples of PropertiesCoproduct and TypesCoproduct, respectively
+fn get_coproduct_tuples(
+    manifest: &PaxManifest,
+    host_crate_info: &HostCrateInfo,
+) -> (CoproductTuples, CoproductTuples) {
     //build tuples for PropertiesCoproduct
     let mut properties_coproduct_tuples: Vec<(String, String)> = manifest
         .components
@@ -280,7 +307,26 @@ fn generate_and_overwrite_properties_coproduct(
         })
         .collect();
     let set: HashSet<(String, String)> = properties_coproduct_tuples.drain(..).collect();
-    properties_coproduct_tuples.extend(set.into_iter());
+    properties_coproduct_tuples.extend(set);
+
+    //include all T such that T is the iterator type for some Property<Vec<T>>, e.g. tuples, so that they may
+    //be repeated — unless T is a component, included above, or built in
+    let component_variants: HashSet<String> = properties_coproduct_tuples
+        .iter()
+        .map(|(variant, _)| variant.clone())
+        .collect();
+    let iterable_tuples: HashSet<(String, String)> = get_iterable_type_definitions(manifest)
+        .filter(|td| {
+            !component_variants.contains(&td.type_id_escaped) && td.type_id_escaped != "isize"
+        })
+        .map(|td| {
+            (
+                td.type_id_escaped.clone(),
+                get_type_path(&td.type_id, host_crate_info),
+            )
+        })
+        .collect();
+    properties_coproduct_tuples.extend(iterable_tuples);
     properties_coproduct_tuples.sort();
 
     //build tuples for TypesCoproduct
@@ -289,7 +335,7 @@ fn generate_and_overwrite_properties_coproduct(
     let mut types_coproduct_tuples: Vec<(String, String)> = manifest
         .components
         .iter()
-        .map(|cd| {
+        .flat_map(|cd| {
             cd.1.get_property_definitions(&manifest.type_table)
                 .iter()
                 .map(|pm| {
@@ -297,13 +343,11 @@ fn generate_and_overwrite_properties_coproduct(
 
                     (
                         td.type_id_escaped.clone(),
-                        host_crate_info.import_prefix.to_string()
-                            + &td.type_id.clone().replace("crate::", ""),
+                        get_type_path(&td.type_id, host_crate_info),
                     )
                 })
                 .collect::<Vec<_>>()
         })
-        .flatten()
         .collect::<Vec<_>>();
 
     let mut set: HashSet<_> = types_coproduct_tuples.drain(..).collect();
@@ -320,18 +364,19 @@ fn generate_and_overwrite_properties_coproduct(
             "std::vec::Vec<std::rc::Rc<PropertiesCoproduct>>",
         ),
         ("Transform2D", "pax_runtime_api::Transform2D"),
-        ("stdCOCOopsCOCORangeLABRisizeRABR", "std::ops::Range<isize>"),
+        ("RepeatRange", "pax_runtime_api::RepeatRange"),
         ("Size", "pax_runtime_api::Size"),
         ("Rotation", "pax_runtime_api::Rotation"),
         ("SizePixels", "pax_runtime_api::SizePixels"),
         ("Numeric", "pax_runtime_api::Numeric"),
         ("StringBox", "pax_runtime_api::StringBox"),
+        ("BlendMode", "pax_runtime_api::BlendMode"),
     ];
 
     TYPES_COPRODUCT_BUILT_INS.iter().for_each(|builtin| {
         set.insert((builtin.0.to_string(), builtin.1.to_string()));
     });
-    types_coproduct_tuples.extend(set.into_iter());
+    types_coproduct_tuples.extend(set);
     types_coproduct_tuples.sort();
 
     types_coproduct_tuples = types_coproduct_tuples
@@ -339,6 +384,39 @@ fn generate_and_overwrite_properties_coproduct(
         .unique_by(|elem| elem.0.to_string())
         .collect::<Vec<(String, String)>>();
 
+    (properties_coproduct_tuples, types_coproduct_tuples)
+}
+
+fn generate_and_overwrite_properties_coproduct(
+    pax_dir: &Path,
+    manifest: &PaxManifest,
+    host_crate_info: &HostCrateInfo,
+) {
+    let target_dir = pax_dir.join(PKG_DIR_NAME).join("pax-properties-coproduct");
+
+    let target_cargo_full_path = fs::canonicalize(target_dir.join("Cargo.toml")).unwrap();
+    let mut target_cargo_toml_contents =
+        toml_edit::Document::from_str(&fs::read_to_string(&target_cargo_full_path).unwrap())
+            .unwrap();
+
+    //insert new entry pointing to userland crate, where `pax_app` is defined
+    std::mem::swap(
+        target_cargo_toml_contents["dependencies"]
+            .get_mut(&host_crate_info.name)
+            .unwrap(),
+        &mut Item::from_str("{ path=\"../../..\" }").unwrap(),
+    );
+
+    //write patched Cargo.toml
+    write_if_changed(
+        &target_cargo_full_path,
+        &target_cargo_toml_contents.to_string(),
+    )
+    .unwrap();
+
+    let (properties_coproduct_tuples, types_coproduct_tuples) =
+        get_coproduct_tuples(manifest, host_crate_info);
+
     //press template into String
     let generated_lib_rs = templating::press_template_codegen_properties_coproduct_lib(
         templating::TemplateArgsCodegenPropertiesCoproductLib {
@@ -348,14 +426,16 @@ fn generate_and_overwrite_properties_coproduct(
     );
 
     //write String to file
-    fs::write(target_dir.join("src/lib.rs"), generated_lib_rs).unwrap();
+    write_if_changed(&target_dir.join("src/lib.rs"), &generated_lib_rs).unwrap();
 }
 
 fn generate_and_overwrite_cartridge(
-    pax_dir: &PathBuf,
+    pax_dir: &Path,
     manifest: &PaxManifest,
     host_crate_info: &HostCrateInfo,
     source_map: &mut SourceMap,
+    interpret_expressions: bool,
+    hot_reload: bool,
 ) -> PathBuf {
     let target_dir = pax_dir.join(PKG_DIR_NAME).join("pax-cartridge");
 
@@ -373,13 +453,14 @@ fn generate_and_overwrite_cartridge(
     );
 
     //write patched Cargo.toml
-    fs::write(
+    write_if_changed(
         &target_cargo_full_path,
         &target_cargo_toml_contents.to_string(),
     )
     .unwrap();
 
-    const IMPORTS_BUILTINS: [&str; 26] = [
+    const IMPORTS_BUILTINS: [&str; 40] = [
+        "std::any::Any",
         "std::cell::RefCell",
         "std::collections::HashMap",
         "std::collections::VecDeque",
@@ -388,9 +469,16 @@ fn generate_and_overwrite_cartridge(
         "pax_runtime_api::PropertyInstance",
         "pax_runtime_api::PropertyLiteral",
         "pax_runtime_api::CommonProperties",
+        "pax_runtime_api::EasingCurve",
+        "pax_runtime_api::Timeline",
+        "pax_runtime_api::RepeatRange",
+        "pax_runtime_api::paxel_std",
+        "pax_runtime_api::reactivity",
         "pax_core::ComponentInstance",
         "pax_core::RenderNodePtr",
         "pax_core::PropertyExpression",
+        "pax_core::PropertyTimeline",
+        "pax_core::Keyframe",
         "pax_core::RenderNodePtrList",
         "pax_core::RenderTreeContext",
         "pax_core::ExpressionContext",
@@ -398,10 +486,16 @@ fn generate_and_overwrite_cartridge(
         "pax_core::RenderNode",
         "pax_core::InstanceRegistry",
         "pax_core::HandlerRegistry",
+        "pax_core::PropertyObserver",
         "pax_core::InstantiationArgs",
         "pax_core::ConditionalInstance",
         "pax_core::SlotInstance",
         "pax_core::StackFrame",
+        "pax_core::interpreter",
+        "pax_core::interpreter::PaxelReflection",
+        "pax_core::interpreter::PaxelScopeFrame",
+        "pax_core::interpreter::PaxelValue",
+        "pax_core::hot_reload::HotReloadCartridge",
         "pax_core::pax_properties_coproduct::PropertiesCoproduct",
         "pax_core::pax_properties_coproduct::TypesCoproduct",
         "pax_core::repeat::RepeatInstance",
@@ -439,20 +533,231 @@ fn generate_and_overwrite_cartridge(
         .as_ref()
         .unwrap()
         .values()
-        .map(|es: &ExpressionSpec| es.clone())
+        .cloned()
         .collect();
     expression_specs = expression_specs.iter().sorted().cloned().collect();
 
     let component_factories_literal = manifest
         .components
         .values()
-        .into_iter()
         .filter(|cd| !cd.is_primitive && !cd.is_struct_only_component)
         .map(|cd| {
-            generate_cartridge_component_factory_literal(manifest, cd, host_crate_info, source_map)
+            generate_cartridge_component_factory_literal(
+                manifest,
+                cd,
+                host_crate_info,
+                source_map,
+                hot_reload,
+            )
         })
         .collect();
 
+    let mut mountable_components: Vec<TemplateArgsCodegenCartridgeMountableComponent> = manifest
+        .components
+        .values()
+        .filter(|cd| !cd.is_primitive && !cd.is_struct_only_component)
+        .map(|cd| TemplateArgsCodegenCartridgeMountableComponent {
+            type_id: cd.type_id.clone(),
+            is_main_component: cd.is_main_component,
+            snake_case_type_id: cd.get_snake_case_id(),
+            component_properties_struct: cd.pascal_identifier.to_string(),
+            properties_coproduct_variant: cd.type_id_escaped.to_string(),
+        })
+        .collect();
+    mountable_components.sort_by(|a, b| a.type_id.cmp(&b.type_id));
+
+    //Build the runtime reflection used by interpreted expressions, to look up properties and call functions by name
+    let (reflected_properties_coproduct_variants, reflected_types, paxel_functions) =
+        if interpret_expressions {
+            let reflect = |property_definitions: &Vec<PropertyDefinition>| {
+                property_definitions
+                    .iter()
+                    .map(|pd| (pd.name.clone(), pd.flags.is_property_wrapped))
+                    .collect::<Vec<_>>()
+            };
+
+            let mut reflected_properties_coproduct_variants = manifest
+                .components
+                .values()
+                .map(|cd| TemplateArgsCodegenCartridgeReflectedType {
+                    identifier: cd.type_id_escaped.clone(),
+                    properties: reflect(cd.get_property_definitions(&manifest.type_table)),
+                })
+                .chain(
+                    //`for` elements of other types, like tuples, may be accessed by field, too
+                    get_iterable_type_definitions(manifest)
+                        .filter(|td| !td.property_definitions.is_empty())
+                        .map(|td| TemplateArgsCodegenCartridgeReflectedType {
+                            identifier: td.type_id_escaped.clone(),
+                            properties: reflect(&td.property_definitions),
+                        }),
+                )
+                .unique_by(|reflected| reflected.identifier.clone())
+                .collect::<Vec<_>>();
+            reflected_properties_coproduct_variants.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+            let mut reflected_types = manifest
+                .type_table
+                .values()
+                .filter(|td| !td.property_definitions.is_empty() && !td.type_id.contains('<'))
+                .map(|td| TemplateArgsCodegenCartridgeReflectedType {
+                    identifier: get_type_path(&td.type_id, host_crate_info),
+                    properties: reflect(&td.property_definitions),
+                })
+                .collect::<Vec<_>>();
+            reflected_types.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+            //Literal values are reinterpreted as expressions when hot reloaded, so may call functions, too
+            let literal_values = manifest
+                .components
+                .values()
+                .filter(|_| hot_reload)
+                .flat_map(|cd| {
+                    let template_settings = cd
+                        .template
+                        .iter()
+                        .flatten()
+                        .flat_map(|tnd| tnd.settings.iter().flatten());
+                    let block_settings = cd
+                        .settings
+                        .iter()
+                        .flatten()
+                        .flat_map(|block| block.value_block.settings_key_value_pairs.iter());
+                    template_settings.chain(block_settings)
+                })
+                .filter_map(|(_, value)| match value {
+                    ValueDefinition::LiteralValue(token) => Some(&token.raw_value),
+                    _ => None,
+                });
+            let paxel_functions = expression_specs
+                .iter()
+                .filter_map(|es| es.interpreted_paxel.as_ref())
+                .chain(literal_values)
+                .flat_map(|paxel| parsing::collect_paxel_function_calls(paxel))
+                .unique_by(|(path, arity, _)| (path.clone(), *arity))
+                .sorted()
+                .map(
+                    |(path, arity, has_args_list)| TemplateArgsCodegenCartridgePaxelFunction {
+                        path,
+                        arity,
+                        has_args_list,
+                    },
+                )
+                .collect();
+
+            (
+                reflected_properties_coproduct_variants,
+                reflected_types,
+                paxel_functions,
+            )
+        } else {
+            (vec![], vec![], vec![])
+        };
+
+    //The element types of `for` sources, whose collections interpreted expressions may call methods on, like `filter`
+    let iterable_types = if interpret_expressions {
+        let (properties_coproduct_tuples, _) = get_coproduct_tuples(manifest, host_crate_info);
+
+        let iterable_type_ids_escaped: HashSet<String> = get_iterable_type_definitions(manifest)
+            .map(|td| td.type_id_escaped.clone())
+            .collect();
+        properties_coproduct_tuples
+            .into_iter()
+            .filter(|(variant, _)| iterable_type_ids_escaped.contains(variant))
+            .collect()
+    } else {
+        vec![]
+    };
+
+    //Build what reloaded templates may refer to: any type, component, or handler known at build time
+    let (types_coproduct_variants, hot_reload_components, hot_reload_handlers) = if hot_reload {
+        let (_, types_coproduct_tuples) = get_coproduct_tuples(manifest, host_crate_info);
+
+        let mut hot_reload_components = manifest
+            .components
+            .values()
+            .filter(|cd| !cd.is_main_component && !cd.is_struct_only_component)
+            .map(|cd| TemplateArgsCodegenCartridgeHotReloadComponent {
+                type_id: cd.type_id.clone(),
+                is_primitive: cd.is_primitive,
+                snake_case_type_id: cd.get_snake_case_id(),
+                primitive_instance_import_path: cd.primitive_instance_import_path.clone(),
+                component_properties_struct: cd.pascal_identifier.to_string(),
+                properties_coproduct_variant: cd.type_id_escaped.to_string(),
+                properties: cd
+                    .get_property_definitions(&manifest.type_table)
+                    .iter()
+                    .map(|pd| pd.name.clone())
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+        hot_reload_components.sort_by(|a, b| a.type_id.cmp(&b.type_id));
+
+        let hot_reload_handlers = manifest
+            .components
+            .values()
+            .filter(|cd| !cd.is_primitive && !cd.is_struct_only_component)
+            .flat_map(|cd| {
+                let property_names = get_property_names(cd, &manifest.type_table);
+                let component_events = cd.events.iter().flatten().flat_map(|event| {
+                    event
+                        .value
+                        .iter()
+                        .map(|handler| (event.key.token_value.clone(), handler.token_value.clone()))
+                });
+                let template_events = cd
+                    .template
+                    .iter()
+                    .flatten()
+                    .flat_map(|tnd| tnd.settings.iter().flatten())
+                    .filter_map(|(key, value)| match value {
+                        ValueDefinition::EventBindingTarget(handler) => {
+                            Some((key.token_value.clone(), handler.token_value.clone()))
+                        }
+                        _ => None,
+                    });
+                component_events
+                    .chain(template_events)
+                    .map(
+                        |(event, method)| TemplateArgsCodegenCartridgeHotReloadHandler {
+                            owner_type_id: cd.type_id.clone(),
+                            property_observer: get_property_observer(&event, &property_names),
+                            event,
+                            method,
+                            owner_properties_coproduct_variant: cd.type_id_escaped.to_string(),
+                            owner_component_properties_struct: cd.pascal_identifier.to_string(),
+                        },
+                    )
+                    .collect::<Vec<_>>()
+            })
+            .unique_by(|handler| {
+                (
+                    handler.owner_type_id.clone(),
+                    handler.event.clone(),
+                    handler.method.clone(),
+                )
+            })
+            .sorted_by(|a, b| {
+                (&a.owner_type_id, &a.event, &a.method).cmp(&(
+                    &b.owner_type_id,
+                    &b.event,
+                    &b.method,
+                ))
+            })
+            .collect();
+
+        (
+            types_coproduct_tuples
+                .into_iter()
+                .map(|(variant, _)| variant)
+                .collect(),
+            hot_reload_components,
+            hot_reload_handlers,
+        )
+    } else {
+        (vec![], vec![], vec![])
+    };
+
     //press template into String
     let generated_lib_rs = templating::press_template_codegen_cartridge_lib(
         templating::TemplateArgsCodegenCartridgeLib {
@@ -460,12 +765,22 @@ fn generate_and_overwrite_cartridge(
             consts,
             expression_specs,
             component_factories_literal,
+            mountable_components,
+            interpret_expressions,
+            reflected_properties_coproduct_variants,
+            reflected_types,
+            paxel_functions,
+            iterable_types,
+            hot_reload,
+            types_coproduct_variants,
+            hot_reload_components,
+            hot_reload_handlers,
         },
     );
 
     // Re: formatting the generated Rust code, see prior art at `_format_generated_lib_rs`
     let path = target_dir.join("src/lib.rs");
-    fs::write(path.clone(), generated_lib_rs).unwrap();
+    write_if_changed(&path, &generated_lib_rs).unwrap();
     path
 }
 
@@ -550,11 +865,13 @@ fn recurse_literal_block(
             + &type_definition
                 .property_definitions
                 .iter()
-                .find(|pd| &pd.name == &key.token_value)
-                .expect(&format!(
-                    "Property {} not found on type {}",
-                    key.token_value, type_definition.type_id
-                ))
+                .find(|pd| pd.name == key.token_value)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Property {} not found on type {}",
+                        key.token_value, type_definition.type_id
+                    )
+                })
                 .type_id;
 
         let mut source_map_start_marker: Option<String> = None;
@@ -615,6 +932,50 @@ fn recurse_literal_block(
     struct_representation
 }
 
+/// Generates a `PropertyTimeline` literal bound to the enclosing component's `timeline`, e.g.
+/// `PropertyTimeline::new(Rc::clone(&timeline), vec![Keyframe::new(0, 0.0, EasingCurve::Linear), ...])`.
+/// If `value_type` is provided, each keyframe value is wrapped in `Into::<value_type>::into(...)`.
+fn generate_property_timeline_literal(
+    keyframes: &[KeyframeDefinition],
+    value_type: Option<&str>,
+) -> String {
+    let keyframes_literal = keyframes
+        .iter()
+        .map(|keyframe| {
+            let value = if let Some(value_type) = value_type {
+                format!(
+                    "Into::<{}>::into({})",
+                    value_type, keyframe.value.token_value
+                )
+            } else {
+                keyframe.value.token_value.clone()
+            };
+            let curve = keyframe
+                .curve
+                .as_ref()
+                .map(|curve| curve.token_value.clone())
+                .unwrap_or("Linear".to_string());
+            let mut keyframe_literal = format!(
+                "Keyframe::new({}, {}, EasingCurve::{})",
+                keyframe
+                    .get_frame()
+                    .expect("keyframe frames are checked when the timeline is compiled"),
+                value,
+                curve
+            );
+            if let Some(name) = &keyframe.name {
+                keyframe_literal = format!("{}.named(\"{}\")", keyframe_literal, name.token_value);
+            }
+            keyframe_literal
+        })
+        .join(", ");
+
+    format!(
+        "PropertyTimeline::new(Rc::clone(&timeline), vec![{}])",
+        keyframes_literal
+    )
+}
+
 fn recurse_generate_render_nodes_literal(
     rngc: &RenderNodesGenerationContext,
     tnd: &TemplateNodeDefinition,
@@ -655,6 +1016,20 @@ fn recurse_generate_render_nodes_literal(
             MappedString::none()
         };
 
+        let cfsd = tnd.control_flow_settings.as_ref().unwrap();
+        let rke = if let Some(t) = &cfsd.repeat_key_expression_paxel {
+            let key_source_id = source_map.insert(t.clone());
+            source_map.generate_mapped_string(
+                format!(
+                    "Some(Box::new(PropertyExpression::new({})))",
+                    cfsd.repeat_key_expression_vtable_id.unwrap()
+                ),
+                key_source_id,
+            )
+        } else {
+            MappedString::none()
+        };
+
         let rse_range = if let Some(t) = &rsd.range_expression_paxel {
             let range_source_id = source_map.insert(t.clone());
             source_map.generate_mapped_string(
@@ -686,6 +1061,7 @@ fn recurse_generate_render_nodes_literal(
             children_literal,
             slot_index_literal: MappedString::none(),
             conditional_boolean_expression_literal: MappedString::none(),
+            else_children_literal: vec![],
             pascal_identifier: rngc
                 .active_component_definition
                 .pascal_identifier
@@ -696,6 +1072,7 @@ fn recurse_generate_render_nodes_literal(
             events,
             repeat_source_expression_literal_vec: rse_vec,
             repeat_source_expression_literal_range: rse_range,
+            repeat_key_expression_literal: rke,
         }
     } else if tnd.type_id == parsing::TYPE_ID_IF {
         // If
@@ -724,6 +1101,23 @@ fn recurse_generate_render_nodes_literal(
             })
             .collect();
 
+        //partition children into the `if` and `else` branches
+        let else_branch_child_ids = tnd
+            .control_flow_settings
+            .as_ref()
+            .unwrap()
+            .else_branch_child_ids
+            .clone()
+            .unwrap_or_default();
+        let (else_children_literal, children_literal): (Vec<_>, Vec<_>) = tnd
+            .child_ids
+            .iter()
+            .cloned()
+            .zip(children_literal)
+            .partition(|(child_id, _)| else_branch_child_ids.contains(child_id));
+        let children_literal = children_literal.into_iter().map(|(_, c)| c).collect();
+        let else_children_literal = else_children_literal.into_iter().map(|(_, c)| c).collect();
+
         let conditional_source_map_id = source_map.insert(conditional_expression_paxel.clone());
         let conditional_mapped_string = source_map.generate_mapped_string(
             format!("Some(Box::new(PropertyExpression::new({})))", id),
@@ -742,7 +1136,9 @@ fn recurse_generate_render_nodes_literal(
             slot_index_literal: MappedString::none(),
             repeat_source_expression_literal_vec: MappedString::none(),
             repeat_source_expression_literal_range: MappedString::none(),
+            repeat_key_expression_literal: MappedString::none(),
             conditional_boolean_expression_literal: conditional_mapped_string,
+            else_children_literal,
             pascal_identifier: rngc
                 .active_component_definition
                 .pascal_identifier
@@ -797,7 +1193,9 @@ fn recurse_generate_render_nodes_literal(
             slot_index_literal: slot_mapped_string,
             repeat_source_expression_literal_vec: MappedString::none(),
             repeat_source_expression_literal_range: MappedString::none(),
+            repeat_key_expression_literal: MappedString::none(),
             conditional_boolean_expression_literal: MappedString::none(),
+            else_children_literal: vec![],
             pascal_identifier: rngc
                 .active_component_definition
                 .pascal_identifier
@@ -862,13 +1260,19 @@ fn recurse_generate_render_nodes_literal(
                                             );
                                         Some((key_mapped_string.clone(), value_mapped_string))
                                     }
+                                    ValueDefinition::Timeline(keyframes) => Some((
+                                        key_mapped_string.clone(),
+                                        MappedString::new(generate_property_timeline_literal(
+                                            keyframes, None,
+                                        )),
+                                    )),
                                     ValueDefinition::Block(block) => Some((
                                         key_mapped_string.clone(),
                                         MappedString::new(format!(
                                             "PropertyLiteral::new({})",
                                             recurse_literal_block(
                                                 block.clone(),
-                                                pd.get_type_definition(&rngc.type_table),
+                                                pd.get_type_definition(rngc.type_table),
                                                 host_crate_info,
                                                 source_map
                                             )
@@ -941,10 +1345,7 @@ fn recurse_generate_render_nodes_literal(
                                     if is_optional(&identifier_and_type.0) {
                                         literal_value = format!("Some({})", literal_value);
                                     }
-                                    let value_mapped_string = source_map.generate_mapped_string(literal_value,
-                                         value_source_map_id);
-
-                                    value_mapped_string
+                                    source_map.generate_mapped_string(literal_value, value_source_map_id)
                                 }
                                 ValueDefinition::Expression(token, id)
                                 | ValueDefinition::Identifier(token, id) => {
@@ -956,9 +1357,17 @@ fn recurse_generate_render_nodes_literal(
                                     if is_optional(&identifier_and_type.0) {
                                         literal_value = format!("Some({})", literal_value);
                                     }
-                                    let value_mapped_string = source_map.generate_mapped_string(literal_value,
-                                        value_source_map_id);
-                                    value_mapped_string
+                                    source_map.generate_mapped_string(literal_value, value_source_map_id)
+                                }
+                                ValueDefinition::Timeline(keyframes) => {
+                                    let mut literal_value = format!(
+                                        "Rc::new(RefCell::new({}))",
+                                        generate_property_timeline_literal(keyframes, Some(identifier_and_type.1.as_str()))
+                                    );
+                                    if is_optional(&identifier_and_type.0) {
+                                        literal_value = format!("Some({})", literal_value);
+                                    }
+                                    MappedString::new(literal_value)
                                 }
                                 _ => {
                                     panic!("Incorrect value bound to attribute")
@@ -994,7 +1403,9 @@ fn recurse_generate_render_nodes_literal(
             slot_index_literal: MappedString::none(),
             repeat_source_expression_literal_vec: MappedString::none(),
             repeat_source_expression_literal_range: MappedString::none(),
+            repeat_key_expression_literal: MappedString::none(),
             conditional_boolean_expression_literal: MappedString::none(),
+            else_children_literal: vec![],
             pascal_identifier: rngc
                 .active_component_definition
                 .pascal_identifier
@@ -1020,36 +1431,103 @@ fn generate_events_map(
     source_map: &mut SourceMap,
 ) -> Vec<(MappedString, Vec<MappedString>)> {
     let mut ret = HashMap::new();
-    let _ = match events {
-        Some(event_list) => {
-            for e in event_list.iter() {
-                let event_values: Vec<MappedString> = e
-                    .value
-                    .clone()
-                    .iter()
-                    .map(|et| {
-                        let et_source_map_id = source_map.insert(et.clone());
-                        let et_mapped_string = source_map
-                            .generate_mapped_string(et.token_value.clone(), et_source_map_id);
-                        et_mapped_string
-                    })
-                    .collect();
-                let key_source_map_id = source_map.insert(e.key.clone());
-                let key_mapped_string =
-                    source_map.generate_mapped_string(e.key.token_value.clone(), key_source_map_id);
-                ret.insert(key_mapped_string, event_values);
-            }
+    if let Some(event_list) = events {
+        for e in event_list.iter() {
+            let event_values = generate_handlers_list(e, source_map);
+            let key_source_map_id = source_map.insert(e.key.clone());
+            let key_mapped_string =
+                source_map.generate_mapped_string(e.key.token_value.clone(), key_source_map_id);
+            ret.insert(key_mapped_string, event_values);
         }
-        _ => {}
-    };
+    }
     ret.into_iter().collect()
 }
 
+fn generate_handlers_list(
+    event: &EventDefinition,
+    source_map: &mut SourceMap,
+) -> Vec<MappedString> {
+    event
+        .value
+        .iter()
+        .map(|et| {
+            let et_source_map_id = source_map.insert(et.clone());
+            source_map.generate_mapped_string(et.token_value.clone(), et_source_map_id)
+        })
+        .collect()
+}
+
+fn get_property_names(cd: &ComponentDefinition, type_table: &TypeTable) -> Vec<String> {
+    cd.get_property_definitions(type_table)
+        .iter()
+        .map(|pd| pd.name.clone())
+        .collect()
+}
+
+/// For `did_update`, which observes every one of the component's `property_names`, and `on_change(property)`,
+/// which observes just `property`: the `HandlerRegistry` field to register with and the properties to observe
+fn get_property_observer(
+    event: &str,
+    property_names: &[String],
+) -> Option<TemplateArgsCodegenCartridgePropertyObserver> {
+    if event == "did_update" {
+        Some(TemplateArgsCodegenCartridgePropertyObserver {
+            event: "did_update".to_string(),
+            observed_properties: property_names.to_vec(),
+        })
+    } else {
+        parsing::get_observed_property(event).map(|property| {
+            TemplateArgsCodegenCartridgePropertyObserver {
+                event: "on_change".to_string(),
+                observed_properties: vec![property.to_string()],
+            }
+        })
+    }
+}
+
+/// Ensure that each `on_change(property)` in a `@handlers` block observes a property of its component, and that
+/// `did_update` — which observes the properties of the component that declares it — is only bound in `@handlers`
+fn validate_property_observers(manifest: &PaxManifest) -> eyre::Result<(), Report> {
+    for cd in manifest.components.values() {
+        let property_names = get_property_names(cd, &manifest.type_table);
+        for event in cd.events.iter().flatten() {
+            if let Some(property) = parsing::get_observed_property(&event.key.token_value) {
+                if !property_names.iter().any(|name| name == property) {
+                    return Err(PaxTemplateError::new(
+                        Some(format!(
+                            "`{}` has no property `{}` to observe",
+                            cd.pascal_identifier, property
+                        )),
+                        event.key.clone(),
+                    ));
+                }
+            }
+        }
+        for tnd in cd.template.iter().flatten() {
+            for (key, value) in tnd.settings.iter().flatten() {
+                if let ValueDefinition::EventBindingTarget(_) = value {
+                    if key.token_value == "did_update" {
+                        return Err(PaxTemplateError::new(
+                            Some(
+                                "`did_update` may only be bound in a component's `@handlers` block"
+                                    .to_string(),
+                            ),
+                            key.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn generate_cartridge_component_factory_literal(
     manifest: &PaxManifest,
     cd: &ComponentDefinition,
     host_crate_info: &HostCrateInfo,
     source_map: &mut SourceMap,
+    hot_reload: bool,
 ) -> String {
     let rngc = RenderNodesGenerationContext {
         components: &manifest.components,
@@ -1057,6 +1535,7 @@ fn generate_cartridge_component_factory_literal(
         type_table: &manifest.type_table,
     };
 
+    let property_names = get_property_names(cd, &manifest.type_table);
     let args = TemplateArgsCodegenCartridgeComponentFactory {
         is_main_component: cd.is_main_component,
         snake_case_type_id: cd.get_snake_case_id(),
@@ -1073,13 +1552,38 @@ fn generate_cartridge_component_factory_literal(
                 )
             })
             .collect(),
-        events: generate_events_map(cd.events.clone(), source_map),
+        events: generate_events_map(
+            cd.events.as_ref().map(|events| {
+                events
+                    .iter()
+                    .filter(|e| get_property_observer(&e.key.token_value, &[]).is_none())
+                    .cloned()
+                    .collect()
+            }),
+            source_map,
+        ),
+        property_observers: cd
+            .events
+            .iter()
+            .flatten()
+            .filter_map(|e| {
+                get_property_observer(&e.key.token_value, &property_names)
+                    .map(|observer| (observer, generate_handlers_list(e, source_map)))
+            })
+            .collect(),
         render_nodes_literal: generate_cartridge_render_nodes_literal(
             &rngc,
             host_crate_info,
             source_map,
         ),
         properties_coproduct_variant: cd.type_id_escaped.to_string(),
+        has_timeline: cd
+            .timeline
+            .as_ref()
+            .map(|timeline| !timeline.is_empty())
+            .unwrap_or(false),
+        type_id: cd.type_id.clone(),
+        hot_reload,
     };
 
     press_template_codegen_cartridge_component_factory(args)
@@ -1111,7 +1615,7 @@ fn get_host_crate_info(cargo_toml_path: &Path) -> HostCrateInfo {
         .as_str()
         .unwrap()
         .to_string();
-    let identifier = name.replace("-", "_"); //NOTE: perhaps this could be less naive?
+    let identifier = name.replace('-', "_"); //NOTE: perhaps this could be less naive?
     let import_prefix = format!("{}::pax_reexports::", &identifier);
 
     HostCrateInfo {
@@ -1147,8 +1651,8 @@ pub fn run_parser_binary(path: &str, process_child_ids: Arc<Mutex<Vec<u64>>>) ->
     let child = cmd.spawn().expect(ERR_SPAWN);
 
     // child.stdin.take().map(drop);
-    let output = wait_with_output(&process_child_ids, child);
-    output
+
+    wait_with_output(&process_child_ids, child)
 }
 
 use colored::{ColoredString, Colorize};
@@ -1213,6 +1717,78 @@ fn get_version_of_whitelisted_packages(path: &str) -> Result<String, &'static st
 /// then run it with a patched build of the `chassis` appropriate for the specified platform
 /// See: pax-compiler-sequence-diagram.png
 pub fn perform_build(ctx: &RunContext) -> eyre::Result<(), Report> {
+    let pax_dir = prepare_pax_directory(ctx);
+
+    println!("{} 🛠️  Building parser binary with `cargo`...", *PAX_BADGE);
+    let (mut manifest, host_crate_info) = parse_manifest(ctx)?;
+
+    //Hot reloading diffs later changes against the manifest as parsed, before its expressions are compiled
+    let hot_reload_baseline = if ctx.hot_reload && ctx.should_also_run {
+        if let RunTarget::Web = ctx.target {
+            Some(manifest.clone())
+        } else {
+            println!(
+                "{} ⚠️  Hot reloading is only supported for the web target; ignoring --hot-reload",
+                *PAX_BADGE
+            );
+            None
+        }
+    } else {
+        None
+    };
+
+    let mut source_map = SourceMap::new();
+
+    validate_property_observers(&manifest)?;
+
+    println!("{} 🧮 Compiling expressions", *PAX_BADGE);
+    expressions::compile_all_expressions(&mut manifest, &mut source_map)?;
+
+    println!("{} 🦀 Generating Rust", *PAX_BADGE);
+    generate_reexports_partial_rs(&pax_dir, &manifest);
+    generate_and_overwrite_properties_coproduct(&pax_dir, &manifest, &host_crate_info);
+    let cartridge_path = generate_and_overwrite_cartridge(
+        &pax_dir,
+        &manifest,
+        &host_crate_info,
+        &mut source_map,
+        ctx.interpret_expressions || hot_reload_baseline.is_some(),
+        hot_reload_baseline.is_some(),
+    );
+    source_map.extract_ranges_from_generated_code(cartridge_path.to_str().unwrap());
+
+    let hot_reload_port = if let Some(baseline) = hot_reload_baseline {
+        let next_expression_id = manifest
+            .expression_specs
+            .as_ref()
+            .unwrap()
+            .keys()
+            .max()
+            .map_or(0, |id| id + 1);
+        Some(hot_reload::start_hot_reload_session(
+            &ctx.path,
+            baseline,
+            next_expression_id,
+            Arc::clone(&ctx.process_child_ids),
+        )?)
+    } else {
+        None
+    };
+
+    //7. Build the appropriate `chassis` from source, with the patched `Cargo.toml`, Properties Coproduct, and Cartridge from above
+    println!("{} 🧱 Building cartridge with `cargo`", *PAX_BADGE);
+    build_chassis_with_cartridge(
+        &pax_dir,
+        ctx,
+        Arc::clone(&ctx.process_child_ids),
+        &source_map,
+        hot_reload_port,
+    )?;
+    Ok(())
+}
+
+/// Creates the project's `.pax` directory, populated with the pax packages to build against, returning its path
+fn prepare_pax_directory(ctx: &RunContext) -> PathBuf {
     //First we clone dependencies into the .pax/pkg directory.  We must do this before running
     //the parser binary specifical for libdev in pax-example — see pax-example/Cargo.toml where
     //dependency paths are `.pax/pkg/*`.
@@ -1226,14 +1802,18 @@ pub fn perform_build(ctx: &RunContext) -> eyre::Result<(), Report> {
     } else {
         Some(get_version_of_whitelisted_packages(&ctx.path).unwrap())
     };
-    clone_all_to_pkg_dir(&pax_dir, &pax_version, &ctx);
+    clone_all_to_pkg_dir(&pax_dir, &pax_version, ctx);
 
     if ctx.is_libdev_mode {
         let full_path = Path::new(&ctx.path);
-        set_path_on_pax_dependencies(&full_path);
+        set_path_on_pax_dependencies(full_path);
     }
 
-    println!("{} 🛠️  Building parser binary with `cargo`...", *PAX_BADGE);
+    pax_dir
+}
+
+/// Runs the parser binary for the project in `ctx`, returning its manifest, whose expressions are not yet compiled
+fn parse_manifest(ctx: &RunContext) -> eyre::Result<(PaxManifest, HostCrateInfo), Report> {
     // Run parser bin from host project with `--features parser`
     let output = run_parser_binary(&ctx.path, Arc::clone(&ctx.process_child_ids));
 
@@ -1249,33 +1829,147 @@ pub fn perform_build(ctx: &RunContext) -> eyre::Result<(), Report> {
     }
 
     let out = String::from_utf8(output.stdout).unwrap();
-    let mut manifest: PaxManifest =
-        serde_json::from_str(&out).expect(&format!("Malformed JSON from parser: {}", &out));
+    let mut manifest: PaxManifest = serde_json::from_str(&out)
+        .unwrap_or_else(|_| panic!("Malformed JSON from parser: {}", &out));
     let host_cargo_toml_path = Path::new(&ctx.path).join("Cargo.toml");
     let host_crate_info = get_host_crate_info(&host_cargo_toml_path);
     update_property_prefixes_in_place(&mut manifest, &host_crate_info);
 
+    Ok((manifest, host_crate_info))
+}
+
+/// For the specified file path or current working directory, build the Pax project, then rebuild it
+/// whenever its sources change.  The manifest is kept between builds, so that only the generated files
+/// affected by a change are regenerated.  Web builds are served throughout, picking up each rebuild.
+pub fn perform_watch(ctx: &RunContext) -> eyre::Result<(), Report> {
+    let pax_dir = prepare_pax_directory(ctx);
+    //Start watching before the first build, so that changes made during it aren't missed
+    let watcher = SourceWatcher::new(&ctx.path)?;
+
+    let mut previous_manifest = None;
     let mut source_map = SourceMap::new();
+    let mut changed_paths = vec![];
+    let mut is_serving = false;
+    loop {
+        match rebuild_watched_project(
+            &pax_dir,
+            ctx,
+            &mut previous_manifest,
+            &mut source_map,
+            &changed_paths,
+        ) {
+            Ok(true) => {
+                if let RunTarget::Web = ctx.target {
+                    if is_serving {
+                        println!("{} 🔄 Rebuilt; refresh to see changes", *PAX_BADGE);
+                    } else {
+                        let public_path = pax_dir
+                            .join(PKG_DIR_NAME)
+                            .join("pax-chassis-web")
+                            .join("interface")
+                            .join(PUBLIC_DIR_NAME);
+                        thread::spawn(move || start_static_http_server(public_path));
+                        is_serving = true;
+                    }
+                }
+            }
+            Ok(false) => println!("{} 💤 Nothing to rebuild", *PAX_BADGE),
+            Err(err) => eprintln!("{} ❌ {}", *PAX_BADGE, err),
+        }
 
-    println!("{} 🧮 Compiling expressions", *PAX_BADGE);
-    expressions::compile_all_expressions(&mut manifest, &mut source_map)?;
+        println!("{} 👀 Watching for changes...", *PAX_BADGE);
+        changed_paths = watcher
+            .next_change()
+            .ok_or_else(|| eyre!("Stopped receiving changes from the file system"))?;
+    }
+}
 
-    println!("{} 🦀 Generating Rust", *PAX_BADGE);
-    generate_reexports_partial_rs(&pax_dir, &manifest);
-    generate_and_overwrite_properties_coproduct(&pax_dir, &manifest, &host_crate_info);
-    let cartridge_path =
-        generate_and_overwrite_cartridge(&pax_dir, &manifest, &host_crate_info, &mut source_map);
-    source_map.extract_ranges_from_generated_code(cartridge_path.to_str().unwrap());
+/// Reparses the watched project, regenerating whichever files differ from `previous_manifest`'s, then rebuilds the
+/// chassis if anything changed.  Returns whether the chassis was rebuilt.
+fn rebuild_watched_project(
+    pax_dir: &Path,
+    ctx: &RunContext,
+    previous_manifest: &mut Option<PaxManifest>,
+    source_map: &mut SourceMap,
+    changed_paths: &[PathBuf],
+) -> eyre::Result<bool, Report> {
+    println!("{} 🛠️  Building parser binary with `cargo`...", *PAX_BADGE);
+    let (manifest, host_crate_info) = parse_manifest(ctx)?;
+
+    let previous = previous_manifest.as_ref();
+    let are_imports_changed = previous.map_or(true, |previous| {
+        previous.import_paths != manifest.import_paths
+    });
+    let are_types_changed = previous.map_or(true, |previous| {
+        normalize(&previous.type_table) != normalize(&manifest.type_table)
+            || previous.components.keys().collect::<HashSet<_>>()
+                != manifest.components.keys().collect::<HashSet<_>>()
+    });
+    //Source locations are compared, too, since the source map refers to them
+    let are_templates_changed = previous.map_or(true, |previous| {
+        previous.main_component_type_id != manifest.main_component_type_id
+            || serde_json::to_value(&previous.type_table).unwrap()
+                != serde_json::to_value(&manifest.type_table).unwrap()
+            || serde_json::to_value(&previous.components).unwrap()
+                != serde_json::to_value(&manifest.components).unwrap()
+    });
+    if !are_imports_changed
+        && !are_types_changed
+        && !are_templates_changed
+        && !watch::includes_rust_sources(changed_paths)
+    {
+        return Ok(false);
+    }
+
+    if let Some(previous) = previous {
+        let changed_components = manifest
+            .components
+            .values()
+            .filter(|cd| previous.components.get(&cd.type_id).map(normalize) != Some(normalize(cd)))
+            .map(|cd| cd.pascal_identifier.as_str())
+            .sorted()
+            .join(", ");
+        if !changed_components.is_empty() {
+            println!("{} ✏️  Changed: {}", *PAX_BADGE, changed_components);
+        }
+    }
+
+    println!("{} 🦀 Regenerating Rust", *PAX_BADGE);
+    if are_imports_changed {
+        generate_reexports_partial_rs(pax_dir, &manifest);
+    }
+    if are_imports_changed || are_types_changed {
+        generate_and_overwrite_properties_coproduct(pax_dir, &manifest, &host_crate_info);
+    }
+    if are_imports_changed || are_types_changed || are_templates_changed {
+        let mut compiled_manifest = manifest.clone();
+        let mut new_source_map = SourceMap::new();
+        validate_property_observers(&compiled_manifest)?;
+        println!("{} 🧮 Compiling expressions", *PAX_BADGE);
+        expressions::compile_all_expressions(&mut compiled_manifest, &mut new_source_map)?;
+        let cartridge_path = generate_and_overwrite_cartridge(
+            pax_dir,
+            &compiled_manifest,
+            &host_crate_info,
+            &mut new_source_map,
+            ctx.interpret_expressions,
+            false,
+        );
+        new_source_map.extract_ranges_from_generated_code(cartridge_path.to_str().unwrap());
+        *source_map = new_source_map;
+    }
+    //The generated files are now up to date with `manifest`, even if the build below fails
+    *previous_manifest = Some(manifest);
 
-    //7. Build the appropriate `chassis` from source, with the patched `Cargo.toml`, Properties Coproduct, and Cartridge from above
     println!("{} 🧱 Building cartridge with `cargo`", *PAX_BADGE);
     build_chassis_with_cartridge(
-        &pax_dir,
-        &ctx,
+        pax_dir,
+        ctx,
         Arc::clone(&ctx.process_child_ids),
-        &source_map,
+        source_map,
+        None,
     )?;
-    Ok(())
+    Ok(true)
 }
 
 fn start_static_http_server(fs_path: PathBuf) -> std::io::Result<()> {
@@ -1287,7 +1981,8 @@ fn start_static_http_server(fs_path: PathBuf) -> std::io::Result<()> {
         .init();
 
     // Create a Runtime
-    let runtime = actix_rt::System::new().block_on(async {
+
+    actix_rt::System::new().block_on(async {
         let mut port = 8080;
         let server = loop {
             // Check if the port is available
@@ -1315,9 +2010,7 @@ fn start_static_http_server(fs_path: PathBuf) -> std::io::Result<()> {
         };
 
         server.run().await
-    });
-
-    runtime
+    })
 }
 
 /// Helper recursive fs copy method, like fs::copy, but suited for our purposes.
@@ -1356,19 +2049,18 @@ pub fn perform_clean(path: &str) {
 
     remove_path_from_pax_dependencies(&path);
 
-    fs::remove_dir_all(&pax_dir).ok();
+    fs::remove_dir_all(pax_dir).ok();
 }
 
-fn run_cargo_build() {}
-
 /// Runs `cargo build` (or `wasm-pack build`) with appropriate env in the directory
 /// of the generated chassis project inside the specified .pax dir
 /// Returns an output object containing bytestreams of stdout/stderr as well as an exit code
 pub fn build_chassis_with_cartridge(
-    pax_dir: &PathBuf,
+    pax_dir: &Path,
     ctx: &RunContext,
     process_child_ids: Arc<Mutex<Vec<u64>>>,
     source_map: &SourceMap,
+    hot_reload_port: Option<u16>,
 ) -> eyre::Result<(), Report> {
     let target: &RunTarget = &ctx.target;
     let target_str: &str = target.into();
@@ -1379,11 +2071,7 @@ pub fn build_chassis_with_cartridge(
         .join(format!("pax-chassis-{}", target_str_lower));
 
     let is_release: bool = ctx.is_release;
-    let is_ios = if let RunTarget::iOS = target {
-        true
-    } else {
-        false
-    };
+    let is_ios = matches!(target, RunTarget::iOS);
 
     let build_mode_name: &str = if is_release { "release" } else { "debug" };
 
@@ -1455,8 +2143,8 @@ pub fn build_chassis_with_cartridge(
             let mut handles = Vec::new();
 
             //(arch id, single-platform .dylib path, stdout/stderr from build)
-            let build_results: Arc<Mutex<HashMap<u32, (String, String, Output)>>> =
-                Arc::new(Mutex::new(HashMap::new()));
+            let build_results =
+                Arc::new(Mutex::new(HashMap::<u32, (String, String, Output)>::new()));
 
             let targets_single_string = target_mappings
                 .iter()
@@ -1472,8 +2160,7 @@ pub fn build_chassis_with_cartridge(
                 target_mappings.len()
             );
 
-            let mut index = 0;
-            for target_mapping in target_mappings {
+            for (index, target_mapping) in (0u32..).zip(target_mappings) {
                 let chassis_path = chassis_path.clone();
                 let pax_dir = pax_dir.clone();
 
@@ -1522,11 +2209,9 @@ pub fn build_chassis_with_cartridge(
                         .unwrap()
                         .insert(index, new_val);
                 });
-                index = index + 1;
                 handles.push(handle);
             }
 
-            let mut index = 0;
             // Wait for all threads to complete and print their outputs
             for handle in handles {
                 handle.join().unwrap();
@@ -1544,21 +2229,19 @@ pub fn build_chassis_with_cartridge(
                 let stdout = String::from_utf8_lossy(&output.stdout).to_string();
                 let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
-                if stdout != "" || stderr != "" {
+                if !stdout.is_empty() || !stderr.is_empty() {
                     println!("{} build finished with output:", &target);
                 }
-                if stdout != "" {
+                if !stdout.is_empty() {
                     println!("{}", &stdout);
                 }
-                if stderr != "" {
+                if !stderr.is_empty() {
                     eprintln!("{}", &stderr);
                 }
 
                 if !output.status.success() {
                     should_abort = true;
                 }
-
-                index = index + 1;
             }
 
             if should_abort {
@@ -1590,12 +2273,7 @@ pub fn build_chassis_with_cartridge(
                 Ok(())
             });
 
-            match result {
-                Err(r) => {
-                    return Err(r);
-                }
-                _ => {}
-            };
+            result?;
 
             let macos_dylib_dest = pax_dir
                 .join(PKG_DIR_NAME)
@@ -1774,7 +2452,7 @@ Note that the temporary directories mentioned above are subject to overwriting.\
                 .join(target_str_lower);
             let executable_output_dir_path = build_dest_base.join("app");
             let executable_dot_app_path =
-                executable_output_dir_path.join(&format!("{}.app", &scheme));
+                executable_output_dir_path.join(format!("{}.app", &scheme));
             let _ = fs::create_dir_all(&executable_output_dir_path);
 
             let sdk = if let RunTarget::iOS = target {
@@ -1927,7 +2605,7 @@ Note that the temporary directories mentioned above are subject to overwriting.\
                     //
 
                     let system_binary_path =
-                        executable_dot_app_path.join(&format!("Contents/MacOS/{}", scheme));
+                        executable_dot_app_path.join(format!("Contents/MacOS/{}", scheme));
 
                     let status = Command::new(system_binary_path)
                         .status() // This will wait for the process to complete
@@ -1957,13 +2635,13 @@ Note that the temporary directories mentioned above are subject to overwriting.\
 
                     let output_str = std::str::from_utf8(&output.stdout)
                         .map_err(|_| eyre!("Failed to parse stdout for xcrun"))?;
-                    let parsed: Value = serde_json::from_str(&output_str)
+                    let parsed: Value = serde_json::from_str(output_str)
                         .map_err(|_| eyre!("Failed to deserialize xcrun."))?;
 
                     // Extract devices
-                    let devices = parsed["devices"].as_object().ok_or_else(|| {
-                        return eyre!("Invalid JSON format for devices.");
-                    })?;
+                    let devices = parsed["devices"]
+                        .as_object()
+                        .ok_or_else(|| eyre!("Invalid JSON format for devices."))?;
 
                     let mut max_iphone_number = 0;
                     let mut desired_udid = None;
@@ -2062,7 +2740,7 @@ Note that the temporary directories mentioned above are subject to overwriting.\
                     {
                         println!("{} 💤 Waiting for simulator to boot...", *PAX_BADGE);
                         std::thread::sleep(std::time::Duration::from_secs(retry_period_secs));
-                        retries = retries + 1;
+                        retries += 1;
                     }
 
                     if retries == max_retries {
@@ -2212,11 +2890,17 @@ Note that the temporary directories mentioned above are subject to overwriting.\
             // Check if the asset_src directory exists before attempting the copy
             if asset_src.exists() {
                 // Perform recursive copy from userland `assets/` to built `assets/`
-                if let Err(e) = copy_dir_recursively(&asset_src, &asset_dest, &vec![]) {
+                if let Err(e) = copy_dir_recursively(&asset_src, &asset_dest, &[]) {
                     return Err(eyre!("Error copying assets: {}", e));
                 }
             }
 
+            //Don't leave a previous run's hot reload config in place, e.g. for publishing
+            let hot_reload_config_path = interface_path
+                .join(PUBLIC_DIR_NAME)
+                .join(HOT_RELOAD_CONFIG_FILE_NAME);
+            let _ = fs::remove_file(&hot_reload_config_path);
+
             //Copy fully built project into .pax/build/web, ready for e.g. publishing
             let build_src = interface_path.join(PUBLIC_DIR_NAME);
             let build_dest = pax_dir
@@ -2235,6 +2919,10 @@ Note that the temporary directories mentioned above are subject to overwriting.\
 
             // Start local server if this is a `run` rather than a `build`
             if ctx.should_also_run {
+                //Tells the running app where to connect for hot reloads
+                if let Some(port) = hot_reload_port {
+                    fs::write(&hot_reload_config_path, format!("{{\"port\":{}}}", port))?;
+                }
                 println!("{} 🐇 Running Pax Web...", *PAX_BADGE);
                 let _ = start_static_http_server(interface_path.join(PUBLIC_DIR_NAME));
             } else {
@@ -2246,6 +2934,139 @@ Note that the temporary directories mentioned above are subject to overwriting.\
                 );
             }
         }
+        RunTarget::Headless => {
+            // First pass cargo build to catch errors in template with source map
+            let mut cmd = Command::new("cargo");
+            cmd.current_dir(&chassis_path)
+                .arg("build")
+                .arg("--message-format=json")
+                .env("PAX_DIR", &pax_dir)
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::piped());
+
+            if is_release {
+                cmd.arg("--release");
+            }
+
+            #[cfg(unix)]
+            unsafe {
+                cmd.pre_exec(pre_exec_hook);
+            }
+
+            let child = cmd.spawn().expect(ERR_SPAWN);
+            let output = wait_with_output(&process_child_ids, child);
+            if !output.status.success() {
+                let result = errors::process_messages(output, source_map);
+                if ctx.verbose {
+                    if let Err(e) = result {
+                        eprintln!("Error encountered: {:?}", e);
+                    }
+                } else {
+                    result?;
+                }
+            }
+
+            // Second pass surfaces any remaining (non-template) errors with cargo's own formatting;
+            // when the first pass succeeded, this is a no-op thanks to cargo's cache
+            let mut cmd = Command::new("cargo");
+            cmd.current_dir(&chassis_path)
+                .arg("build")
+                .arg("--color")
+                .arg("always")
+                .env("PAX_DIR", &pax_dir)
+                .stdout(std::process::Stdio::inherit())
+                .stderr(std::process::Stdio::inherit());
+
+            if is_release {
+                cmd.arg("--release");
+            }
+
+            #[cfg(unix)]
+            unsafe {
+                cmd.pre_exec(pre_exec_hook);
+            }
+
+            let child = cmd.spawn().expect(ERR_SPAWN);
+            let output = wait_with_output(&process_child_ids, child);
+            if !output.status.success() {
+                return Err(eyre!(
+                    "Failed to build project for headless target. Aborting."
+                ));
+            }
+
+            //Copy binary and assets into .pax/build/headless, ready for e.g. running in CI
+            let binary_file_name =
+                format!("{}{}", HEADLESS_BINARY_NAME, std::env::consts::EXE_SUFFIX);
+            let binary_src = chassis_path
+                .join("target")
+                .join(build_mode_name)
+                .join(&binary_file_name);
+            let build_dest = pax_dir
+                .join(BUILD_DIR_NAME)
+                .join(build_mode_name)
+                .join(target_str_lower);
+            if let Err(e) = fs::create_dir_all(&build_dest) {
+                return Err(eyre!("Error creating directory {:?}: {}", build_dest, e));
+            }
+            let binary_dest = build_dest.join(&binary_file_name);
+            if let Err(e) = fs::copy(&binary_src, &binary_dest) {
+                return Err(eyre!(
+                    "Error copying headless binary from {:?} to {:?}: {}",
+                    binary_src,
+                    binary_dest,
+                    e
+                ));
+            }
+
+            let asset_src = pax_dir.join("..").join(ASSETS_DIR_NAME);
+            let asset_dest = build_dest.join(ASSETS_DIR_NAME);
+            if asset_src.exists() {
+                if let Err(e) = copy_dir_recursively(&asset_src, &asset_dest, &[]) {
+                    return Err(eyre!("Error copying assets: {}", e));
+                }
+            }
+
+            if ctx.should_also_run {
+                println!("{} 🐇 Running Pax Headless...", *PAX_BADGE);
+                //the child runs from `build_dest`, so resolve relative paths against our own working directory
+                let cwd = std::env::current_dir()?;
+                let build_dest = cwd.join(&build_dest);
+                let output_dir = match &ctx.headless_output_dir {
+                    Some(output_dir) => cwd.join(output_dir),
+                    None => build_dest.join(HEADLESS_FRAMES_DIR_NAME),
+                };
+                let frame_count = ctx.headless_frame_count.unwrap_or(1);
+
+                let mut cmd = Command::new(build_dest.join(&binary_file_name));
+                cmd.current_dir(&build_dest)
+                    .arg("--frames")
+                    .arg(frame_count.to_string())
+                    .arg("--output-dir")
+                    .arg(output_dir.to_str().unwrap())
+                    .arg("--assets-dir")
+                    .arg(build_dest.to_str().unwrap())
+                    .stdout(std::process::Stdio::inherit())
+                    .stderr(std::process::Stdio::inherit());
+
+                #[cfg(unix)]
+                unsafe {
+                    cmd.pre_exec(pre_exec_hook);
+                }
+
+                let child = cmd.spawn().expect(ERR_SPAWN);
+                let output = wait_with_output(&process_child_ids, child);
+                if !output.status.success() {
+                    return Err(eyre!("Headless run exited with {}", output.status));
+                }
+            } else {
+                println!(
+                    "{} 🗂️ Done: {} build available at {}",
+                    *PAX_BADGE,
+                    build_mode_name,
+                    binary_dest.to_str().unwrap()
+                );
+            }
+        }
     }
     Ok(())
 }
@@ -2264,7 +3085,7 @@ fn is_simulator_booted(device_udid: &str, process_child_ids: &Arc<Mutex<Vec<u64>
         cmd.pre_exec(pre_exec_hook);
     }
     let child = cmd.spawn().expect(ERR_SPAWN);
-    let output = wait_with_output(&process_child_ids, child);
+    let output = wait_with_output(process_child_ids, child);
     if !output.status.success() {
         panic!("Error checking simulator status. This is an unhandled error and may leave orphaned processes.");
     }
@@ -2282,7 +3103,7 @@ pub fn perform_create(ctx: &CreateContext) {
     if full_path.exists() {
         panic!("Error: destination `{:?}` already exists", full_path);
     }
-    let _ = fs::create_dir_all(&full_path);
+    let _ = fs::create_dir_all(full_path);
 
     // clone template into full_path
     if ctx.is_libdev_mode {
@@ -2295,10 +3116,10 @@ pub fn perform_create(ctx: &CreateContext) {
         let mut options = CopyOptions::new();
         options.overwrite = true;
 
-        for entry in std::fs::read_dir(&template_src).expect("Failed to read template directory") {
+        for entry in std::fs::read_dir(template_src).expect("Failed to read template directory") {
             let entry_path = entry.expect("Failed to read entry").path();
             if entry_path.is_dir() {
-                dir::copy(&entry_path, &full_path, &options).expect("Failed to copy directory");
+                dir::copy(&entry_path, full_path, &options).expect("Failed to copy directory");
             } else {
                 fs::copy(&entry_path, full_path.join(entry_path.file_name().unwrap()))
                     .expect("Failed to copy file");
@@ -2307,20 +3128,20 @@ pub fn perform_create(ctx: &CreateContext) {
     } else {
         // File src is include_dir — recursively extract files from include_dir into full_path
         PAX_CREATE_TEMPLATE
-            .extract(&full_path)
+            .extract(full_path)
             .expect("Failed to extract files");
     }
 
     //Patch Cargo.toml
     let cargo_template_path = full_path.join("Cargo.toml.template");
     let extracted_cargo_toml_path = full_path.join("Cargo.toml");
-    let _ = fs::copy(&cargo_template_path, &extracted_cargo_toml_path);
+    let _ = fs::copy(&cargo_template_path, extracted_cargo_toml_path);
     let _ = fs::remove_file(&cargo_template_path);
 
     let crate_name = full_path.file_name().unwrap().to_str().unwrap().to_string();
 
     // Read the Cargo.toml
-    let mut doc = fs::read_to_string(&full_path.join("Cargo.toml"))
+    let mut doc = fs::read_to_string(full_path.join("Cargo.toml"))
         .expect("Failed to read Cargo.toml")
         .parse::<toml_edit::Document>()
         .expect("Failed to parse Cargo.toml");
@@ -2344,7 +3165,7 @@ pub fn perform_create(ctx: &CreateContext) {
     }
 
     // Write the modified Cargo.toml back to disk
-    fs::write(&full_path.join("Cargo.toml"), doc.to_string())
+    fs::write(full_path.join("Cargo.toml"), doc.to_string())
         .expect("Failed to write modified Cargo.toml");
 
     println!(
@@ -2368,6 +3189,15 @@ pub struct RunContext {
     pub is_libdev_mode: bool,
     pub process_child_ids: Arc<Mutex<Vec<u64>>>,
     pub is_release: bool,
+    /// For `headless` runs: number of ticks to execute before exiting
+    pub headless_frame_count: Option<usize>,
+    /// For `headless` runs: directory into which rendered frames are written as PNGs
+    pub headless_output_dir: Option<String>,
+    /// Evaluate expressions with the runtime interpreter instead of compiling them to Rust, so that
+    /// they can be changed without a rebuild; see `pax_core::interpreter`
+    pub interpret_expressions: bool,
+    /// For `web` runs: watch the project and hot reload changed templates into the running app; see `hot_reload`
+    pub hot_reload: bool,
 }
 
 pub enum RunTarget {
@@ -2376,27 +3206,33 @@ pub enum RunTarget {
     Web,
     #[allow(non_camel_case_types)]
     iOS,
+    Headless,
 }
 
-impl From<&str> for RunTarget {
-    fn from(input: &str) -> Self {
+impl std::str::FromStr for RunTarget {
+    type Err = Report;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         match input.to_lowercase().as_str() {
-            "macos" => RunTarget::macOS,
-            "web" => RunTarget::Web,
-            "ios" => RunTarget::iOS,
-            _ => {
-                unreachable!()
-            }
+            "macos" => Ok(RunTarget::macOS),
+            "web" => Ok(RunTarget::Web),
+            "ios" => Ok(RunTarget::iOS),
+            "headless" => Ok(RunTarget::Headless),
+            _ => Err(eyre!(
+                "Unknown target `{}`; expected one of `web`, `macos`, `ios`, or `headless`",
+                input
+            )),
         }
     }
 }
 
-impl<'a> Into<&'a str> for &'a RunTarget {
-    fn into(self) -> &'a str {
-        match self {
+impl<'a> From<&'a RunTarget> for &'a str {
+    fn from(val: &'a RunTarget) -> Self {
+        match val {
             RunTarget::Web => "Web",
             RunTarget::macOS => "macOS",
             RunTarget::iOS => "iOS",
+            RunTarget::Headless => "Headless",
         }
     }
 }
@@ -2504,7 +3340,7 @@ impl NamespaceTrieNode {
             if child.1.node_string.as_ref().unwrap() == "crate" {
                 //handle crate subtrie by skipping the crate NamespaceTrieNode, traversing directly into its children
                 child.1.children.iter().sorted().for_each(|child| {
-                    if child.1.children.len() == 0 {
+                    if child.1.children.is_empty() {
                         //leaf node:  write `pub use ...` entry
                         accum += &format!(
                             "{}pub use {};\n",
@@ -2529,31 +3365,29 @@ impl NamespaceTrieNode {
                         accum += &format!("{}}}\n", indent_str);
                     }
                 })
+            } else if child.1.children.is_empty() {
+                //leaf node:  write `pub use ...` entry
+                accum += &format!(
+                    "{}pub use {};\n",
+                    indent_str,
+                    child.1.node_string.as_ref().unwrap()
+                );
             } else {
-                if child.1.children.len() == 0 {
-                    //leaf node:  write `pub use ...` entry
-                    accum += &format!(
-                        "{}pub use {};\n",
-                        indent_str,
-                        child.1.node_string.as_ref().unwrap()
-                    );
-                } else {
-                    //non-leaf node:  write `pub mod ...` block
-                    accum += &format!(
-                        "{}pub mod {}{{\n",
-                        indent_str,
-                        child
-                            .1
-                            .node_string
-                            .as_ref()
-                            .unwrap()
-                            .split("::")
-                            .last()
-                            .unwrap()
-                    );
-                    accum += &child.1.recurse_serialize_to_reexports(indent + 1);
-                    accum += &format!("{}}}\n", indent_str);
-                }
+                //non-leaf node:  write `pub mod ...` block
+                accum += &format!(
+                    "{}pub mod {}{{\n",
+                    indent_str,
+                    child
+                        .1
+                        .node_string
+                        .as_ref()
+                        .unwrap()
+                        .split("::")
+                        .last()
+                        .unwrap()
+                );
+                accum += &child.1.recurse_serialize_to_reexports(indent + 1);
+                accum += &format!("{}}}\n", indent_str);
             };
         });
 
@@ -2563,8 +3397,9 @@ impl NamespaceTrieNode {
 
 #[cfg(test)]
 mod tests {
-    use super::NamespaceTrieNode;
+    use super::{write_if_changed, NamespaceTrieNode, RunTarget};
     use std::collections::HashMap;
+    use std::fs;
 
     #[test]
     fn test_serialize_to_reexports() {
@@ -2590,7 +3425,7 @@ mod tests {
         };
 
         for namespace_string in input_vec {
-            root_node.insert(&namespace_string);
+            root_node.insert(namespace_string);
         }
 
         let output = root_node.serialize_to_reexports();
@@ -2630,4 +3465,30 @@ mod tests {
 
         assert_eq!(output, expected_output);
     }
+
+    #[test]
+    fn test_write_if_changed_preserves_unchanged_files() {
+        let path =
+            std::env::temp_dir().join(format!("pax-write-if-changed-{}.rs", std::process::id()));
+        write_if_changed(&path, "fn main() {}").unwrap();
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        write_if_changed(&path, "fn main() {}").unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), modified);
+
+        write_if_changed(&path, "fn main() { }").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fn main() { }");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_parse_run_target() {
+        assert!(matches!(
+            "Headless".parse::<RunTarget>(),
+            Ok(RunTarget::Headless)
+        ));
+        assert!(matches!("macos".parse::<RunTarget>(), Ok(RunTarget::macOS)));
+        assert!("android".parse::<RunTarget>().is_err());
+    }
 }
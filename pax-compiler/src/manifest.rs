@@ -9,7 +9,7 @@ use serde_derive::{Deserialize, Serialize};
 use serde_json;
 
 /// Definition container for an entire Pax cartridge
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct PaxManifest {
     pub components: HashMap<String, ComponentDefinition>,
     pub main_component_type_id: String,
@@ -28,7 +28,7 @@ impl PartialEq<Self> for ExpressionSpec {
 
 impl PartialOrd<Self> for ExpressionSpec {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.id.partial_cmp(&other.id)
+        Some(self.cmp(other))
     }
 }
 
@@ -62,6 +62,27 @@ pub struct ExpressionSpec {
     /// The PropertiesCoproduct variant (type_id_escaped) of the inner
     /// type `T` for some iterable repeat source type, e.g. `Vec<T>`
     pub repeat_source_iterable_type_id_escaped: String,
+
+    /// Original PAXEL source, for expressions that may be evaluated by the runtime interpreter
+    /// (see `pax_core::interpreter`) when building with interpreted expressions.  `None` for
+    /// expressions that are always compiled to RIL, namely `for` sources over a `Vec` and `for` keys.
+    pub interpreted_paxel: Option<String>,
+
+    /// The scopes visible to this expression, from the innermost outwards, for resolving symbols at runtime
+    pub scope_frames: Vec<ExpressionScopeFrame>,
+}
+
+/// One scope (i.e. runtime `StackFrame`) visible to an expression, describing the symbols it binds
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExpressionScopeFrame {
+    /// For the scope of a `for` body, the name bound to each element, e.g. `elem` in `for (elem, i) in ...`.
+    /// `None` for a component's scope, whose properties are resolved by name.
+    pub repeat_elem_id: Option<String>,
+    /// For the scope of a `for` body, the name bound to each index, if any, e.g. `i` in `for (elem, i) in ...`
+    pub repeat_index_id: Option<String>,
+    /// For the scope of a `for` body that destructures each element, the names it binds along with the path of
+    /// fields (or tuple indices) to each, e.g. `("score", ["1"])` for `for ((name, score), i) in ...`
+    pub repeat_elem_destructuring: Vec<(String, Vec<String>)>,
 }
 
 /// The spec of an expression `invocation`, the necessary configuration
@@ -114,11 +135,11 @@ pub const SUPPORTED_NONNUMERIC_PRIMITIVES: [&str; 2] = ["String", "bool"];
 
 impl ExpressionSpecInvocation {
     pub fn is_primitive_string(property_properties_coproduct_type: &str) -> bool {
-        &SUPPORTED_NONNUMERIC_PRIMITIVES[0] == &property_properties_coproduct_type
+        SUPPORTED_NONNUMERIC_PRIMITIVES[0] == property_properties_coproduct_type
     }
 
     pub fn is_primitive_bool(property_properties_coproduct_type: &str) -> bool {
-        &SUPPORTED_NONNUMERIC_PRIMITIVES[1] == &property_properties_coproduct_type
+        SUPPORTED_NONNUMERIC_PRIMITIVES[1] == property_properties_coproduct_type
     }
 
     pub fn is_numeric(property_properties_coproduct_type: &str) -> bool {
@@ -150,17 +171,15 @@ pub struct ComponentDefinition {
     pub template: Option<Vec<TemplateNodeDefinition>>,
     pub settings: Option<Vec<SettingsSelectorBlockDefinition>>,
     pub events: Option<Vec<EventDefinition>>,
+    /// Keyframes declared in a `@timeline` block, stored as selector blocks whose values are all `ValueDefinition::Timeline`
+    pub timeline: Option<Vec<SettingsSelectorBlockDefinition>>,
 }
 
 impl ComponentDefinition {
     pub fn get_snake_case_id(&self) -> String {
         self.type_id
             .replace("::", "_")
-            .replace("/", "_")
-            .replace("\\", "_")
-            .replace(">", "_")
-            .replace("<", "_")
-            .replace(".", "_")
+            .replace(['/', '\\', '>', '<', '.'], "_")
     }
 
     pub fn get_property_definitions<'a>(&self, tt: &'a TypeTable) -> &'a Vec<PropertyDefinition> {
@@ -216,6 +235,11 @@ pub struct PropertyDefinition {
 
     /// Statically known type_id for this Property's associated TypeDefinition
     pub type_id: String,
+
+    /// For a symbol bound by destructuring a `Repeat` element, e.g. `score` in `for ((name, score), i) in self.rows`,
+    /// the path of fields (or tuple indices) from the element to the bound value, e.g. `["1"]`.  In that case, `type_id`
+    /// describes the element rather than the bound value.  Empty for all other properties.
+    pub repeat_destructuring_path: Vec<String>,
 }
 
 impl PropertyDefinition {
@@ -272,6 +296,7 @@ impl PropertyDefinition {
             name: symbol_name.to_string(),
             flags: PropertyDefinitionFlags::default(),
             type_id: type_name.to_string(),
+            repeat_destructuring_path: vec![],
         }
     }
 }
@@ -319,14 +344,14 @@ impl TypeDefinition {
         }
     }
 
-    pub fn builtin_range_isize() -> Self {
-        let type_id = "std::ops::Range<isize>";
+    pub fn builtin_repeat_range() -> Self {
+        let type_id = "RepeatRange";
         Self {
             type_id: type_id.to_string(),
             type_id_escaped: escape_identifier(type_id.to_string()),
             property_definitions: vec![],
             inner_iterable_type_id: Some("isize".to_string()),
-            import_path: "std::ops::Range".to_string(),
+            import_path: "pax_runtime_api::RepeatRange".to_string(),
         }
     }
 
@@ -354,6 +379,27 @@ pub enum ValueDefinition {
     /// (Expression contents, vtable id binding)
     Identifier(Token, Option<usize>),
     EventBindingTarget(Token),
+    /// Keyframes bound to a property via `@timeline`
+    Timeline(Vec<KeyframeDefinition>),
+}
+
+/// Container for a single keyframe parsed from a `@timeline` block, for example
+/// the `60 as spun: 360deg ease OutBack` in `#logo { rotate: [0: 0deg, 60 as spun: 360deg ease OutBack] }`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct KeyframeDefinition {
+    /// Frame number, e.g. `60`; checked to fit a `usize` when the timeline is compiled
+    pub frame: Token,
+    pub name: Option<Token>,
+    pub value: Token,
+    /// Name of an `EasingCurve` variant, e.g. `OutBack`; `Linear` if unspecified
+    pub curve: Option<Token>,
+}
+
+impl KeyframeDefinition {
+    /// Returns this keyframe's frame number, or `None` if it does not fit a `usize`
+    pub fn get_frame(&self) -> Option<usize> {
+        self.frame.token_value.parse::<usize>().ok()
+    }
 }
 
 /// Container for holding metadata about original Location in Pax Template
@@ -371,6 +417,59 @@ pub struct LocationInfo {
 pub enum ControlFlowRepeatPredicateDefinition {
     ElemId(Token),
     ElemIdIndexId(Token, Token),
+    /// A destructured element, like the `(name, score, rank)` in `for (name, score, rank) in foo`
+    ElemPattern(ControlFlowRepeatPattern),
+    /// A destructured element and its index, like the `((name, score), i)` in `for ((name, score), i) in foo`
+    ElemPatternIndexId(ControlFlowRepeatPattern, Token),
+}
+
+/// Describes how a Repeat (`for`) predicate destructures each element
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ControlFlowRepeatPattern {
+    /// Binds the entire (sub-)element to a symbol, like `name`
+    Binding(Token),
+    /// Destructures a tuple, like `(name, score)`
+    Tuple(Vec<ControlFlowRepeatPattern>),
+    /// Destructures a struct by field, like `{name, position: (x, y)}`.  A field written
+    /// without a pattern, like `name`, is bound to a symbol of the same name.
+    Struct(Vec<(Token, ControlFlowRepeatPattern)>),
+}
+
+impl ControlFlowRepeatPattern {
+    /// Returns each symbol bound by this pattern, along with the path of fields (or tuple indices)
+    /// leading from the element to the value it binds, e.g. `score` and `["0", "1"]` for `((name, score), rank)`
+    pub fn get_bindings(&self) -> Vec<(Token, Vec<String>)> {
+        match self {
+            ControlFlowRepeatPattern::Binding(token) => vec![(token.clone(), vec![])],
+            ControlFlowRepeatPattern::Tuple(elements) => elements
+                .iter()
+                .enumerate()
+                .flat_map(|(i, element)| {
+                    ControlFlowRepeatPattern::prefix_bindings(i.to_string(), element)
+                })
+                .collect(),
+            ControlFlowRepeatPattern::Struct(fields) => fields
+                .iter()
+                .flat_map(|(field, pattern)| {
+                    ControlFlowRepeatPattern::prefix_bindings(field.token_value.clone(), pattern)
+                })
+                .collect(),
+        }
+    }
+
+    fn prefix_bindings(
+        field: String,
+        pattern: &ControlFlowRepeatPattern,
+    ) -> Vec<(Token, Vec<String>)> {
+        pattern
+            .get_bindings()
+            .into_iter()
+            .map(|(token, mut path)| {
+                path.insert(0, field.clone());
+                (token, path)
+            })
+            .collect()
+    }
 }
 
 /// Container for storing parsed control flow information, for
@@ -380,10 +479,15 @@ pub enum ControlFlowRepeatPredicateDefinition {
 pub struct ControlFlowSettingsDefinition {
     pub condition_expression_paxel: Option<Token>,
     pub condition_expression_vtable_id: Option<usize>,
+    /// For `if`: the subset of the node's `child_ids` belonging to its `else` branch.  An `else if`
+    /// is represented as a single nested `if` node in this list.
+    pub else_branch_child_ids: Option<Vec<usize>>,
     pub slot_index_expression_paxel: Option<Token>,
     pub slot_index_expression_vtable_id: Option<usize>,
     pub repeat_predicate_definition: Option<ControlFlowRepeatPredicateDefinition>,
     pub repeat_source_definition: Option<ControlFlowRepeatSourceDefinition>,
+    pub repeat_key_expression_paxel: Option<Token>,
+    pub repeat_key_expression_vtable_id: Option<usize>,
 }
 
 /// Container describing the possible variants of a Repeat source
@@ -393,9 +497,12 @@ pub struct ControlFlowRepeatSourceDefinition {
     pub range_expression_paxel: Option<Token>,
     pub vtable_id: Option<usize>,
     pub symbolic_binding: Option<Token>,
+    /// Whether `symbolic_binding` calls methods on its symbol, like `self.items.filter(|x| x.visible)`,
+    /// in which case its `token_value` is PAXEL rather than an escaped symbol
+    pub is_method_call: bool,
 }
 
-/// Container for parsed Settings blocks (inside `@settings`)
+/// Container for parsed Settings blocks (inside `@settings`), also used for keyframes inside `@timeline`
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SettingsSelectorBlockDefinition {
     pub selector: Token,
@@ -429,12 +536,16 @@ pub enum TokenType {
     IfExpression,
     ForPredicate,
     ForSource,
+    ForKey,
     SlotExpression,
     EventId,
     Handler,
     SettingKey,
     Selector,
     PascalIdentifier,
+    KeyframeFrame,
+    KeyframeName,
+    EasingCurve,
     #[default]
     Unknown,
 }
@@ -6,10 +6,11 @@ use itertools::{Itertools, MultiPeek};
 use std::ops::RangeFrom;
 
 use crate::manifest::{
-    get_primitive_type_table, ComponentDefinition, ControlFlowRepeatPredicateDefinition,
-    ControlFlowRepeatSourceDefinition, ControlFlowSettingsDefinition, EventDefinition,
-    LiteralBlockDefinition, LocationInfo, PropertyDefinition, SettingsSelectorBlockDefinition,
-    TemplateNodeDefinition, Token, TokenType, TypeDefinition, TypeTable, ValueDefinition,
+    get_primitive_type_table, ComponentDefinition, ControlFlowRepeatPattern,
+    ControlFlowRepeatPredicateDefinition, ControlFlowRepeatSourceDefinition,
+    ControlFlowSettingsDefinition, EventDefinition, KeyframeDefinition, LiteralBlockDefinition,
+    LocationInfo, PropertyDefinition, SettingsSelectorBlockDefinition, TemplateNodeDefinition,
+    Token, TokenType, TypeDefinition, TypeTable, ValueDefinition,
 };
 
 extern crate pest;
@@ -23,11 +24,10 @@ use pest::pratt_parser::{Assoc, Op, PrattParser};
 #[grammar = "pax.pest"]
 pub struct PaxParser;
 
-/// Returns (RIL output string, `symbolic id`s found during parse)
-/// where a `symbolic id` may be something like `self.num_clicks` or `i`
-pub fn run_pratt_parser(input_paxel: &str) -> (String, Vec<String>) {
+/// Returns the Pratt parser for PAXEL's operators
+pub fn get_pratt_parser() -> PrattParser<Rule> {
     // Operator precedence is declared via the ordering here:
-    let pratt = PrattParser::new()
+    PrattParser::new()
         .op(Op::infix(Rule::xo_tern_then, Assoc::Left)
             | Op::infix(Rule::xo_tern_else, Assoc::Right))
         .op(Op::infix(Rule::xo_bool_and, Assoc::Left) | Op::infix(Rule::xo_bool_or, Assoc::Left))
@@ -42,16 +42,237 @@ pub fn run_pratt_parser(input_paxel: &str) -> (String, Vec<String>) {
             | Op::infix(Rule::xo_rel_lte, Assoc::Left)
             | Op::infix(Rule::xo_rel_gt, Assoc::Left)
             | Op::infix(Rule::xo_rel_gte, Assoc::Left))
-        .op(Op::prefix(Rule::xo_bool_not));
+        .op(Op::prefix(Rule::xo_bool_not))
+}
+
+/// Returns (RIL output string, `symbolic id`s found during parse)
+/// where a `symbolic id` may be something like `self.num_clicks` or `i`
+pub fn run_pratt_parser(input_paxel: &str) -> (String, Vec<String>) {
+    let pratt = get_pratt_parser();
 
     let pairs = PaxParser::parse(Rule::expression_body, input_paxel)
-        .expect(&format!("unsuccessful pratt parse {}", &input_paxel));
+        .unwrap_or_else(|_| panic!("unsuccessful pratt parse {}", &input_paxel));
 
     let symbolic_ids = Rc::new(RefCell::new(vec![]));
     let output = recurse_pratt_parse_to_string(pairs, &pratt, Rc::clone(&symbolic_ids));
     (output, symbolic_ids.take())
 }
 
+/// Returns each function call or enum literal in a PAXEL expression, as (path, arity, whether it has an argument list),
+/// e.g. `("Color::rgb", 3, true)` for `Color::rgb(1.0, 0.5, 0)` or `("FontStyle::Normal", 0, false)` for `FontStyle::Normal`.
+/// Returns none for input that doesn't parse as PAXEL.
+pub fn collect_paxel_function_calls(input_paxel: &str) -> Vec<(String, usize, bool)> {
+    fn recurse_collect(pairs: Pairs<Rule>, function_calls: &mut Vec<(String, usize, bool)>) {
+        for pair in pairs {
+            if let Rule::xo_function_call | Rule::literal_enum_value = pair.as_rule() {
+                let mut path = vec![];
+                let mut arity = None;
+                for inner in pair.clone().into_inner() {
+                    match inner.as_rule() {
+                        Rule::identifier | Rule::pascal_identifier => path.push(inner.as_str()),
+                        _ => arity = Some(inner.into_inner().count()),
+                    }
+                }
+                function_calls.push((path.join("::"), arity.unwrap_or(0), arity.is_some()));
+            }
+            recurse_collect(pair.into_inner(), function_calls);
+        }
+    }
+
+    let mut function_calls = vec![];
+    if let Ok(pairs) = PaxParser::parse(Rule::expression_body, input_paxel) {
+        recurse_collect(pairs, &mut function_calls);
+    }
+    function_calls
+}
+
+/// The function through which templates build a `Path`'s segments from SVG path data, like `Path::svg("M0 0 L10 10 Z")`
+pub const SVG_PATH_FUNCTION: &str = "Path::svg";
+
+/// Returns the contents of an `expression_body` that is nothing but a string literal, like the `M0 0` in `"M0 0"`
+pub fn get_string_literal_contents(expression_body: Pair<Rule>) -> Option<Pair<Rule>> {
+    let mut inner = expression_body.into_inner();
+    match (inner.next(), inner.next()) {
+        (Some(literal), None) if literal.as_rule() == Rule::xo_literal => {
+            let string = literal.into_inner().next()?;
+            if string.as_rule() != Rule::string {
+                return None;
+            }
+            string
+                .into_inner()
+                .next()
+                .filter(|contents| contents.as_rule() == Rule::inner)
+        }
+        _ => None,
+    }
+}
+
+/// Parses SVG path data, like the `M0 0 L10 10 Z` in `Path::svg("M0 0 L10 10 Z")`, into RIL that builds the same
+/// `Vec<PathSegment>` through `Path`'s builder functions.  Arcs are approximated by cubic curves, as at runtime.
+pub fn compile_svg_path_data(data: &str) -> Result<String, String> {
+    let bez_path = kurbo::BezPath::from_svg(data).map_err(|err| err.to_string())?;
+    let point = |p: kurbo::Point| format!("({:?}, {:?})", p.x, p.y);
+    let steps = bez_path
+        .elements()
+        .iter()
+        .map(|el| match *el {
+            kurbo::PathEl::MoveTo(p) => format!("Path::move_to(path, {})", point(p)),
+            kurbo::PathEl::LineTo(p) => format!("Path::line(path, {})", point(p)),
+            kurbo::PathEl::QuadTo(p1, p2) => {
+                format!("Path::quad_to(path, {}, {})", point(p1), point(p2))
+            }
+            kurbo::PathEl::CurveTo(p1, p2, p3) => format!(
+                "Path::cubic_to(path, {}, {}, {})",
+                point(p1),
+                point(p2),
+                point(p3)
+            ),
+            kurbo::PathEl::ClosePath => "Path::close(path)".to_string(),
+        })
+        .map(|step| format!("let path = {};", step))
+        .collect::<Vec<_>>();
+    Ok(format!(
+        "{{ let path = Path::start(); {} path }}",
+        steps.join(" ")
+    ))
+}
+
+/// A chain of method calls on a symbol, like `self.items.filter(|x| x.visible).len()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaxelMethodCallChain {
+    /// The symbol on which the first method is called, like `self.items`
+    pub receiver: String,
+    pub calls: Vec<PaxelMethodCall>,
+    /// Offsets of the lambdas within which this chain appears, from the outermost inwards
+    pub enclosing_lambdas: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaxelMethodCall {
+    pub name: String,
+    pub args: Vec<PaxelMethodCallArg>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaxelMethodCallArg {
+    Expression,
+    Lambda(PaxelLambda),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaxelLambda {
+    /// Offset of this lambda within its expression, which identifies it in compiled RIL
+    pub offset: usize,
+    pub params: Vec<String>,
+    /// Symbols bound by this lambda's parameters, like the `x.visible` in `|x| x.visible`
+    pub symbols: Vec<String>,
+}
+
+/// Returns each chain of method calls in a PAXEL expression, including those nested within other chains' arguments,
+/// with outer chains before the chains nested within them.  Returns none for input that doesn't parse as PAXEL.
+pub fn collect_paxel_method_calls(input_paxel: &str) -> Vec<PaxelMethodCallChain> {
+    fn recurse_collect(
+        pair: Pair<Rule>,
+        lambdas: &mut Vec<(usize, Vec<String>)>,
+        chains: &mut Vec<PaxelMethodCallChain>,
+        symbols: &mut Vec<(String, usize)>,
+    ) {
+        match pair.as_rule() {
+            Rule::xo_method_call => {
+                let index = chains.len();
+                chains.push(PaxelMethodCallChain {
+                    receiver: "".to_string(),
+                    calls: vec![],
+                    enclosing_lambdas: lambdas.iter().map(|(offset, _)| *offset).collect(),
+                });
+                let mut inner = pair.into_inner();
+                let receiver = inner.next().unwrap();
+                chains[index].receiver = receiver.as_str().trim().to_string();
+                recurse_collect(receiver, lambdas, chains, symbols);
+
+                let mut calls = vec![];
+                for segment in inner {
+                    let mut segment = segment.into_inner();
+                    let name = segment.next().unwrap().as_str().to_string();
+                    let mut args = vec![];
+                    for arg in segment.next().unwrap().into_inner() {
+                        if let Rule::xo_lambda = arg.as_rule() {
+                            let offset = arg.as_span().start();
+                            let (params, body): (Vec<_>, Vec<_>) = arg
+                                .into_inner()
+                                .partition(|pair| matches!(pair.as_rule(), Rule::identifier));
+                            let params = params
+                                .iter()
+                                .map(|param| param.as_str().to_string())
+                                .collect::<Vec<_>>();
+                            lambdas.push((offset, params.clone()));
+                            body.into_iter()
+                                .for_each(|pair| recurse_collect(pair, lambdas, chains, symbols));
+                            lambdas.pop();
+                            args.push(PaxelMethodCallArg::Lambda(PaxelLambda {
+                                offset,
+                                params,
+                                symbols: vec![],
+                            }));
+                        } else {
+                            recurse_collect(arg, lambdas, chains, symbols);
+                            args.push(PaxelMethodCallArg::Expression);
+                        }
+                    }
+                    calls.push(PaxelMethodCall { name, args });
+                }
+                chains[index].calls = calls;
+            }
+            Rule::xo_symbol => {
+                let symbol = pair.as_str().trim().to_string();
+                //attribute the symbol to the innermost lambda that binds its root, if any
+                if let Some((offset, _)) = lambdas
+                    .iter()
+                    .rev()
+                    .find(|(_, params)| params.iter().any(|p| get_symbol_root(&symbol) == p))
+                {
+                    symbols.push((symbol, *offset));
+                }
+                pair.into_inner()
+                    .for_each(|pair| recurse_collect(pair, lambdas, chains, symbols));
+            }
+            _ => pair
+                .into_inner()
+                .for_each(|pair| recurse_collect(pair, lambdas, chains, symbols)),
+        }
+    }
+
+    let mut chains = vec![];
+    let mut symbols = vec![];
+    if let Ok(pairs) = PaxParser::parse(Rule::expression_body, input_paxel) {
+        pairs.for_each(|pair| recurse_collect(pair, &mut vec![], &mut chains, &mut symbols));
+    }
+    for (symbol, offset) in symbols {
+        let lambda = chains
+            .iter_mut()
+            .flat_map(|chain| chain.calls.iter_mut())
+            .flat_map(|call| call.args.iter_mut())
+            .find_map(|arg| match arg {
+                PaxelMethodCallArg::Lambda(lambda) if lambda.offset == offset => Some(lambda),
+                _ => None,
+            })
+            .unwrap();
+        if !lambda.symbols.contains(&symbol) {
+            lambda.symbols.push(symbol);
+        }
+    }
+    chains
+}
+
+/// Returns the root of a symbol, like the `elem` in `elem.position.0` or the `self` in `self.items[0]`
+pub fn get_symbol_root(symbol: &str) -> &str {
+    symbol
+        .split(|c| c == '.' || c == '[')
+        .next()
+        .unwrap()
+        .trim()
+}
+
 /// Removes leading `self.` or `this.`, escapes remaining symbol to be a suitable atomic identifier
 fn convert_symbolic_binding_from_paxel_to_ril(xo_symbol: Pair<Rule>) -> String {
     let mut pairs = xo_symbol.clone().into_inner();
@@ -64,7 +285,7 @@ fn convert_symbolic_binding_from_paxel_to_ril(xo_symbol: Pair<Rule>) -> String {
         pairs.for_each(|pair| output += &*(".".to_owned() + pair.as_str()));
 
         //remove initial fencepost "."
-        output.replacen(".", "", 1)
+        output.replacen('.', "", 1)
     } else {
         //remove original binding; no self or this
         xo_symbol.as_str().to_string()
@@ -73,8 +294,53 @@ fn convert_symbolic_binding_from_paxel_to_ril(xo_symbol: Pair<Rule>) -> String {
     escape_identifier(self_or_this_removed)
 }
 
+/// Compiles a literal, as found in `xo_literal` or (recursively) `literal_value`, into RIL
+fn convert_literal_to_ril(literal_kind: Pair<Rule>) -> String {
+    match literal_kind.as_rule() {
+        Rule::literal_value => convert_literal_to_ril(literal_kind.into_inner().next().unwrap()),
+        Rule::literal_number_with_unit => {
+            let mut inner = literal_kind.into_inner();
+
+            let value = inner.next().unwrap().as_str();
+            let unit = inner.next().unwrap().as_str();
+
+            if unit == "px" {
+                format!("Size::Pixels({}.into())", value)
+            } else if unit == "%" {
+                format!("Size::Percent({}.into())", value)
+            } else if unit == "deg" {
+                format!("Rotation::Degrees({}.into())", value)
+            } else if unit == "rad" {
+                format!("Rotation::Radians({}.into())", value)
+            } else {
+                unreachable!()
+            }
+        }
+        Rule::literal_number => {
+            let mut inner = literal_kind.into_inner();
+            let value = inner.next().unwrap().as_str();
+            format!("Numeric::from({})", value)
+        }
+        Rule::string => {
+            format!("StringBox::from({})", literal_kind.as_str())
+        }
+        Rule::literal_tuple => {
+            //compile each element, like the `10px` and `5%` in `(10px, 5%, 0)`
+            let elements = literal_kind
+                .into_inner()
+                .map(convert_literal_to_ril)
+                .collect::<Vec<_>>();
+            format!("({})", elements.join(","))
+        }
+        _ => {
+            /* {literal_enum_value | literal_boolean} */
+            literal_kind.as_str().to_string()
+        }
+    }
+}
+
 /// Workhorse method for compiling Expressions into Rust Intermediate Language (RIL, a string of Rust)
-fn recurse_pratt_parse_to_string<'a>(
+fn recurse_pratt_parse_to_string(
     expression: Pairs<Rule>,
     pratt_parser: &PrattParser<Rule>,
     symbolic_ids: Rc<RefCell<Vec<String>>>,
@@ -115,97 +381,108 @@ fn recurse_pratt_parse_to_string<'a>(
                 let mut output = "".to_string();
                 let mut next_pair = pairs.next().unwrap();
                 while let Rule::identifier = next_pair.as_rule() {
-                    output = output + next_pair.as_str();
+                    output += next_pair.as_str();
                     next_pair = pairs.next().unwrap();
                     if let Rule::identifier = next_pair.as_rule() {
                         //look-ahead
-                        output = output + "::";
+                        output += "::";
                     }
                 };
 
-                let mut expression_body_pairs = next_pair.into_inner();
+                let expression_body_pairs = next_pair.into_inner();
 
-                output = output + "(";
-                while let Some(next_pair) = expression_body_pairs.next() {
+                //literal SVG path data is parsed now, rather than each time the expression is evaluated
+                if output == SVG_PATH_FUNCTION && expression_body_pairs.clone().count() == 1 {
+                    let svg_path_data = get_string_literal_contents(expression_body_pairs.clone().next().unwrap());
+                    if let Some(Ok(compiled)) = svg_path_data.map(|data| compile_svg_path_data(data.as_str())) {
+                        return compiled;
+                    }
+                }
+
+                output += "(";
+                for next_pair in expression_body_pairs {
                     output = output + "(" + &recurse_pratt_parse_to_string(next_pair.into_inner(), pratt_parser, Rc::clone(&symbolic_ids)) + "),"
                 }
-                output = output + ")";
+                output += ")";
 
                 output
             },
             Rule::xo_range => {
-                /* { op0: (xo_literal | xo_symbol) ~ op1: (xo_range_inclusive | xo_range_exclusive) ~ op2: (xo_literal | xo_symbol)} */
+                /* { op0: (xo_literal | xo_symbol) ~ op1: (xo_range_inclusive | xo_range_exclusive) ~ op2: (xo_literal | xo_symbol) ~ xo_range_step? }
+                   xo_range_step = { "step" ~ (xo_literal | xo_symbol) } */
                 let mut pairs = primary.into_inner();
 
-                let op0 = pairs.next().unwrap();
-
-                let op0_out = match op0.as_rule() {
-                    Rule::xo_literal => {
-                        //return the literal exactly as it is
-                        op0.as_str().to_string()
-                    },
-                    Rule::xo_symbol => {
-                        symbolic_ids.borrow_mut().push(op0.as_str().to_string());
-                        //for symbolic identifiers, remove any "this" or "self", then return string
-                        format!("{}.get_as_int()",convert_symbolic_binding_from_paxel_to_ril(op0))
-                    },
-                    _ => unimplemented!("")
+                let convert_operand = |op: Pair<Rule>| {
+                    match op.as_rule() {
+                        Rule::xo_literal => {
+                            //return the literal exactly as it is
+                            op.as_str().trim().to_string()
+                        },
+                        Rule::xo_symbol => {
+                            symbolic_ids.borrow_mut().push(op.as_str().to_string());
+                            //for symbolic identifiers, remove any "this" or "self", then return string
+                            format!("{}.get_as_int()",convert_symbolic_binding_from_paxel_to_ril(op))
+                        },
+                        _ => unimplemented!("")
+                    }
                 };
 
-                let op1 = pairs.next().unwrap();
-                let op1_out = op1.as_str().to_string();
-
-                let op2 = pairs.next().unwrap();
-                let op2_out = match op2.as_rule() {
-                    Rule::xo_literal => {
-                        //return the literal exactly as it is
-                        op2.as_str().to_string()
-                    },
-                    Rule::xo_symbol => {
-                        symbolic_ids.borrow_mut().push(op2.as_str().to_string());
-                        //for symbolic identifiers, remove any "this" or "self", then return string
-                        format!("{}.get_as_int()",convert_symbolic_binding_from_paxel_to_ril(op2))
-                    },
-                    _ => unimplemented!("")
+                let op0_out = convert_operand(pairs.next().unwrap());
+                let inclusive = matches!(pairs.next().unwrap().as_rule(), Rule::xo_range_inclusive);
+                let op2_out = convert_operand(pairs.next().unwrap());
+                let step_out = match pairs.next() {
+                    Some(step) => format!("Some({})", convert_operand(step.into_inner().next().unwrap())),
+                    None => "None".to_string(),
                 };
 
-                format!("{}", op0_out + &op1_out + &op2_out)
+                format!("RepeatRange::new({},{},{},{})", op0_out, op2_out, inclusive, step_out)
             },
-            Rule::xo_literal => {
-                let literal_kind = primary.into_inner().next().unwrap();
-
-                match literal_kind.as_rule() {
-                    Rule::literal_number_with_unit => {
-                        let mut inner = literal_kind.into_inner();
-
-                        let value = inner.next().unwrap().as_str();
-                        let unit = inner.next().unwrap().as_str();
-
-                        if unit == "px" {
-                            format!("Size::Pixels({}.into())", value)
-                        } else if unit == "%" {
-                            format!("Size::Percent({}.into())", value)
-                        } else if unit == "deg" {
-                            format!("Rotation::Degrees({}.into())", value)
-                        } else if unit == "rad" {
-                            format!("Rotation::Radians({}.into())", value)
-                        } else {
-                            unreachable!()
-                        }
-                    },
-                    Rule::literal_number => {
-                        let mut inner = literal_kind.into_inner();
-                        let value = inner.next().unwrap().as_str();
-                        format!("Numeric::from({})", value)
-                    },
-                    Rule::string => {
-                        format!("StringBox::from({})",literal_kind.as_str().to_string())
-                    },
-                    _ => {
-                        /* {literal_enum_value | literal_tuple_access | literal_tuple | string } */
-                        literal_kind.as_str().to_string()
+            Rule::xo_method_call => {
+                /* xo_method_call = { xo_symbol ~ xo_method_call_segment+ }
+                   xo_method_call_segment = { "." ~ identifier ~ "(" ~ xo_method_call_args_list ~ ")" } */
+                //each method is a function in `paxel_std`, called with the receiver as its first argument
+                let mut pairs = primary.into_inner();
+                let receiver = pairs.next().unwrap();
+                symbolic_ids.borrow_mut().push(receiver.as_str().trim().to_string());
+                let mut output = convert_symbolic_binding_from_paxel_to_ril(receiver);
+
+                for segment in pairs {
+                    let mut segment = segment.into_inner();
+                    let name = segment.next().unwrap().as_str();
+                    let mut args = vec![format!("&({})", output)];
+                    for arg in segment.next().unwrap().into_inner() {
+                        args.push(match arg.as_rule() {
+                            Rule::xo_lambda => {
+                                /* xo_lambda = { "|" ~ identifier ~ ("," ~ identifier)* ~ "|" ~ expression_body } */
+                                //the marker is replaced with bindings for the lambda's parameters, once their types are known
+                                let offset = arg.as_span().start();
+                                let mut inner = arg.into_inner();
+                                let mut params = vec![];
+                                let mut body = inner.next().unwrap();
+                                while let Rule::identifier = body.as_rule() {
+                                    params.push(body.as_str().to_string());
+                                    body = inner.next().unwrap();
+                                }
+                                let outer_symbolic_ids_count = symbolic_ids.borrow().len();
+                                let body = recurse_pratt_parse_to_string(body.into_inner(), pratt_parser, Rc::clone(&symbolic_ids));
+                                //symbols bound by the lambda's parameters aren't resolved through the scope stack
+                                let mut symbolic_ids = symbolic_ids.borrow_mut();
+                                let lambda_symbolic_ids = symbolic_ids.split_off(outer_symbolic_ids_count);
+                                symbolic_ids.extend(lambda_symbolic_ids.into_iter().filter(|symbol| {
+                                    !params.iter().any(|param| get_symbol_root(symbol) == param)
+                                }));
+                                format!("|{}| {{ /*lambda@{}*/ {} }}", params.join(","), offset, body)
+                            },
+                            _ => format!("&({})", recurse_pratt_parse_to_string(arg.into_inner(), pratt_parser, Rc::clone(&symbolic_ids))),
+                        });
                     }
+                    output = format!("paxel_std::{}({})", name, args.join(","));
                 }
+
+                output
+            },
+            Rule::xo_literal => {
+                convert_literal_to_ril(primary.into_inner().next().unwrap())
             },
             Rule::xo_object => {
                 let mut output : String = "".to_string();
@@ -216,7 +493,7 @@ fn recurse_pratt_parse_to_string<'a>(
 
                 //for parsing xo_object_settings_key_value_pair
                 //iterate over key-value pairs; recurse into expressions
-                fn handle_xoskvp<'a>(xoskvp: Pair<Rule>, pratt_parser: &PrattParser<Rule>, symbolic_ids: Rc<RefCell<Vec<String>>>) -> String {
+                fn handle_xoskvp(xoskvp: Pair<Rule>, pratt_parser: &PrattParser<Rule>, symbolic_ids: Rc<RefCell<Vec<String>>>) -> String {
                     let mut inner_kvp = xoskvp.into_inner();
                     let settings_key = inner_kvp.next().unwrap().as_str().to_string();
                     let expression_body = inner_kvp.next().unwrap().into_inner();
@@ -235,9 +512,7 @@ fn recurse_pratt_parse_to_string<'a>(
                     output += &ril;
                 }
 
-                let mut remaining_kvps = inner.into_iter();
-
-                while let Some(xoskkvp) = remaining_kvps.next() {
+                for xoskkvp in inner {
                     let ril =  handle_xoskvp(xoskkvp, pratt_parser, Rc::clone(&symbolic_ids));
                     output += &ril;
                 }
@@ -246,21 +521,20 @@ fn recurse_pratt_parse_to_string<'a>(
             },
             Rule::xo_symbol => {
                 symbolic_ids.borrow_mut().push(primary.as_str().to_string());
-                format!("{}",convert_symbolic_binding_from_paxel_to_ril(primary))
+                convert_symbolic_binding_from_paxel_to_ril(primary).to_string()
             },
             Rule::xo_tuple => {
-                let mut tuple = primary.into_inner();
-                let exp0 = tuple.next().unwrap();
-                let exp1 = tuple.next().unwrap();
-                let exp0 = recurse_pratt_parse_to_string( exp0.into_inner(), pratt_parser, Rc::clone(&symbolic_ids));
-                let exp1 = recurse_pratt_parse_to_string( exp1.into_inner(), pratt_parser, Rc::clone(&symbolic_ids));
-                format!("({},{})", exp0, exp1)
+                let tuple = primary.into_inner();
+                let elements = tuple.map(|element| {
+                    recurse_pratt_parse_to_string(element.into_inner(), pratt_parser, Rc::clone(&symbolic_ids))
+                }).collect::<Vec<_>>();
+                format!("({})", elements.join(","))
             },
             Rule::xo_list => {
-                let mut list = primary.into_inner();
+                let list = primary.into_inner();
                 let mut vec = Vec::new();
 
-                while let Some(item) = list.next() {
+                for item in list {
                     let item_str = recurse_pratt_parse_to_string(item.into_inner(), pratt_parser, Rc::clone(&symbolic_ids));
                     vec.push(item_str);
                 }
@@ -304,7 +578,7 @@ fn recurse_pratt_parse_to_string<'a>(
 
 fn parse_template_from_component_definition_string(ctx: &mut TemplateNodeParseContext, pax: &str) {
     let pax_component_definition = PaxParser::parse(Rule::pax_component_definition, pax)
-        .expect(&format!("unsuccessful parse from {}", &pax)) // unwrap the parse result
+        .unwrap_or_else(|_| panic!("unsuccessful parse from {}", &pax)) // unwrap the parse result
         .next()
         .unwrap(); // get and unwrap the `pax_component_definition` rule
 
@@ -316,14 +590,12 @@ fn parse_template_from_component_definition_string(ctx: &mut TemplateNodeParseCo
 
     pax_component_definition
         .into_inner()
-        .for_each(|pair| match pair.as_rule() {
-            Rule::root_tag_pair => {
-                ctx.child_id_tracking_stack.push(vec![]);
-                let next_id = ctx.uid_gen.peek().unwrap();
-                roots_ids.push(*next_id);
-                recurse_visit_tag_pairs_for_template(ctx, pair.into_inner().next().unwrap(), pax);
-            }
-            _ => {}
+        .filter(|pair| pair.as_rule() == Rule::root_tag_pair)
+        .for_each(|pair| {
+            ctx.child_id_tracking_stack.push(vec![]);
+            let next_id = ctx.uid_gen.peek().unwrap();
+            roots_ids.push(*next_id);
+            recurse_visit_tag_pairs_for_template(ctx, pair.into_inner().next().unwrap(), pax);
         });
 
     // This IMPLICIT_ROOT placeholder node, at index 0 of the TND vec,
@@ -408,7 +680,7 @@ fn recurse_visit_tag_pairs_for_template(
                 type_id: ctx
                     .pascal_identifier_to_type_id_map
                     .get(pascal_identifier)
-                    .expect(&format!("Template key not found {}", &pascal_identifier))
+                    .unwrap_or_else(|| panic!("Template key not found {}", &pascal_identifier))
                     .to_string(),
                 settings: parse_inline_attribute_from_final_pairs_of_tag(open_tag, pax),
                 child_ids: ctx.child_id_tracking_stack.pop().unwrap(),
@@ -429,7 +701,7 @@ fn recurse_visit_tag_pairs_for_template(
                 type_id: ctx
                     .pascal_identifier_to_type_id_map
                     .get(pascal_identifier)
-                    .expect(&format!("Template key not found {}", &pascal_identifier))
+                    .unwrap_or_else(|| panic!("Template key not found {}", &pascal_identifier))
                     .to_string(),
                 settings: parse_inline_attribute_from_final_pairs_of_tag(tag_pairs, pax),
                 child_ids: vec![],
@@ -451,37 +723,28 @@ fn recurse_visit_tag_pairs_for_template(
                 Rule::statement_if => {
                     let mut statement_if = any_tag_pair.into_inner();
                     let expression_body = statement_if.next().unwrap();
-                    let expression_body_location = span_to_location(&expression_body.as_span());
-                    let expression_body_token = Token::new(
-                        expression_body.as_str().to_string(),
-                        TokenType::IfExpression,
-                        expression_body_location,
-                        pax,
-                    );
-                    let prospective_inner_nodes = statement_if.next();
-
-                    if let Some(inner_nodes) = prospective_inner_nodes {
-                        inner_nodes.into_inner().for_each(|sub_tag_pair| {
-                            recurse_visit_tag_pairs_for_template(ctx, sub_tag_pair, pax);
-                        })
-                    }
+                    let inner_nodes = statement_if.next().unwrap();
+
+                    // Each `else if` becomes a nested `if` inside the `else` branch of its predecessor
+                    let mut branches = vec![(expression_body, inner_nodes)];
+                    let mut else_inner_nodes = None;
+                    statement_if.for_each(|pair| match pair.as_rule() {
+                        Rule::statement_else_if => {
+                            let mut statement_else_if = pair.into_inner();
+                            branches.push((
+                                statement_else_if.next().unwrap(),
+                                statement_else_if.next().unwrap(),
+                            ));
+                        }
+                        Rule::statement_else => {
+                            else_inner_nodes = Some(pair.into_inner().next().unwrap());
+                        }
+                        _ => {
+                            unreachable!("Parsing error: {:?}", pair.as_rule());
+                        }
+                    });
 
-                    //`if` TemplateNodeDefinition
-                    TemplateNodeDefinition {
-                        id: new_id,
-                        control_flow_settings: Some(ControlFlowSettingsDefinition {
-                            condition_expression_paxel: Some(expression_body_token),
-                            condition_expression_vtable_id: None, //This will be written back to this data structure later, during expression compilation
-                            slot_index_expression_paxel: None,
-                            slot_index_expression_vtable_id: None,
-                            repeat_predicate_definition: None,
-                            repeat_source_definition: None,
-                        }),
-                        type_id: TYPE_ID_IF.to_string(),
-                        settings: None,
-                        child_ids: ctx.child_id_tracking_stack.pop().unwrap(),
-                        pascal_identifier: "Conditional".to_string(),
-                    }
+                    visit_conditional_branches(ctx, new_id, branches, else_inner_nodes, pax)
                 }
                 Rule::statement_for => {
                     let mut cfavd = ControlFlowSettingsDefinition::default();
@@ -489,46 +752,68 @@ fn recurse_visit_tag_pairs_for_template(
                     let mut predicate_declaration = for_statement.next().unwrap().into_inner();
                     let source = for_statement.next().unwrap();
 
-                    let prospective_inner_nodes = for_statement.next();
-
-                    if predicate_declaration.clone().count() > 1 {
-                        //tuple, like the `elem, i` in `for (elem, i) in self.some_list`
-                        let elem = predicate_declaration.next().unwrap();
-                        let elem_location = span_to_location(&elem.as_span());
-                        let elem_token = Token::new(
-                            elem.as_str().to_string(),
-                            TokenType::ForPredicate,
-                            elem_location,
-                            pax,
-                        );
-                        let index = predicate_declaration.next().unwrap();
-                        let index_location = span_to_location(&index.as_span());
-                        let index_token = Token::new(
-                            index.as_str().to_string(),
-                            TokenType::ForPredicate,
-                            index_location,
-                            pax,
-                        );
-                        cfavd.repeat_predicate_definition =
-                            Some(ControlFlowRepeatPredicateDefinition::ElemIdIndexId(
-                                elem_token,
-                                index_token,
+                    let mut prospective_inner_nodes = for_statement.next();
+                    if let Some(key) = prospective_inner_nodes.clone() {
+                        if let Rule::statement_for_key = key.as_rule() {
+                            //keyed `for`, like the `key elem.id` in `for elem in self.some_list key elem.id`
+                            let key_expression_body = key.into_inner().next().unwrap();
+                            let key_expression_location =
+                                span_to_location(&key_expression_body.as_span());
+                            cfavd.repeat_key_expression_paxel = Some(Token::new(
+                                key_expression_body.as_str().to_string(),
+                                TokenType::ForKey,
+                                key_expression_location,
+                                pax,
                             ));
-                    } else {
-                        let elem = predicate_declaration.next().unwrap();
+                            prospective_inner_nodes = for_statement.next();
+                        }
+                    }
+
+                    let elem = predicate_declaration.next().unwrap();
+                    let elem_pattern = if let Rule::identifier = elem.as_rule() {
                         //single identifier, like the `elem` in `for elem in self.some_list`
-                        let predicate_declaration_location = span_to_location(&elem.as_span());
-                        let predicate_declaration_token = Token::new(
+                        ControlFlowRepeatPattern::Binding(Token::new(
                             elem.as_str().to_string(),
                             TokenType::ForPredicate,
-                            predicate_declaration_location,
+                            span_to_location(&elem.as_span()),
                             pax,
-                        );
-                        cfavd.repeat_predicate_definition =
-                            Some(ControlFlowRepeatPredicateDefinition::ElemId(
-                                predicate_declaration_token,
-                            ));
-                    }
+                        ))
+                    } else {
+                        //possibly destructured, like the `(name, score)` in `for ((name, score), i) in self.some_list`
+                        parse_for_pattern(elem, pax)
+                    };
+
+                    cfavd.repeat_predicate_definition =
+                        Some(match (elem_pattern, predicate_declaration.next()) {
+                            (ControlFlowRepeatPattern::Binding(elem_token), None) => {
+                                ControlFlowRepeatPredicateDefinition::ElemId(elem_token)
+                            }
+                            (elem_pattern, None) => {
+                                ControlFlowRepeatPredicateDefinition::ElemPattern(elem_pattern)
+                            }
+                            (elem_pattern, Some(index)) => {
+                                //tuple, like the `elem, i` in `for (elem, i) in self.some_list`
+                                let index_location = span_to_location(&index.as_span());
+                                let index_token = Token::new(
+                                    index.as_str().to_string(),
+                                    TokenType::ForPredicate,
+                                    index_location,
+                                    pax,
+                                );
+                                if let ControlFlowRepeatPattern::Binding(elem_token) = elem_pattern
+                                {
+                                    ControlFlowRepeatPredicateDefinition::ElemIdIndexId(
+                                        elem_token,
+                                        index_token,
+                                    )
+                                } else {
+                                    ControlFlowRepeatPredicateDefinition::ElemPatternIndexId(
+                                        elem_pattern,
+                                        index_token,
+                                    )
+                                }
+                            }
+                        });
 
                     let inner_source = source.into_inner().next().unwrap();
                     let inner_source_location = span_to_location(&inner_source.as_span());
@@ -538,15 +823,22 @@ fn recurse_visit_tag_pairs_for_template(
                         inner_source_location,
                         pax,
                     );
-                    /* statement_for_source = { xo_range | xo_symbol } */
+                    /* statement_for_source = { xo_range | xo_method_call | xo_symbol } */
                     let repeat_source_definition = match inner_source.as_rule() {
                         Rule::xo_range => {
                             ControlFlowRepeatSourceDefinition {
                                 range_expression_paxel: Some(inner_source_token),
                                 vtable_id: None, //This will be written back to this data structure later, during expression compilation
                                 symbolic_binding: None,
+                                is_method_call: false,
                             }
                         }
+                        Rule::xo_method_call => ControlFlowRepeatSourceDefinition {
+                            range_expression_paxel: None,
+                            vtable_id: None,
+                            symbolic_binding: Some(inner_source_token),
+                            is_method_call: true,
+                        },
                         Rule::xo_symbol => {
                             inner_source_token.token_value =
                                 convert_symbolic_binding_from_paxel_to_ril(inner_source);
@@ -554,6 +846,7 @@ fn recurse_visit_tag_pairs_for_template(
                                 range_expression_paxel: None,
                                 vtable_id: None,
                                 symbolic_binding: Some(inner_source_token),
+                                is_method_call: false,
                             }
                         }
                         _ => {
@@ -598,14 +891,17 @@ fn recurse_visit_tag_pairs_for_template(
                     }
 
                     TemplateNodeDefinition {
-                        id: *&new_id,
+                        id: new_id,
                         control_flow_settings: Some(ControlFlowSettingsDefinition {
                             condition_expression_paxel: None,
                             condition_expression_vtable_id: None,
+                            else_branch_child_ids: None,
                             slot_index_expression_paxel: Some(expression_body_token),
                             slot_index_expression_vtable_id: None, //This will be written back to this data structure later, during expression compilation
                             repeat_predicate_definition: None,
                             repeat_source_definition: None,
+                            repeat_key_expression_paxel: None,
+                            repeat_key_expression_vtable_id: None,
                         }),
                         type_id: TYPE_ID_SLOT.to_string(),
                         settings: None,
@@ -633,6 +929,120 @@ fn recurse_visit_tag_pairs_for_template(
     }
 }
 
+/// Builds the `if` TemplateNodeDefinition for `id` from the first of `branches`, recursing to build
+/// any subsequent `else if` branches as a nested `if` node.  Expects the frame for this node's
+/// children to have already been pushed onto `child_id_tracking_stack`; pops it before returning.
+/// Parses the destructuring of a `for` element, like the `(name, {score, rank})` in `for ((name, {score, rank}), i) in self.rows`
+fn parse_for_pattern(statement_for_pattern: Pair<Rule>, pax: &str) -> ControlFlowRepeatPattern {
+    /* statement_for_pattern = { identifier | statement_for_tuple_pattern | statement_for_struct_pattern } */
+    let pattern = statement_for_pattern.into_inner().next().unwrap();
+    match pattern.as_rule() {
+        Rule::identifier => ControlFlowRepeatPattern::Binding(Token::new(
+            pattern.as_str().to_string(),
+            TokenType::ForPredicate,
+            span_to_location(&pattern.as_span()),
+            pax,
+        )),
+        Rule::statement_for_tuple_pattern => ControlFlowRepeatPattern::Tuple(
+            pattern
+                .into_inner()
+                .map(|element| parse_for_pattern(element, pax))
+                .collect(),
+        ),
+        Rule::statement_for_struct_pattern => ControlFlowRepeatPattern::Struct(
+            pattern
+                .into_inner()
+                .map(|field| {
+                    /* statement_for_struct_pattern_field = { identifier ~ (":" ~ statement_for_pattern)? } */
+                    let mut field = field.into_inner();
+                    let name = field.next().unwrap();
+                    let name_token = Token::new(
+                        name.as_str().to_string(),
+                        TokenType::ForPredicate,
+                        span_to_location(&name.as_span()),
+                        pax,
+                    );
+                    let field_pattern = match field.next() {
+                        Some(field_pattern) => parse_for_pattern(field_pattern, pax),
+                        None => ControlFlowRepeatPattern::Binding(name_token.clone()),
+                    };
+                    (name_token, field_pattern)
+                })
+                .collect(),
+        ),
+        _ => unreachable!("Parsing error: {:?}", pattern.as_rule()),
+    }
+}
+
+fn visit_conditional_branches(
+    ctx: &mut TemplateNodeParseContext,
+    id: usize,
+    mut branches: Vec<(Pair<Rule>, Pair<Rule>)>,
+    else_inner_nodes: Option<Pair<Rule>>,
+    pax: &str,
+) -> TemplateNodeDefinition {
+    let (expression_body, inner_nodes) = branches.remove(0);
+    let expression_body_location = span_to_location(&expression_body.as_span());
+    let expression_body_token = Token::new(
+        expression_body.as_str().to_string(),
+        TokenType::IfExpression,
+        expression_body_location,
+        pax,
+    );
+
+    inner_nodes.into_inner().for_each(|sub_tag_pair| {
+        recurse_visit_tag_pairs_for_template(ctx, sub_tag_pair, pax);
+    });
+    let true_branch_len = ctx.child_id_tracking_stack.last().unwrap().len();
+
+    if !branches.is_empty() {
+        //`else if` — the else branch is a single nested `if`
+        let nested_id = ctx.uid_gen.next().unwrap();
+        ctx.template_node_definitions
+            .insert(nested_id, TemplateNodeDefinition::default());
+        ctx.child_id_tracking_stack
+            .last_mut()
+            .unwrap()
+            .push(nested_id);
+
+        ctx.child_id_tracking_stack.push(vec![]);
+        let mut nested_template_node =
+            visit_conditional_branches(ctx, nested_id, branches, else_inner_nodes, pax);
+        std::mem::swap(
+            ctx.template_node_definitions.get_mut(nested_id).unwrap(),
+            &mut nested_template_node,
+        );
+    } else if let Some(else_inner_nodes) = else_inner_nodes {
+        else_inner_nodes.into_inner().for_each(|sub_tag_pair| {
+            recurse_visit_tag_pairs_for_template(ctx, sub_tag_pair, pax);
+        });
+    }
+
+    let child_ids = ctx.child_id_tracking_stack.pop().unwrap();
+    let else_branch_child_ids = child_ids[true_branch_len..].to_vec();
+
+    //`if` TemplateNodeDefinition
+    TemplateNodeDefinition {
+        id,
+        control_flow_settings: Some(ControlFlowSettingsDefinition {
+            condition_expression_paxel: Some(expression_body_token),
+            condition_expression_vtable_id: None, //This will be written back to this data structure later, during expression compilation
+            else_branch_child_ids: (!else_branch_child_ids.is_empty())
+                .then_some(else_branch_child_ids),
+            slot_index_expression_paxel: None,
+            slot_index_expression_vtable_id: None,
+            repeat_predicate_definition: None,
+            repeat_source_definition: None,
+            repeat_key_expression_paxel: None,
+            repeat_key_expression_vtable_id: None,
+        }),
+        type_id: TYPE_ID_IF.to_string(),
+        settings: None,
+        child_ids,
+        pascal_identifier: "Conditional".to_string(),
+    }
+}
+
 fn parse_inline_attribute_from_final_pairs_of_tag(
     final_pairs_of_tag: Pairs<Rule>,
     pax: &str,
@@ -742,7 +1152,7 @@ fn parse_inline_attribute_from_final_pairs_of_tag(
         })
         .collect();
 
-    if vec.len() > 0 {
+    if !vec.is_empty() {
         Some(vec)
     } else {
         None
@@ -830,7 +1240,7 @@ fn parse_settings_from_component_definition_string(
     pax: &str,
 ) -> Option<Vec<SettingsSelectorBlockDefinition>> {
     let pax_component_definition = PaxParser::parse(Rule::pax_component_definition, pax)
-        .expect(&format!("unsuccessful parse from {}", &pax)) // unwrap the parse result
+        .unwrap_or_else(|_| panic!("unsuccessful parse from {}", &pax)) // unwrap the parse result
         .next()
         .unwrap(); // get and unwrap the `pax_component_definition` rule
 
@@ -838,52 +1248,199 @@ fn parse_settings_from_component_definition_string(
 
     pax_component_definition
         .into_inner()
+        .filter(|top_level_pair| top_level_pair.as_rule() == Rule::settings_block_declaration)
         .for_each(|top_level_pair| {
-            match top_level_pair.as_rule() {
-                Rule::settings_block_declaration => {
-                    let selector_block_definitions: Vec<SettingsSelectorBlockDefinition> =
-                        top_level_pair
-                            .into_inner()
-                            .map(|selector_block| {
-                                //selector_block => settings_key_value_pair where v is a ValueDefinition
-                                let mut selector_block_pairs = selector_block.into_inner();
-                                //first pair is the selector itself
-                                let raw_selector = selector_block_pairs.next().unwrap();
-                                let raw_value_location = span_to_location(&raw_selector.as_span());
-                                let selector: String = raw_selector
-                                    .as_str()
-                                    .chars()
-                                    .filter(|c| !c.is_whitespace())
-                                    .collect();
-                                let token = Token::new(
-                                    selector,
-                                    TokenType::Selector,
-                                    raw_value_location,
-                                    pax,
-                                );
-                                let literal_object = selector_block_pairs.next().unwrap();
+            let selector_block_definitions: Vec<SettingsSelectorBlockDefinition> = top_level_pair
+                .into_inner()
+                .map(|selector_block| {
+                    //selector_block => settings_key_value_pair where v is a ValueDefinition
+                    let mut selector_block_pairs = selector_block.into_inner();
+                    //first pair is the selector itself
+                    let raw_selector = selector_block_pairs.next().unwrap();
+                    let raw_value_location = span_to_location(&raw_selector.as_span());
+                    let selector: String = raw_selector
+                        .as_str()
+                        .chars()
+                        .filter(|c| !c.is_whitespace())
+                        .collect();
+                    let token = Token::new(selector, TokenType::Selector, raw_value_location, pax);
+                    let literal_object = selector_block_pairs.next().unwrap();
+
+                    SettingsSelectorBlockDefinition {
+                        selector: token,
+                        value_block: derive_value_definition_from_literal_object_pair(
+                            literal_object,
+                            pax,
+                        ),
+                    }
+                })
+                .collect();
+
+            ret.extend(selector_block_definitions);
+        });
+    Some(ret)
+}
+
+/// Easing curves that may be referenced by name from a `@timeline` keyframe, e.g. `ease OutBack`.
+/// Must be kept in sync with `pax_runtime_api::EasingCurve`, as checked by `test_timeline_easing_curves_match_easing_curve`.
+pub const TIMELINE_EASING_CURVES: [&str; 6] = [
+    "Linear",
+    "InQuad",
+    "OutQuad",
+    "InBack",
+    "OutBack",
+    "InOutBack",
+];
+
+fn derive_keyframe_definition_from_timeline_keyframe_pair(
+    timeline_keyframe: Pair<Rule>,
+    pax: &str,
+) -> KeyframeDefinition {
+    let mut pairs = timeline_keyframe.into_inner();
+    //frames too large for a `usize` are reported, with their locations, when the timeline is compiled
+    let raw_frame = pairs.next().unwrap();
+    let location_info = span_to_location(&raw_frame.as_span());
+    let frame = Token::new(
+        raw_frame
+            .as_str()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>(),
+        TokenType::KeyframeFrame,
+        location_info,
+        pax,
+    );
+
+    let mut name = None;
+    let mut value = None;
+    let mut curve = None;
+    pairs.for_each(|pair| match pair.as_rule() {
+        Rule::timeline_keyframe_name => {
+            let raw_name = pair.into_inner().next().unwrap();
+            let location_info = span_to_location(&raw_name.as_span());
+            name = Some(Token::new(
+                raw_name.as_str().to_string(),
+                TokenType::KeyframeName,
+                location_info,
+                pax,
+            ));
+        }
+        Rule::literal_value => {
+            //pratt-parse literals as in `@settings`, to unpack `px`, `%`, `deg`, etc.
+            let (output_string, _) = crate::parsing::run_pratt_parser(pair.as_str());
+            let location_info = span_to_location(&pair.as_span());
+            value = Some(Token::new_with_raw_value(
+                output_string,
+                pair.as_str().to_string(),
+                TokenType::LiteralValue,
+                location_info,
+                pax,
+            ));
+        }
+        Rule::timeline_keyframe_curve => {
+            //unknown curves are reported, with their locations, when the timeline is compiled
+            let raw_curve = pair.into_inner().next().unwrap();
+            let location_info = span_to_location(&raw_curve.as_span());
+            curve = Some(Token::new(
+                raw_curve.as_str().to_string(),
+                TokenType::EasingCurve,
+                location_info,
+                pax,
+            ));
+        }
+        _ => {
+            unreachable!("Parsing error: {:?}", pair.as_rule());
+        }
+    });
+
+    KeyframeDefinition {
+        frame,
+        name,
+        value: value.unwrap(),
+        curve,
+    }
+}
+
+/// Parses a component's `@timeline` block into selector blocks, each binding one or more
+/// properties to a list of keyframes, e.g. `#logo { rotate: [0: 0deg, 60: 360deg ease OutBack] }`
+fn parse_timeline_from_component_definition_string(
+    pax: &str,
+) -> Option<Vec<SettingsSelectorBlockDefinition>> {
+    let pax_component_definition = PaxParser::parse(Rule::pax_component_definition, pax)
+        .unwrap_or_else(|_| panic!("unsuccessful parse from {}", &pax)) // unwrap the parse result
+        .next()
+        .unwrap(); // get and unwrap the `pax_component_definition` rule
 
-                                SettingsSelectorBlockDefinition {
-                                    selector: token,
-                                    value_block: derive_value_definition_from_literal_object_pair(
-                                        literal_object,
+    let mut ret: Vec<SettingsSelectorBlockDefinition> = vec![];
+
+    pax_component_definition
+        .into_inner()
+        .filter(|top_level_pair| top_level_pair.as_rule() == Rule::timeline_block_declaration)
+        .for_each(|top_level_pair| {
+            let selector_block_definitions: Vec<SettingsSelectorBlockDefinition> = top_level_pair
+                .into_inner()
+                .map(|timeline_selector_block| {
+                    let mut selector_block_pairs = timeline_selector_block.into_inner();
+                    //first pair is the selector itself
+                    let raw_selector = selector_block_pairs.next().unwrap();
+                    let raw_value_location = span_to_location(&raw_selector.as_span());
+                    let selector: String = raw_selector
+                        .as_str()
+                        .chars()
+                        .filter(|c| !c.is_whitespace())
+                        .collect();
+                    let token = Token::new(selector, TokenType::Selector, raw_value_location, pax);
+
+                    //remaining pairs are `property: [keyframes]`
+                    let settings_key_value_pairs = selector_block_pairs
+                        .map(|timeline_key_value_pair| {
+                            let mut pairs = timeline_key_value_pair.into_inner();
+                            let setting_key = pairs.next().unwrap().into_inner().next().unwrap();
+                            let setting_key_location = span_to_location(&setting_key.as_span());
+                            let setting_key_token = Token::new(
+                                setting_key.as_str().to_string(),
+                                TokenType::SettingKey,
+                                setting_key_location,
+                                pax,
+                            );
+                            let keyframes = pairs
+                                .next()
+                                .unwrap()
+                                .into_inner()
+                                .map(|timeline_keyframe| {
+                                    derive_keyframe_definition_from_timeline_keyframe_pair(
+                                        timeline_keyframe,
                                         pax,
-                                    ),
-                                }
-                            })
-                            .collect();
+                                    )
+                                })
+                                .collect();
+                            (setting_key_token, ValueDefinition::Timeline(keyframes))
+                        })
+                        .collect();
+
+                    SettingsSelectorBlockDefinition {
+                        selector: token,
+                        value_block: LiteralBlockDefinition {
+                            explicit_type_pascal_identifier: None,
+                            settings_key_value_pairs,
+                        },
+                    }
+                })
+                .collect();
 
-                    ret.extend(selector_block_definitions);
-                }
-                _ => {}
-            }
+            ret.extend(selector_block_definitions);
         });
     Some(ret)
 }
 
+/// The property observed by an `on_change(property)` key in a `@handlers` block, if `event_id` is one
+pub fn get_observed_property(event_id: &str) -> Option<&str> {
+    event_id.strip_prefix("on_change(")?.strip_suffix(')')
+}
+
 fn parse_events_from_component_definition_string(pax: &str) -> Option<Vec<EventDefinition>> {
     let pax_component_definition = PaxParser::parse(Rule::pax_component_definition, pax)
-        .expect(&format!("unsuccessful parse from {}", &pax)) // unwrap the parse result
+        .unwrap_or_else(|_| panic!("unsuccessful parse from {}", &pax)) // unwrap the parse result
         .next()
         .unwrap(); // get and unwrap the `pax_component_definition` rule
 
@@ -891,19 +1448,38 @@ fn parse_events_from_component_definition_string(pax: &str) -> Option<Vec<EventD
 
     pax_component_definition
         .into_inner()
-        .for_each(|top_level_pair| match top_level_pair.as_rule() {
-            Rule::handlers_block_declaration => {
-                let event_definitions: Vec<EventDefinition> = top_level_pair
-                    .into_inner()
-                    .map(|handlers_key_value_pair| {
-                        let mut pairs = handlers_key_value_pair.into_inner();
-                        let key = pairs.next().unwrap().into_inner().next().unwrap();
-                        let key_value = key.as_str().to_string();
-                        let key_location = span_to_location(&key.as_span());
-                        let raw_values = pairs.next().unwrap().into_inner().next().unwrap();
-                        let value = match raw_values.as_rule() {
-                            Rule::literal_function => {
-                                let raw_value = raw_values.into_inner().next().unwrap();
+        .filter(|top_level_pair| top_level_pair.as_rule() == Rule::handlers_block_declaration)
+        .for_each(|top_level_pair| {
+            let event_definitions: Vec<EventDefinition> = top_level_pair
+                .into_inner()
+                .map(|handlers_key_value_pair| {
+                    let mut pairs = handlers_key_value_pair.into_inner();
+                    let key = pairs.next().unwrap().into_inner().next().unwrap();
+                    let key_value = match key.as_rule() {
+                        Rule::handlers_key_on_change => format!(
+                            "on_change({})",
+                            key.clone().into_inner().next().unwrap().as_str()
+                        ),
+                        _ => key.as_str().to_string(),
+                    };
+                    let key_location = span_to_location(&key.as_span());
+                    let raw_values = pairs.next().unwrap().into_inner().next().unwrap();
+                    let value = match raw_values.as_rule() {
+                        Rule::literal_function => {
+                            let raw_value = raw_values.into_inner().next().unwrap();
+                            let raw_value_location = span_to_location(&raw_value.as_span());
+                            let token = Token::new(
+                                raw_value.as_str().to_string(),
+                                TokenType::Handler,
+                                raw_value_location,
+                                pax,
+                            );
+                            vec![token]
+                        }
+                        Rule::function_list => raw_values
+                            .into_inner()
+                            .map(|literal_function| {
+                                let raw_value = literal_function.into_inner().next().unwrap();
                                 let raw_value_location = span_to_location(&raw_value.as_span());
                                 let token = Token::new(
                                     raw_value.as_str().to_string(),
@@ -911,36 +1487,21 @@ fn parse_events_from_component_definition_string(pax: &str) -> Option<Vec<EventD
                                     raw_value_location,
                                     pax,
                                 );
-                                vec![token]
-                            }
-                            Rule::function_list => raw_values
-                                .into_inner()
-                                .map(|literal_function| {
-                                    let raw_value = literal_function.into_inner().next().unwrap();
-                                    let raw_value_location = span_to_location(&raw_value.as_span());
-                                    let token = Token::new(
-                                        raw_value.as_str().to_string(),
-                                        TokenType::Handler,
-                                        raw_value_location,
-                                        pax,
-                                    );
-                                    token
-                                })
-                                .collect(),
-                            _ => {
-                                unreachable!("Parsing error: {:?}", raw_values.as_rule());
-                            }
-                        };
-                        EventDefinition {
-                            key: Token::new(key_value, TokenType::EventId, key_location, pax),
-                            value,
+                                token
+                            })
+                            .collect(),
+                        _ => {
+                            unreachable!("Parsing error: {:?}", raw_values.as_rule());
                         }
-                    })
-                    .collect();
+                    };
+                    EventDefinition {
+                        key: Token::new(key_value, TokenType::EventId, key_location, pax),
+                        value,
+                    }
+                })
+                .collect();
 
-                ret.extend(event_definitions);
-            }
-            _ => {}
+            ret.extend(event_definitions);
         });
     Some(ret)
 }
@@ -1056,7 +1617,7 @@ pub fn assemble_component_definition(
     self_type_id: &str,
 ) -> (ParsingContext, ComponentDefinition) {
     let _ast = PaxParser::parse(Rule::pax_component_definition, pax)
-        .expect(&format!("unsuccessful parse from {}", &pax)) // unwrap the parse result
+        .unwrap_or_else(|_| panic!("unsuccessful parse from {}", &pax)) // unwrap the parse result
         .next()
         .unwrap(); // get and unwrap the `pax_component_definition` rule
 
@@ -1117,6 +1678,7 @@ pub fn assemble_component_definition(
         template: Some(tpc.template_node_definitions),
         settings: parse_settings_from_component_definition_string(pax),
         events: parse_events_from_component_definition_string(pax),
+        timeline: parse_timeline_from_component_definition_string(pax),
         module_path: modified_module_path,
     };
 
@@ -1151,6 +1713,7 @@ pub fn assemble_struct_only_component_definition(
         template: None,
         settings: None,
         events: None,
+        timeline: None,
     };
 
     (ctx, new_def)
@@ -1176,6 +1739,7 @@ pub fn assemble_primitive_definition(
         settings: None,
         module_path: modified_module_path,
         events: None,
+        timeline: None,
     }
 }
 
@@ -1204,19 +1768,19 @@ pub fn assemble_type_definition(
 
 pub fn escape_identifier(input: String) -> String {
     input
-        .replace("(", "LPAR")
+        .replace('(', "LPAR")
         .replace("::", "COCO")
-        .replace(")", "RPAR")
-        .replace("<", "LABR")
-        .replace(">", "RABR")
-        .replace(",", "COMM")
-        .replace(".", "PERI")
-        .replace("[", "LSQB")
-        .replace("]", "RSQB")
-        .replace("/", "FSLA")
-        .replace("\\", "BSLA")
-        .replace("#", "HASH")
-        .replace("-", "HYPH")
+        .replace(')', "RPAR")
+        .replace('<', "LABR")
+        .replace('>', "RABR")
+        .replace(',', "COMM")
+        .replace('.', "PERI")
+        .replace('[', "LSQB")
+        .replace(']', "RSQB")
+        .replace('/', "FSLA")
+        .replace('\\', "BSLA")
+        .replace('#', "HASH")
+        .replace('-', "HYPH")
 }
 
 /// Given a Pest Span returns starting and ending (line,col)
@@ -1251,9 +1815,7 @@ pub trait Reflectable {
             import_path: type_id.to_string(),
         };
 
-        if !ctx.type_table.contains_key(&type_id) {
-            ctx.type_table.insert(type_id, td);
-        }
+        ctx.type_table.entry(type_id).or_insert(td);
 
         (ctx, vec![])
     }
@@ -1376,9 +1938,7 @@ impl<T: Reflectable> Reflectable for std::option::Option<T> {
             import_path: type_id.to_string(),
         };
 
-        if !ctx.type_table.contains_key(&type_id) {
-            ctx.type_table.insert(type_id, td);
-        }
+        ctx.type_table.entry(type_id).or_insert(td);
 
         let (ctx, _) = T::parse_to_manifest(ctx);
         (ctx, vec![]) //Option itself has no PAXEL-addressable properties
@@ -1391,7 +1951,7 @@ impl<T: Reflectable> Reflectable for std::option::Option<T> {
     }
 
     fn get_type_id() -> String {
-        format!("std::option::Option<{}{}>", "{PREFIX}", &T::get_type_id())
+        format!("std::option::Option<{}>", prefix_type_id(T::get_type_id()))
     }
 }
 
@@ -1455,6 +2015,16 @@ impl Reflectable for pax_runtime_api::Transform2D {
     }
 }
 
+impl Reflectable for pax_runtime_api::BlendMode {
+    fn get_import_path() -> String {
+        "pax_lang::api::BlendMode".to_string()
+    }
+
+    fn get_self_pascal_identifier() -> String {
+        "BlendMode".to_string()
+    }
+}
+
 impl Reflectable for pax_runtime_api::StringBox {
     fn get_import_path() -> String {
         "pax_lang::api::StringBox".to_string()
@@ -1475,9 +2045,7 @@ impl<T: Reflectable> Reflectable for std::vec::Vec<T> {
             property_definitions: vec![],
         };
 
-        if !ctx.type_table.contains_key(&type_id) {
-            ctx.type_table.insert(type_id, td);
-        }
+        ctx.type_table.entry(type_id).or_insert(td);
 
         // Also parse iterable type
         T::parse_to_manifest(ctx)
@@ -1491,12 +2059,393 @@ impl<T: Reflectable> Reflectable for std::vec::Vec<T> {
     fn get_type_id() -> String {
         //Need to encode generics contents as part of unique id for iterables
         format!(
-            "std::vec::Vec<{}{}>",
-            "{PREFIX}",
-            &Self::get_iterable_type_id().unwrap()
+            "std::vec::Vec<{}>",
+            prefix_type_id(Self::get_iterable_type_id().unwrap())
         )
     }
     fn get_iterable_type_id() -> Option<String> {
         Some(T::get_type_id())
     }
 }
+
+/// Prefixes a type_id nested within another (e.g. the `T` in `Vec<T>`) with `{PREFIX}`, to be replaced with
+/// the host crate's import prefix.  Tuples are left as they are, since each of their elements is already prefixed.
+fn prefix_type_id(type_id: String) -> String {
+    if type_id.starts_with('(') {
+        type_id
+    } else {
+        format!("{{PREFIX}}{}", type_id)
+    }
+}
+
+/// Tuples expose their elements as properties named by index, so that they may be accessed
+/// like `self.position.0` and destructured like `for ((x, y), i) in self.positions`
+macro_rules! impl_reflectable_for_tuple {
+    ($($element:ident $index:tt),+) => {
+        impl<$($element: Reflectable),+> Reflectable for ($($element,)+) {
+            fn parse_to_manifest(mut ctx: ParsingContext) -> (ParsingContext, Vec<PropertyDefinition>) {
+                let type_id = Self::get_type_id();
+                let property_definitions = vec![$(
+                    PropertyDefinition::primitive_with_name(&$element::get_type_id(), stringify!($index)),
+                )+];
+                let td = TypeDefinition {
+                    type_id: type_id.to_string(),
+                    type_id_escaped: escape_identifier(type_id.to_string()),
+                    inner_iterable_type_id: None,
+                    property_definitions: property_definitions.clone(),
+                    import_path: type_id.to_string(),
+                };
+
+                if !ctx.type_table.contains_key(&type_id) {
+                    ctx.type_table.insert(type_id, td);
+                }
+
+                $(let (ctx_with_element, _) = $element::parse_to_manifest(ctx);
+                ctx = ctx_with_element;)+
+                (ctx, property_definitions)
+            }
+            fn get_self_pascal_identifier() -> String {
+                format!("({})", vec![$($element::get_self_pascal_identifier()),+].join(","))
+            }
+            fn get_type_id() -> String {
+                format!("({})", vec![$(prefix_type_id($element::get_type_id())),+].join(","))
+            }
+        }
+    };
+}
+
+impl_reflectable_for_tuple!(A 0, B 1);
+impl_reflectable_for_tuple!(A 0, B 1, C 2);
+impl_reflectable_for_tuple!(A 0, B 1, C 2, D 3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeline_block() {
+        let pax = r#"<Image id=logo />
+@timeline {
+    #logo {
+        rotate: [0: 0deg, 60 as spun: 360deg ease OutBack],
+        x: [0: 10px, 30: 50%,]
+    }
+}"#;
+        let timeline = parse_timeline_from_component_definition_string(pax).unwrap();
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].selector.token_value, "#logo");
+
+        let pairs = &timeline[0].value_block.settings_key_value_pairs;
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].0.token_value, "rotate");
+        if let ValueDefinition::Timeline(keyframes) = &pairs[0].1 {
+            assert_eq!(keyframes.len(), 2);
+            assert_eq!(keyframes[1].get_frame(), Some(60));
+            assert_eq!(keyframes[1].name.as_ref().unwrap().token_value, "spun");
+            assert_eq!(keyframes[1].curve.as_ref().unwrap().token_value, "OutBack");
+            assert!(keyframes[0].curve.is_none());
+        } else {
+            panic!("Expected keyframes for `rotate`");
+        }
+    }
+
+    #[test]
+    fn test_parse_timeline_block_leaves_validation_to_compilation() {
+        //empty keyframe lists, out-of-range frames and unknown curves are reported as template errors once
+        //compiled, with their locations
+        let pax = r#"<Image id=logo />
+@timeline {
+    #logo {
+        rotate: [],
+        x: [0: 10px ease Bouncy],
+        y: [99999999999999999999999: 10px]
+    }
+}"#;
+        let timeline = parse_timeline_from_component_definition_string(pax).unwrap();
+        let pairs = &timeline[0].value_block.settings_key_value_pairs;
+        assert!(
+            matches!(&pairs[0].1, ValueDefinition::Timeline(keyframes) if keyframes.is_empty())
+        );
+        if let ValueDefinition::Timeline(keyframes) = &pairs[1].1 {
+            let curve = keyframes[0].curve.as_ref().unwrap();
+            assert_eq!(curve.token_value, "Bouncy");
+            assert!(curve.token_location.is_some());
+        } else {
+            panic!("Expected keyframes for `x`");
+        }
+        if let ValueDefinition::Timeline(keyframes) = &pairs[2].1 {
+            assert_eq!(keyframes[0].get_frame(), None);
+            assert!(keyframes[0].frame.token_location.is_some());
+        } else {
+            panic!("Expected keyframes for `y`");
+        }
+    }
+
+    #[test]
+    fn test_timeline_easing_curves_match_easing_curve() {
+        use pax_runtime_api::EasingCurve;
+        //exhaustive, so that a new `EasingCurve` variant fails to compile until it is named here
+        fn timeline_name(curve: &EasingCurve) -> Option<&'static str> {
+            match curve {
+                EasingCurve::Linear => Some("Linear"),
+                EasingCurve::InQuad => Some("InQuad"),
+                EasingCurve::OutQuad => Some("OutQuad"),
+                EasingCurve::InBack => Some("InBack"),
+                EasingCurve::OutBack => Some("OutBack"),
+                EasingCurve::InOutBack => Some("InOutBack"),
+                EasingCurve::Custom(_) => None,
+            }
+        }
+        let curves = [
+            EasingCurve::Linear,
+            EasingCurve::InQuad,
+            EasingCurve::OutQuad,
+            EasingCurve::InBack,
+            EasingCurve::OutBack,
+            EasingCurve::InOutBack,
+            EasingCurve::Custom(Box::new(|t| t)),
+        ];
+        let names = curves.iter().filter_map(timeline_name).collect::<Vec<_>>();
+        assert_eq!(names, TIMELINE_EASING_CURVES);
+    }
+
+    #[test]
+    fn test_parse_lifecycle_handlers() {
+        let pax = r#"<Rectangle />
+@handlers {
+    will_unmount: self.handle_will_unmount,
+    did_update: [self.handle_did_update, self.log_update],
+    on_change( count ): self.handle_count_change,
+}"#;
+        let events = parse_events_from_component_definition_string(pax).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].key.token_value, "will_unmount");
+        assert_eq!(events[1].value.len(), 2);
+        assert_eq!(events[2].key.token_value, "on_change(count)");
+        assert_eq!(events[2].value[0].token_value, "handle_count_change");
+        assert_eq!(
+            get_observed_property(&events[2].key.token_value),
+            Some("count")
+        );
+        assert_eq!(get_observed_property(&events[1].key.token_value), None);
+    }
+
+    #[test]
+    fn test_parse_if_else_if_else() {
+        let pax = r#"if self.a {
+    <Rectangle />
+} else if self.b {
+    <Ellipse />
+} else {
+    <Rectangle />
+    <Ellipse />
+}"#;
+        let mut ctx = TemplateNodeParseContext {
+            pascal_identifier_to_type_id_map: HashMap::from([
+                ("Rectangle".to_string(), "Rectangle".to_string()),
+                ("Ellipse".to_string(), "Ellipse".to_string()),
+            ]),
+            template_node_definitions: vec![],
+            child_id_tracking_stack: vec![],
+            uid_gen: (1..).multipeek(),
+        };
+        parse_template_from_component_definition_string(&mut ctx, pax);
+        let tnds = ctx.template_node_definitions;
+
+        //outer `if`: true branch is the Rectangle; else branch is a single nested `if`
+        let outer = &tnds[1];
+        assert_eq!(outer.type_id, TYPE_ID_IF);
+        assert_eq!(outer.child_ids, vec![2, 3]);
+        let outer_cfs = outer.control_flow_settings.as_ref().unwrap();
+        assert_eq!(outer_cfs.else_branch_child_ids, Some(vec![3]));
+
+        //nested `if` (from `else if`): true branch is the Ellipse; else branch holds both `else` nodes
+        let nested = &tnds[3];
+        assert_eq!(nested.type_id, TYPE_ID_IF);
+        let nested_cfs = nested.control_flow_settings.as_ref().unwrap();
+        assert_eq!(
+            nested_cfs
+                .condition_expression_paxel
+                .as_ref()
+                .unwrap()
+                .token_value
+                .trim(),
+            "self.b"
+        );
+        assert_eq!(nested.child_ids, vec![4, 5, 6]);
+        assert_eq!(nested_cfs.else_branch_child_ids, Some(vec![5, 6]));
+    }
+
+    #[test]
+    fn test_parse_keyed_for() {
+        let pax = r#"for item in self.items key item.id {
+    <Rectangle />
+}"#;
+        let mut ctx = TemplateNodeParseContext {
+            pascal_identifier_to_type_id_map: HashMap::from([(
+                "Rectangle".to_string(),
+                "Rectangle".to_string(),
+            )]),
+            template_node_definitions: vec![],
+            child_id_tracking_stack: vec![],
+            uid_gen: (1..).multipeek(),
+        };
+        parse_template_from_component_definition_string(&mut ctx, pax);
+
+        let repeat = &ctx.template_node_definitions[1];
+        assert_eq!(repeat.type_id, TYPE_ID_REPEAT);
+        assert_eq!(repeat.child_ids, vec![2]);
+        let cfs = repeat.control_flow_settings.as_ref().unwrap();
+        assert_eq!(
+            cfs.repeat_key_expression_paxel
+                .as_ref()
+                .unwrap()
+                .token_value
+                .trim(),
+            "item.id"
+        );
+    }
+
+    #[test]
+    fn test_parse_destructuring_for() {
+        let pax = r#"for ((name, {score, position: (x, y)}), i) in self.rows {
+    <Rectangle />
+}
+for (a, b, c) in self.triples {
+    <Rectangle />
+}"#;
+        let mut ctx = TemplateNodeParseContext {
+            pascal_identifier_to_type_id_map: HashMap::from([(
+                "Rectangle".to_string(),
+                "Rectangle".to_string(),
+            )]),
+            template_node_definitions: vec![],
+            child_id_tracking_stack: vec![],
+            uid_gen: (1..).multipeek(),
+        };
+        parse_template_from_component_definition_string(&mut ctx, pax);
+
+        let get_predicate = |id: usize| {
+            ctx.template_node_definitions[id]
+                .control_flow_settings
+                .as_ref()
+                .unwrap()
+                .repeat_predicate_definition
+                .clone()
+                .unwrap()
+        };
+        let get_bindings = |pattern: &ControlFlowRepeatPattern| {
+            pattern
+                .get_bindings()
+                .into_iter()
+                .map(|(token, path)| (token.token_value, path.join(".")))
+                .collect::<Vec<_>>()
+        };
+
+        match get_predicate(1) {
+            ControlFlowRepeatPredicateDefinition::ElemPatternIndexId(pattern, index) => {
+                assert_eq!(index.token_value, "i");
+                assert_eq!(
+                    get_bindings(&pattern),
+                    vec![
+                        ("name".to_string(), "0".to_string()),
+                        ("score".to_string(), "1.score".to_string()),
+                        ("x".to_string(), "1.position.0".to_string()),
+                        ("y".to_string(), "1.position.1".to_string()),
+                    ]
+                );
+            }
+            _ => panic!("expected a destructured element and index"),
+        }
+        match get_predicate(3) {
+            ControlFlowRepeatPredicateDefinition::ElemPattern(pattern) => {
+                assert_eq!(get_bindings(&pattern).len(), 3);
+            }
+            _ => panic!("expected a destructured element"),
+        }
+    }
+
+    #[test]
+    fn test_compile_n_ary_tuples() {
+        let (output, symbolic_ids) = run_pratt_parser("(1, 10px, self.position.1)");
+        assert_eq!(
+            output,
+            "(Numeric::from(1),Size::Pixels(10.into()),positionPERI1)"
+        );
+        assert_eq!(symbolic_ids, vec!["self.position.1".to_string()]);
+    }
+
+    #[test]
+    fn test_compile_ranges() {
+        let (output, _) = run_pratt_parser("0..=5");
+        assert_eq!(output, "RepeatRange::new(0,5,true,None)");
+
+        let (output, symbolic_ids) = run_pratt_parser("self.count..0 step 5");
+        assert_eq!(
+            output,
+            "RepeatRange::new(count.get_as_int(),0,false,Some(5))"
+        );
+        assert_eq!(symbolic_ids, vec!["self.count".to_string()]);
+    }
+
+    #[test]
+    fn test_compile_method_calls() {
+        let (output, symbolic_ids) = run_pratt_parser("self.name.trim().to_uppercase()");
+        assert_eq!(
+            output,
+            "paxel_std::to_uppercase(&(paxel_std::trim(&(name))))"
+        );
+        assert_eq!(symbolic_ids, vec!["self.name".to_string()]);
+
+        let paxel = "self.items.filter(|x| x.visible && x.score > self.threshold).len()";
+        let (output, symbolic_ids) = run_pratt_parser(paxel);
+        assert_eq!(
+            output,
+            "paxel_std::len(&(paxel_std::filter(&(items),|x| { /*lambda@18*/ (xPERIvisible&&(xPERIscore>threshold)) })))"
+        );
+        assert_eq!(
+            symbolic_ids,
+            vec!["self.items".to_string(), "self.threshold".to_string()]
+        );
+
+        let chains = collect_paxel_method_calls(paxel);
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].receiver, "self.items");
+        assert_eq!(
+            chains[0]
+                .calls
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["filter", "len"]
+        );
+        assert_eq!(
+            chains[0].calls[0].args,
+            vec![PaxelMethodCallArg::Lambda(PaxelLambda {
+                offset: 18,
+                params: vec!["x".to_string()],
+                symbols: vec!["x.visible".to_string(), "x.score".to_string()],
+            })]
+        );
+    }
+
+    #[test]
+    fn test_compile_svg_paths() {
+        let (output, symbolic_ids) =
+            run_pratt_parser("Path::svg(\"M0 0 L10 10.5 Q 20 0 30 10 Z\")");
+        assert_eq!(
+            output,
+            "{ let path = Path::start(); let path = Path::move_to(path, (0.0, 0.0)); \
+             let path = Path::line(path, (10.0, 10.5)); \
+             let path = Path::quad_to(path, (20.0, 0.0), (30.0, 10.0)); \
+             let path = Path::close(path); path }"
+        );
+        assert!(symbolic_ids.is_empty());
+
+        //only literal path data is parsed at compile time
+        let (output, _) = run_pratt_parser("Path::svg(self.data)");
+        assert_eq!(output, "Path::svg((data),)");
+
+        assert!(compile_svg_path_data("M0 0 X10 10").is_err());
+    }
+}
@@ -3,10 +3,7 @@ use include_dir::{include_dir, Dir};
 use serde_derive::{Deserialize, Serialize};
 #[allow(unused_imports)]
 use serde_json;
-use std::{
-    collections::HashMap,
-    hash::{Hash, Hasher},
-};
+use std::hash::{Hash, Hasher};
 use tera::{Context, Tera};
 
 use crate::manifest::{ExpressionSpec, PropertyDefinition};
@@ -39,6 +36,96 @@ pub struct TemplateArgsCodegenCartridgeLib {
 
     /// List of component factory definitions, as pre-assembled literal Strings.
     pub component_factories_literal: Vec<String>,
+
+    /// Components that may be mounted as a root by type id, e.g. by headless snapshot tests
+    pub mountable_components: Vec<TemplateArgsCodegenCartridgeMountableComponent>,
+
+    /// Whether expressions are evaluated by the runtime interpreter rather than compiled to RIL, where possible
+    pub interpret_expressions: bool,
+
+    /// For interpreted expressions: the properties of each PropertiesCoproduct variant, addressable by name
+    pub reflected_properties_coproduct_variants: Vec<TemplateArgsCodegenCartridgeReflectedType>,
+
+    /// For interpreted expressions: the fields of each struct type, addressable by name
+    pub reflected_types: Vec<TemplateArgsCodegenCartridgeReflectedType>,
+
+    /// For interpreted expressions: every function call or enum literal found in an interpreted expression
+    pub paxel_functions: Vec<TemplateArgsCodegenCartridgePaxelFunction>,
+
+    /// For interpreted expressions: the (PropertiesCoproduct variant, type) of each `T` in a `Vec<T>` property, i.e. of
+    /// each possible element of a `for` source or collection on which methods like `filter` may be called
+    pub iterable_types: Vec<(String, String)>,
+
+    /// Whether templates may be hot reloaded by `pax run --hot-reload`; implies `interpret_expressions`
+    pub hot_reload: bool,
+
+    /// For hot reloading: every TypesCoproduct variant, into which interpreted values may be converted
+    pub types_coproduct_variants: Vec<String>,
+
+    /// For hot reloading: the components that may be instantiated in a reloaded template
+    pub hot_reload_components: Vec<TemplateArgsCodegenCartridgeHotReloadComponent>,
+
+    /// For hot reloading: every handler bound when the cartridge was built
+    pub hot_reload_handlers: Vec<TemplateArgsCodegenCartridgeHotReloadHandler>,
+}
+
+#[derive(Serialize)]
+pub struct TemplateArgsCodegenCartridgeHotReloadComponent {
+    pub type_id: String,
+    pub is_primitive: bool,
+    pub snake_case_type_id: String,
+    pub primitive_instance_import_path: Option<String>,
+    pub component_properties_struct: String,
+    pub properties_coproduct_variant: String,
+    pub properties: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct TemplateArgsCodegenCartridgeHotReloadHandler {
+    /// The component whose template binds this handler, and whose method it calls
+    pub owner_type_id: String,
+    pub event: String,
+    pub method: String,
+    pub owner_properties_coproduct_variant: String,
+    pub owner_component_properties_struct: String,
+    /// For `did_update` and `on_change` handlers, the `HandlerRegistry` field they register with and the
+    /// properties they observe
+    pub property_observer: Option<TemplateArgsCodegenCartridgePropertyObserver>,
+}
+
+/// A `did_update` or `on_change` handler, which fires after a tick in which any of its observed properties changed
+#[derive(Serialize)]
+pub struct TemplateArgsCodegenCartridgePropertyObserver {
+    /// `did_update` or `on_change`, naming the `HandlerRegistry` field to register with
+    pub event: String,
+    /// Every property of the component for `did_update`; the single property named by `on_change(property)`
+    pub observed_properties: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct TemplateArgsCodegenCartridgeReflectedType {
+    /// PropertiesCoproduct variant or fully qualified type
+    pub identifier: String,
+    /// Name of each property, and whether it is wrapped in a `Property`
+    pub properties: Vec<(String, bool)>,
+}
+
+#[derive(Serialize)]
+pub struct TemplateArgsCodegenCartridgePaxelFunction {
+    /// e.g. `Color::rgb`
+    pub path: String,
+    pub arity: usize,
+    /// False for enum literals without arguments, e.g. `FontStyle::Normal`
+    pub has_args_list: bool,
+}
+
+#[derive(Serialize)]
+pub struct TemplateArgsCodegenCartridgeMountableComponent {
+    pub type_id: String,
+    pub is_main_component: bool,
+    pub snake_case_type_id: String,
+    pub component_properties_struct: String,
+    pub properties_coproduct_variant: String,
 }
 
 #[derive(Serialize)]
@@ -48,8 +135,18 @@ pub struct TemplateArgsCodegenCartridgeComponentFactory {
     pub component_properties_struct: String,
     pub properties: Vec<(PropertyDefinition, String)>, //PropertyDefinition, TypeIdPascalized
     pub events: Vec<(MappedString, Vec<MappedString>)>,
+    /// The `did_update` and `on_change` handlers declared in `@handlers`, each with the properties it observes
+    pub property_observers: Vec<(
+        TemplateArgsCodegenCartridgePropertyObserver,
+        Vec<MappedString>,
+    )>,
     pub render_nodes_literal: String,
     pub properties_coproduct_variant: String,
+    /// Whether this component declares a `@timeline`, to which its template's keyframed properties are bound
+    pub has_timeline: bool,
+    pub type_id: String,
+    /// Whether to register instances for hot reloading
+    pub hot_reload: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug)]
@@ -106,7 +203,10 @@ pub struct TemplateArgsCodegenCartridgeRenderNodeLiteral {
     pub slot_index_literal: MappedString,
     pub repeat_source_expression_literal_vec: MappedString,
     pub repeat_source_expression_literal_range: MappedString,
+    pub repeat_key_expression_literal: MappedString,
     pub conditional_boolean_expression_literal: MappedString,
+    /// For Conditional: the children rendered when `conditional_boolean_expression_literal` is false
+    pub else_children_literal: Vec<String>,
     pub pascal_identifier: String,
     pub type_id_escaped: String,
     pub events: Vec<(MappedString, MappedString)>,
@@ -124,7 +224,7 @@ pub fn press_template_codegen_properties_coproduct_lib(
         .contents_utf8()
         .unwrap();
     Tera::one_off(
-        template.into(),
+        template,
         &tera::Context::from_serialize(args).unwrap(),
         false,
     )
@@ -140,7 +240,7 @@ pub fn press_template_codegen_cartridge_lib(args: TemplateArgsCodegenCartridgeLi
         .contents_utf8()
         .unwrap();
     Tera::one_off(
-        template.into(),
+        template,
         &tera::Context::from_serialize(args).unwrap(),
         false,
     )
@@ -159,7 +259,7 @@ pub fn press_template_codegen_cartridge_component_factory(
         .contents_utf8()
         .unwrap();
     Tera::one_off(
-        template.into(),
+        template,
         &tera::Context::from_serialize(args).unwrap(),
         false,
     )
@@ -0,0 +1,729 @@
+use std::cell::RefCell;
+use std::fmt;
+
+use color_eyre::eyre;
+use pest::iterators::{Pair, Pairs};
+use pest::pratt_parser::PrattParser;
+use pest::Parser;
+
+use crate::errors::PaxTemplateError;
+use crate::expressions::{
+    clean_and_split_symbols, ExpressionCompilationContext, PaxelKind, PaxelMethodArg,
+    PaxelMethodReturn, PAXEL_STD_METHODS,
+};
+use crate::manifest::{LocationInfo, PropertyDefinition, Token, SUPPORTED_NUMERIC_PRIMITIVES};
+use crate::parsing::{
+    compile_svg_path_data, get_pratt_parser, get_string_literal_contents, get_symbol_root,
+    PaxParser, Rule, SVG_PATH_FUNCTION,
+};
+
+// Static type checking for PAXEL.  Before an expression is compiled to RIL, its type is inferred from
+// the `PropertyDefinition`s of the symbols it refers to, following the same rules for operators as the
+// runtime interpreter, and checked against the type of the property (or control-flow statement) to which
+// it's bound.  Mismatches are reported as `PaxTemplateError`s pointing at the offending part of the
+// expression, rather than as rustc errors on the generated cartridge.
+
+/// The statically inferred type of a PAXEL expression
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaxelType {
+    Numeric,
+    Bool,
+    String,
+    Size,
+    Rotation,
+    Transform2D,
+    Range,
+    Tuple(Vec<PaxelType>),
+    List(Box<PaxelType>),
+    /// Any other type, like a userland struct or enum, by name
+    Other(String),
+    /// A type that can't be inferred statically, like the return value of a userland function
+    Unknown,
+}
+
+impl PaxelType {
+    /// Describes a type from the `TypeTable`, like `f64`, `pax_lang::api::Size` or `std::vec::Vec<Foo>`
+    pub fn from_type_id(type_id: &str) -> Self {
+        if type_id.starts_with('(') {
+            return PaxelType::Unknown;
+        }
+        let type_name = get_type_name(type_id);
+        if SUPPORTED_NUMERIC_PRIMITIVES.contains(&type_name)
+            || type_name == "f32"
+            || type_name == "Numeric"
+        {
+            PaxelType::Numeric
+        } else {
+            match type_name {
+                "bool" => PaxelType::Bool,
+                "String" | "StringBox" => PaxelType::String,
+                "Size" => PaxelType::Size,
+                "Rotation" => PaxelType::Rotation,
+                "Transform2D" => PaxelType::Transform2D,
+                "RepeatRange" => PaxelType::Range,
+                _ => PaxelType::Other(type_name.to_string()),
+            }
+        }
+    }
+
+    fn is_known(&self) -> bool {
+        *self != PaxelType::Unknown
+    }
+
+    /// Whether a value of this type may be bound to a property of `expected`, either directly or
+    /// through one of the conversions that bound values undergo, like `Numeric` into `f64`
+    pub fn is_assignable_to(&self, expected: &PaxelType) -> bool {
+        match (self, expected) {
+            (PaxelType::Unknown, _) | (_, PaxelType::Unknown) => true,
+            (PaxelType::Numeric, PaxelType::Rotation) | (PaxelType::Size, PaxelType::Rotation) => {
+                true
+            }
+            (PaxelType::Numeric | PaxelType::Size, PaxelType::Other(name)) => name == "SizePixels",
+            (PaxelType::List(_), PaxelType::Other(name)) => name == "Vec",
+            (PaxelType::Tuple(found), PaxelType::Tuple(expected)) => {
+                found.len() == expected.len()
+                    && found
+                        .iter()
+                        .zip(expected.iter())
+                        .all(|(found, expected)| found.is_assignable_to(expected))
+            }
+            (found, expected) => found == expected,
+        }
+    }
+
+    /// The type of a value that may be either `self` or `other`, like the branches of a ternary or the elements of a list
+    fn unify(&self, other: &PaxelType) -> Option<PaxelType> {
+        if !self.is_known() {
+            Some(other.clone())
+        } else if !other.is_known() || self == other {
+            Some(self.clone())
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for PaxelType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaxelType::Numeric => write!(f, "Numeric"),
+            PaxelType::Bool => write!(f, "bool"),
+            PaxelType::String => write!(f, "String"),
+            PaxelType::Size => write!(f, "Size"),
+            PaxelType::Rotation => write!(f, "Rotation"),
+            PaxelType::Transform2D => write!(f, "Transform2D"),
+            PaxelType::Range => write!(f, "RepeatRange"),
+            PaxelType::Tuple(elements) => write!(
+                f,
+                "({})",
+                elements
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            PaxelType::List(element) => write!(f, "Vec<{}>", element),
+            PaxelType::Other(name) => write!(f, "{}", name),
+            PaxelType::Unknown => write!(f, "_"),
+        }
+    }
+}
+
+/// Returns the unqualified name of a type, like `Size` for `pax_lang::api::Size` or `Vec` for `std::vec::Vec<Foo>`
+fn get_type_name(type_id: &str) -> &str {
+    type_id
+        .split('<')
+        .next()
+        .unwrap()
+        .rsplit("::")
+        .next()
+        .unwrap()
+        .trim()
+}
+
+/// Infers the type of the PAXEL expression in `paxel`, returning an error pointing at the offending span
+/// if any part of it is ill-typed, or if its type isn't assignable to `expected_type_id`
+pub fn check_expression_type(
+    paxel: &Token,
+    expected_type_id: Option<&str>,
+    ctx: &ExpressionCompilationContext,
+) -> Result<PaxelType, eyre::Report> {
+    let pairs = match PaxParser::parse(Rule::expression_body, &paxel.token_value) {
+        Ok(pairs) => pairs,
+        // leave reporting of syntax errors to the parser
+        Err(_) => return Ok(PaxelType::Unknown),
+    };
+    let checker = TypeChecker {
+        ctx,
+        paxel,
+        pratt: get_pratt_parser(),
+        lambda_params: RefCell::new(vec![]),
+    };
+    let checked = checker.check_expression(pairs)?;
+    if let Some(expected_type_id) = expected_type_id {
+        let expected = PaxelType::from_type_id(expected_type_id);
+        if !checked.ty.is_assignable_to(&expected) {
+            return Err(checker.error(
+                format!(
+                    "Type mismatch: expected `{}`, found `{}`",
+                    expected, checked.ty
+                ),
+                checked.start,
+                checked.end,
+            ));
+        }
+    }
+    Ok(checked.ty)
+}
+
+/// Returns a token for the span from `start` to `end` within `token`'s value, so that errors may point at a
+/// particular part of an expression
+fn get_span_token(token: &Token, start: usize, end: usize) -> Token {
+    let value = &token.token_value;
+    let get_line_col = |offset: usize| {
+        let prefix = &value[..offset];
+        let line = prefix.matches('\n').count();
+        let col = match prefix.rfind('\n') {
+            Some(newline) => offset - newline - 1,
+            None => offset,
+        };
+        (line, col)
+    };
+    let ((start_line, start_col), (end_line, end_col)) = (get_line_col(start), get_line_col(end));
+    let (token_location, source_line) = match &token.token_location {
+        Some(location) => {
+            let (line, col) = location.start_line_col;
+            let offset_line_col = |l: usize, c: usize| {
+                if l == 0 {
+                    (line, col + c)
+                } else {
+                    (line + l, c)
+                }
+            };
+            let source_line = if start_line == 0 {
+                token.source_line.clone()
+            } else {
+                value.lines().nth(start_line).map(|line| line.to_string())
+            };
+            (
+                Some(LocationInfo {
+                    start_line_col: offset_line_col(start_line, start_col),
+                    end_line_col: offset_line_col(end_line, end_col),
+                }),
+                source_line,
+            )
+        }
+        None => (None, token.source_line.clone()),
+    };
+    Token {
+        token_value: value[start..end].to_string(),
+        raw_value: value[start..end].to_string(),
+        token_type: token.token_type.clone(),
+        source_line,
+        token_location,
+    }
+}
+
+/// A subexpression's inferred type, along with its span within the checked expression
+struct Checked {
+    ty: PaxelType,
+    start: usize,
+    end: usize,
+    /// For the `condition ? then` half of a ternary, which is only complete once paired with its `: else`
+    is_incomplete_ternary: bool,
+}
+
+impl Checked {
+    fn new(ty: PaxelType, pair: &Pair<Rule>) -> Self {
+        let span = pair.as_span();
+        Checked {
+            ty,
+            start: span.start(),
+            end: span.end(),
+            is_incomplete_ternary: false,
+        }
+    }
+}
+
+struct TypeChecker<'a, 'b> {
+    ctx: &'a ExpressionCompilationContext<'b>,
+    paxel: &'a Token,
+    pratt: PrattParser<Rule>,
+    /// The parameters of the lambdas being checked, with their types, innermost last
+    lambda_params: RefCell<Vec<PropertyDefinition>>,
+}
+
+impl<'a, 'b> TypeChecker<'a, 'b> {
+    fn error(&self, message: String, start: usize, end: usize) -> eyre::Report {
+        PaxTemplateError::new(Some(message), get_span_token(self.paxel, start, end))
+    }
+
+    fn expect(&self, checked: &Checked, expected: PaxelType) -> Result<(), eyre::Report> {
+        if checked.ty.is_known() && checked.ty != expected {
+            Err(self.error(
+                format!(
+                    "Type mismatch: expected `{}`, found `{}`",
+                    expected, checked.ty
+                ),
+                checked.start,
+                checked.end,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn check_expression(&self, expression: Pairs<Rule>) -> Result<Checked, eyre::Report> {
+        let checked = self
+            .pratt
+            .map_primary(|primary| self.check_primary(primary))
+            .map_prefix(|op, rhs| {
+                let rhs = rhs?;
+                let ty = match op.as_rule() {
+                    Rule::xo_neg => match rhs.ty {
+                        PaxelType::Numeric
+                        | PaxelType::Size
+                        | PaxelType::Rotation
+                        | PaxelType::Unknown => rhs.ty.clone(),
+                        _ => {
+                            return Err(self.error(
+                                format!("Unable to negate a `{}`", rhs.ty),
+                                op.as_span().start(),
+                                rhs.end,
+                            ))
+                        }
+                    },
+                    _ => {
+                        self.expect(&rhs, PaxelType::Bool)?;
+                        PaxelType::Bool
+                    }
+                };
+                Ok(Checked {
+                    ty,
+                    start: op.as_span().start(),
+                    ..rhs
+                })
+            })
+            .map_infix(|lhs, op, rhs| self.check_infix(lhs?, op, rhs?))
+            .parse(expression)?;
+        if checked.is_incomplete_ternary {
+            Ok(Checked {
+                ty: PaxelType::Unknown,
+                ..checked
+            })
+        } else {
+            Ok(checked)
+        }
+    }
+
+    /// Literal SVG path data is parsed at compile time, so any error in it is reported now
+    fn check_svg_path_data(&self, arg: Pair<Rule>) -> Result<(), eyre::Report> {
+        if let Some(data) = get_string_literal_contents(arg) {
+            if let Err(err) = compile_svg_path_data(data.as_str()) {
+                let span = data.as_span();
+                return Err(self.error(
+                    format!("Invalid SVG path data: {}", err),
+                    span.start(),
+                    span.end(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_infix(
+        &self,
+        lhs: Checked,
+        op: Pair<Rule>,
+        rhs: Checked,
+    ) -> Result<Checked, eyre::Report> {
+        use PaxelType::*;
+        let (start, end) = (lhs.start, rhs.end);
+        let mismatch = |verb: &str| {
+            Err(self.error(
+                format!(
+                    "Unable to {} `{}` and `{}` with `{}`",
+                    verb,
+                    lhs.ty,
+                    rhs.ty,
+                    op.as_str().trim()
+                ),
+                start,
+                end,
+            ))
+        };
+        let ty = match op.as_rule() {
+            Rule::xo_tern_then => {
+                self.expect(&lhs, Bool)?;
+                return Ok(Checked {
+                    ty: rhs.ty,
+                    start,
+                    end,
+                    is_incomplete_ternary: true,
+                });
+            }
+            Rule::xo_tern_else => {
+                if !lhs.is_incomplete_ternary {
+                    return Ok(Checked::new(Unknown, &op));
+                }
+                match lhs.ty.unify(&rhs.ty) {
+                    Some(ty) => ty,
+                    None => return Err(self.error(
+                        format!(
+                            "The branches of this conditional have different types: `{}` and `{}`",
+                            lhs.ty, rhs.ty
+                        ),
+                        start,
+                        end,
+                    )),
+                }
+            }
+            Rule::xo_bool_and | Rule::xo_bool_or => {
+                self.expect(&lhs, Bool)?;
+                self.expect(&rhs, Bool)?;
+                Bool
+            }
+            Rule::xo_rel_eq | Rule::xo_rel_neq => {
+                if lhs.ty.unify(&rhs.ty).is_none() {
+                    return mismatch("compare");
+                }
+                Bool
+            }
+            Rule::xo_rel_lt | Rule::xo_rel_lte | Rule::xo_rel_gt | Rule::xo_rel_gte => {
+                match (&lhs.ty, &rhs.ty) {
+                    (Numeric | Unknown, Numeric | Unknown) => Bool,
+                    _ => return mismatch("compare"),
+                }
+            }
+            Rule::xo_add => match (&lhs.ty, &rhs.ty) {
+                (Unknown, _) | (_, Unknown) => Unknown,
+                (Numeric, Numeric) => Numeric,
+                (Size, Size) => Size,
+                (String, String) => String,
+                _ => return mismatch("add"),
+            },
+            Rule::xo_sub => match (&lhs.ty, &rhs.ty) {
+                (Unknown, _) | (_, Unknown) => Unknown,
+                (Numeric, Numeric) => Numeric,
+                (Size, Size) => Size,
+                _ => return mismatch("subtract"),
+            },
+            Rule::xo_mul => match (&lhs.ty, &rhs.ty) {
+                (Unknown, _) | (_, Unknown) => Unknown,
+                (Numeric, Numeric) => Numeric,
+                (Size, Size) | (Numeric, Size) | (Size, Numeric) => Size,
+                (Numeric, Rotation) | (Rotation, Numeric) => Rotation,
+                (Transform2D, Transform2D) => Transform2D,
+                _ => return mismatch("multiply"),
+            },
+            Rule::xo_div => match (&lhs.ty, &rhs.ty) {
+                (Unknown, _) | (_, Unknown) => Unknown,
+                (Numeric, Numeric) => Numeric,
+                (Size, Numeric) => Size,
+                _ => return mismatch("divide"),
+            },
+            _ => {
+                /* xo_mod | xo_exp */
+                self.expect(&lhs, Numeric)?;
+                self.expect(&rhs, Numeric)?;
+                Numeric
+            }
+        };
+        Ok(Checked {
+            ty,
+            start,
+            end,
+            is_incomplete_ternary: false,
+        })
+    }
+
+    fn check_primary(&self, primary: Pair<Rule>) -> Result<Checked, eyre::Report> {
+        let ty = match primary.as_rule() {
+            Rule::expression_grouped => {
+                /* expression_grouped = { "(" ~ expression_body ~ ")" ~ literal_number_unit? } */
+                let mut inner = primary.clone().into_inner();
+                let grouped = self.check_expression(inner.next().unwrap().into_inner())?;
+                match inner.next() {
+                    Some(unit) => {
+                        self.expect(&grouped, PaxelType::Numeric)?;
+                        get_unit_type(unit.as_str())
+                    }
+                    None => grouped.ty,
+                }
+            }
+            Rule::xo_function_call => {
+                /* xo_function_call = {identifier ~ (("::") ~ identifier)* ~ ("("~xo_function_args_list~")")} */
+                let mut path = vec![];
+                for pair in primary.clone().into_inner() {
+                    match pair.as_rule() {
+                        Rule::identifier => path.push(pair.as_str()),
+                        _ => {
+                            for arg in pair.into_inner() {
+                                if path.join("::") == SVG_PATH_FUNCTION {
+                                    self.check_svg_path_data(arg.clone())?;
+                                }
+                                self.check_expression(arg.into_inner())?;
+                            }
+                        }
+                    }
+                }
+                // Only the built-in constructors' types are known; userland functions may return anything
+                match path.first().copied() {
+                    Some("Transform2D") => PaxelType::Transform2D,
+                    Some("Size") => PaxelType::Size,
+                    Some("Rotation") => PaxelType::Rotation,
+                    _ => PaxelType::Unknown,
+                }
+            }
+            Rule::xo_method_call => self.check_method_call(primary.clone())?,
+            Rule::xo_range => {
+                /* { (xo_literal | xo_symbol) ~ (xo_range_inclusive | xo_range_exclusive) ~ (xo_literal | xo_symbol) ~ xo_range_step? } */
+                for operand in primary.clone().into_inner() {
+                    let operand = match operand.as_rule() {
+                        Rule::xo_range_step => operand.into_inner().next().unwrap(),
+                        Rule::xo_range_inclusive | Rule::xo_range_exclusive => continue,
+                        _ => operand,
+                    };
+                    let checked = self.check_primary(operand)?;
+                    self.expect(&checked, PaxelType::Numeric)?;
+                }
+                PaxelType::Range
+            }
+            Rule::xo_literal => get_literal_type(primary.clone().into_inner().next().unwrap()),
+            Rule::xo_symbol => self.check_symbol(&primary)?,
+            Rule::xo_tuple => PaxelType::Tuple(
+                primary
+                    .clone()
+                    .into_inner()
+                    .map(|element| Ok(self.check_expression(element.into_inner())?.ty))
+                    .collect::<Result<Vec<_>, eyre::Report>>()?,
+            ),
+            Rule::xo_list => {
+                let mut element_type = PaxelType::Unknown;
+                for element in primary.clone().into_inner() {
+                    let checked = self.check_expression(element.into_inner())?;
+                    element_type = element_type.unify(&checked.ty).ok_or_else(|| {
+                        self.error(
+                            format!(
+                                "The elements of this list have different types: `{}` and `{}`",
+                                element_type, checked.ty
+                            ),
+                            checked.start,
+                            checked.end,
+                        )
+                    })?;
+                }
+                PaxelType::List(Box::new(element_type))
+            }
+            Rule::expression_body => {
+                return self.check_expression(primary.into_inner());
+            }
+            /* xo_object */
+            _ => PaxelType::Unknown,
+        };
+        Ok(Checked::new(ty, &primary))
+    }
+
+    /// Resolves the `PropertyDefinition` of a symbol, like `self.count`, `elem.position.0`, or the `x.visible` in `|x| x.visible`
+    fn resolve_symbol(
+        &self,
+        symbol: &str,
+        token: Token,
+    ) -> Result<PropertyDefinition, eyre::Report> {
+        let lambda_param = self
+            .lambda_params
+            .borrow()
+            .iter()
+            .rev()
+            .find(|pd| pd.name == get_symbol_root(symbol))
+            .cloned();
+        let prop_def_chain = match lambda_param {
+            Some(lambda_param) => self.ctx.resolve_nested_symbols(
+                lambda_param,
+                clean_and_split_symbols(symbol).iter().skip(1),
+                symbol,
+                token,
+            )?,
+            None => self
+                .ctx
+                .resolve_symbol_as_prop_def(symbol, token.clone())?
+                .ok_or::<eyre::Report>(PaxTemplateError::new(
+                    Some(format!("Symbol not found: `{}`", symbol)),
+                    token,
+                ))?,
+        };
+        Ok(prop_def_chain.last().unwrap().clone())
+    }
+
+    fn check_symbol(&self, primary: &Pair<Rule>) -> Result<PaxelType, eyre::Report> {
+        let symbol = primary.as_str().trim();
+        if symbol == "true" || symbol == "false" {
+            return Ok(PaxelType::Bool);
+        }
+        // Built-ins like `$container` and indexed symbols like `self.items[0]` aren't yet typed
+        if symbol.starts_with('$') || symbol.contains('[') {
+            return Ok(PaxelType::Unknown);
+        }
+        let span = primary.as_span();
+        let token = get_span_token(self.paxel, span.start(), span.start() + symbol.len());
+        let pd = self.resolve_symbol(symbol, token)?;
+        Ok(PaxelType::from_type_id(&pd.type_id))
+    }
+
+    /// Infers the type returned by a chain of calls to PAXEL's standard library, checking the types of their
+    /// arguments along the way.  Unknown methods and mismatched arities are reported during compilation.
+    fn check_method_call(&self, primary: Pair<Rule>) -> Result<PaxelType, eyre::Report> {
+        /* xo_method_call = { xo_symbol ~ xo_method_call_segment+ } */
+        let mut inner = primary.into_inner();
+        let receiver = inner.next().unwrap();
+        let span = receiver.as_span();
+        let receiver_symbol = receiver.as_str().trim();
+        let mut type_id = self
+            .resolve_symbol(
+                receiver_symbol,
+                get_span_token(
+                    self.paxel,
+                    span.start(),
+                    span.start() + receiver_symbol.len(),
+                ),
+            )?
+            .type_id;
+
+        for segment in inner {
+            let mut segment = segment.into_inner();
+            let name = segment.next().unwrap().as_str();
+            let args = segment.next().unwrap().into_inner().collect::<Vec<_>>();
+            let kind = match PaxelKind::from_type_id(&type_id, self.ctx.type_table) {
+                Some(kind) => kind,
+                None => return Ok(PaxelType::Unknown),
+            };
+            let (expected_args, ret) =
+                match PAXEL_STD_METHODS.iter().find(|(k, n, args_spec, _)| {
+                    *k == kind && *n == name && args_spec.len() == args.len()
+                }) {
+                    Some((_, _, expected_args, ret)) => (*expected_args, *ret),
+                    None => return Ok(PaxelType::Unknown),
+                };
+            for (expected, arg) in expected_args.iter().zip(args) {
+                match (expected, arg.as_rule()) {
+                    (PaxelMethodArg::Lambda, Rule::xo_lambda) => {
+                        /* xo_lambda = { "|" ~ identifier ~ ("," ~ identifier)* ~ "|" ~ expression_body } */
+                        let mut lambda = arg.into_inner();
+                        let param = lambda.next().unwrap().as_str();
+                        let body = match lambda.next() {
+                            Some(body) if body.as_rule() == Rule::expression_body => body,
+                            _ => return Ok(PaxelType::Unknown),
+                        };
+                        let elem_type_id = self
+                            .ctx
+                            .type_table
+                            .get(&type_id)
+                            .and_then(|td| td.inner_iterable_type_id.clone())
+                            .unwrap();
+                        self.lambda_params.borrow_mut().push(
+                            PropertyDefinition::primitive_with_name(&elem_type_id, param),
+                        );
+                        let checked = self.check_expression(body.into_inner());
+                        self.lambda_params.borrow_mut().pop();
+                        // the lambdas of `filter`, `any`, `all` and `count` are predicates
+                        self.expect(&checked?, PaxelType::Bool)?;
+                    }
+                    (PaxelMethodArg::Value, Rule::expression_body) => {
+                        let checked = self.check_expression(arg.into_inner())?;
+                        self.expect(
+                            &checked,
+                            match kind {
+                                PaxelKind::String => PaxelType::String,
+                                _ => PaxelType::Numeric,
+                            },
+                        )?;
+                    }
+                    _ => return Ok(PaxelType::Unknown),
+                }
+            }
+            type_id = match ret {
+                PaxelMethodReturn::Kind(PaxelKind::Numeric) => "Numeric".to_string(),
+                PaxelMethodReturn::Kind(PaxelKind::String) => "StringBox".to_string(),
+                PaxelMethodReturn::Kind(PaxelKind::Bool) => "bool".to_string(),
+                PaxelMethodReturn::Kind(PaxelKind::Collection) | PaxelMethodReturn::Receiver => {
+                    type_id
+                }
+            };
+        }
+        Ok(PaxelType::from_type_id(&type_id))
+    }
+}
+
+fn get_unit_type(unit: &str) -> PaxelType {
+    match unit {
+        "px" | "%" => PaxelType::Size,
+        _ => PaxelType::Rotation,
+    }
+}
+
+/// Infers the type of any of the literal rules, as found in `xo_literal` or (recursively) `literal_value`
+fn get_literal_type(literal: Pair<Rule>) -> PaxelType {
+    match literal.as_rule() {
+        Rule::literal_value => get_literal_type(literal.into_inner().next().unwrap()),
+        Rule::literal_number_with_unit => {
+            get_unit_type(literal.into_inner().nth(1).unwrap().as_str())
+        }
+        Rule::literal_number => PaxelType::Numeric,
+        Rule::literal_boolean => PaxelType::Bool,
+        Rule::string => PaxelType::String,
+        Rule::literal_tuple => {
+            PaxelType::Tuple(literal.into_inner().map(get_literal_type).collect())
+        }
+        /* literal_enum_value */
+        _ => PaxelType::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paxel_type_assignability() {
+        assert_eq!(PaxelType::from_type_id("usize"), PaxelType::Numeric);
+        assert_eq!(
+            PaxelType::from_type_id("pax_lang::api::Size"),
+            PaxelType::Size
+        );
+        assert_eq!(
+            PaxelType::from_type_id("std::vec::Vec<crate::Item>"),
+            PaxelType::Other("Vec".to_string())
+        );
+
+        assert!(PaxelType::Numeric.is_assignable_to(&PaxelType::from_type_id("f64")));
+        assert!(PaxelType::Size.is_assignable_to(&PaxelType::Rotation));
+        assert!(!PaxelType::String.is_assignable_to(&PaxelType::Size));
+        assert!(PaxelType::Unknown.is_assignable_to(&PaxelType::Size));
+        assert!(PaxelType::List(Box::new(PaxelType::Numeric))
+            .is_assignable_to(&PaxelType::from_type_id("std::vec::Vec<f64>")));
+    }
+
+    #[test]
+    fn test_span_token_points_within_expression() {
+        let token = Token {
+            token_value: "self.count +\n  \"oops\"".to_string(),
+            raw_value: "".to_string(),
+            token_type: Default::default(),
+            source_line: Some("<Text x={self.count +".to_string()),
+            token_location: Some(LocationInfo {
+                start_line_col: (11, 9),
+                end_line_col: (12, 8),
+            }),
+        };
+        let span_token = get_span_token(&token, 5, 10);
+        assert_eq!(span_token.token_value, "count");
+        let location = span_token.token_location.unwrap();
+        assert_eq!(location.start_line_col, (11, 14));
+        assert_eq!(location.end_line_col, (11, 19));
+
+        let span_token = get_span_token(&token, 15, 21);
+        assert_eq!(span_token.token_value, "\"oops\"");
+        assert_eq!(span_token.token_location.unwrap().start_line_col, (12, 2));
+        assert_eq!(span_token.source_line.unwrap(), "  \"oops\"");
+    }
+}
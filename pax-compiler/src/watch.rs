@@ -0,0 +1,87 @@
+//! Watching a project's sources for changes, shared by `pax watch` and `pax run --hot-reload`.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use color_eyre::eyre::{self, Report};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+
+/// Changes are batched until the sources have been quiet for this long, since editors often write
+/// a file in several steps
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(250);
+
+/// Watches the `.pax` and `.rs` files under a project's `src` directory
+pub struct SourceWatcher {
+    receiver: mpsc::Receiver<notify::Result<notify::Event>>,
+    //stops watching when dropped
+    _watcher: RecommendedWatcher,
+}
+
+impl SourceWatcher {
+    pub fn new(project_path: &str) -> eyre::Result<Self, Report> {
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(
+            &Path::new(project_path).join("src"),
+            RecursiveMode::Recursive,
+        )?;
+        Ok(SourceWatcher {
+            receiver,
+            _watcher: watcher,
+        })
+    }
+
+    /// Blocks until some sources change, returning the changed paths once the sources have been quiet for
+    /// `DEBOUNCE_DURATION`.  Returns `None` if watching failed.
+    pub fn next_change(&self) -> Option<Vec<PathBuf>> {
+        loop {
+            let mut changed_paths = vec![];
+            let mut event = Some(self.receiver.recv().ok()?);
+            while let Some(Ok(notify::Event { kind, paths, .. })) = event.take() {
+                if !matches!(kind, EventKind::Access(_)) {
+                    changed_paths.extend(paths);
+                }
+                event = self.receiver.recv_timeout(DEBOUNCE_DURATION).ok();
+            }
+            changed_paths.retain(|path| {
+                matches!(
+                    path.extension().and_then(|extension| extension.to_str()),
+                    Some("pax") | Some("rs")
+                )
+            });
+            changed_paths.sort();
+            changed_paths.dedup();
+            if !changed_paths.is_empty() {
+                return Some(changed_paths);
+            }
+        }
+    }
+}
+
+/// Whether any of `paths` is a Rust source, i.e. whether a change may reach beyond templates
+pub fn includes_rust_sources(paths: &[PathBuf]) -> bool {
+    paths.iter().any(|path| {
+        path.extension()
+            .map_or(false, |extension| extension == "rs")
+    })
+}
+
+/// Serializes `value` for comparison, disregarding source locations, which shift with unrelated edits
+pub fn normalize<T: Serialize>(value: &T) -> serde_json::Value {
+    fn strip_locations(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                map.remove("token_location");
+                map.remove("source_line");
+                map.values_mut().for_each(strip_locations);
+            }
+            serde_json::Value::Array(values) => values.iter_mut().for_each(strip_locations),
+            _ => {}
+        }
+    }
+    let mut value = serde_json::to_value(value).unwrap();
+    strip_locations(&mut value);
+    value
+}
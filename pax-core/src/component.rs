@@ -4,8 +4,8 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::{
-    HandlerRegistry, InstantiationArgs, RenderNode, RenderNodePtr, RenderNodePtrList,
-    RenderTreeContext, Runtime,
+    ComputePropertiesFn, HandlerRegistry, InstanceRegistry, InstantiationArgs, RenderNode,
+    RenderNodePtr, RenderNodePtrList, RenderTreeContext, Runtime,
 };
 use pax_properties_coproduct::PropertiesCoproduct;
 
@@ -24,14 +24,38 @@ pub struct ComponentInstance<R: 'static + RenderContext> {
     pub template: RenderNodePtrList<R>,
     pub children: RenderNodePtrList<R>,
     pub handler_registry: Option<Rc<RefCell<HandlerRegistry<R>>>>,
+    /// The nodes of a template replaced by `replace_template`, to be unmounted on the next traversal
+    pub cleanup_children: RenderNodePtrList<R>,
     pub properties: Rc<RefCell<PropertiesCoproduct>>,
     pub timeline: Option<Rc<RefCell<Timeline>>>,
-    pub compute_properties_fn:
-        Box<dyn FnMut(Rc<RefCell<PropertiesCoproduct>>, &mut RenderTreeContext<R>)>,
+    /// For the `RepeatItem`s of a keyed `for`: a stable identity used in place of the item's index
+    /// when building id_chains, so that an item keeps its identity (and native elements) when it moves
+    pub repeat_item_identity: Option<u32>,
+    pub compute_properties_fn: ComputePropertiesFn<R>,
 
     pub common_properties: CommonProperties,
 }
 
+impl<R: 'static + RenderContext> ComponentInstance<R> {
+    /// Swap in a new template (and the handlers for this component's own events), e.g. when hot reloading.
+    /// `properties` are kept, while the nodes of the previous template are unmounted on the next traversal.
+    pub fn replace_template(
+        &mut self,
+        template: RenderNodePtrList<R>,
+        handler_registry: Rc<RefCell<HandlerRegistry<R>>>,
+        instance_registry: &mut InstanceRegistry<R>,
+    ) {
+        let previous_template = std::mem::replace(&mut self.template, template);
+        (*previous_template).borrow().iter().for_each(|node| {
+            instance_registry.mark_for_unmount((**node).borrow().get_instance_id());
+        });
+        (*self.cleanup_children)
+            .borrow_mut()
+            .extend((*previous_template).borrow().iter().cloned());
+        self.handler_registry = Some(handler_registry);
+    }
+}
+
 impl<R: 'static + RenderContext> RenderNode<R> for ComponentInstance<R> {
     fn get_common_properties(&self) -> &CommonProperties {
         &self.common_properties
@@ -43,11 +67,12 @@ impl<R: 'static + RenderContext> RenderNode<R> for ComponentInstance<R> {
         Rc::clone(&self.template)
     }
 
+    fn pop_cleanup_children(&mut self) -> RenderNodePtrList<R> {
+        std::mem::replace(&mut self.cleanup_children, Rc::new(RefCell::new(vec![])))
+    }
+
     fn get_handler_registry(&self) -> Option<Rc<RefCell<HandlerRegistry<R>>>> {
-        match &self.handler_registry {
-            Some(registry) => Some(Rc::clone(&registry)),
-            _ => None,
-        }
+        self.handler_registry.as_ref().map(Rc::clone)
     }
 
     fn handle_did_render(&mut self, rtc: &mut RenderTreeContext<R>, _rcs: &mut HashMap<String, R>) {
@@ -76,7 +101,9 @@ impl<R: 'static + RenderContext> RenderNode<R> for ComponentInstance<R> {
                 .compute_properties_fn
                 .expect("must pass a compute_properties_fn to a Component instance"),
             timeline: None,
+            repeat_item_identity: None,
             handler_registry: args.handler_registry,
+            cleanup_children: Rc::new(RefCell::new(vec![])),
         }));
 
         instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
@@ -99,15 +126,24 @@ impl<R: 'static + RenderContext> RenderNode<R> for ComponentInstance<R> {
             (*unflattened_adoptees)
                 .borrow()
                 .iter()
-                .map(|adoptee| Runtime::process__should_flatten__adoptees_recursive(adoptee, rtc))
-                .flatten()
+                .flat_map(|adoptee| {
+                    Runtime::process__should_flatten__adoptees_recursive(adoptee, rtc)
+                })
                 .collect(),
         ));
 
+        if let Some(timeline) = &self.timeline {
+            (**timeline).borrow_mut().advance(rtc.engine.frames_elapsed);
+            if (**timeline).borrow().is_playing {
+                rtc.engine.request_next_frame();
+            }
+        }
+
         (*rtc.runtime).borrow_mut().push_stack_frame(
             Rc::clone(&flattened_adoptees),
             Rc::clone(&self.properties),
             self.timeline.clone(),
+            self.repeat_item_identity,
         );
 
         self.common_properties.compute_properties(rtc);
@@ -118,3 +154,61 @@ impl<R: 'static + RenderContext> RenderNode<R> for ComponentInstance<R> {
         Layer::DontCare
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::tests::{build_engine_with_template, build_test_node, tick, R};
+    use crate::HandlerRegistry;
+
+    #[test]
+    fn test_replace_template_keeps_properties_and_unmounts_the_previous_template() {
+        let mut old = vec![];
+        let engine = &mut build_engine_with_template(|instance_registry| {
+            old = vec![
+                build_test_node(instance_registry, None, vec![]) as RenderNodePtr<R>,
+                build_test_node(instance_registry, None, vec![]) as RenderNodePtr<R>,
+            ];
+            old.clone()
+        });
+        tick(engine);
+        let old_ids: Vec<u32> = old
+            .iter()
+            .map(|node| node.borrow().get_instance_id())
+            .collect();
+
+        let main_component = Rc::clone(&engine.main_component);
+        let properties = Rc::clone(&main_component.borrow().properties);
+        let new_node = build_test_node(&mut engine.instance_registry.borrow_mut(), None, vec![]);
+        let new_id = new_node.borrow().instance_id;
+        let handler_registry = Rc::new(RefCell::new(HandlerRegistry::default()));
+        main_component.borrow_mut().replace_template(
+            Rc::new(RefCell::new(vec![new_node as RenderNodePtr<R>])),
+            Rc::clone(&handler_registry),
+            &mut engine.instance_registry.borrow_mut(),
+        );
+        {
+            let main_component = main_component.borrow();
+            assert!(Rc::ptr_eq(&main_component.properties, &properties));
+            assert!(Rc::ptr_eq(
+                main_component.handler_registry.as_ref().unwrap(),
+                &handler_registry
+            ));
+            let cleanup_children = main_component.cleanup_children.borrow();
+            assert_eq!(cleanup_children.len(), old.len());
+            assert!(cleanup_children
+                .iter()
+                .zip(old.iter())
+                .all(|(a, b)| Rc::ptr_eq(a, b)));
+        }
+
+        engine.invalidate();
+        tick(engine);
+        let instance_registry = engine.instance_registry.borrow();
+        for id in old_ids {
+            assert!(!instance_registry.is_mounted(&vec![id]));
+        }
+        assert!(instance_registry.is_mounted(&vec![new_id]));
+        assert!(main_component.borrow().cleanup_children.borrow().is_empty());
+    }
+}
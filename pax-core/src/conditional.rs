@@ -10,7 +10,9 @@ use piet_common::RenderContext;
 /// subtree of a component template to be rendered conditionally,
 /// based on the value of the property `boolean_expression`.
 /// The Pax compiler handles ConditionalInstance specially
-/// with the `if` syntax in templates.
+/// with the `if` syntax in templates.  An `else` branch is rendered from
+/// `false_branch_children`; `else if` chains are expressed as a
+/// nested ConditionalInstance inside `false_branch_children`.
 pub struct ConditionalInstance<R: 'static + RenderContext> {
     pub instance_id: u32,
 
@@ -19,6 +21,8 @@ pub struct ConditionalInstance<R: 'static + RenderContext> {
     pub false_branch_children: RenderNodePtrList<R>,
     pub cleanup_children: RenderNodePtrList<R>,
     pub common_properties: CommonProperties,
+    /// Whether `boolean_expression` has been evaluated at least once, i.e. whether either branch may have been mounted
+    has_evaluated: bool,
 }
 
 impl<R: 'static + RenderContext> RenderNode<R> for ConditionalInstance<R> {
@@ -46,8 +50,12 @@ impl<R: 'static + RenderContext> RenderNode<R> for ConditionalInstance<R> {
             boolean_expression: args
                 .conditional_boolean_expression
                 .expect("Conditional requires boolean_expression"),
-            false_branch_children: Rc::new(RefCell::new(vec![])),
+            false_branch_children: match args.conditional_else_children {
+                None => Rc::new(RefCell::new(vec![])),
+                Some(children) => children,
+            },
             cleanup_children: Rc::new(RefCell::new(vec![])),
+            has_evaluated: false,
         }));
 
         instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
@@ -65,9 +73,16 @@ impl<R: 'static + RenderContext> RenderNode<R> for ConditionalInstance<R> {
                 unreachable!()
             };
 
-            let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
-            if old_value && !new_value {
-                (*self.true_branch_children)
+            if self.has_evaluated && old_value != new_value {
+                //Deregister and unmount the branch being swapped out.  Its nodes are retained,
+                //so the branch being swapped in (re-)registers its own.
+                let (outgoing_branch_children, incoming_branch_children) = if old_value {
+                    (&self.true_branch_children, &self.false_branch_children)
+                } else {
+                    (&self.false_branch_children, &self.true_branch_children)
+                };
+                let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
+                (*outgoing_branch_children)
                     .borrow_mut()
                     .iter()
                     .for_each(|child| {
@@ -75,9 +90,17 @@ impl<R: 'static + RenderContext> RenderNode<R> for ConditionalInstance<R> {
                         instance_registry.deregister(instance_id);
                         instance_registry.mark_for_unmount(instance_id);
                     });
-                self.cleanup_children = self.true_branch_children.clone();
+                (*incoming_branch_children)
+                    .borrow()
+                    .iter()
+                    .for_each(|child| {
+                        let instance_id = (*(*child)).borrow().get_instance_id();
+                        instance_registry.register(instance_id, Rc::clone(child));
+                    });
+                self.cleanup_children = Rc::clone(outgoing_branch_children);
             }
             self.boolean_expression.set(new_value);
+            self.has_evaluated = true;
         }
     }
 
@@ -107,3 +130,98 @@ impl<R: 'static + RenderContext> RenderNode<R> for ConditionalInstance<R> {
         Layer::DontCare
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::tests::{
+        build_engine_with_expressions, build_test_node, get_value, set_value, take_calls, tick, R,
+    };
+    use crate::{ExpressionFn, InstanceRegistry, PropertyExpression};
+    use std::collections::HashMap;
+
+    fn build_conditional(
+        instance_registry: &mut InstanceRegistry<R>,
+        vtable_id: usize,
+        true_branch_children: Vec<RenderNodePtr<R>>,
+        false_branch_children: Vec<RenderNodePtr<R>>,
+    ) -> RenderNodePtr<R> {
+        let instance_id = instance_registry.mint_id();
+        let conditional: RenderNodePtr<R> = Rc::new(RefCell::new(ConditionalInstance {
+            instance_id,
+            boolean_expression: Box::new(PropertyExpression::new(vtable_id)),
+            true_branch_children: Rc::new(RefCell::new(true_branch_children)),
+            false_branch_children: Rc::new(RefCell::new(false_branch_children)),
+            cleanup_children: Rc::new(RefCell::new(vec![])),
+            common_properties: CommonProperties::default(),
+            has_evaluated: false,
+        }));
+        instance_registry.register(instance_id, Rc::clone(&conditional));
+        conditional
+    }
+
+    #[test]
+    fn test_switching_branches_mounts_the_incoming_and_unmounts_the_outgoing() {
+        set_value("if", 1.0);
+        set_value("else_if", 0.0);
+        let mut expression_table: HashMap<usize, ExpressionFn<R>> = HashMap::new();
+        expression_table.insert(
+            0,
+            Box::new(|_| TypesCoproduct::bool(get_value("if") != 0.0)),
+        );
+        expression_table.insert(
+            1,
+            Box::new(|_| TypesCoproduct::bool(get_value("else_if") != 0.0)),
+        );
+
+        // if { a } else if { b } else { c }
+        let (mut a, mut b, mut c, mut else_if_id) = (0, 0, 0, 0);
+        let engine = &mut build_engine_with_expressions(expression_table, |instance_registry| {
+            let a_node = build_test_node(instance_registry, None, vec![]);
+            let b_node = build_test_node(instance_registry, None, vec![]);
+            let c_node = build_test_node(instance_registry, None, vec![]);
+            for (id, node) in [(&mut a, &a_node), (&mut b, &b_node), (&mut c, &c_node)] {
+                *id = node.borrow().instance_id;
+                instance_registry.register(*id, Rc::clone(node) as RenderNodePtr<R>);
+            }
+            let else_if = build_conditional(
+                instance_registry,
+                1,
+                vec![b_node as RenderNodePtr<R>],
+                vec![c_node as RenderNodePtr<R>],
+            );
+            else_if_id = else_if.borrow().get_instance_id();
+            vec![build_conditional(
+                instance_registry,
+                0,
+                vec![a_node as RenderNodePtr<R>],
+                vec![else_if],
+            )]
+        });
+
+        let assert_branch = |engine: &mut crate::PaxEngine<R>, mounted: u32, unmounted: &[u32]| {
+            assert!(tick(engine));
+            let calls = take_calls();
+            let instance_registry = engine.instance_registry.borrow();
+            assert!(instance_registry.is_mounted(&vec![mounted]));
+            assert!(instance_registry.is_registered(mounted));
+            assert!(calls.contains(&("render", vec![mounted])));
+            for id in unmounted {
+                assert!(calls.contains(&("will_unmount", vec![*id])));
+                assert!(!instance_registry.is_mounted(&vec![*id]));
+            }
+        };
+
+        assert_branch(engine, a, &[]);
+        set_value("if", 0.0);
+        set_value("else_if", 1.0);
+        assert_branch(engine, b, &[a]);
+        assert!(!engine.instance_registry.borrow().is_registered(a));
+        set_value("else_if", 0.0);
+        assert_branch(engine, c, &[b]);
+        //the nested `else if` is swapped out whole, `else` branch and all
+        set_value("if", 1.0);
+        assert_branch(engine, a, &[c]);
+        assert!(!engine.instance_registry.borrow().is_registered(else_if_id));
+    }
+}
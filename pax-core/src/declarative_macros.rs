@@ -21,7 +21,7 @@ macro_rules! unsafe_unwrap {
                 let enum_ptr = Box::into_raw(boxed_enum);
                 let target_ptr = (enum_ptr as *mut u8).add(align_of_enum) as *mut U;
 
-                std::mem::swap(&mut *target_ptr, &mut default_value);
+                std::ptr::swap(target_ptr, &mut default_value);
 
                 // We no longer need the boxed enum, so it can be safely dropped.
                 // Note that because the value inside the enum variant was replaced with a default value,
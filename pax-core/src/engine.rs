@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
 
 use std::rc::{Rc, Weak};
@@ -16,21 +16,184 @@ use crate::{
 };
 use pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 
+use pax_runtime_api::reactivity::{self, Dependencies, PropertyId};
 use pax_runtime_api::{
-    ArgsClick, ArgsContextMenu, ArgsDoubleClick, ArgsJab, ArgsKeyDown, ArgsKeyPress, ArgsKeyUp,
-    ArgsMouseDown, ArgsMouseMove, ArgsMouseOut, ArgsMouseOver, ArgsMouseUp, ArgsScroll,
-    ArgsTouchEnd, ArgsTouchMove, ArgsTouchStart, ArgsWheel, CommonProperties, Interpolatable,
-    Layer, Rotation, RuntimeContext, Size, Transform2D, TransitionManager, ZIndex,
+    ArgsBlur, ArgsButtonClick, ArgsClick, ArgsContextMenu, ArgsDoubleClick, ArgsDropdownChange,
+    ArgsFocus, ArgsJab, ArgsKeyDown, ArgsKeyPress, ArgsKeyUp, ArgsMouseDown, ArgsMouseMove,
+    ArgsMouseOut, ArgsMouseOver, ArgsMouseUp, ArgsScroll, ArgsSliderChange, ArgsTextboxChange,
+    ArgsTouchEnd, ArgsTouchMove, ArgsTouchStart, ArgsWheel, BlendMode, CommonProperties, Event,
+    FocusRequest, Interpolatable, Layer, ModifierKey, Rotation, RuntimeContext, Size, Timeline,
+    Transform2D, TransitionManager, ZIndex,
 };
 
 pub struct PaxEngine<R: 'static + RenderContext> {
     pub frames_elapsed: usize,
     pub instance_registry: Rc<RefCell<InstanceRegistry<R>>>,
-    pub expression_table: HashMap<usize, Box<dyn Fn(ExpressionContext<R>) -> TypesCoproduct>>,
+    pub expression_table: HashMap<usize, ExpressionFn<R>>,
     pub main_component: Rc<RefCell<ComponentInstance<R>>>,
     pub runtime: Rc<RefCell<Runtime<R>>>,
-    pub image_map: HashMap<Vec<u32>, (Box<Vec<u8>>, usize, usize)>,
+    pub image_map: ImageMap,
     viewport_tab: TransformAndBounds,
+    /// The dependencies of each expression's most recent evaluation, per scope (the properties of the
+    /// stack frame it was evaluated against), used to skip re-evaluating expressions whose inputs are unchanged
+    expression_dependencies:
+        RefCell<HashMap<(usize, *const RefCell<PropertiesCoproduct>), ExpressionEvaluation>>,
+    /// Whether the most recent traversal found work that continues into the next tick; see `request_next_frame`
+    has_per_frame_work: Cell<bool>,
+    /// Set by changes that aren't property changes, e.g. viewport resizes and image loads
+    is_invalidated: bool,
+    /// `reactivity::current_revision()` as of the end of the most recent traversal
+    last_traversal_revision: Option<u64>,
+    /// The record of the most recent traversal, from which the next one reuses clean subtrees
+    last_render_record: Option<Rc<RenderRecord<R>>>,
+    /// The `id_chain` of the element with keyboard focus, if any; see `focus`
+    focused_id_chain: RefCell<Option<Vec<u32>>>,
+    /// Focus changes requested by handlers through their `RuntimeContext`, applied at the start of each tick
+    focus_requests: Rc<RefCell<Vec<FocusRequest>>>,
+    /// Applies opacity and blend mode to a drawing context, by id, on behalf of `CommonProperties::opacity`
+    /// and `blend_mode`; see `set_compositor`
+    compositor: Option<Box<dyn Compositor>>,
+}
+
+/// A chassis' means of applying opacity and blend mode to its drawing contexts; see `PaxEngine::set_compositor`.
+/// Implemented by closures `Fn(canvas_id, opacity, blend_mode)`, which act as `begin`.
+pub trait Compositor {
+    /// Invoked with the accumulated `opacity` and `blend_mode` once the drawing context `canvas_id` is `save`d,
+    /// before a composited subtree is drawn to it
+    fn begin(&self, canvas_id: &str, opacity: f64, blend_mode: BlendMode);
+
+    /// Invoked once a composited subtree is drawn to the drawing context `canvas_id`, before it's `restore`d.
+    /// Needed only where `restore` doesn't undo `begin`, e.g. where `begin` redirects drawing to an offscreen group
+    fn end(&self, _canvas_id: &str) {}
+}
+
+impl<F: Fn(&str, f64, BlendMode)> Compositor for F {
+    fn begin(&self, canvas_id: &str, opacity: f64, blend_mode: BlendMode) {
+        self(canvas_id, opacity, blend_mode)
+    }
+}
+
+struct ExpressionEvaluation {
+    /// Held weakly (and compared by pointer), so that a scope freed and re-allocated at the same address is not mistaken for this one
+    scope: Weak<RefCell<PropertiesCoproduct>>,
+    dependencies: Dependencies,
+}
+
+/// What traversing a node and its subtree computed, retained until the next traversal.  A subtree
+/// traversed with the same `inputs`, none of whose `dependencies` have changed since, would compute the same
+/// again, so the next traversal reuses its record instead, painting it without traversing it (see `paint_render_record`).
+struct RenderRecord<R: 'static + RenderContext> {
+    node: RenderNodePtr<R>,
+    inputs: TraversalInputs<R>,
+    /// Everything read while traversing the subtree, e.g. properties and the dependencies of expressions
+    dependencies: Dependencies,
+    /// Whether traversing the subtree called `request_next_frame`, in which case it must be traversed again
+    has_per_frame_work: bool,
+    repeat_expanded_node: Rc<RepeatExpandedNode<R>>,
+    /// The stack frame pushed by the node, if any (e.g. a component's), pushed again to paint its subtree
+    stack_frame: Option<Rc<RefCell<StackFrame<R>>>>,
+    transform_global: Affine,
+    transform_scroller_reset: Affine,
+    bounds: (f64, f64),
+    opacity: f64,
+    blend_mode: BlendMode,
+    timeline_playhead_position: usize,
+    timeline: Option<Rc<RefCell<Timeline>>>,
+    /// Whether the node sets `opacity` or `blend_mode`, which composite every layer its subtree paints to
+    is_composited: bool,
+    canvas_id: String,
+    is_viewport_culled: bool,
+    /// Whether the subtree contains `Repeat`ed nodes.  These share a single instance between all of their repeat-expanded
+    /// nodes, whose properties are only current while that node is being traversed, so the subtree can't be reused.
+    is_repeated: bool,
+    /// In traversal (and paint) order
+    children: Vec<Rc<RenderRecord<R>>>,
+}
+
+/// The state that a node inherits from its ancestors when it is traversed
+struct TraversalInputs<R: 'static + RenderContext> {
+    transform_global: Affine,
+    transform_scroller_reset: Affine,
+    bounds: (f64, f64),
+    opacity: f64,
+    blend_mode: BlendMode,
+    timeline_playhead_position: usize,
+    z_index: ZIndex,
+    /// The properties and adoptees of the current stack frame, which is itself rebuilt each tick
+    scope: Option<Scope<R>>,
+}
+
+/// The properties and adoptees of a stack frame
+type Scope<R> = (Rc<RefCell<PropertiesCoproduct>>, Vec<RenderNodePtr<R>>);
+
+impl<R: 'static + RenderContext> TraversalInputs<R> {
+    fn new(rtc: &RenderTreeContext<R>, z_index_info: &ZIndex) -> Self {
+        let scope = (*rtc.runtime)
+            .borrow_mut()
+            .peek_stack_frame()
+            .map(|stack_frame| {
+                let stack_frame = (*stack_frame).borrow();
+                let adoptees = (*stack_frame.get_unflattened_adoptees()).borrow().clone();
+                (stack_frame.get_properties(), adoptees)
+            });
+        TraversalInputs {
+            transform_global: rtc.transform_global,
+            transform_scroller_reset: rtc.transform_scroller_reset,
+            bounds: rtc.bounds,
+            opacity: rtc.opacity,
+            blend_mode: rtc.blend_mode,
+            timeline_playhead_position: rtc.timeline_playhead_position,
+            z_index: z_index_info.clone(),
+            scope,
+        }
+    }
+}
+
+impl<R: 'static + RenderContext> PartialEq for TraversalInputs<R> {
+    fn eq(&self, other: &Self) -> bool {
+        let is_same_scope = match (&self.scope, &other.scope) {
+            (Some((properties, adoptees)), Some((other_properties, other_adoptees))) => {
+                Rc::ptr_eq(properties, other_properties)
+                    && adoptees.len() == other_adoptees.len()
+                    && adoptees
+                        .iter()
+                        .zip(other_adoptees.iter())
+                        .all(|(adoptee, other_adoptee)| is_same_node(adoptee, other_adoptee))
+            }
+            (None, None) => true,
+            _ => false,
+        };
+        is_same_scope
+            && self.transform_global == other.transform_global
+            && self.transform_scroller_reset == other.transform_scroller_reset
+            && self.bounds == other.bounds
+            && self.opacity == other.opacity
+            && self.blend_mode == other.blend_mode
+            && self.timeline_playhead_position == other.timeline_playhead_position
+            && self.z_index == other.z_index
+    }
+}
+
+/// Compares render nodes by address alone, since the vtables of the same node may differ between codegen units
+fn is_same_node<R: 'static + RenderContext>(a: &RenderNodePtr<R>, b: &RenderNodePtr<R>) -> bool {
+    std::ptr::eq(Rc::as_ptr(a).cast::<()>(), Rc::as_ptr(b).cast::<()>())
+}
+
+/// The record of `node` among `records`, which are usually in the same order as the nodes being traversed
+fn find_render_record<R: 'static + RenderContext>(
+    records: &[Rc<RenderRecord<R>>],
+    node: &RenderNodePtr<R>,
+    index_hint: usize,
+) -> Option<Rc<RenderRecord<R>>> {
+    records
+        .get(index_hint)
+        .filter(|record| is_same_node(&record.node, node))
+        .or_else(|| {
+            records
+                .iter()
+                .find(|record| is_same_node(&record.node, node))
+        })
+        .map(Rc::clone)
 }
 
 pub struct RenderTreeContext<'a, R: 'static + RenderContext> {
@@ -42,13 +205,21 @@ pub struct RenderTreeContext<'a, R: 'static + RenderContext> {
     pub node: RenderNodePtr<R>,
     pub parent_repeat_expanded_node: Option<Weak<RepeatExpandedNode<R>>>,
     pub timeline_playhead_position: usize,
+    /// Timeline of the nearest component carrying one, scoped alongside `timeline_playhead_position`
+    pub timeline: Option<Rc<RefCell<Timeline>>>,
     pub inherited_adoptees: Option<RenderNodePtrList<R>>,
+    /// Opacity accumulated from this node's ancestors (and, once computed, the node itself)
+    pub opacity: f64,
+    /// Blend mode of the nearest ancestor (or this node) that specifies one
+    pub blend_mode: BlendMode,
 }
 
 macro_rules! handle_vtable_update {
     ($rtc:expr, $var:ident . $field:ident, $types_coproduct_type:ident) => {{
         let current_prop = &mut *$var.$field.as_ref().borrow_mut();
-        if let Some(new_value) = $rtc.compute_vtable_value(current_prop._get_vtable_id()) {
+        if let Some(new_value) = current_prop._compute_timeline_value() {
+            current_prop.set(new_value);
+        } else if let Some(new_value) = $rtc.compute_vtable_value(current_prop._get_vtable_id()) {
             let new_value = if let TypesCoproduct::$types_coproduct_type(val) = new_value {
                 val
             } else {
@@ -63,7 +234,10 @@ macro_rules! handle_vtable_update_optional {
     ($rtc:expr, $var:ident . $field:ident, $types_coproduct_type:ident) => {{
         if let Some(_) = $var.$field {
             let current_prop = &mut *$var.$field.as_ref().unwrap().borrow_mut();
-            if let Some(new_value) = $rtc.compute_vtable_value(current_prop._get_vtable_id()) {
+            if let Some(new_value) = current_prop._compute_timeline_value() {
+                current_prop.set(new_value);
+            } else if let Some(new_value) = $rtc.compute_vtable_value(current_prop._get_vtable_id())
+            {
                 let new_value = if let TypesCoproduct::$types_coproduct_type(val) = new_value {
                     val
                 } else {
@@ -96,6 +270,10 @@ impl<R: 'static + RenderContext> PropertiesComputable<R> for CommonProperties {
         handle_vtable_update_optional!(rtc, self.anchor_y, Size);
         handle_vtable_update_optional!(rtc, self.x, Size);
         handle_vtable_update_optional!(rtc, self.y, Size);
+        handle_vtable_update_optional!(rtc, self.focusable, bool);
+        handle_vtable_update_optional!(rtc, self.tab_index, isize);
+        handle_vtable_update_optional!(rtc, self.opacity, f64);
+        handle_vtable_update_optional!(rtc, self.blend_mode, BlendMode);
     }
 }
 
@@ -104,6 +282,10 @@ impl<'a, R: 'static + RenderContext> RenderTreeContext<'a, R> {
         RuntimeContext {
             bounds_parent: self.bounds,
             frames_elapsed: self.engine.frames_elapsed,
+            timeline_playhead_position: self.timeline_playhead_position,
+            timeline: self.timeline.clone(),
+            id_chain: self.get_id_chain(self.node.borrow().get_instance_id()),
+            focus_requests: Rc::clone(&self.engine.focus_requests),
         }
     }
 }
@@ -111,15 +293,18 @@ impl<'a, R: 'static + RenderContext> RenderTreeContext<'a, R> {
 impl<'a, R: 'static + RenderContext> Clone for RenderTreeContext<'a, R> {
     fn clone(&self) -> Self {
         RenderTreeContext {
-            engine: &self.engine,
-            transform_global: self.transform_global.clone(),
-            transform_scroller_reset: self.transform_scroller_reset.clone(),
-            bounds: self.bounds.clone(),
+            engine: self.engine,
+            transform_global: self.transform_global,
+            transform_scroller_reset: self.transform_scroller_reset,
+            bounds: self.bounds,
             runtime: Rc::clone(&self.runtime),
             node: Rc::clone(&self.node),
             parent_repeat_expanded_node: self.parent_repeat_expanded_node.clone(),
-            timeline_playhead_position: self.timeline_playhead_position.clone(),
+            timeline_playhead_position: self.timeline_playhead_position,
+            timeline: self.timeline.clone(),
             inherited_adoptees: self.inherited_adoptees.clone(),
+            opacity: self.opacity,
+            blend_mode: self.blend_mode,
         }
     }
 }
@@ -130,9 +315,10 @@ impl<'a, R: RenderContext> RenderTreeContext<'a, R> {
         transition_manager: Option<&mut TransitionManager<T>>,
     ) -> Option<T> {
         if let Some(tm) = transition_manager {
-            if tm.queue.len() > 0 {
+            if !tm.queue.is_empty() {
+                self.engine.request_next_frame();
                 let current_transition = tm.queue.get_mut(0).unwrap();
-                if let None = current_transition.global_frame_started {
+                if current_transition.global_frame_started.is_none() {
                     current_transition.global_frame_started = Some(self.engine.frames_elapsed);
                 }
                 let progress = (1.0 + self.engine.frames_elapsed as f64
@@ -185,67 +371,190 @@ impl<'a, R: RenderContext> RenderTreeContext<'a, R> {
         indices
     }
 
+    /// Evaluates the expression with the given `vtable_id` against the current stack frame.
+    /// Returns `None` if there is no such expression, or if none of the dependencies read by its previous
+    /// evaluation (against this same stack frame) have changed since, in which case the previously computed value still stands.
     pub fn compute_vtable_value(&self, vtable_id: Option<usize>) -> Option<TypesCoproduct> {
-        if let Some(id) = vtable_id {
-            if let Some(evaluator) = self.engine.expression_table.get(&id) {
-                let ec = ExpressionContext {
-                    engine: self.engine,
-                    stack_frame: Rc::clone(
-                        &(*self.runtime).borrow_mut().peek_stack_frame().unwrap(),
-                    ),
-                };
-                return Some((**evaluator)(ec));
+        let id = vtable_id?;
+        let evaluator = self.engine.expression_table.get(&id)?;
+        let stack_frame = Rc::clone(&(*self.runtime).borrow_mut().peek_stack_frame().unwrap());
+        let scope = (*stack_frame).borrow().get_properties();
+        let cache_key = (id, Rc::as_ptr(&scope));
+
+        if let Some(evaluation) = self.engine.expression_dependencies.borrow().get(&cache_key) {
+            let is_same_scope = evaluation
+                .scope
+                .upgrade()
+                .map_or(false, |cached_scope| Rc::ptr_eq(&cached_scope, &scope));
+            if is_same_scope && !evaluation.dependencies.have_changed() {
+                //Whatever encloses this evaluation (e.g. a subtree traversal) depends on what it depends on
+                reactivity::notify_read_all(&evaluation.dependencies);
+                return None;
             }
-        } //FUTURE: for timelines: else if present in timeline vtable...
+        }
 
-        None
+        let ec = ExpressionContext {
+            engine: self.engine,
+            stack_frame,
+        };
+        let (value, dependencies) = reactivity::track_dependencies(|| {
+            reactivity::notify_read(reactivity::get_expression_property_id(id));
+            (**evaluator)(ec)
+        });
+        self.engine.expression_dependencies.borrow_mut().insert(
+            cache_key,
+            ExpressionEvaluation {
+                scope: Rc::downgrade(&scope),
+                dependencies,
+            },
+        );
+        Some(value)
+    }
+
+    /// Evaluates the expression with the given `vtable_id` against the current stack frame, regardless of
+    /// whether its dependencies have changed.  Used for evaluations against transient stack frames, e.g. `Repeat` keys.
+    pub fn compute_vtable_value_uncached(
+        &self,
+        vtable_id: Option<usize>,
+    ) -> Option<TypesCoproduct> {
+        let evaluator = self.engine.expression_table.get(&vtable_id?)?;
+        let ec = ExpressionContext {
+            engine: self.engine,
+            stack_frame: Rc::clone(&(*self.runtime).borrow_mut().peek_stack_frame().unwrap()),
+        };
+        Some((**evaluator)(ec))
     }
 }
 
+/// An expression, compiled into the cartridge's expression table (or interpreted, when hot reloading), which
+/// evaluates to its value wrapped in a `TypesCoproduct`
+pub type ExpressionFn<R> = Box<dyn Fn(ExpressionContext<R>) -> TypesCoproduct>;
+
+/// Decoded images by the id_chain of the `Image` that loaded them: (RGBA pixels, width, height)
+pub type ImageMap = HashMap<Vec<u32>, (Box<Vec<u8>>, usize, usize)>;
+
+/// A handler for events with args `T`, bound with e.g. `@click=self.handle_click`, or with
+/// e.g. `@click_capture=self.handle_click` to be called during the capture phase; see `Event`
+pub type EventHandler<R, T> = fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, Event<T>);
+
+/// A handler for a lifecycle event, e.g. `did_mount`, called with the properties of the component that declares it
+pub type LifecycleHandler = fn(Rc<RefCell<PropertiesCoproduct>>, RuntimeContext);
+
+/// A lifecycle handler fired after a tick in which any of its observed properties changed: all of a component's
+/// properties for `did_update`, or a single property for e.g. `on_change(count)`
+pub struct PropertyObserver {
+    pub get_observed_property_ids: fn(&PropertiesCoproduct) -> Vec<PropertyId>,
+    pub handler: LifecycleHandler,
+}
+
 pub struct HandlerRegistry<R: 'static + RenderContext> {
-    pub scroll_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsScroll)>,
-    pub jab_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsJab)>,
-    pub touch_start_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsTouchStart)>,
-    pub touch_move_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsTouchMove)>,
-    pub touch_end_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsTouchEnd)>,
-    pub key_down_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsKeyDown)>,
-    pub key_up_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsKeyUp)>,
-    pub key_press_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsKeyPress)>,
-    pub click_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsClick)>,
-    pub mouse_down_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsMouseDown)>,
-    pub mouse_up_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsMouseUp)>,
-    pub mouse_move_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsMouseMove)>,
-    pub mouse_over_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsMouseOver)>,
-    pub mouse_out_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsMouseOut)>,
-    pub double_click_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsDoubleClick)>,
-    pub context_menu_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsContextMenu)>,
-    pub wheel_handlers: Vec<fn(Rc<RefCell<StackFrame<R>>>, RuntimeContext, ArgsWheel)>,
-    pub will_render_handlers: Vec<fn(Rc<RefCell<PropertiesCoproduct>>, RuntimeContext)>,
-    pub did_mount_handlers: Vec<fn(Rc<RefCell<PropertiesCoproduct>>, RuntimeContext)>,
+    pub scroll_handlers: Vec<EventHandler<R, ArgsScroll>>,
+    pub scroll_capture_handlers: Vec<EventHandler<R, ArgsScroll>>,
+    pub jab_handlers: Vec<EventHandler<R, ArgsJab>>,
+    pub jab_capture_handlers: Vec<EventHandler<R, ArgsJab>>,
+    pub touch_start_handlers: Vec<EventHandler<R, ArgsTouchStart>>,
+    pub touch_start_capture_handlers: Vec<EventHandler<R, ArgsTouchStart>>,
+    pub touch_move_handlers: Vec<EventHandler<R, ArgsTouchMove>>,
+    pub touch_move_capture_handlers: Vec<EventHandler<R, ArgsTouchMove>>,
+    pub touch_end_handlers: Vec<EventHandler<R, ArgsTouchEnd>>,
+    pub touch_end_capture_handlers: Vec<EventHandler<R, ArgsTouchEnd>>,
+    pub key_down_handlers: Vec<EventHandler<R, ArgsKeyDown>>,
+    pub key_down_capture_handlers: Vec<EventHandler<R, ArgsKeyDown>>,
+    pub key_up_handlers: Vec<EventHandler<R, ArgsKeyUp>>,
+    pub key_up_capture_handlers: Vec<EventHandler<R, ArgsKeyUp>>,
+    pub key_press_handlers: Vec<EventHandler<R, ArgsKeyPress>>,
+    pub key_press_capture_handlers: Vec<EventHandler<R, ArgsKeyPress>>,
+    pub click_handlers: Vec<EventHandler<R, ArgsClick>>,
+    pub click_capture_handlers: Vec<EventHandler<R, ArgsClick>>,
+    pub mouse_down_handlers: Vec<EventHandler<R, ArgsMouseDown>>,
+    pub mouse_down_capture_handlers: Vec<EventHandler<R, ArgsMouseDown>>,
+    pub mouse_up_handlers: Vec<EventHandler<R, ArgsMouseUp>>,
+    pub mouse_up_capture_handlers: Vec<EventHandler<R, ArgsMouseUp>>,
+    pub mouse_move_handlers: Vec<EventHandler<R, ArgsMouseMove>>,
+    pub mouse_move_capture_handlers: Vec<EventHandler<R, ArgsMouseMove>>,
+    pub mouse_over_handlers: Vec<EventHandler<R, ArgsMouseOver>>,
+    pub mouse_over_capture_handlers: Vec<EventHandler<R, ArgsMouseOver>>,
+    pub mouse_out_handlers: Vec<EventHandler<R, ArgsMouseOut>>,
+    pub mouse_out_capture_handlers: Vec<EventHandler<R, ArgsMouseOut>>,
+    pub double_click_handlers: Vec<EventHandler<R, ArgsDoubleClick>>,
+    pub double_click_capture_handlers: Vec<EventHandler<R, ArgsDoubleClick>>,
+    pub context_menu_handlers: Vec<EventHandler<R, ArgsContextMenu>>,
+    pub context_menu_capture_handlers: Vec<EventHandler<R, ArgsContextMenu>>,
+    pub wheel_handlers: Vec<EventHandler<R, ArgsWheel>>,
+    pub wheel_capture_handlers: Vec<EventHandler<R, ArgsWheel>>,
+    pub button_click_handlers: Vec<EventHandler<R, ArgsButtonClick>>,
+    pub button_click_capture_handlers: Vec<EventHandler<R, ArgsButtonClick>>,
+    pub slider_change_handlers: Vec<EventHandler<R, ArgsSliderChange>>,
+    pub slider_change_capture_handlers: Vec<EventHandler<R, ArgsSliderChange>>,
+    pub dropdown_change_handlers: Vec<EventHandler<R, ArgsDropdownChange>>,
+    pub dropdown_change_capture_handlers: Vec<EventHandler<R, ArgsDropdownChange>>,
+    pub textbox_change_handlers: Vec<EventHandler<R, ArgsTextboxChange>>,
+    pub textbox_change_capture_handlers: Vec<EventHandler<R, ArgsTextboxChange>>,
+    pub focus_handlers: Vec<EventHandler<R, ArgsFocus>>,
+    pub focus_capture_handlers: Vec<EventHandler<R, ArgsFocus>>,
+    pub blur_handlers: Vec<EventHandler<R, ArgsBlur>>,
+    pub blur_capture_handlers: Vec<EventHandler<R, ArgsBlur>>,
+    pub will_render_handlers: Vec<LifecycleHandler>,
+    pub did_mount_handlers: Vec<LifecycleHandler>,
+    pub will_unmount_handlers: Vec<LifecycleHandler>,
+    pub did_update_handlers: Vec<PropertyObserver>,
+    pub on_change_handlers: Vec<PropertyObserver>,
 }
 
 impl<R: 'static + RenderContext> Default for HandlerRegistry<R> {
     fn default() -> Self {
         HandlerRegistry {
             scroll_handlers: Vec::new(),
+            scroll_capture_handlers: Vec::new(),
             jab_handlers: Vec::new(),
+            jab_capture_handlers: Vec::new(),
             touch_start_handlers: Vec::new(),
+            touch_start_capture_handlers: Vec::new(),
             touch_move_handlers: Vec::new(),
+            touch_move_capture_handlers: Vec::new(),
             touch_end_handlers: Vec::new(),
+            touch_end_capture_handlers: Vec::new(),
             key_down_handlers: Vec::new(),
+            key_down_capture_handlers: Vec::new(),
             key_up_handlers: Vec::new(),
+            key_up_capture_handlers: Vec::new(),
             key_press_handlers: Vec::new(),
+            key_press_capture_handlers: Vec::new(),
             click_handlers: Vec::new(),
+            click_capture_handlers: Vec::new(),
             mouse_down_handlers: Vec::new(),
+            mouse_down_capture_handlers: Vec::new(),
             mouse_up_handlers: Vec::new(),
+            mouse_up_capture_handlers: Vec::new(),
             mouse_move_handlers: Vec::new(),
+            mouse_move_capture_handlers: Vec::new(),
             mouse_over_handlers: Vec::new(),
+            mouse_over_capture_handlers: Vec::new(),
             mouse_out_handlers: Vec::new(),
+            mouse_out_capture_handlers: Vec::new(),
             double_click_handlers: Vec::new(),
+            double_click_capture_handlers: Vec::new(),
             context_menu_handlers: Vec::new(),
+            context_menu_capture_handlers: Vec::new(),
             wheel_handlers: Vec::new(),
+            wheel_capture_handlers: Vec::new(),
+            button_click_handlers: Vec::new(),
+            button_click_capture_handlers: Vec::new(),
+            slider_change_handlers: Vec::new(),
+            slider_change_capture_handlers: Vec::new(),
+            dropdown_change_handlers: Vec::new(),
+            dropdown_change_capture_handlers: Vec::new(),
+            textbox_change_handlers: Vec::new(),
+            textbox_change_capture_handlers: Vec::new(),
+            focus_handlers: Vec::new(),
+            focus_capture_handlers: Vec::new(),
+            blur_handlers: Vec::new(),
+            blur_capture_handlers: Vec::new(),
             will_render_handlers: Vec::new(),
             did_mount_handlers: Vec::new(),
+            will_unmount_handlers: Vec::new(),
+            did_update_handlers: Vec::new(),
+            on_change_handlers: Vec::new(),
         }
     }
 }
@@ -254,326 +563,282 @@ impl<R: 'static + RenderContext> Default for HandlerRegistry<R> {
 /// a `for j in 0..4` would have 12 repeat-expanded nodes representing the 12 virtual Rectangles in the
 /// rendered scene graph. These nodes are addressed uniquely by id_chain (see documentation for `get_id_chain`.)
 pub struct RepeatExpandedNode<R: 'static + RenderContext> {
-    #[allow(dead_code)]
     id_chain: Vec<u32>,
-    parent_repeat_expanded_node: Option<Weak<RepeatExpandedNode<R>>>,
+    /// Re-pointed when this node's subtree is reused beneath a re-traversed parent; see `RenderRecord`
+    parent_repeat_expanded_node: RefCell<Option<Weak<RepeatExpandedNode<R>>>>,
     instance_node: RenderNodePtr<R>,
     stack_frame: Rc<RefCell<crate::StackFrame<R>>>,
     tab: TransformAndBounds,
-    node_context: RuntimeContext,
+    node_context: RefCell<RuntimeContext>,
 }
 
 impl<R: 'static + RenderContext> RepeatExpandedNode<R> {
-    pub fn dispatch_scroll(&self, args_scroll: ArgsScroll) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().scroll_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_scroll.clone(),
-                );
-            });
-        }
-        (*self.instance_node)
-            .borrow_mut()
-            .handle_scroll(args_scroll.clone());
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_scroll(args_scroll);
-        }
+    fn get_parent(&self) -> Option<Rc<RepeatExpandedNode<R>>> {
+        self.parent_repeat_expanded_node
+            .borrow()
+            .as_ref()
+            .and_then(|weak| weak.upgrade())
     }
 
-    pub fn dispatch_jab(&self, args_jab: ArgsJab) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().jab_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_jab.clone(),
-                );
-            });
-        }
-
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_jab(args_jab);
+    /// This node followed by each of its ancestors, up to the root
+    fn get_ancestry(&self) -> Vec<Rc<RepeatExpandedNode<R>>> {
+        let mut ancestors = vec![];
+        let mut parent = self.get_parent();
+        while let Some(node) = parent {
+            parent = node.get_parent();
+            ancestors.push(node);
         }
+        ancestors
     }
 
-    pub fn dispatch_touch_start(&self, args_touch_start: ArgsTouchStart) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().touch_start_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_touch_start.clone(),
-                );
-            });
-        }
+    /// Dispatches an event targeting this node, DOM-style: first to the capture handlers of each node from the
+    /// root down to this one, then to the handlers of each node from this one back up to the root, stopping early
+    /// if a handler calls `stop_propagation`.  Returns whether a handler called `prevent_default`, in which case
+    /// the chassis should suppress the event's native default action.
+    fn dispatch<T: Clone>(
+        &self,
+        args: T,
+        get_capture_handlers: fn(&HandlerRegistry<R>) -> &Vec<EventHandler<R, T>>,
+        get_handlers: fn(&HandlerRegistry<R>) -> &Vec<EventHandler<R, T>>,
+    ) -> bool {
+        let event = Event::new(args);
+        let ancestors = self.get_ancestry();
+        let path: Vec<&RepeatExpandedNode<R>> = std::iter::once(self)
+            .chain(ancestors.iter().map(|node| node.as_ref()))
+            .collect();
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent
-                .upgrade()
-                .unwrap()
-                .dispatch_touch_start(args_touch_start);
+        let capture = path.iter().rev().map(|node| (node, get_capture_handlers));
+        let bubble = path.iter().map(|node| (node, get_handlers));
+        for (node, get_node_handlers) in capture.chain(bubble) {
+            node.call_handlers(&event, get_node_handlers);
+            if event.is_propagation_stopped() {
+                break;
+            }
         }
+        event.is_default_prevented()
     }
 
-    pub fn dispatch_touch_move(&self, args_touch_move: ArgsTouchMove) {
+    fn call_handlers<T: Clone>(
+        &self,
+        event: &Event<T>,
+        get_handlers: fn(&HandlerRegistry<R>) -> &Vec<EventHandler<R, T>>,
+    ) {
         if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().touch_move_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_touch_move.clone(),
-                );
-            });
-        }
-
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent
-                .upgrade()
-                .unwrap()
-                .dispatch_touch_move(args_touch_move);
+            get_handlers(&(*registry).borrow())
+                .iter()
+                .for_each(|handler| {
+                    handler(
+                        Rc::clone(&self.stack_frame),
+                        self.node_context.borrow().clone(),
+                        event.clone(),
+                    );
+                });
         }
     }
 
-    pub fn dispatch_touch_end(&self, args_touch_end: ArgsTouchEnd) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().touch_end_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_touch_end.clone(),
-                );
-            });
-        }
-
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_touch_end(args_touch_end);
+    /// Besides calling handlers, scrolls each `Scroller` from this node up to the root, unless a handler prevents that
+    pub fn dispatch_scroll(&self, args_scroll: ArgsScroll) -> bool {
+        let default_prevented = self.dispatch(
+            args_scroll.clone(),
+            |registry| &registry.scroll_capture_handlers,
+            |registry| &registry.scroll_handlers,
+        );
+        if !default_prevented {
+            (*self.instance_node)
+                .borrow_mut()
+                .handle_scroll(args_scroll.clone());
+            for node in self.get_ancestry() {
+                (*node.instance_node)
+                    .borrow_mut()
+                    .handle_scroll(args_scroll.clone());
+            }
         }
+        default_prevented
     }
 
-    pub fn dispatch_key_down(&self, args_key_down: ArgsKeyDown) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().key_down_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_key_down.clone(),
-                );
-            });
-        }
-
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_key_down(args_key_down);
-        }
+    pub fn dispatch_jab(&self, args_jab: ArgsJab) -> bool {
+        self.dispatch(
+            args_jab,
+            |registry| &registry.jab_capture_handlers,
+            |registry| &registry.jab_handlers,
+        )
     }
 
-    pub fn dispatch_key_up(&self, args_key_up: ArgsKeyUp) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().key_up_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_key_up.clone(),
-                );
-            });
-        }
-
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_key_up(args_key_up);
-        }
+    pub fn dispatch_touch_start(&self, args_touch_start: ArgsTouchStart) -> bool {
+        self.dispatch(
+            args_touch_start,
+            |registry| &registry.touch_start_capture_handlers,
+            |registry| &registry.touch_start_handlers,
+        )
     }
 
-    pub fn dispatch_key_press(&self, args_key_press: ArgsKeyPress) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().key_press_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_key_press.clone(),
-                );
-            });
-        }
+    pub fn dispatch_touch_move(&self, args_touch_move: ArgsTouchMove) -> bool {
+        self.dispatch(
+            args_touch_move,
+            |registry| &registry.touch_move_capture_handlers,
+            |registry| &registry.touch_move_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_key_press(args_key_press);
-        }
+    pub fn dispatch_touch_end(&self, args_touch_end: ArgsTouchEnd) -> bool {
+        self.dispatch(
+            args_touch_end,
+            |registry| &registry.touch_end_capture_handlers,
+            |registry| &registry.touch_end_handlers,
+        )
     }
 
-    pub fn dispatch_click(&self, args_click: ArgsClick) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().click_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_click.clone(),
-                );
-            });
-        }
+    pub fn dispatch_key_down(&self, args_key_down: ArgsKeyDown) -> bool {
+        self.dispatch(
+            args_key_down,
+            |registry| &registry.key_down_capture_handlers,
+            |registry| &registry.key_down_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_click(args_click);
-        }
+    pub fn dispatch_key_up(&self, args_key_up: ArgsKeyUp) -> bool {
+        self.dispatch(
+            args_key_up,
+            |registry| &registry.key_up_capture_handlers,
+            |registry| &registry.key_up_handlers,
+        )
     }
 
-    pub fn dispatch_mouse_down(&self, args_mouse_down: ArgsMouseDown) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().mouse_down_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_mouse_down.clone(),
-                );
-            });
-        }
+    pub fn dispatch_key_press(&self, args_key_press: ArgsKeyPress) -> bool {
+        self.dispatch(
+            args_key_press,
+            |registry| &registry.key_press_capture_handlers,
+            |registry| &registry.key_press_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent
-                .upgrade()
-                .unwrap()
-                .dispatch_mouse_down(args_mouse_down);
-        }
+    pub fn dispatch_click(&self, args_click: ArgsClick) -> bool {
+        self.dispatch(
+            args_click,
+            |registry| &registry.click_capture_handlers,
+            |registry| &registry.click_handlers,
+        )
     }
 
-    pub fn dispatch_mouse_up(&self, args_mouse_up: ArgsMouseUp) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().mouse_up_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_mouse_up.clone(),
-                );
-            });
-        }
+    pub fn dispatch_mouse_down(&self, args_mouse_down: ArgsMouseDown) -> bool {
+        self.dispatch(
+            args_mouse_down,
+            |registry| &registry.mouse_down_capture_handlers,
+            |registry| &registry.mouse_down_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_mouse_up(args_mouse_up);
-        }
+    pub fn dispatch_mouse_up(&self, args_mouse_up: ArgsMouseUp) -> bool {
+        self.dispatch(
+            args_mouse_up,
+            |registry| &registry.mouse_up_capture_handlers,
+            |registry| &registry.mouse_up_handlers,
+        )
     }
 
-    pub fn dispatch_mouse_move(&self, args_mouse_move: ArgsMouseMove) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().mouse_move_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_mouse_move.clone(),
-                );
-            });
-        }
+    pub fn dispatch_mouse_move(&self, args_mouse_move: ArgsMouseMove) -> bool {
+        self.dispatch(
+            args_mouse_move,
+            |registry| &registry.mouse_move_capture_handlers,
+            |registry| &registry.mouse_move_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent
-                .upgrade()
-                .unwrap()
-                .dispatch_mouse_move(args_mouse_move);
-        }
+    pub fn dispatch_mouse_over(&self, args_mouse_over: ArgsMouseOver) -> bool {
+        self.dispatch(
+            args_mouse_over,
+            |registry| &registry.mouse_over_capture_handlers,
+            |registry| &registry.mouse_over_handlers,
+        )
     }
 
-    pub fn dispatch_mouse_over(&self, args_mouse_over: ArgsMouseOver) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().mouse_over_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_mouse_over.clone(),
-                );
-            });
-        }
+    pub fn dispatch_mouse_out(&self, args_mouse_out: ArgsMouseOut) -> bool {
+        self.dispatch(
+            args_mouse_out,
+            |registry| &registry.mouse_out_capture_handlers,
+            |registry| &registry.mouse_out_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent
-                .upgrade()
-                .unwrap()
-                .dispatch_mouse_over(args_mouse_over);
-        }
+    pub fn dispatch_double_click(&self, args_double_click: ArgsDoubleClick) -> bool {
+        self.dispatch(
+            args_double_click,
+            |registry| &registry.double_click_capture_handlers,
+            |registry| &registry.double_click_handlers,
+        )
     }
 
-    pub fn dispatch_mouse_out(&self, args_mouse_out: ArgsMouseOut) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().mouse_out_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_mouse_out.clone(),
-                );
-            });
-        }
+    pub fn dispatch_context_menu(&self, args_context_menu: ArgsContextMenu) -> bool {
+        self.dispatch(
+            args_context_menu,
+            |registry| &registry.context_menu_capture_handlers,
+            |registry| &registry.context_menu_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_mouse_out(args_mouse_out);
-        }
+    pub fn dispatch_wheel(&self, args_wheel: ArgsWheel) -> bool {
+        self.dispatch(
+            args_wheel,
+            |registry| &registry.wheel_capture_handlers,
+            |registry| &registry.wheel_handlers,
+        )
     }
 
-    pub fn dispatch_double_click(&self, args_double_click: ArgsDoubleClick) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().double_click_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_double_click.clone(),
-                );
-            });
-        }
+    pub fn dispatch_button_click(&self, args_button_click: ArgsButtonClick) -> bool {
+        self.dispatch(
+            args_button_click,
+            |registry| &registry.button_click_capture_handlers,
+            |registry| &registry.button_click_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent
-                .upgrade()
-                .unwrap()
-                .dispatch_double_click(args_double_click);
-        }
+    pub fn dispatch_slider_change(&self, args_slider_change: ArgsSliderChange) -> bool {
+        (*self.instance_node)
+            .borrow_mut()
+            .handle_slider_change(&self.id_chain, &args_slider_change);
+        self.dispatch(
+            args_slider_change,
+            |registry| &registry.slider_change_capture_handlers,
+            |registry| &registry.slider_change_handlers,
+        )
     }
 
-    pub fn dispatch_context_menu(&self, args_context_menu: ArgsContextMenu) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().context_menu_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_context_menu.clone(),
-                );
-            });
-        }
+    pub fn dispatch_dropdown_change(&self, args_dropdown_change: ArgsDropdownChange) -> bool {
+        (*self.instance_node)
+            .borrow_mut()
+            .handle_dropdown_change(&self.id_chain, &args_dropdown_change);
+        self.dispatch(
+            args_dropdown_change,
+            |registry| &registry.dropdown_change_capture_handlers,
+            |registry| &registry.dropdown_change_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent
-                .upgrade()
-                .unwrap()
-                .dispatch_context_menu(args_context_menu);
-        }
+    pub fn dispatch_textbox_change(&self, args_textbox_change: ArgsTextboxChange) -> bool {
+        (*self.instance_node)
+            .borrow_mut()
+            .handle_textbox_change(&self.id_chain, &args_textbox_change);
+        self.dispatch(
+            args_textbox_change,
+            |registry| &registry.textbox_change_capture_handlers,
+            |registry| &registry.textbox_change_handlers,
+        )
     }
 
-    pub fn dispatch_wheel(&self, args_wheel: ArgsWheel) {
-        if let Some(registry) = (*self.instance_node).borrow().get_handler_registry() {
-            let handlers = &(*registry).borrow().wheel_handlers;
-            handlers.iter().for_each(|handler| {
-                handler(
-                    Rc::clone(&self.stack_frame),
-                    self.node_context.clone(),
-                    args_wheel.clone(),
-                );
-            });
-        }
+    pub fn dispatch_focus(&self, args_focus: ArgsFocus) -> bool {
+        self.dispatch(
+            args_focus,
+            |registry| &registry.focus_capture_handlers,
+            |registry| &registry.focus_handlers,
+        )
+    }
 
-        if let Some(parent) = &self.parent_repeat_expanded_node {
-            parent.upgrade().unwrap().dispatch_wheel(args_wheel);
-        }
+    pub fn dispatch_blur(&self, args_blur: ArgsBlur) -> bool {
+        self.dispatch(
+            args_blur,
+            |registry| &registry.blur_capture_handlers,
+            |registry| &registry.blur_handlers,
+        )
     }
 }
 
@@ -591,9 +856,21 @@ pub struct InstanceRegistry<R: 'static + RenderContext> {
     mounted_set: HashSet<Vec<u32>>,
     ///tracks whichs instance nodes are marked for unmounting, to be done at the correct point in the render tree lifecycle
     marked_for_unmount_set: HashSet<u32>,
+    ///the revision (see `reactivity::current_revision`) at which each mounted repeat-expanded element last fired its
+    ///`did_update` and `on_change` handlers, against which changes to their observed properties are checked
+    observed_revisions: HashMap<Vec<u32>, u64>,
 
     ///register holding the next value to mint as an id
     next_id: u32,
+
+    ///live instances of each component, by type_id, tracked for hot reloading (see `PaxEngine::hot_reload`)
+    component_instances: HashMap<String, Vec<Weak<RefCell<ComponentInstance<R>>>>>,
+}
+
+impl<R: 'static + RenderContext> Default for InstanceRegistry<R> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<R: 'static + RenderContext> InstanceRegistry<R> {
@@ -601,15 +878,17 @@ impl<R: 'static + RenderContext> InstanceRegistry<R> {
         Self {
             mounted_set: HashSet::new(),
             marked_for_unmount_set: HashSet::new(),
+            observed_revisions: HashMap::new(),
             instance_map: HashMap::new(),
             repeat_expanded_node_cache: vec![],
             next_id: 0,
+            component_instances: HashMap::new(),
         }
     }
 
     pub fn mint_id(&mut self) -> u32 {
         let new_id = self.next_id;
-        self.next_id = self.next_id + 1;
+        self.next_id += 1;
         new_id
     }
 
@@ -621,10 +900,46 @@ impl<R: 'static + RenderContext> InstanceRegistry<R> {
         self.instance_map.remove(&instance_id);
     }
 
+    pub fn is_registered(&self, instance_id: u32) -> bool {
+        self.instance_map.contains_key(&instance_id)
+    }
+
+    pub fn register_component_instance(
+        &mut self,
+        type_id: &str,
+        instance: &Rc<RefCell<ComponentInstance<R>>>,
+    ) {
+        self.component_instances
+            .entry(type_id.to_string())
+            .or_default()
+            .push(Rc::downgrade(instance));
+    }
+
+    /// The live instances of the component `type_id`, as registered with `register_component_instance`
+    pub fn get_component_instances(
+        &mut self,
+        type_id: &str,
+    ) -> Vec<Rc<RefCell<ComponentInstance<R>>>> {
+        match self.component_instances.get_mut(type_id) {
+            Some(instances) => {
+                instances.retain(|instance| instance.strong_count() > 0);
+                instances.iter().filter_map(Weak::upgrade).collect()
+            }
+            None => vec![],
+        }
+    }
+
     pub fn mark_mounted(&mut self, id_chain: Vec<u32>) {
+        self.observed_revisions
+            .insert(id_chain.clone(), reactivity::current_revision());
         self.mounted_set.insert(id_chain);
     }
 
+    pub fn mark_unmounted(&mut self, id_chain: &Vec<u32>) {
+        self.observed_revisions.remove(id_chain);
+        self.mounted_set.remove(id_chain);
+    }
+
     pub fn is_mounted(&self, id_chain: &Vec<u32>) -> bool {
         self.mounted_set.contains(id_chain)
     }
@@ -649,7 +964,7 @@ impl<R: 'static + RenderContext> InstanceRegistry<R> {
 impl<R: 'static + RenderContext> PaxEngine<R> {
     pub fn new(
         main_component_instance: Rc<RefCell<ComponentInstance<R>>>,
-        expression_table: HashMap<usize, Box<dyn Fn(ExpressionContext<R>) -> TypesCoproduct>>,
+        expression_table: HashMap<usize, ExpressionFn<R>>,
         logger: pax_runtime_api::PlatformSpecificLogger,
         viewport_size: (f64, f64),
         instance_registry: Rc<RefCell<InstanceRegistry<R>>>,
@@ -667,13 +982,78 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
                 clipping_bounds: Some(viewport_size),
             },
             image_map: HashMap::new(),
+            expression_dependencies: RefCell::new(HashMap::new()),
+            has_per_frame_work: Cell::new(false),
+            is_invalidated: true,
+            last_traversal_revision: None,
+            last_render_record: None,
+            focused_id_chain: RefCell::new(None),
+            focus_requests: Rc::new(RefCell::new(vec![])),
+            compositor: None,
+        }
+    }
+
+    /// Registers the chassis' means of applying opacity and blend mode to the drawing context with the given id.
+    /// Before drawing a subtree whose root sets `opacity` or `blend_mode`, the engine `save`s each drawing
+    /// context and `begin`s compositing it with the accumulated values; after the subtree is drawn, it `end`s
+    /// compositing and `restore`s each context.  Without a compositor, `opacity` and `blend_mode` affect only
+    /// native elements.
+    pub fn set_compositor(&mut self, compositor: Box<dyn Compositor>) {
+        self.compositor = Some(compositor);
+    }
+
+    /// `save`s each drawing context and `begin`s compositing it, returning the ids of the contexts saved, to be
+    /// passed to `end_compositing` once the subtree is drawn.  A context that fails to `save` is drawn to
+    /// uncomposited, rather than later `restore`d past the state it had before.
+    fn begin_compositing(
+        &self,
+        rcs: &mut HashMap<String, R>,
+        opacity: f64,
+        blend_mode: BlendMode,
+    ) -> Vec<String> {
+        rcs.iter_mut()
+            .filter_map(|(canvas_id, rc)| match rc.save() {
+                Ok(()) => {
+                    if let Some(compositor) = &self.compositor {
+                        compositor.begin(canvas_id, opacity, blend_mode);
+                    }
+                    Some(canvas_id.clone())
+                }
+                Err(err) => {
+                    pax_runtime_api::log(&format!(
+                        "Failed to save drawing context {} for compositing: {}",
+                        canvas_id, err
+                    ));
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// `end`s compositing and `restore`s the drawing contexts saved by `begin_compositing`
+    fn end_compositing(&self, rcs: &mut HashMap<String, R>, saved_canvas_ids: &[String]) {
+        for canvas_id in saved_canvas_ids {
+            if let Some(rc) = rcs.get_mut(canvas_id) {
+                if let Some(compositor) = &self.compositor {
+                    compositor.end(canvas_id);
+                }
+                if let Err(err) = rc.restore() {
+                    pax_runtime_api::log(&format!(
+                        "Failed to restore drawing context {} after compositing: {}",
+                        canvas_id, err
+                    ));
+                }
+            }
         }
     }
 
+    /// Traverses and renders the render tree, reusing the subtrees of `previous_render_record` that are still clean,
+    /// and returns the enqueued native messages along with the record of this traversal
     fn traverse_render_tree(
         &self,
         rcs: &mut HashMap<String, R>,
-    ) -> Vec<pax_message::NativeMessage> {
+        previous_render_record: Option<Rc<RenderRecord<R>>>,
+    ) -> (Vec<pax_message::NativeMessage>, Rc<RenderRecord<R>>) {
         //Broadly:
         // 1. compute properties
         // 2. find lowest node (last child of last node), accumulating transform along the way
@@ -682,7 +1062,7 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         let cast_component_rc: RenderNodePtr<R> = self.main_component.clone();
 
         let mut rtc = RenderTreeContext {
-            engine: &self,
+            engine: self,
             transform_global: Affine::default(),
             transform_scroller_reset: Affine::default(),
             bounds: self.viewport_tab.bounds,
@@ -690,24 +1070,106 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             node: Rc::clone(&cast_component_rc),
             parent_repeat_expanded_node: None,
             timeline_playhead_position: self.frames_elapsed,
+            timeline: None,
             inherited_adoptees: None,
+            opacity: 1.0,
+            blend_mode: BlendMode::default(),
         };
 
         let mut z_index = ZIndex::new(None);
-        self.recurse_traverse_render_tree(
+        let render_record = self.traverse_subtree(
             &mut rtc,
             rcs,
             Rc::clone(&cast_component_rc),
             &mut z_index,
             false,
+            previous_render_record,
         );
         //reset the marked_for_unmount set
         self.instance_registry.borrow_mut().marked_for_unmount_set = HashSet::new();
 
         let native_render_queue = (*self.runtime).borrow_mut().take_native_message_queue();
-        native_render_queue.into()
+        (native_render_queue.into(), render_record)
+    }
+
+    /// Traverses and renders the subtree rooted at `node`, unless it is clean: that is, unless `previous` was recorded
+    /// for `node` with the same inputs, and nothing read while recording it has changed since.  A clean subtree's
+    /// record is reused as is and painted from, so its properties aren't recomputed, its layout isn't redone, and its
+    /// lifecycle handlers don't fire, save for `will_render`, which fires on every tick that renders.
+    fn traverse_subtree(
+        &self,
+        rtc: &mut RenderTreeContext<R>,
+        rcs: &mut HashMap<String, R>,
+        node: RenderNodePtr<R>,
+        z_index_info: &mut ZIndex,
+        marked_for_unmount: bool,
+        previous: Option<Rc<RenderRecord<R>>>,
+    ) -> Rc<RenderRecord<R>> {
+        let inputs = TraversalInputs::new(rtc, z_index_info);
+        if let Some(previous) = &previous {
+            let is_clean = !marked_for_unmount
+                && !previous.is_repeated
+                && is_same_node(&previous.node, &node)
+                && previous.inputs == inputs
+                && !previous.has_per_frame_work
+                && !previous.dependencies.have_tracked_dependencies_changed()
+                && self
+                    .instance_registry
+                    .borrow()
+                    .marked_for_unmount_set
+                    .is_empty();
+            if is_clean {
+                *previous
+                    .repeat_expanded_node
+                    .parent_repeat_expanded_node
+                    .borrow_mut() = rtc.parent_repeat_expanded_node.clone();
+                self.reuse_render_record(previous);
+                self.paint_render_record(previous, rcs);
+                //Whatever encloses this subtree depends on what it depends on
+                reactivity::notify_read_all(&previous.dependencies);
+                return Rc::clone(previous);
+            }
+        }
+
+        let had_per_frame_work = self.has_per_frame_work.replace(false);
+        let (mut render_record, mut dependencies) = reactivity::track_dependencies(|| {
+            self.recurse_traverse_render_tree(
+                rtc,
+                rcs,
+                node,
+                z_index_info,
+                marked_for_unmount,
+                previous.as_deref(),
+                inputs,
+            )
+        });
+        //Changes made while traversing the subtree precede the reads that depend on them (e.g. a computed property
+        //is set before it's read), except for changes made by handlers, which request another frame instead
+        dependencies.disregard_changes_until_now();
+        render_record.dependencies = dependencies;
+        render_record.has_per_frame_work = self.has_per_frame_work.get();
+        self.has_per_frame_work
+            .set(had_per_frame_work || render_record.has_per_frame_work);
+        Rc::new(render_record)
+    }
+
+    /// Re-adds the repeat-expanded nodes of a reused subtree to the cache, in traversal order, as if it had been traversed
+    fn reuse_render_record(&self, render_record: &RenderRecord<R>) {
+        let repeat_expanded_node = &render_record.repeat_expanded_node;
+        repeat_expanded_node
+            .node_context
+            .borrow_mut()
+            .frames_elapsed = self.frames_elapsed;
+        (*self.instance_registry)
+            .borrow_mut()
+            .add_to_repeat_expanded_node_cache(Rc::clone(repeat_expanded_node));
+        render_record
+            .children
+            .iter()
+            .for_each(|child| self.reuse_render_record(child));
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn recurse_traverse_render_tree(
         &self,
         rtc: &mut RenderTreeContext<R>,
@@ -715,7 +1177,9 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         node: RenderNodePtr<R>,
         z_index_info: &mut ZIndex,
         marked_for_unmount: bool,
-    ) {
+        previous: Option<&RenderRecord<R>>,
+        inputs: TraversalInputs<R>,
+    ) -> RenderRecord<R> {
         //Recurse:
         //  - compute properties for this node
         //  - fire lifecycle events for this node
@@ -728,7 +1192,17 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         rtc.node = Rc::clone(&node);
 
         //lifecycle: compute_properties happens before rendering
+        let outer_stack_frame = rtc.runtime.borrow_mut().peek_stack_frame();
         node.borrow_mut().compute_properties(rtc);
+        //e.g. components push a stack frame for their subtree, which is popped by their `did_render`
+        let pushed_stack_frame =
+            rtc.runtime
+                .borrow_mut()
+                .peek_stack_frame()
+                .filter(|stack_frame| match &outer_stack_frame {
+                    Some(outer_stack_frame) => !Rc::ptr_eq(outer_stack_frame, stack_frame),
+                    None => true,
+                });
         let accumulated_transform = rtc.transform_global;
         let accumulated_scroller_normalized_transform = rtc.transform_scroller_reset;
         let accumulated_bounds = rtc.bounds;
@@ -739,16 +1213,27 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         z_index_info.update_z_index(node_type.clone());
         let current_z_index = z_index_info.get_level();
         let scroller_ids = (*rtc.engine.runtime).borrow().get_current_scroller_ids();
-        let scroller_id = match scroller_ids.last() {
-            None => None,
-            Some(v) => Some(v.clone()),
-        };
+        let scroller_id = scroller_ids.last().cloned();
         let canvas_id = ZIndex::generate_location_id(scroller_id.clone(), current_z_index);
 
-        //fire `did_mount` event if this is this node's first frame
+        //peek at the current stack frame and set a scoped playhead position as needed.
+        //This happens before lifecycle handlers fire, so that they receive the correct timeline
+        if let Some(stack_frame) = rtc.runtime.borrow_mut().peek_stack_frame() {
+            rtc.timeline_playhead_position =
+                stack_frame.borrow_mut().get_timeline_playhead_position();
+            rtc.timeline = stack_frame.borrow().get_timeline();
+        }
+
+        //grab Rc of properties from stack frame; pass to type-specific lifecycle handlers
+        //on instance in order to dispatch cartridge methods
+        let stack_frame = rtc.runtime.borrow_mut().peek_stack_frame();
+
+        //fire `did_mount` event if this is this node's first frame, otherwise `did_update` and `on_change` events
+        //for observed properties that changed since the previous frame.
         //Note that this must happen after initial `compute_properties`, which performs the
         //necessary side-effect of creating the `self` that must be passed to handlers
         {
+            let revision_before_handlers = reactivity::current_revision();
             let id = (*rtc.node).borrow().get_instance_id();
             let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
 
@@ -767,34 +1252,61 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
 
                 //Fire registered did_mount events
                 let registry = (*node).borrow().get_handler_registry();
-                if let Some(registry) = registry {
-                    //grab Rc of properties from stack frame; pass to type-specific handler
-                    //on instance in order to dispatch cartridge method
-                    match rtc.runtime.borrow_mut().peek_stack_frame() {
-                        Some(stack_frame) => {
-                            for handler in (*registry).borrow().did_mount_handlers.iter() {
-                                handler(
-                                    stack_frame.borrow_mut().get_properties(),
-                                    rtc.distill_userland_node_context(),
-                                );
-                            }
-                        }
-                        None => {}
+                if let (Some(registry), Some(stack_frame)) = (registry, &stack_frame) {
+                    for handler in (*registry).borrow().did_mount_handlers.iter() {
+                        let properties = stack_frame.borrow().get_properties();
+                        handler(properties, rtc.distill_userland_node_context());
                     }
                 }
                 instance_registry.mark_mounted(id_chain);
+            } else {
+                //Fire registered did_update and on_change events
+                let registry = (*node).borrow().get_handler_registry();
+                if let (Some(registry), Some(stack_frame)) = (registry, &stack_frame) {
+                    let revision = instance_registry.observed_revisions[&id_chain];
+                    let properties = stack_frame.borrow().get_properties();
+                    let registry = (*registry).borrow();
+                    for observer in registry
+                        .did_update_handlers
+                        .iter()
+                        .chain(registry.on_change_handlers.iter())
+                    {
+                        let has_changed =
+                            (observer.get_observed_property_ids)(&properties.borrow())
+                                .into_iter()
+                                .any(|id| reactivity::has_changed_since(id, revision));
+                        if has_changed {
+                            (observer.handler)(
+                                Rc::clone(&properties),
+                                rtc.distill_userland_node_context(),
+                            );
+                        }
+                    }
+                    //Changes made by the handlers themselves are not observed, lest they fire again next frame
+                    instance_registry
+                        .observed_revisions
+                        .insert(id_chain, reactivity::current_revision());
+                }
             }
-        }
 
-        //peek at the current stack frame and set a scoped playhead position as needed
-        match rtc.runtime.borrow_mut().peek_stack_frame() {
-            Some(stack_frame) => {
-                rtc.timeline_playhead_position = stack_frame
-                    .borrow_mut()
-                    .get_timeline_playhead_position()
-                    .clone();
+            //As with `will_render` below, handlers that change properties may have changed ones already read
+            //this tick, so this subtree must be traversed again next tick (see `traverse_subtree`)
+            if reactivity::current_revision() != revision_before_handlers {
+                self.request_next_frame();
+            }
+
+            //Observed properties must dirty this subtree even if nothing else in it reads them
+            let registry = (*node).borrow().get_handler_registry();
+            if let (Some(registry), Some(stack_frame)) = (registry, &stack_frame) {
+                let properties = stack_frame.borrow().get_properties();
+                let registry = (*registry).borrow();
+                registry
+                    .did_update_handlers
+                    .iter()
+                    .chain(registry.on_change_handlers.iter())
+                    .flat_map(|observer| (observer.get_observed_property_ids)(&properties.borrow()))
+                    .for_each(reactivity::notify_read);
             }
-            None => (),
         }
 
         //get the size of this node (calc'd or otherwise) and use
@@ -829,12 +1341,12 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
 
             let translate = [
                 if let Some(ref val) = cp.x {
-                    val.borrow().get().clone()
+                    *val.borrow().get()
                 } else {
                     Size::ZERO()
                 },
                 if let Some(ref val) = cp.y {
-                    val.borrow().get().clone()
+                    *val.borrow().get()
                 } else {
                     Size::ZERO()
                 },
@@ -843,12 +1355,12 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
 
             let anchor = [
                 if let Some(ref val) = cp.anchor_x {
-                    val.borrow().get().clone()
+                    *val.borrow().get()
                 } else {
                     Size::ZERO()
                 },
                 if let Some(ref val) = cp.anchor_y {
-                    val.borrow().get().clone()
+                    *val.borrow().get()
                 } else {
                     Size::ZERO()
                 },
@@ -857,12 +1369,12 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
 
             let scale = [
                 if let Some(ref val) = cp.scale_x {
-                    val.borrow().get().clone()
+                    *val.borrow().get()
                 } else {
                     Size::Percent(pax_runtime_api::Numeric::from(100.0))
                 },
                 if let Some(ref val) = cp.scale_y {
-                    val.borrow().get().clone()
+                    *val.borrow().get()
                 } else {
                     Size::Percent(pax_runtime_api::Numeric::from(100.0))
                 },
@@ -902,9 +1414,28 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
                 * desugared_transform
                 * node_transform_property_computed;
 
-        rtc.bounds = new_accumulated_bounds.clone();
-        rtc.transform_global = new_accumulated_transform.clone();
-        rtc.transform_scroller_reset = new_scroller_normalized_accumulated_transform.clone();
+        rtc.bounds = new_accumulated_bounds;
+        rtc.transform_global = new_accumulated_transform;
+        rtc.transform_scroller_reset = new_scroller_normalized_accumulated_transform;
+
+        //`opacity` and `blend_mode` apply to this node's whole subtree, so composite every layer
+        //with the accumulated values until this node's `did_render`
+        let is_composited = {
+            let node_borrowed = rtc.node.borrow();
+            let cp = node_borrowed.get_common_properties();
+            if let Some(ref val) = cp.opacity {
+                rtc.opacity *= val.borrow().get().clamp(0.0, 1.0);
+            }
+            if let Some(ref val) = cp.blend_mode {
+                rtc.blend_mode = *val.borrow().get();
+            }
+            cp.opacity.is_some() || cp.blend_mode.is_some()
+        };
+        let composited_canvas_ids = if is_composited {
+            self.begin_compositing(rcs, rtc.opacity, rtc.blend_mode)
+        } else {
+            vec![]
+        };
 
         //lifecycle: will_render for primitives
         node.borrow_mut().handle_will_render(rtc, rcs);
@@ -912,21 +1443,26 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         //fire `will_render` handlers
         let registry = (*node).borrow().get_handler_registry();
         if let Some(registry) = registry {
+            let revision_before_handlers = reactivity::current_revision();
             //grab Rc of properties from stack frame; pass to type-specific handler
             //on instance in order to dispatch cartridge method
-            match rtc.runtime.borrow_mut().peek_stack_frame() {
+            let stack_frame = rtc.runtime.borrow_mut().peek_stack_frame();
+            match stack_frame {
                 Some(stack_frame) => {
                     for handler in (*registry).borrow().will_render_handlers.iter() {
-                        handler(
-                            stack_frame.borrow_mut().get_properties(),
-                            rtc.distill_userland_node_context(),
-                        );
+                        let properties = stack_frame.borrow().get_properties();
+                        handler(properties, rtc.distill_userland_node_context());
                     }
                 }
                 None => {
                     panic!("can't bind events without a component")
                 }
             }
+            //A `will_render` handler that changes properties (e.g. to animate) expects to run again next tick,
+            //whereas one that changes nothing needn't wake an otherwise-idle engine
+            if reactivity::current_revision() != revision_before_handlers {
+                self.request_next_frame();
+            }
         }
 
         //create the `repeat_expanded_node` for the current node
@@ -935,25 +1471,23 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         let clipping = node
             .borrow_mut()
             .compute_clipping_within_bounds(accumulated_bounds);
-        let clipping_bounds = match node.borrow_mut().get_clipping_bounds() {
-            None => None,
-            Some(_) => Some(clipping),
-        };
+        let clipping_bounds = node.borrow_mut().get_clipping_bounds().map(|_| clipping);
 
         let repeat_expanded_node_tab = TransformAndBounds {
             bounds: node_size,
             clipping_bounds,
-            transform: new_scroller_normalized_accumulated_transform.clone(),
+            transform: new_scroller_normalized_accumulated_transform,
         };
 
         let parent_repeat_expanded_node = rtc.parent_repeat_expanded_node.clone();
+        let node_context = rtc.distill_userland_node_context();
         let repeat_expanded_node = Rc::new(RepeatExpandedNode {
             stack_frame: rtc.runtime.borrow_mut().peek_stack_frame().unwrap(),
             tab: repeat_expanded_node_tab.clone(),
             id_chain: id_chain.clone(),
             instance_node: Rc::clone(&node),
-            parent_repeat_expanded_node,
-            node_context: rtc.distill_userland_node_context(),
+            parent_repeat_expanded_node: RefCell::new(parent_repeat_expanded_node),
+            node_context: RefCell::new(node_context),
         });
 
         //Note: ray-casting requires that the repeat_expanded_node_cache is sorted by z-index,
@@ -983,12 +1517,13 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             .rev()
             .for_each(|child| {
                 let mut new_rtc = rtc.clone();
-                self.recurse_traverse_render_tree(
+                self.traverse_subtree(
                     &mut new_rtc,
                     rcs,
                     Rc::clone(child),
                     &mut z_index_info.clone(),
                     true,
+                    None,
                 );
             });
 
@@ -1000,25 +1535,33 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             let mut reset_transform = Affine::default();
             reset_transform =
                 reset_transform.then_translate(Vec2::new(scroll_offset_x, scroll_offset_y));
-            rtc.transform_scroller_reset = reset_transform.clone();
+            rtc.transform_scroller_reset = reset_transform;
         }
 
-        children.borrow_mut().iter().rev().for_each(|child| {
-            //note that we're iterating starting from the last child, for z-index (.rev())
-            let mut new_rtc = rtc.clone();
-            new_rtc.parent_repeat_expanded_node = Some(Rc::downgrade(&repeat_expanded_node));
-            // if it's a scroller reset the z-index context for its children
-            self.recurse_traverse_render_tree(
-                &mut new_rtc,
-                rcs,
-                Rc::clone(child),
-                &mut child_z_index_info.clone(),
-                marked_for_unmount,
-            );
-            //FUTURE: for dependency management, return computed values from subtree above
+        let mut child_render_records = vec![];
+        children
+            .borrow_mut()
+            .iter()
+            .rev()
+            .enumerate()
+            .for_each(|(i, child)| {
+                //note that we're iterating starting from the last child, for z-index (.rev())
+                let mut new_rtc = rtc.clone();
+                new_rtc.parent_repeat_expanded_node = Some(Rc::downgrade(&repeat_expanded_node));
+                let previous_child =
+                    previous.and_then(|previous| find_render_record(&previous.children, child, i));
+                // if it's a scroller reset the z-index context for its children
+                child_render_records.push(self.traverse_subtree(
+                    &mut new_rtc,
+                    rcs,
+                    Rc::clone(child),
+                    &mut child_z_index_info.clone(),
+                    marked_for_unmount,
+                    previous_child,
+                ));
 
-            subtree_depth = subtree_depth.max(child_z_index_info.get_level());
-        });
+                subtree_depth = subtree_depth.max(child_z_index_info.get_level());
+            });
 
         let is_viewport_culled = !repeat_expanded_node_tab.intersects(&self.viewport_tab);
 
@@ -1053,28 +1596,140 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             if !is_viewport_culled {
                 node.borrow_mut().handle_render(rtc, rc);
             }
-        } else {
-            if let Some(rc) = rcs.get_mut("0") {
-                if !is_viewport_culled {
-                    node.borrow_mut().handle_render(rtc, rc);
-                }
+        } else if let Some(rc) = rcs.get_mut("0") {
+            if !is_viewport_culled {
+                node.borrow_mut().handle_render(rtc, rc);
             }
         }
 
         //lifecycle: did_render
         node.borrow_mut().handle_did_render(rtc, rcs);
 
+        self.end_compositing(rcs, &composited_canvas_ids);
+
         //Handle node unmounting
         if marked_for_unmount {
+            let revision_before_handlers = reactivity::current_revision();
             //lifecycle: will_unmount
             node.borrow_mut().handle_will_unmount(rtc);
+
+            //Fire registered will_unmount events
+            let registry = (*node).borrow().get_handler_registry();
+            if let (Some(registry), Some(stack_frame)) = (registry, &stack_frame) {
+                for handler in (*registry).borrow().will_unmount_handlers.iter() {
+                    let properties = stack_frame.borrow().get_properties();
+                    handler(properties, rtc.distill_userland_node_context());
+                }
+            }
+            if reactivity::current_revision() != revision_before_handlers {
+                self.request_next_frame();
+            }
             let id_chain = rtc.get_id_chain(instance_id);
 
+            //An unmounted element can't keep focus, nor is there a native element left to blur
+            if self.focused_id_chain.borrow().as_ref() == Some(&id_chain) {
+                *self.focused_id_chain.borrow_mut() = None;
+            }
+
             self.instance_registry
                 .borrow_mut()
-                .mounted_set
-                .remove(&id_chain);
+                .mark_unmounted(&id_chain);
+        }
+
+        RenderRecord {
+            node,
+            inputs,
+            dependencies: Dependencies::default(),
+            has_per_frame_work: false,
+            repeat_expanded_node,
+            stack_frame: pushed_stack_frame,
+            transform_global: rtc.transform_global,
+            transform_scroller_reset: new_scroller_normalized_accumulated_transform,
+            bounds: rtc.bounds,
+            opacity: rtc.opacity,
+            blend_mode: rtc.blend_mode,
+            timeline_playhead_position: rtc.timeline_playhead_position,
+            timeline: rtc.timeline.clone(),
+            is_composited,
+            canvas_id,
+            is_viewport_culled,
+            is_repeated: id_chain.len() > 1
+                || child_render_records.iter().any(|child| child.is_repeated),
+            children: child_render_records,
+        }
+    }
+
+    /// Paints the reused subtree of `render_record` to `rcs`, firing each node's `will_render` (and `will_render` handlers),
+    /// `render` and `did_render` in traversal order, against the state recorded when that node was last traversed
+    fn paint_render_record(&self, render_record: &RenderRecord<R>, rcs: &mut HashMap<String, R>) {
+        let composited_canvas_ids = if render_record.is_composited {
+            self.begin_compositing(rcs, render_record.opacity, render_record.blend_mode)
+        } else {
+            vec![]
+        };
+        if let Some(stack_frame) = &render_record.stack_frame {
+            (*self.runtime)
+                .borrow_mut()
+                .push_existing_stack_frame(Rc::clone(stack_frame));
+        }
+
+        let node = &render_record.node;
+        let mut rtc = RenderTreeContext {
+            engine: self,
+            transform_global: render_record.transform_global,
+            transform_scroller_reset: render_record.transform_scroller_reset,
+            bounds: render_record.bounds,
+            runtime: self.runtime.clone(),
+            node: Rc::clone(node),
+            parent_repeat_expanded_node: None,
+            timeline_playhead_position: render_record.timeline_playhead_position,
+            timeline: render_record.timeline.clone(),
+            inherited_adoptees: None,
+            opacity: render_record.opacity,
+            blend_mode: render_record.blend_mode,
+        };
+
+        node.borrow_mut().handle_will_render(&mut rtc, rcs);
+
+        //fire `will_render` handlers, as a traversal would
+        let registry = (*node).borrow().get_handler_registry();
+        let stack_frame = (*self.runtime).borrow_mut().peek_stack_frame();
+        if let (Some(registry), Some(stack_frame)) = (registry, stack_frame) {
+            let revision_before_handlers = reactivity::current_revision();
+            let node_context = render_record
+                .repeat_expanded_node
+                .node_context
+                .borrow()
+                .clone();
+            for handler in (*registry).borrow().will_render_handlers.iter() {
+                let properties = stack_frame.borrow().get_properties();
+                handler(properties, node_context.clone());
+            }
+            if reactivity::current_revision() != revision_before_handlers {
+                self.request_next_frame();
+            }
+        }
+
+        render_record
+            .children
+            .iter()
+            .for_each(|child| self.paint_render_record(child, rcs));
+
+        //lifecycle: render
+        //this is this node's time to do its own rendering, aside
+        //from the rendering of its children. Its children have already been rendered.
+        if !render_record.is_viewport_culled {
+            if let Some(rc) = rcs.get_mut(&render_record.canvas_id) {
+                node.borrow_mut().handle_render(&mut rtc, rc);
+            } else if let Some(rc) = rcs.get_mut("0") {
+                node.borrow_mut().handle_render(&mut rtc, rc);
+            }
         }
+
+        //lifecycle: did_render, which also pops any stack frame pushed above
+        node.borrow_mut().handle_did_render(&mut rtc, rcs);
+
+        self.end_compositing(rcs, &composited_canvas_ids);
     }
 
     /// Simple 2D raycasting: the coordinates of the ray represent a
@@ -1106,7 +1761,7 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
             .repeat_expanded_node_cache
             .iter()
             .rev()
-            .map(|rc| Rc::clone(rc))
+            .map(Rc::clone)
             .collect();
 
         // remove root element that is moved to top during reversal
@@ -1126,30 +1781,23 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
                 //calculation when we find the first matching node
 
                 let mut ancestral_clipping_bounds_are_satisfied = true;
-                let mut parent: Option<Rc<RepeatExpandedNode<R>>> = node
-                    .parent_repeat_expanded_node
-                    .as_ref()
-                    .and_then(|weak| weak.upgrade());
-
-                loop {
-                    if let Some(unwrapped_parent) = parent {
-                        if let Some(_) = (*unwrapped_parent.instance_node)
+                let mut parent: Option<Rc<RepeatExpandedNode<R>>> = node.get_parent();
+
+                //every clipping ancestor must be hit, since each may clip to a different shape
+                while let Some(unwrapped_parent) = parent {
+                    if (*unwrapped_parent.instance_node)
+                        .borrow()
+                        .get_clipping_bounds()
+                        .is_some()
+                    {
+                        ancestral_clipping_bounds_are_satisfied = (*unwrapped_parent.instance_node)
                             .borrow()
-                            .get_clipping_bounds()
-                        {
-                            ancestral_clipping_bounds_are_satisfied = (*unwrapped_parent
-                                .instance_node)
-                                .borrow()
-                                .ray_cast_test(&ray, &unwrapped_parent.tab);
+                            .ray_cast_test(&ray, &unwrapped_parent.tab);
+                        if !ancestral_clipping_bounds_are_satisfied {
                             break;
                         }
-                        parent = unwrapped_parent
-                            .parent_repeat_expanded_node
-                            .as_ref()
-                            .and_then(|weak| weak.upgrade());
-                    } else {
-                        break;
                     }
+                    parent = unwrapped_parent.get_parent();
                 }
 
                 if ancestral_clipping_bounds_are_satisfied {
@@ -1162,24 +1810,256 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
         ret
     }
 
-    pub fn get_focused_element(&self) -> Option<Rc<RepeatExpandedNode<R>>> {
+    /// Finds the repeat-expanded node addressed by `id_chain`, e.g. the native form control that raised an interrupt
+    pub fn get_repeat_expanded_node(&self, id_chain: &[u32]) -> Option<Rc<RepeatExpandedNode<R>>> {
+        (*self.instance_registry)
+            .borrow()
+            .repeat_expanded_node_cache
+            .iter()
+            .find(|node| node.id_chain == id_chain)
+            .map(Rc::clone)
+    }
+
+    /// Ray-casts the center of the viewport, e.g. to find the element that a scroll without coordinates applies to
+    pub fn get_topmost_element_beneath_viewport_center(&self) -> Option<Rc<RepeatExpandedNode<R>>> {
         let (x, y) = self.viewport_tab.bounds;
         self.get_topmost_element_beneath_ray((x / 2.0, y / 2.0))
     }
 
+    /// The element with keyboard focus, if any
+    pub fn get_focused_element(&self) -> Option<Rc<RepeatExpandedNode<R>>> {
+        self.focused_id_chain
+            .borrow()
+            .as_ref()
+            .and_then(|id_chain| self.get_repeat_expanded_node(id_chain))
+    }
+
+    /// The element to which keyboard events are dispatched (and from which they bubble): the focused element,
+    /// or else the root component, so that app-wide key handlers fire whether or not anything is focused
+    pub fn get_keyboard_event_target(&self) -> Option<Rc<RepeatExpandedNode<R>>> {
+        self.get_focused_element().or_else(|| {
+            (*self.instance_registry)
+                .borrow()
+                .repeat_expanded_node_cache
+                .iter()
+                .find(|node| node.parent_repeat_expanded_node.borrow().is_none())
+                .map(Rc::clone)
+        })
+    }
+
+    /// Moves keyboard focus to the element addressed by `id_chain`, dispatching `blur` to the previously focused
+    /// element and `focus` to the newly focused one.  No-op if that element is already focused, or is not focusable.
+    pub fn focus(&mut self, id_chain: &[u32]) {
+        if self.focused_id_chain.borrow().as_deref() == Some(id_chain) {
+            return;
+        }
+        let node = match self.get_repeat_expanded_node(id_chain) {
+            Some(node) if (*node.instance_node).borrow().is_focusable() => node,
+            _ => return,
+        };
+        self.blur_focused_element();
+        *self.focused_id_chain.borrow_mut() = Some(id_chain.to_vec());
+        (*self.runtime)
+            .borrow_mut()
+            .enqueue_native_message(NativeMessage::Focus(id_chain.to_vec()));
+        node.dispatch_focus(ArgsFocus {});
+    }
+
+    /// Releases keyboard focus from the element addressed by `id_chain`, if it is focused
+    pub fn blur(&mut self, id_chain: &[u32]) {
+        if self.focused_id_chain.borrow().as_deref() == Some(id_chain) {
+            self.blur_focused_element();
+        }
+    }
+
+    fn blur_focused_element(&mut self) {
+        let focused_id_chain = self.focused_id_chain.borrow_mut().take();
+        if let Some(id_chain) = focused_id_chain {
+            (*self.runtime)
+                .borrow_mut()
+                .enqueue_native_message(NativeMessage::Blur(id_chain.clone()));
+            if let Some(node) = self.get_repeat_expanded_node(&id_chain) {
+                node.dispatch_blur(ArgsBlur {});
+            }
+        }
+    }
+
+    /// Moves focus in response to a pointer press on `node` (e.g. as found by `get_topmost_element_beneath_ray`):
+    /// to the nearest focusable element among it and its ancestors, or else away from whatever was focused
+    pub fn focus_pointed_element(&mut self, node: Option<Rc<RepeatExpandedNode<R>>>) {
+        let mut current = node;
+        while let Some(node) = current {
+            if (*node.instance_node).borrow().is_focusable() {
+                self.focus(&node.id_chain);
+                return;
+            }
+            current = node.get_parent();
+        }
+        self.blur_focused_element();
+    }
+
+    /// Moves focus to the next element in tab order, wrapping around after the last
+    pub fn focus_next(&mut self) {
+        self.move_focus(true);
+    }
+
+    /// Moves focus to the previous element in tab order, wrapping around before the first
+    pub fn focus_previous(&mut self) {
+        self.move_focus(false);
+    }
+
+    fn move_focus(&mut self, forwards: bool) {
+        let tab_order = self.get_tab_order();
+        if tab_order.is_empty() {
+            return;
+        }
+        let current = tab_order
+            .iter()
+            .position(|node| self.focused_id_chain.borrow().as_ref() == Some(&node.id_chain));
+        let next = match (current, forwards) {
+            (Some(i), true) => (i + 1) % tab_order.len(),
+            (Some(i), false) => (i + tab_order.len() - 1) % tab_order.len(),
+            (None, true) => 0,
+            (None, false) => tab_order.len() - 1,
+        };
+        self.focus(&tab_order[next].id_chain);
+    }
+
+    /// The focusable elements, in ascending `tab_index` and then in the order they were declared;
+    /// elements with a negative `tab_index` are left out.  See `CommonProperties::tab_index`.
+    fn get_tab_order(&self) -> Vec<Rc<RepeatExpandedNode<R>>> {
+        let mut tab_order: Vec<(isize, Rc<RepeatExpandedNode<R>>)> = (*self.instance_registry)
+            .borrow()
+            .repeat_expanded_node_cache
+            .iter()
+            .filter_map(|node| {
+                let instance_node = (*node.instance_node).borrow();
+                if !instance_node.is_focusable() {
+                    return None;
+                }
+                let tab_index = instance_node
+                    .get_common_properties()
+                    .tab_index
+                    .as_ref()
+                    .map_or(0, |tab_index| *tab_index.borrow().get());
+                (tab_index >= 0).then(|| (tab_index, Rc::clone(node)))
+            })
+            .collect();
+        //Instance ids are minted in declaration order, and repeated elements follow with their indices
+        tab_order.sort_by(|a, b| (a.0, &a.1.id_chain).cmp(&(b.0, &b.1.id_chain)));
+        tab_order.into_iter().map(|(_, node)| node).collect()
+    }
+
+    /// Handles a `KeyDown` for focus traversal: `Tab` moves focus forwards through tab order, `Shift+Tab` backwards.
+    /// Chassis should suppress their platform's own traversal, so that focus moves only once.
+    pub fn handle_focus_traversal_key(&mut self, key: &str, modifiers: &[ModifierKey]) {
+        if key != "Tab" {
+            return;
+        }
+        if modifiers
+            .iter()
+            .any(|modifier| matches!(modifier, ModifierKey::Shift))
+        {
+            self.focus_previous();
+        } else {
+            self.focus_next();
+        }
+    }
+
+    /// Applies the focus changes requested by handlers since the last call, e.g. with `RuntimeContext::focus`
+    pub fn apply_focus_requests(&mut self) {
+        let requests = std::mem::take(&mut *self.focus_requests.borrow_mut());
+        for request in requests {
+            match request {
+                FocusRequest::Focus(id_chain) => self.focus(&id_chain),
+                FocusRequest::Blur(id_chain) => self.blur(&id_chain),
+                FocusRequest::Next => self.focus_next(),
+                FocusRequest::Previous => self.focus_previous(),
+            }
+        }
+    }
+
     /// Called by chassis when viewport size changes, e.g. with native window resizes
     pub fn set_viewport_size(&mut self, new_viewport_size: (f64, f64)) {
+        if self.viewport_tab.bounds != new_viewport_size {
+            self.is_invalidated = true;
+        }
         self.viewport_tab.bounds = new_viewport_size;
     }
 
+    /// Force the next tick to render, e.g. for chassis whose render contexts don't retain the previous frame
+    pub fn invalidate(&mut self) {
+        self.is_invalidated = true;
+    }
+
+    /// Called during traversal by nodes that will render differently on the next tick even if no property changes
+    /// in the meantime, e.g. while a timeline is playing or a transition is in progress
+    pub fn request_next_frame(&self) {
+        self.has_per_frame_work.set(true);
+    }
+
+    /// Whether the next tick could render anything different from the last.  When `false`, `tick` is a no-op:
+    /// it neither traverses nor draws to the render contexts, so chassis should retain (not clear) the previous frame.
+    /// Note that damage is only tracked per frame: a tick either draws nothing or repaints every canvas layer whole,
+    /// as no regions of layers are cleared and repainted on their own (see `tick`.)
+    ///
+    /// Besides the explicit signals above, the engine is woken by any property change since the last traversal (e.g.
+    /// from an event handler), by focus changes requested since the last tick (whose `focus` and `blur` handlers `tick`
+    /// fires before traversing) and by any expression whose tracked dependencies changed after it was evaluated
+    /// during the last traversal.  Volatile expressions (those reading a `RepeatItem`'s `elem` or `i`) are only
+    /// woken by `RepeatItem`s being updated in place; see `reactivity::notify_volatile_changed`.
+    pub fn needs_render(&self) -> bool {
+        self.is_invalidated
+            || self.has_per_frame_work.get()
+            || self.last_traversal_revision != Some(reactivity::current_revision())
+            || !self.focus_requests.borrow().is_empty()
+            || self
+                .expression_dependencies
+                .borrow()
+                .values()
+                .any(|evaluation| evaluation.dependencies.have_tracked_dependencies_changed())
+    }
+
     /// Workhorse method to advance rendering and property calculation by one discrete tick
     /// Will be executed synchronously up to 240 times/second.
+    /// Ticks for which `needs_render` is `false` skip traversal, returning only the messages enqueued outside of it
+    /// (e.g. by focus changes.)  Otherwise, only the subtrees that may have changed are traversed (see `traverse_subtree`),
+    /// while the whole tree is painted to `rcs`.
     pub fn tick(&mut self, rcs: &mut HashMap<String, R>) -> Vec<NativeMessage> {
-        (*self.instance_registry)
-            .borrow_mut()
-            .reset_repeat_expanded_node_cache();
-        let native_render_queue = self.traverse_render_tree(rcs);
-        self.frames_elapsed = self.frames_elapsed + 1;
+        //FUTURE: damage regions, i.e. tracking which nodes' transforms, bounds or properties changed and clearing and
+        //repainting only their regions of the affected canvas layers (per `ZIndex::generate_location_id`), rather than
+        //whole frames.  Painting would have to wait until all damage is known, but the properties of `Repeat`ed nodes,
+        //which share a single instance, are only current while each of their repeat-expanded nodes is traversed.
+        //Apply focus changes before rendering, so that changes made by `focus` and `blur` handlers show this tick
+        self.apply_focus_requests();
+        let native_render_queue = if self.needs_render() {
+            //Invalidation may stem from changes that no subtree's dependencies reflect, e.g. a loaded image
+            let previous_render_record = self
+                .last_render_record
+                .take()
+                .filter(|_| !self.is_invalidated);
+            self.is_invalidated = false;
+            self.has_per_frame_work.set(false);
+            (*self.instance_registry)
+                .borrow_mut()
+                .reset_repeat_expanded_node_cache();
+            //Release the cached evaluations of scopes that no longer exist, e.g. unmounted `RepeatItem`s
+            self.expression_dependencies
+                .borrow_mut()
+                .retain(|_, evaluation| evaluation.scope.strong_count() > 0);
+            let (native_render_queue, render_record) =
+                self.traverse_render_tree(rcs, previous_render_record);
+            self.last_render_record = Some(render_record);
+            self.last_traversal_revision = Some(reactivity::current_revision());
+            native_render_queue
+        } else {
+            //Still flush messages enqueued outside of traversal, e.g. by focus changes
+            (*self.runtime)
+                .borrow_mut()
+                .take_native_message_queue()
+                .into()
+        };
+        self.frames_elapsed += 1;
         native_render_queue
     }
 
@@ -1192,5 +2072,778 @@ impl<R: 'static + RenderContext> PaxEngine<R> {
     ) {
         self.image_map
             .insert(id_chain, (Box::new(image_data), width, height));
+        self.is_invalidated = true;
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{InstantiationArgs, RenderNode};
+    use pax_runtime_api::{
+        MouseButton, MouseEventArgs, PlatformSpecificLogger, PropertyInstance, PropertyLiteral,
+    };
+    use piet::NullRenderContext;
+    use std::sync::Mutex;
+
+    pub(crate) type R = NullRenderContext;
+
+    thread_local! {
+        /// The handlers and lifecycle methods called so far, by name, with the `id_chain` of the node each was called on
+        static CALLS: RefCell<Vec<(&'static str, Vec<u32>)>> = RefCell::new(vec![]);
+        /// The handler, by name and `id_chain`, that stops the propagation of the event it's called with
+        static STOP_PROPAGATION_AT: RefCell<Option<(&'static str, Vec<u32>)>> = RefCell::new(None);
+        /// Properties standing in for those of userland components, by name; see `TestNode::reads`
+        static VALUES: RefCell<HashMap<&'static str, PropertyLiteral<f64>>> = RefCell::new(HashMap::new());
+    }
+
+    /// `register_logger` can't be raced, and tests run on parallel threads
+    static ENGINE_CONSTRUCTION: Mutex<()> = Mutex::new(());
+
+    fn record<T>(name: &'static str, ctx: &RuntimeContext, event: &Event<T>) {
+        let call = (name, ctx.id_chain.clone());
+        if STOP_PROPAGATION_AT.with(|stop| stop.borrow().as_ref() == Some(&call)) {
+            event.stop_propagation();
+        }
+        CALLS.with(|calls| calls.borrow_mut().push(call));
+    }
+
+    pub(crate) fn push_call(name: &'static str, id_chain: &[u32]) {
+        CALLS.with(|calls| calls.borrow_mut().push((name, id_chain.to_vec())));
+    }
+
+    pub(crate) fn take_calls() -> Vec<(&'static str, Vec<u32>)> {
+        CALLS.with(|calls| calls.take())
+    }
+
+    pub(crate) fn set_value(name: &'static str, value: f64) {
+        VALUES.with(|values| {
+            values
+                .borrow_mut()
+                .entry(name)
+                .or_insert_with(|| PropertyLiteral::new(0.0))
+                .set(value)
+        });
+    }
+
+    pub(crate) fn get_value(name: &'static str) -> f64 {
+        VALUES.with(|values| *values.borrow()[name].get())
+    }
+
+    pub(crate) fn get_value_property_id(name: &'static str) -> PropertyId {
+        VALUES.with(|values| values.borrow()[name]._get_property_id())
+    }
+
+    /// A bare element, with just enough of `RenderNode` to be traversed, focused and to receive events.
+    /// Records its `compute_properties` (which is skipped when its subtree is reused) and `render` to `CALLS`.
+    pub(crate) struct TestNode {
+        pub(crate) instance_id: u32,
+        common_properties: CommonProperties,
+        pub(crate) handler_registry: Rc<RefCell<HandlerRegistry<R>>>,
+        is_focusable: bool,
+        children: RenderNodePtrList<R>,
+        /// The value in `VALUES`, if any, read while rendering, on which this node's subtree then depends
+        reads: Option<&'static str>,
+    }
+
+    impl RenderNode<R> for TestNode {
+        fn instantiate(_args: InstantiationArgs<R>) -> Rc<RefCell<Self>>
+        where
+            Self: Sized,
+        {
+            unimplemented!()
+        }
+
+        fn get_rendering_children(&self) -> RenderNodePtrList<R> {
+            Rc::clone(&self.children)
+        }
+
+        fn get_common_properties(&self) -> &CommonProperties {
+            &self.common_properties
+        }
+
+        fn compute_properties(&mut self, _rtc: &mut RenderTreeContext<R>) {
+            push_call("compute_properties", &[self.instance_id]);
+        }
+
+        fn handle_render(&mut self, _rtc: &mut RenderTreeContext<R>, _rc: &mut R) {
+            if let Some(name) = self.reads {
+                VALUES.with(|values| *values.borrow()[name].get());
+            }
+            push_call("render", &[self.instance_id]);
+        }
+
+        fn get_handler_registry(&self) -> Option<Rc<RefCell<HandlerRegistry<R>>>> {
+            Some(Rc::clone(&self.handler_registry))
+        }
+
+        fn is_focusable(&self) -> bool {
+            self.is_focusable
+        }
+
+        fn get_instance_id(&self) -> u32 {
+            self.instance_id
+        }
+
+        fn handle_slider_change(&mut self, id_chain: &[u32], _args: &ArgsSliderChange) {
+            push_call("apply_slider_change", id_chain);
+        }
+
+        fn handle_dropdown_change(&mut self, id_chain: &[u32], _args: &ArgsDropdownChange) {
+            push_call("apply_dropdown_change", id_chain);
+        }
+
+        fn handle_textbox_change(&mut self, id_chain: &[u32], _args: &ArgsTextboxChange) {
+            push_call("apply_textbox_change", id_chain);
+        }
+    }
+
+    /// Handlers recording `focus` and `blur`, `click` in both phases, the form changes `slider_change`,
+    /// `dropdown_change` and `textbox_change`, and the lifecycle events `will_render` and `will_unmount`
+    fn build_recording_handler_registry() -> HandlerRegistry<R> {
+        let mut handler_registry: HandlerRegistry<R> = HandlerRegistry::default();
+        handler_registry
+            .focus_handlers
+            .push(|_, ctx, event| record("focus", &ctx, &event));
+        handler_registry
+            .blur_handlers
+            .push(|_, ctx, event| record("blur", &ctx, &event));
+        handler_registry
+            .click_capture_handlers
+            .push(|_, ctx, event| record("click_capture", &ctx, &event));
+        handler_registry
+            .click_handlers
+            .push(|_, ctx, event| record("click", &ctx, &event));
+        handler_registry
+            .slider_change_handlers
+            .push(|_, ctx, event| record("slider_change", &ctx, &event));
+        handler_registry
+            .dropdown_change_handlers
+            .push(|_, ctx, event| record("dropdown_change", &ctx, &event));
+        handler_registry
+            .textbox_change_handlers
+            .push(|_, ctx, event| record("textbox_change", &ctx, &event));
+        handler_registry
+            .will_render_handlers
+            .push(|_, ctx| push_call("will_render", &ctx.id_chain));
+        handler_registry
+            .will_unmount_handlers
+            .push(|_, ctx| push_call("will_unmount", &ctx.id_chain));
+        handler_registry
+    }
+
+    pub(crate) fn build_test_node(
+        instance_registry: &mut InstanceRegistry<R>,
+        reads: Option<&'static str>,
+        children: Vec<RenderNodePtr<R>>,
+    ) -> Rc<RefCell<TestNode>> {
+        Rc::new(RefCell::new(TestNode {
+            instance_id: instance_registry.mint_id(),
+            common_properties: CommonProperties::default(),
+            handler_registry: Rc::new(RefCell::new(build_recording_handler_registry())),
+            is_focusable: true,
+            children: Rc::new(RefCell::new(children)),
+            reads,
+        }))
+    }
+
+    fn build_engine() -> PaxEngine<R> {
+        build_engine_with_template(|_| vec![])
+    }
+
+    /// Builds an engine whose main component's template is built by `build_template`
+    pub(crate) fn build_engine_with_template(
+        build_template: impl FnOnce(&mut InstanceRegistry<R>) -> Vec<RenderNodePtr<R>>,
+    ) -> PaxEngine<R> {
+        build_engine_with_expressions(HashMap::new(), build_template)
+    }
+
+    /// As `build_engine_with_template`, with the given expression table
+    pub(crate) fn build_engine_with_expressions(
+        expression_table: HashMap<usize, ExpressionFn<R>>,
+        build_template: impl FnOnce(&mut InstanceRegistry<R>) -> Vec<RenderNodePtr<R>>,
+    ) -> PaxEngine<R> {
+        let instance_registry = Rc::new(RefCell::new(InstanceRegistry::new()));
+        let instance_id = instance_registry.borrow_mut().mint_id();
+        let template = build_template(&mut instance_registry.borrow_mut());
+        let main_component = Rc::new(RefCell::new(ComponentInstance {
+            instance_id,
+            template: Rc::new(RefCell::new(template)),
+            children: Rc::new(RefCell::new(vec![])),
+            handler_registry: None,
+            cleanup_children: Rc::new(RefCell::new(vec![])),
+            properties: Rc::new(RefCell::new(PropertiesCoproduct::None)),
+            timeline: None,
+            repeat_item_identity: None,
+            compute_properties_fn: Box::new(|_, _| {}),
+            common_properties: CommonProperties::default(),
+        }));
+        let _guard = ENGINE_CONSTRUCTION.lock().unwrap();
+        PaxEngine::new(
+            main_component,
+            expression_table,
+            PlatformSpecificLogger::Headless(|_| {}),
+            (100.0, 100.0),
+            instance_registry,
+        )
+    }
+
+    /// Ticks with a single drawing context, returning whether anything was rendered to it
+    pub(crate) fn tick(engine: &mut PaxEngine<R>) -> bool {
+        take_calls();
+        let mut rcs = HashMap::from([("0".to_string(), NullRenderContext::new())]);
+        engine.tick(&mut rcs);
+        CALLS.with(|calls| calls.borrow().iter().any(|(name, _)| *name == "render"))
+    }
+
+    /// Adds a `TestNode` beneath `parent` to the repeat-expanded node cache, as if a traversal had just found it.
+    fn add_node(
+        engine: &PaxEngine<R>,
+        parent: Option<&Rc<RepeatExpandedNode<R>>>,
+        tab_index: Option<isize>,
+        is_focusable: bool,
+    ) -> Rc<RepeatExpandedNode<R>> {
+        let common_properties = CommonProperties {
+            tab_index: tab_index.map(|tab_index| {
+                Rc::new(RefCell::new(PropertyLiteral::new(tab_index)))
+                    as Rc<RefCell<dyn PropertyInstance<isize>>>
+            }),
+            ..Default::default()
+        };
+        let instance_id = engine.instance_registry.borrow_mut().mint_id();
+        let instance_node: RenderNodePtr<R> = Rc::new(RefCell::new(TestNode {
+            instance_id,
+            common_properties,
+            handler_registry: Rc::new(RefCell::new(build_recording_handler_registry())),
+            is_focusable,
+            children: Rc::new(RefCell::new(vec![])),
+            reads: None,
+        }));
+
+        let id_chain = vec![instance_id];
+        let node = Rc::new(RepeatExpandedNode {
+            id_chain: id_chain.clone(),
+            parent_repeat_expanded_node: RefCell::new(parent.map(Rc::downgrade)),
+            instance_node,
+            stack_frame: Rc::new(RefCell::new(StackFrame::new(
+                Rc::new(RefCell::new(vec![])),
+                Rc::new(RefCell::new(PropertiesCoproduct::None)),
+                None,
+                None,
+                None,
+            ))),
+            tab: TransformAndBounds {
+                transform: Affine::default(),
+                bounds: (10.0, 10.0),
+                clipping_bounds: None,
+            },
+            node_context: RefCell::new(RuntimeContext {
+                frames_elapsed: 0,
+                bounds_parent: (100.0, 100.0),
+                timeline_playhead_position: 0,
+                timeline: None,
+                id_chain,
+                focus_requests: Rc::clone(&engine.focus_requests),
+            }),
+        });
+        engine
+            .instance_registry
+            .borrow_mut()
+            .add_to_repeat_expanded_node_cache(Rc::clone(&node));
+        node
+    }
+
+    fn get_focused_id_chain(engine: &PaxEngine<R>) -> Option<Vec<u32>> {
+        engine.focused_id_chain.borrow().clone()
+    }
+
+    #[test]
+    fn test_tab_order_sorts_by_tab_index_and_wraps_around() {
+        let engine = &mut build_engine();
+        let root = add_node(engine, None, None, false);
+        let late = add_node(engine, Some(&root), Some(1), true);
+        let first = add_node(engine, Some(&root), None, true);
+        let _skipped = add_node(engine, Some(&root), Some(-1), true);
+        let _unfocusable = add_node(engine, Some(&root), Some(0), false);
+        let second = add_node(engine, Some(&root), Some(0), true);
+
+        let tab_order: Vec<Vec<u32>> = engine
+            .get_tab_order()
+            .iter()
+            .map(|node| node.id_chain.clone())
+            .collect();
+        assert_eq!(
+            tab_order,
+            vec![
+                first.id_chain.clone(),
+                second.id_chain.clone(),
+                late.id_chain.clone()
+            ]
+        );
+
+        for expected in [&first, &second, &late, &first] {
+            engine.handle_focus_traversal_key("Tab", &[]);
+            assert_eq!(
+                get_focused_id_chain(engine),
+                Some(expected.id_chain.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn test_shift_tab_moves_focus_backwards() {
+        let engine = &mut build_engine();
+        let root = add_node(engine, None, None, false);
+        let first = add_node(engine, Some(&root), None, true);
+        let second = add_node(engine, Some(&root), None, true);
+
+        //with nothing focused, Shift+Tab starts from the end of tab order
+        for expected in [&second, &first, &second] {
+            engine.handle_focus_traversal_key("Tab", &[ModifierKey::Shift]);
+            assert_eq!(
+                get_focused_id_chain(engine),
+                Some(expected.id_chain.clone())
+            );
+        }
+        engine.handle_focus_traversal_key("Enter", &[ModifierKey::Shift]);
+        assert_eq!(get_focused_id_chain(engine), Some(second.id_chain.clone()));
+    }
+
+    #[test]
+    fn test_focus_and_blur_dispatch_handlers_and_native_messages() {
+        let engine = &mut build_engine();
+        let root = add_node(engine, None, None, false);
+        let a = add_node(engine, Some(&root), None, true);
+        let b = add_node(engine, Some(&root), None, true);
+        take_calls();
+
+        //like every other event, `focus` and `blur` bubble up to the root
+        engine.focus(&a.id_chain);
+        assert_eq!(
+            take_calls(),
+            vec![
+                ("focus", a.id_chain.clone()),
+                ("focus", root.id_chain.clone())
+            ]
+        );
+        engine.focus(&b.id_chain);
+        assert_eq!(
+            take_calls(),
+            vec![
+                ("blur", a.id_chain.clone()),
+                ("blur", root.id_chain.clone()),
+                ("focus", b.id_chain.clone()),
+                ("focus", root.id_chain.clone())
+            ]
+        );
+
+        //refocusing, focusing the unfocusable and blurring what isn't focused are no-ops
+        engine.focus(&b.id_chain);
+        engine.focus(&root.id_chain);
+        engine.blur(&a.id_chain);
+        assert!(take_calls().is_empty());
+        assert_eq!(get_focused_id_chain(engine), Some(b.id_chain.clone()));
+
+        engine.blur(&b.id_chain);
+        assert_eq!(
+            take_calls(),
+            vec![
+                ("blur", b.id_chain.clone()),
+                ("blur", root.id_chain.clone())
+            ]
+        );
+        assert_eq!(get_focused_id_chain(engine), None);
+
+        let messages = engine.runtime.borrow_mut().take_native_message_queue();
+        let focus_messages: Vec<(&str, &Vec<u32>)> = messages
+            .iter()
+            .filter_map(|message| match message {
+                NativeMessage::Focus(id_chain) => Some(("focus", id_chain)),
+                NativeMessage::Blur(id_chain) => Some(("blur", id_chain)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            focus_messages,
+            vec![
+                ("focus", &a.id_chain),
+                ("blur", &a.id_chain),
+                ("focus", &b.id_chain),
+                ("blur", &b.id_chain)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_focus_requests_from_handlers_apply_in_order() {
+        let engine = &mut build_engine();
+        let root = add_node(engine, None, None, false);
+        let a = add_node(engine, Some(&root), None, true);
+        let b = add_node(engine, Some(&root), None, true);
+        take_calls();
+
+        a.node_context.borrow().focus();
+        b.node_context.borrow().focus_next();
+        engine.apply_focus_requests();
+        assert_eq!(get_focused_id_chain(engine), Some(b.id_chain.clone()));
+        assert_eq!(
+            take_calls(),
+            vec![
+                ("focus", a.id_chain.clone()),
+                ("focus", root.id_chain.clone()),
+                ("blur", a.id_chain.clone()),
+                ("blur", root.id_chain.clone()),
+                ("focus", b.id_chain.clone()),
+                ("focus", root.id_chain.clone())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_clean_subtrees_are_reused_until_a_dependency_changes() {
+        set_value("reused_a", 0.0);
+        set_value("reused_b", 0.0);
+        let (mut a, mut b, mut b_child) = (vec![], vec![], vec![]);
+        let engine = &mut build_engine_with_template(|instance_registry| {
+            let b_child_node = build_test_node(instance_registry, None, vec![]);
+            let a_node = build_test_node(instance_registry, Some("reused_a"), vec![]);
+            let b_node = build_test_node(
+                instance_registry,
+                Some("reused_b"),
+                vec![Rc::clone(&b_child_node) as RenderNodePtr<R>],
+            );
+            a = vec![a_node.borrow().instance_id];
+            b = vec![b_node.borrow().instance_id];
+            b_child = vec![b_child_node.borrow().instance_id];
+            vec![a_node as RenderNodePtr<R>, b_node as RenderNodePtr<R>]
+        });
+        assert!(tick(engine));
+        let calls = take_calls();
+        for id_chain in [&a, &b, &b_child] {
+            assert!(calls.contains(&("compute_properties", id_chain.clone())));
+        }
+
+        set_value("reused_b", 1.0);
+        assert!(tick(engine));
+        let calls = take_calls();
+        //`b` read the changed value, so it's traversed again...
+        assert!(calls.contains(&("compute_properties", b.clone())));
+        //...while the subtrees that didn't are painted from their records, still firing `will_render` handlers
+        for id_chain in [&a, &b_child] {
+            assert!(!calls.contains(&("compute_properties", id_chain.clone())));
+            assert!(calls.contains(&("will_render", id_chain.clone())));
+            assert!(calls.contains(&("render", id_chain.clone())));
+        }
+    }
+
+    #[test]
+    fn test_idle_ticks_skip_rendering_until_something_changes() {
+        set_value("idle_value", 0.0);
+        let engine = &mut build_engine_with_template(|instance_registry| {
+            vec![build_test_node(instance_registry, Some("idle_value"), vec![]) as RenderNodePtr<R>]
+        });
+        assert!(engine.needs_render());
+        assert!(tick(engine));
+
+        //nothing has changed, so there's nothing new to draw
+        assert!(!engine.needs_render());
+        assert!(!tick(engine));
+        assert!(take_calls().is_empty());
+
+        set_value("idle_value", 1.0);
+        assert!(engine.needs_render());
+        assert!(tick(engine));
+        assert!(!tick(engine));
+
+        //as well as changes that no property reflects
+        engine.invalidate();
+        assert!(tick(engine));
+        assert!(!tick(engine));
+    }
+
+    #[test]
+    fn test_focus_handler_changes_render_on_the_same_tick() {
+        set_value("focus_value", 0.0);
+        let mut id_chain = vec![];
+        let engine = &mut build_engine_with_template(|instance_registry| {
+            let node = build_test_node(instance_registry, Some("focus_value"), vec![]);
+            node.borrow()
+                .handler_registry
+                .borrow_mut()
+                .focus_handlers
+                .push(|_, _, _| set_value("focus_value", 1.0));
+            id_chain = vec![node.borrow().instance_id];
+            vec![node as RenderNodePtr<R>]
+        });
+        assert!(tick(engine));
+        assert!(!tick(engine));
+
+        //chassis consult `needs_render` before ticking, e.g. to decide whether to clear their canvases
+        let node = engine.get_repeat_expanded_node(&id_chain).unwrap();
+        node.node_context.borrow().focus();
+        assert!(engine.needs_render());
+        assert!(tick(engine));
+        let calls = take_calls();
+        let focused_at = calls
+            .iter()
+            .position(|call| *call == ("focus", id_chain.clone()));
+        let rendered_at = calls
+            .iter()
+            .position(|call| *call == ("render", id_chain.clone()));
+        assert!(focused_at.unwrap() < rendered_at.unwrap());
+        assert_eq!(get_focused_id_chain(engine), Some(id_chain));
+    }
+
+    #[test]
+    fn test_will_unmount_fires_for_replaced_template_nodes() {
+        let (mut old, mut old_child) = (vec![], vec![]);
+        let engine = &mut build_engine_with_template(|instance_registry| {
+            let old_child_node = build_test_node(instance_registry, None, vec![]);
+            let old_node = build_test_node(
+                instance_registry,
+                None,
+                vec![Rc::clone(&old_child_node) as RenderNodePtr<R>],
+            );
+            old = vec![old_node.borrow().instance_id];
+            old_child = vec![old_child_node.borrow().instance_id];
+            vec![old_node as RenderNodePtr<R>]
+        });
+        assert!(tick(engine));
+        assert!(!take_calls().iter().any(|(name, _)| *name == "will_unmount"));
+
+        let new_node = build_test_node(&mut engine.instance_registry.borrow_mut(), None, vec![]);
+        let new = vec![new_node.borrow().instance_id];
+        let properties = Rc::clone(&engine.main_component.borrow().properties);
+        engine.main_component.borrow_mut().replace_template(
+            Rc::new(RefCell::new(vec![new_node as RenderNodePtr<R>])),
+            Rc::new(RefCell::new(HandlerRegistry::default())),
+            &mut engine.instance_registry.borrow_mut(),
+        );
+        assert!(Rc::ptr_eq(
+            &properties,
+            &engine.main_component.borrow().properties
+        ));
+        engine.invalidate();
+        assert!(tick(engine));
+        let calls = take_calls();
+        //the replaced nodes, and everything beneath them, are unmounted...
+        for id_chain in [&old, &old_child] {
+            assert!(calls.contains(&("will_unmount", id_chain.clone())));
+            assert!(!engine.instance_registry.borrow().is_mounted(id_chain));
+        }
+        //...while their replacement is mounted
+        assert!(!calls.contains(&("will_unmount", new.clone())));
+        assert!(engine.instance_registry.borrow().is_mounted(&new));
+
+        //and only once
+        engine.invalidate();
+        assert!(tick(engine));
+        assert!(!take_calls().iter().any(|(name, _)| *name == "will_unmount"));
+    }
+
+    #[test]
+    fn test_did_update_and_on_change_fire_only_for_changed_observed_properties() {
+        set_value("observed_a", 0.0);
+        set_value("observed_b", 0.0);
+        set_value("unobserved", 0.0);
+        let mut id_chain = vec![];
+        let engine = &mut build_engine_with_template(|instance_registry| {
+            let node = build_test_node(instance_registry, None, vec![]);
+            {
+                let node = node.borrow();
+                let mut handler_registry = node.handler_registry.borrow_mut();
+                handler_registry.did_update_handlers.push(PropertyObserver {
+                    get_observed_property_ids: |_| {
+                        vec![
+                            get_value_property_id("observed_a"),
+                            get_value_property_id("observed_b"),
+                        ]
+                    },
+                    handler: |_, ctx| push_call("did_update", &ctx.id_chain),
+                });
+                handler_registry.on_change_handlers.push(PropertyObserver {
+                    get_observed_property_ids: |_| vec![get_value_property_id("observed_a")],
+                    handler: |_, ctx| push_call("on_change(observed_a)", &ctx.id_chain),
+                });
+            }
+            id_chain = vec![node.borrow().instance_id];
+            vec![node as RenderNodePtr<R>]
+        });
+        let handler_calls = |engine: &mut PaxEngine<R>| {
+            engine.invalidate();
+            tick(engine);
+            take_calls()
+                .into_iter()
+                .filter(|(name, _)| *name == "did_update" || *name == "on_change(observed_a)")
+                .collect::<Vec<_>>()
+        };
+
+        //neither fires on mount, nor while nothing has changed
+        assert!(handler_calls(engine).is_empty());
+        assert!(handler_calls(engine).is_empty());
+
+        set_value("unobserved", 1.0);
+        assert!(handler_calls(engine).is_empty());
+
+        set_value("observed_b", 1.0);
+        assert_eq!(
+            handler_calls(engine),
+            vec![("did_update", id_chain.clone())]
+        );
+        assert!(handler_calls(engine).is_empty());
+
+        set_value("observed_a", 1.0);
+        assert_eq!(
+            handler_calls(engine),
+            vec![
+                ("did_update", id_chain.clone()),
+                ("on_change(observed_a)", id_chain.clone())
+            ]
+        );
+        assert!(handler_calls(engine).is_empty());
+    }
+
+    fn click() -> ArgsClick {
+        ArgsClick {
+            mouse: MouseEventArgs {
+                x: 0.0,
+                y: 0.0,
+                button: MouseButton::Left,
+                modifiers: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_events_capture_down_then_bubble_up() {
+        let engine = &build_engine();
+        let root = add_node(engine, None, None, false);
+        let parent = add_node(engine, Some(&root), None, false);
+        let target = add_node(engine, Some(&parent), None, false);
+        take_calls();
+
+        assert!(!target.dispatch_click(click()));
+        assert_eq!(
+            take_calls(),
+            vec![
+                ("click_capture", root.id_chain.clone()),
+                ("click_capture", parent.id_chain.clone()),
+                ("click_capture", target.id_chain.clone()),
+                ("click", target.id_chain.clone()),
+                ("click", parent.id_chain.clone()),
+                ("click", root.id_chain.clone()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stop_propagation_skips_the_remaining_handlers() {
+        let engine = &build_engine();
+        let root = add_node(engine, None, None, false);
+        let parent = add_node(engine, Some(&root), None, false);
+        let target = add_node(engine, Some(&parent), None, false);
+        take_calls();
+
+        //stopped while bubbling, past the target
+        STOP_PROPAGATION_AT
+            .with(|stop| *stop.borrow_mut() = Some(("click", parent.id_chain.clone())));
+        target.dispatch_click(click());
+        assert_eq!(
+            take_calls(),
+            vec![
+                ("click_capture", root.id_chain.clone()),
+                ("click_capture", parent.id_chain.clone()),
+                ("click_capture", target.id_chain.clone()),
+                ("click", target.id_chain.clone()),
+                ("click", parent.id_chain.clone()),
+            ]
+        );
+
+        //stopped while capturing, before reaching the target
+        STOP_PROPAGATION_AT
+            .with(|stop| *stop.borrow_mut() = Some(("click_capture", root.id_chain.clone())));
+        target.dispatch_click(click());
+        assert_eq!(take_calls(), vec![("click_capture", root.id_chain.clone())]);
+        STOP_PROPAGATION_AT.with(|stop| *stop.borrow_mut() = None);
+    }
+
+    #[test]
+    fn test_form_changes_apply_to_the_target_before_its_handlers_run() {
+        let engine = &build_engine();
+        let root = add_node(engine, None, None, false);
+        let target = add_node(engine, Some(&root), None, true);
+        take_calls();
+
+        //handlers, including those of ancestors, observe the value the user already sees in the native control
+        target.dispatch_slider_change(ArgsSliderChange { value: 1.0 });
+        target.dispatch_dropdown_change(ArgsDropdownChange { selected_index: 1 });
+        target.dispatch_textbox_change(ArgsTextboxChange {
+            text: "text".to_string(),
+        });
+        assert_eq!(
+            take_calls(),
+            vec![
+                ("apply_slider_change", target.id_chain.clone()),
+                ("slider_change", target.id_chain.clone()),
+                ("slider_change", root.id_chain.clone()),
+                ("apply_dropdown_change", target.id_chain.clone()),
+                ("dropdown_change", target.id_chain.clone()),
+                ("dropdown_change", root.id_chain.clone()),
+                ("apply_textbox_change", target.id_chain.clone()),
+                ("textbox_change", target.id_chain.clone()),
+                ("textbox_change", root.id_chain.clone()),
+            ]
+        );
+    }
+
+    /// Records each `begin` and `end` of compositing, as `begin <canvas_id> <opacity>` and `end <canvas_id>`
+    struct RecordingCompositor(Rc<RefCell<Vec<String>>>);
+
+    impl Compositor for RecordingCompositor {
+        fn begin(&self, canvas_id: &str, opacity: f64, _blend_mode: BlendMode) {
+            (*self.0)
+                .borrow_mut()
+                .push(format!("begin {} {}", canvas_id, opacity));
+        }
+
+        fn end(&self, canvas_id: &str) {
+            (*self.0).borrow_mut().push(format!("end {}", canvas_id));
+        }
+    }
+
+    #[test]
+    fn test_nested_opacity_accumulates_and_is_composited_per_subtree() {
+        set_value("opacity_sibling", 0.0);
+        let mut parent_id = 0;
+        let engine = &mut build_engine_with_template(|instance_registry| {
+            let child = build_test_node(instance_registry, None, vec![]);
+            let parent = build_test_node(instance_registry, None, vec![child.clone()]);
+            for node in [&parent, &child] {
+                node.borrow_mut().common_properties.opacity =
+                    Some(Rc::new(RefCell::new(PropertyLiteral::new(0.5)))
+                        as Rc<RefCell<dyn PropertyInstance<f64>>>);
+            }
+            parent_id = parent.borrow().instance_id;
+            let sibling = build_test_node(instance_registry, Some("opacity_sibling"), vec![]);
+            vec![parent as RenderNodePtr<R>, sibling]
+        });
+        let composited = Rc::new(RefCell::new(vec![]));
+        engine.set_compositor(Box::new(RecordingCompositor(Rc::clone(&composited))));
+
+        //each composited subtree is bracketed by `begin` and `end`, with its opacity compounding its ancestors'
+        let expected = vec![
+            "begin 0 0.5".to_string(),
+            "begin 0 0.25".to_string(),
+            "end 0".to_string(),
+            "end 0".to_string(),
+        ];
+        assert!(tick(engine));
+        assert_eq!(composited.take(), expected);
+
+        //as when the subtree is painted from a previous traversal
+        set_value("opacity_sibling", 1.0);
+        assert!(tick(engine));
+        assert!(!take_calls().contains(&("compute_properties", vec![parent_id])));
+        assert_eq!(composited.take(), expected);
     }
 }
@@ -6,6 +6,7 @@ use std::rc::Rc;
 use crate::runtime::StackFrame;
 use crate::PaxEngine;
 
+use pax_runtime_api::reactivity::{self, PropertyId};
 use pax_runtime_api::{EasingCurve, PropertyInstance, TransitionManager, TransitionQueueEntry};
 
 // The `Expression` form of a property — stores a function
@@ -14,22 +15,43 @@ use pax_runtime_api::{EasingCurve, PropertyInstance, TransitionManager, Transiti
 // via calls to `read()`
 pub struct PropertyExpression<T: Default> {
     pub id: usize,
+    property_id: PropertyId,
     pub cached_value: T,
     pub transition_manager: TransitionManager<T>,
+    /// The progress of an `ease_to_default`, if one is under way
+    ease_to_default_state: Option<EaseToDefaultState>,
+}
+
+/// An expression eases to its default — its own value — in two steps, since that value is only known once
+/// the expression is re-evaluated
+enum EaseToDefaultState {
+    /// The transition is queued, but its `ending_value` awaits the expression's next evaluation (see `set`)
+    AwaitingValue,
+    /// The transition is under way; the expression is tracked again once the transition queue empties
+    Easing,
 }
 
 impl<T: Default> PropertyExpression<T> {
     pub fn new(id: usize) -> Self {
         Self {
             id,
+            property_id: reactivity::mint_property_id(),
             cached_value: Default::default(),
             transition_manager: TransitionManager::new(),
+            ease_to_default_state: None,
         }
     }
 }
 
+impl<T: Default> Drop for PropertyExpression<T> {
+    fn drop(&mut self) {
+        reactivity::forget_property(self.property_id);
+    }
+}
+
 impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
     fn get(&self) -> &T {
+        reactivity::notify_read(self.property_id);
         &self.cached_value
     }
 
@@ -41,12 +63,28 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
         Some(self.id)
     }
 
+    fn _get_property_id(&self) -> PropertyId {
+        self.property_id
+    }
+
     fn set(&mut self, value: T) {
-        self.cached_value = value;
+        reactivity::notify_changed(self.property_id);
+        if let Some(EaseToDefaultState::AwaitingValue) = self.ease_to_default_state {
+            //`value` is the freshly re-evaluated expression, which the queued transition eases to
+            if let Some(transition) = self.transition_manager.queue.front_mut() {
+                transition.ending_value = value;
+            }
+            self.transition_manager.value = Some(self.cached_value.clone());
+            self.ease_to_default_state = Some(EaseToDefaultState::Easing);
+        } else {
+            self.cached_value = value;
+        }
     }
 
     //FUTURE: when trait fields land, DRY this implementation vs. other <T: PropertyInstance> implementations
     fn ease_to(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+        reactivity::notify_changed(self.property_id);
+        self.ease_to_default_state = None;
         self.transition_manager.value = Some(self.get().clone());
         self.transition_manager.queue.clear();
         self.transition_manager
@@ -61,7 +99,9 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
     }
 
     fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
-        if let None = self.transition_manager.value {
+        reactivity::notify_changed(self.property_id);
+        self.ease_to_default_state = None;
+        if self.transition_manager.value.is_none() {
             //handle case where transition queue is empty -- a None value gets skipped, so populate it with Some
             self.transition_manager.value = Some(self.get().clone());
         }
@@ -77,12 +117,46 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyExpression<T> {
     }
 
     fn _get_transition_manager(&mut self) -> Option<&mut TransitionManager<T>> {
-        if let None = self.transition_manager.value {
+        if let Some(EaseToDefaultState::Easing) = self.ease_to_default_state {
+            if self.transition_manager.queue.is_empty() {
+                //hand control back to the expression, whose last evaluation is the value we eased to
+                self.ease_to_default_state = None;
+                self.transition_manager.value = None;
+            }
+        }
+        if self.transition_manager.value.is_none() {
             None
         } else {
             Some(&mut self.transition_manager)
         }
     }
+
+    fn to_default(&mut self) {
+        self.transition_manager.queue.clear();
+        self.transition_manager.value = None;
+        self.ease_to_default_state = None;
+        //re-evaluate next tick, discarding any value `set` since the expression's last evaluation
+        reactivity::invalidate_expression(self.id);
+    }
+
+    fn ease_to_default(&mut self, duration_frames: u64, curve: EasingCurve) {
+        //The value to ease to isn't known until the expression is re-evaluated next tick, at which point
+        //the engine passes it to `set`.  Until then, the transition waits, leaving the current value in place:
+        //with no `transition_manager.value`, the engine evaluates the expression rather than easing.
+        self.transition_manager.queue.clear();
+        self.transition_manager.value = None;
+        self.transition_manager
+            .queue
+            .push_back(TransitionQueueEntry {
+                global_frame_started: None,
+                duration_frames,
+                curve,
+                starting_value: self.cached_value.clone(),
+                ending_value: self.cached_value.clone(),
+            });
+        self.ease_to_default_state = Some(EaseToDefaultState::AwaitingValue);
+        reactivity::invalidate_expression(self.id);
+    }
 }
 
 /// Data structure used for dynamic injection of values
@@ -94,3 +168,49 @@ pub struct ExpressionContext<'a, R: 'static + RenderContext> {
     //TODO: is the following the right approach to enabling evaluation of built-ins?
     // pub render_node: RenderNodePtr<R>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_transition(property: &mut PropertyExpression<f64>) -> Option<(f64, f64)> {
+        let transition_manager = property._get_transition_manager()?;
+        let transition = transition_manager.queue.front()?;
+        Some((transition.starting_value, transition.ending_value))
+    }
+
+    #[test]
+    fn test_expressions_ease_to_their_next_evaluation_then_resume_tracking_it() {
+        let mut property = PropertyExpression::new(0);
+        //as evaluated by the engine
+        property.set(1.0);
+        property.ease_to(5.0, 10, EasingCurve::Linear);
+        property.set(5.0);
+
+        //the value to ease back to is only known once the expression is evaluated again
+        property.ease_to_default(10, EasingCurve::Linear);
+        assert!(get_transition(&mut property).is_none());
+        assert_eq!(*property.get(), 5.0);
+        property.set(2.0);
+        assert_eq!(*property.get(), 5.0);
+        assert_eq!(get_transition(&mut property), Some((5.0, 2.0)));
+
+        //once the transition completes, the expression is tracked again
+        property.transition_manager.queue.clear();
+        assert!(property._get_transition_manager().is_none());
+        property.set(3.0);
+        assert_eq!(*property.get(), 3.0);
+    }
+
+    #[test]
+    fn test_expressions_reset_to_their_next_evaluation() {
+        let mut property = PropertyExpression::new(0);
+        property.set(1.0);
+        property.ease_to_default(10, EasingCurve::Linear);
+        property.to_default();
+        assert!(property._get_transition_manager().is_none());
+        property.set(2.0);
+        assert_eq!(*property.get(), 2.0);
+        assert!(property._get_transition_manager().is_none());
+    }
+}
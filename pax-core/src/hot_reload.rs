@@ -0,0 +1,477 @@
+//! Hot reloading of component templates, per the `ComponentTemplateReload`s sent by `pax run --hot-reload`.
+//! The engine rebuilds each live instance of the reloaded component from the new template, binding
+//! its expressions to the runtime interpreter.  Everything that can only be named in Rust — userland
+//! types, handlers, functions — is resolved through the cartridge's `HotReloadCartridge`.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use pax_message::hot_reload::{
+    ComponentTemplateReload, InterpretedExpression, InterpretedExpressionKind,
+    InterpretedScopeFrame, TemplateNodePatch,
+};
+use pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
+use pax_runtime_api::CommonProperties;
+use piet_common::RenderContext;
+
+use crate::interpreter::{self, PaxelReflection, PaxelScopeFrame, PaxelValue};
+use crate::{
+    ConditionalInstance, ExpressionContext, ExpressionFn, HandlerRegistry, InstanceRegistry,
+    InstantiationArgs, PaxEngine, PropertyExpression, RenderNode, RenderNodePtr, RenderNodePtrList,
+    RepeatInstance, SlotInstance,
+};
+
+/// The cartridge's side of hot reloading: the parts of instantiating a template that require naming
+/// userland types.  Implemented by cartridges built with `--hot-reload`.
+pub trait HotReloadCartridge<R: 'static + RenderContext> {
+    fn get_reflection(&self) -> &dyn PaxelReflection;
+    /// Convert an interpreted value into the `TypesCoproduct` variant `pascalized_return_type`
+    fn wrap_typed(
+        &self,
+        pascalized_return_type: &str,
+        value: PaxelValue,
+    ) -> Result<TypesCoproduct, String>;
+    /// The default value of the `TypesCoproduct` variant `pascalized_return_type`, to which an expression
+    /// falls back if it fails to evaluate before ever succeeding
+    fn default_typed(&self, pascalized_return_type: &str) -> Result<TypesCoproduct, String>;
+    /// Convert the interpreted source of a `for` into `Repeat`'s data, where each element is wrapped
+    /// in the `PropertiesCoproduct` variant `iterable_type_id_escaped`
+    fn rewrap_repeat_source(
+        &self,
+        iterable_type_id_escaped: &str,
+        value: PaxelValue,
+    ) -> Result<Vec<Rc<PropertiesCoproduct>>, String>;
+    /// Instantiate a primitive or userland component, binding each (property, expression id) in `settings`.
+    /// `args` carries everything else, including the common properties.
+    fn instantiate_component(
+        &self,
+        type_id: &str,
+        settings: &[(String, usize)],
+        args: InstantiationArgs<R>,
+    ) -> Result<RenderNodePtr<R>, String>;
+    /// Register `method` of the component `owner_type_id` as a handler for `event`
+    fn register_handler(
+        &self,
+        handler_registry: &mut HandlerRegistry<R>,
+        owner_type_id: &str,
+        event: &str,
+        method: &str,
+    ) -> Result<(), String>;
+}
+
+impl<R: 'static + RenderContext> PaxEngine<R> {
+    /// Replace the template of every live instance of `reload.component_type_id`.  The instances keep their
+    /// properties; the nodes of their previous templates are unmounted on the next tick.
+    pub fn hot_reload(
+        &mut self,
+        reload: ComponentTemplateReload,
+        cartridge: Rc<dyn HotReloadCartridge<R>>,
+    ) -> Result<(), String> {
+        for expression in reload.expressions.iter() {
+            interpreter::try_register_expression(expression.id, &expression.paxel)
+                .map_err(|err| format!("Failed to parse `{}`: {}", expression.paxel, err))?;
+            cartridge.default_typed(&expression.pascalized_return_type)?;
+            self.expression_table.insert(
+                expression.id,
+                build_interpreted_expression(expression, Rc::clone(&cartridge)),
+            );
+        }
+
+        let instances = (*self.instance_registry)
+            .borrow_mut()
+            .get_component_instances(&reload.component_type_id);
+
+        //Build every instance's new template before swapping any in, so that a failure leaves the app untouched
+        let mut replacements = vec![];
+        for instance in instances.iter() {
+            let mut handler_registry = HandlerRegistry::default();
+            for (event, method) in reload.handlers.iter() {
+                cartridge.register_handler(
+                    &mut handler_registry,
+                    &reload.component_type_id,
+                    event,
+                    method,
+                )?;
+            }
+            let template = instantiate_node_patches(
+                &reload.template,
+                &reload.component_type_id,
+                &cartridge,
+                &self.instance_registry,
+            )?;
+            replacements.push((instance, template, handler_registry));
+        }
+
+        let mut instance_registry = (*self.instance_registry).borrow_mut();
+        for (instance, template, handler_registry) in replacements {
+            (**instance).borrow_mut().replace_template(
+                template,
+                Rc::new(RefCell::new(handler_registry)),
+                &mut instance_registry,
+            );
+        }
+        drop(instance_registry);
+
+        self.invalidate();
+        Ok(())
+    }
+}
+
+fn build_interpreted_expression<R: 'static + RenderContext>(
+    expression: &InterpretedExpression,
+    cartridge: Rc<dyn HotReloadCartridge<R>>,
+) -> ExpressionFn<R> {
+    let id = expression.id;
+    let paxel = expression.paxel.clone();
+    let pascalized_return_type = expression.pascalized_return_type.clone();
+    let kind = expression.kind.clone();
+    let scope: Vec<PaxelScopeFrame> = expression
+        .scope_frames
+        .iter()
+        .map(|frame| match frame {
+            InterpretedScopeFrame::Properties => PaxelScopeFrame::Properties,
+            InterpretedScopeFrame::RepeatItem {
+                elem,
+                i,
+                destructured,
+            } => PaxelScopeFrame::RepeatItem {
+                elem: elem.clone(),
+                i: i.clone(),
+                destructured: destructured.clone(),
+            },
+        })
+        .collect();
+
+    //An edited template may well not evaluate, e.g. mid-edit, or for some data but not others.  Rather than
+    //crash the app, such an expression keeps its last good value, or else its type's default.
+    let last_good_value: RefCell<Option<PaxelValue>> = RefCell::new(None);
+    Box::new(move |ec: ExpressionContext<R>| -> TypesCoproduct {
+        let wrap = |value: PaxelValue| {
+            match &kind {
+            InterpretedExpressionKind::Value => cartridge.wrap_typed(&pascalized_return_type, value),
+            InterpretedExpressionKind::RepeatSourceIterable(iterable_type_id_escaped) => {
+                cartridge
+                    .rewrap_repeat_source(iterable_type_id_escaped, value)
+                    .map(TypesCoproduct::stdCOCOvecCOCOVecLABRstdCOCOrcCOCORcLABRPropertiesCoproductRABRRABR)
+            }
+            InterpretedExpressionKind::RepeatKey => value.into_key().map(TypesCoproduct::String),
+        }
+        };
+        interpreter::evaluate_registered(id, &scope, &ec.stack_frame, cartridge.get_reflection())
+            .and_then(|value| {
+                let wrapped = wrap(value.clone())?;
+                //A key is evaluated per item, so another item's key is no fallback
+                if !matches!(kind, InterpretedExpressionKind::RepeatKey) {
+                    *last_good_value.borrow_mut() = Some(value);
+                }
+                Ok(wrapped)
+            })
+            .unwrap_or_else(|err| {
+                pax_runtime_api::log(&format!(
+                    "Failed to evaluate expression `{}`: {}",
+                    paxel, err
+                ));
+                last_good_value
+                    .borrow()
+                    .clone()
+                    .and_then(|value| wrap(value).ok())
+                    .unwrap_or_else(|| {
+                        cartridge
+                            .default_typed(&pascalized_return_type)
+                            .expect("return types are checked by `hot_reload`")
+                    })
+            })
+    })
+}
+
+fn instantiate_node_patches<R: 'static + RenderContext>(
+    patches: &[TemplateNodePatch],
+    owner_type_id: &str,
+    cartridge: &Rc<dyn HotReloadCartridge<R>>,
+    instance_registry: &Rc<RefCell<InstanceRegistry<R>>>,
+) -> Result<RenderNodePtrList<R>, String> {
+    let nodes = patches
+        .iter()
+        .map(|patch| instantiate_node_patch(patch, owner_type_id, cartridge, instance_registry))
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(Rc::new(RefCell::new(nodes)))
+}
+
+fn instantiate_node_patch<R: 'static + RenderContext>(
+    patch: &TemplateNodePatch,
+    owner_type_id: &str,
+    cartridge: &Rc<dyn HotReloadCartridge<R>>,
+    instance_registry: &Rc<RefCell<InstanceRegistry<R>>>,
+) -> Result<RenderNodePtr<R>, String> {
+    let mut args = InstantiationArgs {
+        common_properties: CommonProperties::default(),
+        properties: PropertiesCoproduct::None,
+        handler_registry: None,
+        instance_registry: Rc::clone(instance_registry),
+        children: None,
+        component_template: None,
+        scroller_args: None,
+        slot_index: None,
+        repeat_source_expression_vec: None,
+        repeat_source_expression_range: None,
+        repeat_key_expression: None,
+        conditional_boolean_expression: None,
+        conditional_else_children: None,
+        compute_properties_fn: None,
+    };
+
+    match patch {
+        TemplateNodePatch::Component {
+            type_id,
+            settings,
+            handlers,
+            children,
+        } => {
+            let component_settings: Vec<(String, usize)> = settings
+                .iter()
+                .filter(|(name, id)| !set_common_property(&mut args.common_properties, name, *id))
+                .cloned()
+                .collect();
+
+            let mut handler_registry = HandlerRegistry::default();
+            for (event, method) in handlers.iter() {
+                cartridge.register_handler(&mut handler_registry, owner_type_id, event, method)?;
+            }
+            args.handler_registry = Some(Rc::new(RefCell::new(handler_registry)));
+            args.children = Some(instantiate_node_patches(
+                children,
+                owner_type_id,
+                cartridge,
+                instance_registry,
+            )?);
+            cartridge.instantiate_component(type_id, &component_settings, args)
+        }
+        TemplateNodePatch::Repeat {
+            source,
+            is_source_range,
+            key,
+            children,
+        } => {
+            if *is_source_range {
+                args.repeat_source_expression_range =
+                    Some(Box::new(PropertyExpression::new(*source)));
+            } else {
+                args.repeat_source_expression_vec =
+                    Some(Box::new(PropertyExpression::new(*source)));
+            }
+            args.repeat_key_expression = key.map(|key| Box::new(PropertyExpression::new(key)) as _);
+            args.children = Some(instantiate_node_patches(
+                children,
+                owner_type_id,
+                cartridge,
+                instance_registry,
+            )?);
+            Ok(RepeatInstance::instantiate(args))
+        }
+        TemplateNodePatch::Conditional {
+            condition,
+            children,
+            else_children,
+        } => {
+            args.conditional_boolean_expression =
+                Some(Box::new(PropertyExpression::new(*condition)));
+            args.children = Some(instantiate_node_patches(
+                children,
+                owner_type_id,
+                cartridge,
+                instance_registry,
+            )?);
+            if !else_children.is_empty() {
+                args.conditional_else_children = Some(instantiate_node_patches(
+                    else_children,
+                    owner_type_id,
+                    cartridge,
+                    instance_registry,
+                )?);
+            }
+            Ok(ConditionalInstance::instantiate(args))
+        }
+        TemplateNodePatch::Slot { index } => {
+            args.slot_index = Some(Box::new(PropertyExpression::new(*index)));
+            Ok(SlotInstance::instantiate(args))
+        }
+    }
+}
+
+/// Bind the expression `id` to the common property `name`, returning `false` if there is no such common property
+fn set_common_property(common_properties: &mut CommonProperties, name: &str, id: usize) -> bool {
+    match name {
+        "x" => common_properties.x = Some(Rc::new(RefCell::new(PropertyExpression::new(id)))),
+        "y" => common_properties.y = Some(Rc::new(RefCell::new(PropertyExpression::new(id)))),
+        "scale_x" => {
+            common_properties.scale_x = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "scale_y" => {
+            common_properties.scale_y = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "skew_x" => {
+            common_properties.skew_x = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "skew_y" => {
+            common_properties.skew_y = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "rotate" => {
+            common_properties.rotate = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "anchor_x" => {
+            common_properties.anchor_x = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "anchor_y" => {
+            common_properties.anchor_y = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "transform" => {
+            common_properties.transform = Rc::new(RefCell::new(PropertyExpression::new(id)))
+        }
+        "width" => common_properties.width = Rc::new(RefCell::new(PropertyExpression::new(id))),
+        "height" => common_properties.height = Rc::new(RefCell::new(PropertyExpression::new(id))),
+        "focusable" => {
+            common_properties.focusable = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "tab_index" => {
+            common_properties.tab_index = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "opacity" => {
+            common_properties.opacity = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        "blend_mode" => {
+            common_properties.blend_mode = Some(Rc::new(RefCell::new(PropertyExpression::new(id))))
+        }
+        _ => return false,
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::tests::{build_engine_with_template, R};
+    use crate::StackFrame;
+    use std::any::Any;
+
+    /// Converts to and from `f64`, the only type known to this cartridge, and fails on negative numbers
+    struct F64Cartridge;
+
+    impl PaxelReflection for F64Cartridge {
+        fn get_property(&self, _: &PropertiesCoproduct, _: &str) -> Option<PaxelValue> {
+            None
+        }
+        fn get_field(&self, _: &dyn Any, _: &str) -> Option<PaxelValue> {
+            None
+        }
+        fn get_elements(&self, _: &dyn Any) -> Option<Vec<PaxelValue>> {
+            None
+        }
+        fn call_function(&self, _: &str, _: Vec<PaxelValue>) -> Option<Result<PaxelValue, String>> {
+            None
+        }
+    }
+
+    impl HotReloadCartridge<R> for F64Cartridge {
+        fn get_reflection(&self) -> &dyn PaxelReflection {
+            self
+        }
+        fn wrap_typed(&self, _: &str, value: PaxelValue) -> Result<TypesCoproduct, String> {
+            match value.into_typed::<f64>()? {
+                value if value < 0.0 => Err("negative".to_string()),
+                value => Ok(TypesCoproduct::f64(value)),
+            }
+        }
+        fn default_typed(&self, pascalized_return_type: &str) -> Result<TypesCoproduct, String> {
+            match pascalized_return_type {
+                "f64" => Ok(TypesCoproduct::f64(Default::default())),
+                _ => Err(format!("unknown type `{}`", pascalized_return_type)),
+            }
+        }
+        fn rewrap_repeat_source(
+            &self,
+            _: &str,
+            _: PaxelValue,
+        ) -> Result<Vec<Rc<PropertiesCoproduct>>, String> {
+            unimplemented!()
+        }
+        fn instantiate_component(
+            &self,
+            _: &str,
+            _: &[(String, usize)],
+            _: InstantiationArgs<R>,
+        ) -> Result<RenderNodePtr<R>, String> {
+            unimplemented!()
+        }
+        fn register_handler(
+            &self,
+            _: &mut HandlerRegistry<R>,
+            _: &str,
+            _: &str,
+            _: &str,
+        ) -> Result<(), String> {
+            unimplemented!()
+        }
+    }
+
+    fn build_expression(id: usize, paxel: &str) -> InterpretedExpression {
+        InterpretedExpression {
+            id,
+            paxel: paxel.to_string(),
+            pascalized_return_type: "f64".to_string(),
+            scope_frames: vec![InterpretedScopeFrame::RepeatItem {
+                elem: "elem".to_string(),
+                i: None,
+                destructured: vec![],
+            }],
+            kind: InterpretedExpressionKind::Value,
+        }
+    }
+
+    #[test]
+    fn test_failing_expressions_keep_their_last_good_value_or_default() {
+        let engine = build_engine_with_template(|_| vec![]);
+        let expression = build_expression(1_000_000, "elem * 2");
+        interpreter::try_register_expression(expression.id, &expression.paxel).unwrap();
+        let evaluate = build_interpreted_expression(&expression, Rc::new(F64Cartridge));
+        let evaluate_with_elem = |elem: isize| {
+            let stack_frame = Rc::new(RefCell::new(StackFrame::new(
+                Rc::new(RefCell::new(vec![])),
+                Rc::new(RefCell::new(PropertiesCoproduct::RepeatItem(
+                    Rc::new(PropertiesCoproduct::isize(elem)),
+                    0,
+                ))),
+                None,
+                None,
+                None,
+            )));
+            match evaluate(ExpressionContext {
+                engine: &engine,
+                stack_frame,
+            }) {
+                TypesCoproduct::f64(value) => value,
+                _ => unreachable!(),
+            }
+        };
+
+        //falls back to the type's default until it first evaluates...
+        assert_eq!(evaluate_with_elem(-1), 0.0);
+        assert_eq!(evaluate_with_elem(3), 6.0);
+        //...and to its last good value after
+        assert_eq!(evaluate_with_elem(-1), 6.0);
+        assert_eq!(evaluate_with_elem(4), 8.0);
+    }
+
+    #[test]
+    fn test_reloads_with_unknown_return_types_are_rejected() {
+        let mut engine = build_engine_with_template(|_| vec![]);
+        let mut expression = build_expression(1_000_001, "1.0");
+        expression.pascalized_return_type = "crate::Unknown".to_string();
+        let reload = ComponentTemplateReload {
+            component_type_id: "crate::Example".to_string(),
+            expressions: vec![expression],
+            template: vec![],
+            handlers: vec![],
+        };
+        assert!(engine.hot_reload(reload, Rc::new(F64Cartridge)).is_err());
+    }
+}
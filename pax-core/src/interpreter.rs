@@ -0,0 +1,1324 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use pest::iterators::{Pair, Pairs};
+use pest::pratt_parser::{Assoc, Op, PrattParser};
+use pest::Parser;
+use pest_derive::Parser;
+use piet::RenderContext;
+
+use pax_properties_coproduct::PropertiesCoproduct;
+use pax_runtime_api::{paxel_std, reactivity};
+use pax_runtime_api::{Numeric, RepeatRange, Rotation, Size, SizePixels, StringBox, Transform2D};
+
+use crate::runtime::StackFrame;
+
+// The runtime PAXEL interpreter.  By default, the compiler transpiles each PAXEL expression into
+// Rust (RIL) that is built into the cartridge; when building with `--interpret-expressions`, the cartridge
+// instead registers each expression's source here, to be parsed once and evaluated against the
+// current `StackFrame` whenever the engine computes that expression.  Interpreted expressions can be
+// replaced at runtime via `hot_swap_expression`, without rebuilding.
+
+/// Parses PAXEL with the same grammar as the compiler's `PaxParser`
+#[derive(Parser)]
+#[grammar = "pax.pest"]
+struct PaxelParser;
+
+/// A parsed PAXEL expression
+#[derive(Clone)]
+pub enum PaxelExpression {
+    Literal(PaxelValue),
+    /// A symbol like `self.foo.bar`, `elem` or `self.position.0`, stripped of any leading `self.` or `this.`: its root
+    /// identifier, followed by any nested field (including tuple index) or index accessors
+    Symbol(String, Vec<PaxelAccessor>),
+    Unary(PaxelUnaryOperator, Box<PaxelExpression>),
+    Binary(
+        PaxelBinaryOperator,
+        Box<PaxelExpression>,
+        Box<PaxelExpression>,
+    ),
+    /// `condition ? then : else`.  The `else` branch is `None` only while parsing.
+    Conditional(
+        Box<PaxelExpression>,
+        Box<PaxelExpression>,
+        Option<Box<PaxelExpression>>,
+    ),
+    /// A grouped expression with a unit, e.g. `(i * 20)px`
+    WithUnit(Box<PaxelExpression>, PaxelUnit),
+    Tuple(Vec<PaxelExpression>),
+    List(Vec<PaxelExpression>),
+    /// A range, e.g. `0..n`, `0..=n` or `0..100 step 5`, with its bounds, inclusivity and optional step
+    Range(
+        Box<PaxelExpression>,
+        Box<PaxelExpression>,
+        bool,
+        Option<Box<PaxelExpression>>,
+    ),
+    /// A function call or enum literal, e.g. `Color::rgb(1.0, 0.5, 0)` or `FontStyle::Normal`, by path
+    FunctionCall(String, Vec<PaxelExpression>),
+    /// A call to a method of PAXEL's standard library on a value, e.g. `self.items.filter(|x| x.visible)`:
+    /// the receiver, method name and arguments
+    MethodCall(Box<PaxelExpression>, String, Vec<PaxelExpression>),
+    /// A lambda passed to a method, e.g. the `|x| x.visible` in `self.items.filter(|x| x.visible)`
+    Lambda(Vec<String>, Box<PaxelExpression>),
+}
+
+#[derive(Clone)]
+pub enum PaxelAccessor {
+    Field(String),
+    Index(Box<PaxelExpression>),
+}
+
+#[derive(Clone, Copy)]
+pub enum PaxelUnaryOperator {
+    Neg,
+    Not,
+}
+
+#[derive(Clone, Copy)]
+pub enum PaxelBinaryOperator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Exp,
+    And,
+    Or,
+    Eq,
+    Neq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+impl PaxelBinaryOperator {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PaxelBinaryOperator::Add => "+",
+            PaxelBinaryOperator::Sub => "-",
+            PaxelBinaryOperator::Mul => "*",
+            PaxelBinaryOperator::Div => "/",
+            PaxelBinaryOperator::Mod => "%%",
+            PaxelBinaryOperator::Exp => "^",
+            PaxelBinaryOperator::And => "&&",
+            PaxelBinaryOperator::Or => "||",
+            PaxelBinaryOperator::Eq => "==",
+            PaxelBinaryOperator::Neq => "!=",
+            PaxelBinaryOperator::Lt => "<",
+            PaxelBinaryOperator::Lte => "<=",
+            PaxelBinaryOperator::Gt => ">",
+            PaxelBinaryOperator::Gte => ">=",
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum PaxelUnit {
+    Pixels,
+    Percent,
+    Degrees,
+    Radians,
+}
+
+impl PaxelUnit {
+    fn from_str(unit: &str) -> Result<Self, String> {
+        match unit {
+            "px" => Ok(PaxelUnit::Pixels),
+            "%" => Ok(PaxelUnit::Percent),
+            "deg" => Ok(PaxelUnit::Degrees),
+            "rad" => Ok(PaxelUnit::Radians),
+            _ => Err(format!("unknown unit `{}`", unit)),
+        }
+    }
+
+    fn apply(&self, value: Numeric) -> PaxelValue {
+        match self {
+            PaxelUnit::Pixels => PaxelValue::Size(Size::Pixels(value)),
+            PaxelUnit::Percent => PaxelValue::Size(Size::Percent(value)),
+            PaxelUnit::Degrees => PaxelValue::Rotation(Rotation::Degrees(value)),
+            PaxelUnit::Radians => PaxelValue::Rotation(Rotation::Radians(value)),
+        }
+    }
+}
+
+/// A value produced by the interpreter.  Values of the built-in types that PAXEL operates on are
+/// represented directly; any other value (e.g. an instance of a userland struct) is carried as `Opaque`.
+#[derive(Clone)]
+pub enum PaxelValue {
+    Numeric(Numeric),
+    Bool(bool),
+    String(StringBox),
+    Size(Size),
+    Rotation(Rotation),
+    Transform2D(Transform2D),
+    Range(RepeatRange),
+    Tuple(Vec<PaxelValue>),
+    List(Vec<PaxelValue>),
+    /// A `Repeat` datum, e.g. the `elem` in `for elem in self.elements`
+    Datum(Rc<PropertiesCoproduct>),
+    Opaque(Rc<dyn Any>),
+}
+
+/// Returns `f()` as a `T`, if `T` and `U` are the same type.  `f` is only invoked if so, which matters for
+/// conversions that panic on overflow, like `usize::from(Numeric)` for a negative value.
+fn convert<T: Any, U: Any>(f: impl FnOnce() -> U) -> Option<T> {
+    if TypeId::of::<T>() == TypeId::of::<U>() {
+        (Box::new(f()) as Box<dyn Any>)
+            .downcast::<T>()
+            .ok()
+            .map(|value| *value)
+    } else {
+        None
+    }
+}
+
+impl PaxelValue {
+    /// Wrap an arbitrary value, unwrapping it into the appropriate built-in variant if it has one
+    pub fn from_any<T: Any>(value: T) -> Self {
+        let value: Box<dyn Any> = Box::new(value);
+        macro_rules! unwrap_as {
+            ($value:ident, $type:ty, $wrap:expr) => {
+                let $value = match $value.downcast::<$type>() {
+                    Ok(unwrapped) => {
+                        let wrap = $wrap;
+                        return wrap(*unwrapped);
+                    }
+                    Err($value) => $value,
+                };
+            };
+        }
+        macro_rules! for_each_numeric_type {
+            ($value:ident) => {
+                for_each_numeric_type!($value, f64, isize, usize, i64, i32, u64, u32, u8);
+            };
+            ($value:ident, $($type:ty),*) => {
+                $(unwrap_as!($value, $type, |v: $type| PaxelValue::Numeric(Numeric::from(v)));)*
+            };
+        }
+        unwrap_as!(value, Numeric, PaxelValue::Numeric);
+        unwrap_as!(value, f32, |v: f32| PaxelValue::Numeric(Numeric::from(
+            v as f64
+        )));
+        for_each_numeric_type!(value);
+        unwrap_as!(value, bool, PaxelValue::Bool);
+        unwrap_as!(value, StringBox, PaxelValue::String);
+        unwrap_as!(value, String, |v: String| PaxelValue::String(
+            StringBox::from(v)
+        ));
+        unwrap_as!(value, Size, PaxelValue::Size);
+        unwrap_as!(value, SizePixels, |v: SizePixels| PaxelValue::Size(
+            Size::Pixels(v.0)
+        ));
+        unwrap_as!(value, Rotation, PaxelValue::Rotation);
+        unwrap_as!(value, Transform2D, PaxelValue::Transform2D);
+        unwrap_as!(value, RepeatRange, PaxelValue::Range);
+        PaxelValue::Opaque(Rc::from(value))
+    }
+
+    /// Convert this value into a `T`, e.g. the type of the property to which an expression is bound
+    pub fn into_typed<T: Any + Clone>(self) -> Result<T, String> {
+        let description = self.describe();
+        let converted = match self {
+            PaxelValue::Numeric(n) => convert::<T, _>(|| n)
+                .or_else(|| convert::<T, _>(|| f64::from(n)))
+                .or_else(|| convert::<T, _>(|| f32::from(n)))
+                .or_else(|| convert::<T, _>(|| isize::from(n)))
+                .or_else(|| convert::<T, _>(|| usize::from(n)))
+                .or_else(|| convert::<T, _>(|| i64::from(n)))
+                .or_else(|| convert::<T, _>(|| i32::from(n)))
+                .or_else(|| convert::<T, _>(|| u64::from(n)))
+                .or_else(|| convert::<T, _>(|| u32::from(n)))
+                .or_else(|| convert::<T, _>(|| u8::from(n)))
+                .or_else(|| convert::<T, _>(|| Rotation::Radians(n)))
+                .or_else(|| convert::<T, _>(|| SizePixels(n))),
+            PaxelValue::Bool(b) => convert::<T, _>(|| b),
+            PaxelValue::String(s) => {
+                convert::<T, _>(|| s.clone()).or_else(|| convert::<T, _>(|| s.string))
+            }
+            PaxelValue::Size(size) => convert::<T, _>(|| size)
+                .or_else(|| convert::<T, _>(|| SizePixels::from(size)))
+                .or_else(|| match size {
+                    Size::Percent(percent) => convert::<T, _>(|| Rotation::Percent(percent)),
+                    _ => None,
+                }),
+            PaxelValue::Rotation(rotation) => convert::<T, _>(|| rotation),
+            PaxelValue::Transform2D(transform) => convert::<T, _>(|| transform),
+            PaxelValue::Range(range) => convert::<T, _>(|| range),
+            PaxelValue::Opaque(value) => value.downcast_ref::<T>().cloned(),
+            PaxelValue::Tuple(_) | PaxelValue::List(_) | PaxelValue::Datum(_) => None,
+        };
+        converted.ok_or_else(|| {
+            format!(
+                "cannot convert {} to `{}`",
+                description,
+                std::any::type_name::<T>()
+            )
+        })
+    }
+
+    /// Convert this value into a `Vec<T>`, e.g. the source of a `for`, whether it's a list literal or a `Vec<T>` property
+    pub fn into_typed_vec<T: Any + Clone>(self) -> Result<Vec<T>, String> {
+        match self {
+            PaxelValue::List(elements) => elements
+                .into_iter()
+                .map(|element| element.into_typed::<T>())
+                .collect(),
+            other => other.into_typed::<Vec<T>>(),
+        }
+    }
+
+    /// Stringify this value as the key of a keyed `for`, as compiled keys are stringified with `to_string()`
+    pub fn into_key(self) -> Result<String, String> {
+        match self {
+            PaxelValue::Numeric(n) => Ok(n.to_string()),
+            PaxelValue::Bool(b) => Ok(b.to_string()),
+            PaxelValue::String(s) => Ok(s.string),
+            other => Err(format!("cannot use {} as a key", other.describe())),
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            PaxelValue::Numeric(_) => "a number",
+            PaxelValue::Bool(_) => "a bool",
+            PaxelValue::String(_) => "a string",
+            PaxelValue::Size(_) => "a Size",
+            PaxelValue::Rotation(_) => "a Rotation",
+            PaxelValue::Transform2D(_) => "a Transform2D",
+            PaxelValue::Range(_) => "a range",
+            PaxelValue::Tuple(_) => "a tuple",
+            PaxelValue::List(_) => "a list",
+            PaxelValue::Datum(_) => "a `for` element",
+            PaxelValue::Opaque(_) => "a value",
+        }
+    }
+
+    fn expect_numeric(self) -> Result<Numeric, String> {
+        match self {
+            PaxelValue::Numeric(n) => Ok(n),
+            other => Err(format!("expected a number, found {}", other.describe())),
+        }
+    }
+
+    fn expect_bool(self) -> Result<bool, String> {
+        match self {
+            PaxelValue::Bool(b) => Ok(b),
+            other => Err(format!("expected a bool, found {}", other.describe())),
+        }
+    }
+}
+
+/// Access to the userland types behind `PropertiesCoproduct`, which are only known to the cartridge.
+/// Implemented by the cartridge for builds with interpreted expressions.
+pub trait PaxelReflection {
+    /// The property `name` of a component's (or `Repeat` datum's) properties, if it has one
+    fn get_property(&self, properties: &PropertiesCoproduct, name: &str) -> Option<PaxelValue>;
+    /// The field `name` of an opaque value, if it is of a known type with such a field
+    fn get_field(&self, value: &dyn Any, name: &str) -> Option<PaxelValue>;
+    /// The elements of an opaque collection, if it is a `Vec` of a known type
+    fn get_elements(&self, value: &dyn Any) -> Option<Vec<PaxelValue>>;
+    /// Call the function (or construct the enum variant) at `path`, if it was known when the cartridge was built
+    fn call_function(
+        &self,
+        path: &str,
+        args: Vec<PaxelValue>,
+    ) -> Option<Result<PaxelValue, String>>;
+}
+
+/// Describes, for an expression, the symbols bound by one of the `StackFrame`s in its scope
+#[derive(Clone)]
+pub enum PaxelScopeFrame {
+    /// A component's frame, whose properties are resolved through `PaxelReflection`
+    Properties,
+    /// A `RepeatItem`'s frame, binding e.g. `elem` and `i` in `for (elem, i) in self.elements`, along with any symbols
+    /// bound by destructuring each element and the path of fields to each, e.g. `("score", ["1"])` for `(name, score)`
+    RepeatItem {
+        elem: String,
+        i: Option<String>,
+        destructured: Vec<(String, Vec<String>)>,
+    },
+}
+
+fn build_pratt_parser() -> PrattParser<Rule> {
+    // Mirrors the operator precedence of the compiler's `run_pratt_parser`
+    PrattParser::new()
+        .op(Op::infix(Rule::xo_tern_then, Assoc::Left)
+            | Op::infix(Rule::xo_tern_else, Assoc::Right))
+        .op(Op::infix(Rule::xo_bool_and, Assoc::Left) | Op::infix(Rule::xo_bool_or, Assoc::Left))
+        .op(Op::infix(Rule::xo_add, Assoc::Left) | Op::infix(Rule::xo_sub, Assoc::Left))
+        .op(Op::infix(Rule::xo_mul, Assoc::Left) | Op::infix(Rule::xo_div, Assoc::Left))
+        .op(Op::infix(Rule::xo_mod, Assoc::Left))
+        .op(Op::infix(Rule::xo_exp, Assoc::Right))
+        .op(Op::prefix(Rule::xo_neg))
+        .op(Op::infix(Rule::xo_rel_eq, Assoc::Left)
+            | Op::infix(Rule::xo_rel_neq, Assoc::Left)
+            | Op::infix(Rule::xo_rel_lt, Assoc::Left)
+            | Op::infix(Rule::xo_rel_lte, Assoc::Left)
+            | Op::infix(Rule::xo_rel_gt, Assoc::Left)
+            | Op::infix(Rule::xo_rel_gte, Assoc::Left))
+        .op(Op::prefix(Rule::xo_bool_not))
+}
+
+/// Parse a PAXEL expression, e.g. the `self.num_clicks + 5` in `<SomeNode some_property={self.num_clicks + 5} />`
+pub fn parse(paxel: &str) -> Result<PaxelExpression, String> {
+    let paxel = paxel.trim();
+    let mut pairs =
+        PaxelParser::parse(Rule::expression_body, paxel).map_err(|err| format!("{}", err))?;
+    let expression_body = pairs.next().unwrap();
+    if expression_body.as_span().end() != paxel.len() {
+        return Err(format!(
+            "unexpected input `{}`",
+            &paxel[expression_body.as_span().end()..]
+        ));
+    }
+    build_expression(expression_body.into_inner(), &build_pratt_parser())
+}
+
+fn build_expression(
+    pairs: Pairs<Rule>,
+    pratt_parser: &PrattParser<Rule>,
+) -> Result<PaxelExpression, String> {
+    pratt_parser
+        .map_primary(|primary| build_primary(primary, pratt_parser))
+        .map_prefix(|op, rhs| {
+            let operator = match op.as_rule() {
+                Rule::xo_neg => PaxelUnaryOperator::Neg,
+                Rule::xo_bool_not => PaxelUnaryOperator::Not,
+                _ => unreachable!(),
+            };
+            Ok(PaxelExpression::Unary(operator, Box::new(rhs?)))
+        })
+        .map_infix(|lhs, op, rhs| {
+            let (lhs, rhs) = (lhs?, rhs?);
+            let operator = match op.as_rule() {
+                Rule::xo_add => PaxelBinaryOperator::Add,
+                Rule::xo_sub => PaxelBinaryOperator::Sub,
+                Rule::xo_mul => PaxelBinaryOperator::Mul,
+                Rule::xo_div => PaxelBinaryOperator::Div,
+                Rule::xo_mod => PaxelBinaryOperator::Mod,
+                Rule::xo_exp => PaxelBinaryOperator::Exp,
+                Rule::xo_bool_and => PaxelBinaryOperator::And,
+                Rule::xo_bool_or => PaxelBinaryOperator::Or,
+                Rule::xo_rel_eq => PaxelBinaryOperator::Eq,
+                Rule::xo_rel_neq => PaxelBinaryOperator::Neq,
+                Rule::xo_rel_lt => PaxelBinaryOperator::Lt,
+                Rule::xo_rel_lte => PaxelBinaryOperator::Lte,
+                Rule::xo_rel_gt => PaxelBinaryOperator::Gt,
+                Rule::xo_rel_gte => PaxelBinaryOperator::Gte,
+                Rule::xo_tern_then => {
+                    return Ok(PaxelExpression::Conditional(
+                        Box::new(lhs),
+                        Box::new(rhs),
+                        None,
+                    ))
+                }
+                Rule::xo_tern_else => {
+                    //`a ? b : c` parses as `(a ? b) : c`
+                    return match lhs {
+                        PaxelExpression::Conditional(condition, then, None) => Ok(
+                            PaxelExpression::Conditional(condition, then, Some(Box::new(rhs))),
+                        ),
+                        _ => Err("`:` without a preceding `?`".to_string()),
+                    };
+                }
+                _ => unreachable!(),
+            };
+            Ok(PaxelExpression::Binary(
+                operator,
+                Box::new(lhs),
+                Box::new(rhs),
+            ))
+        })
+        .parse(pairs)
+}
+
+fn build_primary(
+    primary: Pair<Rule>,
+    pratt_parser: &PrattParser<Rule>,
+) -> Result<PaxelExpression, String> {
+    match primary.as_rule() {
+        Rule::expression_grouped => {
+            /* expression_grouped = { "(" ~ expression_body ~ ")" ~ literal_number_unit? } */
+            let mut inner = primary.into_inner();
+            let expression = build_expression(inner.next().unwrap().into_inner(), pratt_parser)?;
+            match inner.next() {
+                Some(unit) => Ok(PaxelExpression::WithUnit(
+                    Box::new(expression),
+                    PaxelUnit::from_str(unit.as_str())?,
+                )),
+                None => Ok(expression),
+            }
+        }
+        Rule::xo_function_call => {
+            /* xo_function_call = {identifier ~ (("::") ~ identifier)* ~ ("("~xo_function_args_list~")")} */
+            let mut path = vec![];
+            let mut args = vec![];
+            for pair in primary.into_inner() {
+                match pair.as_rule() {
+                    Rule::identifier => path.push(pair.as_str()),
+                    _ => {
+                        for arg in pair.into_inner() {
+                            args.push(build_expression(arg.into_inner(), pratt_parser)?);
+                        }
+                    }
+                }
+            }
+            Ok(PaxelExpression::FunctionCall(path.join("::"), args))
+        }
+        Rule::xo_range => {
+            /* { (xo_literal | xo_symbol) ~ (xo_range_inclusive | xo_range_exclusive) ~ (xo_literal | xo_symbol) ~ xo_range_step? } */
+            let mut inner = primary.into_inner();
+            let start = build_primary(inner.next().unwrap(), pratt_parser)?;
+            let inclusive = matches!(inner.next().unwrap().as_rule(), Rule::xo_range_inclusive);
+            let end = build_primary(inner.next().unwrap(), pratt_parser)?;
+            let step = match inner.next() {
+                Some(step) => Some(Box::new(build_primary(
+                    step.into_inner().next().unwrap(),
+                    pratt_parser,
+                )?)),
+                None => None,
+            };
+            Ok(PaxelExpression::Range(
+                Box::new(start),
+                Box::new(end),
+                inclusive,
+                step,
+            ))
+        }
+        Rule::xo_method_call => {
+            /* xo_method_call = { xo_symbol ~ xo_method_call_segment+ }
+            xo_method_call_segment = { "." ~ identifier ~ "(" ~ xo_method_call_args_list ~ ")" } */
+            let mut inner = primary.into_inner();
+            let mut expression = build_primary(inner.next().unwrap(), pratt_parser)?;
+            for segment in inner {
+                let mut segment = segment.into_inner();
+                let name = segment.next().unwrap().as_str().to_string();
+                let args = segment
+                    .next()
+                    .unwrap()
+                    .into_inner()
+                    .map(|arg| match arg.as_rule() {
+                        Rule::xo_lambda => {
+                            /* xo_lambda = { "|" ~ identifier ~ ("," ~ identifier)* ~ "|" ~ expression_body } */
+                            let (params, body): (Vec<_>, Vec<_>) = arg
+                                .into_inner()
+                                .partition(|pair| matches!(pair.as_rule(), Rule::identifier));
+                            Ok(PaxelExpression::Lambda(
+                                params.iter().map(|p| p.as_str().to_string()).collect(),
+                                Box::new(build_primary(
+                                    body.into_iter().next().unwrap(),
+                                    pratt_parser,
+                                )?),
+                            ))
+                        }
+                        _ => build_expression(arg.into_inner(), pratt_parser),
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                expression = PaxelExpression::MethodCall(Box::new(expression), name, args);
+            }
+            Ok(expression)
+        }
+        Rule::xo_literal => build_literal(primary.into_inner().next().unwrap()),
+        Rule::xo_object => Err("object literals are not supported by the interpreter".to_string()),
+        Rule::xo_symbol => {
+            /* xo_symbol = { "$"? ~ identifier ~ (("." ~ (identifier | xo_symbol_tuple_index)) | ("[" ~ expression_body ~ "]") )* } */
+            if primary.as_str().starts_with('$') {
+                return Err(format!(
+                    "built-ins like `{}` are not yet supported",
+                    primary.as_str()
+                ));
+            }
+            let mut inner = primary.into_inner().peekable();
+            let mut root = inner.next().unwrap().as_str();
+            if root == "self" || root == "this" {
+                if let Some(pair) = inner.next() {
+                    root = pair.as_str();
+                }
+            }
+            if inner.peek().is_none() && (root == "true" || root == "false") {
+                return Ok(PaxelExpression::Literal(PaxelValue::Bool(root == "true")));
+            }
+            let accessors = inner
+                .map(|pair| match pair.as_rule() {
+                    Rule::identifier | Rule::xo_symbol_tuple_index => {
+                        Ok(PaxelAccessor::Field(pair.as_str().to_string()))
+                    }
+                    _ => Ok(PaxelAccessor::Index(Box::new(build_expression(
+                        pair.into_inner(),
+                        pratt_parser,
+                    )?))),
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok(PaxelExpression::Symbol(root.to_string(), accessors))
+        }
+        Rule::xo_tuple => Ok(PaxelExpression::Tuple(
+            primary
+                .into_inner()
+                .map(|element| build_expression(element.into_inner(), pratt_parser))
+                .collect::<Result<Vec<_>, String>>()?,
+        )),
+        Rule::xo_list => Ok(PaxelExpression::List(
+            primary
+                .into_inner()
+                .map(|element| build_expression(element.into_inner(), pratt_parser))
+                .collect::<Result<Vec<_>, String>>()?,
+        )),
+        Rule::expression_body => build_expression(primary.into_inner(), pratt_parser),
+        _ => unreachable!("{}", primary.as_str()),
+    }
+}
+
+/// Builds any of the literal rules, as found in `xo_literal` or (recursively) `literal_value`
+fn build_literal(literal: Pair<Rule>) -> Result<PaxelExpression, String> {
+    match literal.as_rule() {
+        Rule::literal_value => build_literal(literal.into_inner().next().unwrap()),
+        Rule::literal_number_with_unit => {
+            let mut inner = literal.into_inner();
+            let value = parse_number(inner.next().unwrap().as_str())?;
+            let unit = PaxelUnit::from_str(inner.next().unwrap().as_str())?;
+            Ok(PaxelExpression::Literal(unit.apply(value)))
+        }
+        Rule::literal_number => Ok(PaxelExpression::Literal(PaxelValue::Numeric(parse_number(
+            literal.as_str(),
+        )?))),
+        Rule::literal_boolean => Ok(PaxelExpression::Literal(PaxelValue::Bool(
+            literal.as_str() == "true",
+        ))),
+        Rule::string => {
+            let quoted = literal.as_str();
+            Ok(PaxelExpression::Literal(PaxelValue::String(
+                StringBox::from(unescape(&quoted[1..quoted.len() - 1])),
+            )))
+        }
+        Rule::literal_enum_value => {
+            /* literal_enum_value = {pascal_identifier ~ ("::" ~ pascal_identifier)+ ~ ("("~literal_enum_args_list~")")?} */
+            let mut path = vec![];
+            let mut args = vec![];
+            for pair in literal.into_inner() {
+                match pair.as_rule() {
+                    Rule::pascal_identifier => path.push(pair.as_str()),
+                    _ => {
+                        for arg in pair.into_inner() {
+                            args.push(build_literal(arg)?);
+                        }
+                    }
+                }
+            }
+            Ok(PaxelExpression::FunctionCall(path.join("::"), args))
+        }
+        Rule::literal_tuple => Ok(PaxelExpression::Tuple(
+            literal
+                .into_inner()
+                .map(build_literal)
+                .collect::<Result<Vec<_>, String>>()?,
+        )),
+        _ => unreachable!("{}", literal.as_str()),
+    }
+}
+
+fn parse_number(number: &str) -> Result<Numeric, String> {
+    //`literal_number` is not atomic, so it may span whitespace, e.g. after a `-`
+    let number: String = number.chars().filter(|c| !c.is_whitespace()).collect();
+    if number.contains('.') {
+        number
+            .parse::<f64>()
+            .map(Numeric::from)
+            .map_err(|err| format!("{}", err))
+    } else {
+        number
+            .parse::<isize>()
+            .map(Numeric::from)
+            .map_err(|err| format!("{}", err))
+    }
+}
+
+fn unescape(string: &str) -> String {
+    let mut ret = String::with_capacity(string.len());
+    let mut chars = string.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') => ret.push('\n'),
+                Some('t') => ret.push('\t'),
+                Some('r') => ret.push('\r'),
+                Some(other) => ret.push(other),
+                None => ret.push('\\'),
+            }
+        } else {
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+/// Evaluates expressions within a particular scope
+struct Evaluator<'a> {
+    scope: &'a [PaxelScopeFrame],
+    /// The properties of the `StackFrame` at the given offset from the current one
+    get_frame_properties: &'a dyn Fn(usize) -> Rc<RefCell<PropertiesCoproduct>>,
+    reflection: &'a dyn PaxelReflection,
+    /// Values bound by the parameters of the lambdas being evaluated, innermost last
+    locals: Vec<(String, PaxelValue)>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn evaluate(&self, expression: &PaxelExpression) -> Result<PaxelValue, String> {
+        match expression {
+            PaxelExpression::Literal(value) => Ok(value.clone()),
+            PaxelExpression::Symbol(root, accessors) => {
+                let mut value = self.resolve_root(root)?;
+                for accessor in accessors {
+                    value = match accessor {
+                        PaxelAccessor::Field(name) => self.get_field(value, name)?,
+                        PaxelAccessor::Index(index) => {
+                            let index = self.evaluate(index)?.expect_numeric()?.get_as_int();
+                            match value {
+                                PaxelValue::List(mut elements) if index >= 0 => {
+                                    if (index as usize) < elements.len() {
+                                        elements.swap_remove(index as usize)
+                                    } else {
+                                        return Err(format!("index {} out of bounds", index));
+                                    }
+                                }
+                                other => {
+                                    return Err(format!("cannot index into {}", other.describe()))
+                                }
+                            }
+                        }
+                    };
+                }
+                Ok(value)
+            }
+            PaxelExpression::Unary(operator, operand) => {
+                let operand = self.evaluate(operand)?;
+                match (operator, operand) {
+                    (PaxelUnaryOperator::Neg, PaxelValue::Numeric(n)) => {
+                        Ok(PaxelValue::Numeric(-n))
+                    }
+                    (PaxelUnaryOperator::Neg, PaxelValue::Size(s)) => Ok(PaxelValue::Size(-s)),
+                    (PaxelUnaryOperator::Neg, PaxelValue::Rotation(r)) => {
+                        Ok(PaxelValue::Rotation(-r))
+                    }
+                    (PaxelUnaryOperator::Not, PaxelValue::Bool(b)) => Ok(PaxelValue::Bool(!b)),
+                    (PaxelUnaryOperator::Neg, other) => {
+                        Err(format!("cannot negate {}", other.describe()))
+                    }
+                    (PaxelUnaryOperator::Not, other) => {
+                        Err(format!("cannot apply `!` to {}", other.describe()))
+                    }
+                }
+            }
+            PaxelExpression::Binary(PaxelBinaryOperator::And, lhs, rhs) => Ok(PaxelValue::Bool(
+                self.evaluate(lhs)?.expect_bool()? && self.evaluate(rhs)?.expect_bool()?,
+            )),
+            PaxelExpression::Binary(PaxelBinaryOperator::Or, lhs, rhs) => Ok(PaxelValue::Bool(
+                self.evaluate(lhs)?.expect_bool()? || self.evaluate(rhs)?.expect_bool()?,
+            )),
+            PaxelExpression::Binary(operator, lhs, rhs) => {
+                evaluate_binary(*operator, self.evaluate(lhs)?, self.evaluate(rhs)?)
+            }
+            PaxelExpression::Conditional(condition, then, otherwise) => {
+                let otherwise = otherwise
+                    .as_ref()
+                    .ok_or_else(|| "`?` without a matching `:`".to_string())?;
+                if self.evaluate(condition)?.expect_bool()? {
+                    self.evaluate(then)
+                } else {
+                    self.evaluate(otherwise)
+                }
+            }
+            PaxelExpression::WithUnit(expression, unit) => {
+                Ok(unit.apply(self.evaluate(expression)?.expect_numeric()?))
+            }
+            PaxelExpression::Tuple(elements) => Ok(PaxelValue::Tuple(
+                elements
+                    .iter()
+                    .map(|element| self.evaluate(element))
+                    .collect::<Result<Vec<_>, String>>()?,
+            )),
+            PaxelExpression::List(elements) => Ok(PaxelValue::List(
+                elements
+                    .iter()
+                    .map(|element| self.evaluate(element))
+                    .collect::<Result<Vec<_>, String>>()?,
+            )),
+            PaxelExpression::Range(start, end, inclusive, step) => {
+                let start = self.evaluate(start)?.expect_numeric()?.get_as_int();
+                let end = self.evaluate(end)?.expect_numeric()?.get_as_int();
+                let step = match step {
+                    Some(step) => Some(self.evaluate(step)?.expect_numeric()?.get_as_int()),
+                    None => None,
+                };
+                Ok(PaxelValue::Range(RepeatRange::new(
+                    start, end, *inclusive, step,
+                )))
+            }
+            PaxelExpression::FunctionCall(path, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.evaluate(arg))
+                    .collect::<Result<Vec<_>, String>>()?;
+                if let Some(result) = call_builtin_function(path, args.clone()) {
+                    return result;
+                }
+                self.reflection.call_function(path, args).unwrap_or_else(|| {
+                    Err(format!(
+                        "unknown function `{}`; functions that weren't used when the cartridge was built require a rebuild",
+                        path
+                    ))
+                })
+            }
+            PaxelExpression::MethodCall(receiver, name, args) => {
+                let receiver = self.evaluate(receiver)?;
+                self.call_method(receiver, name, args)
+            }
+            PaxelExpression::Lambda(..) => {
+                Err("lambdas may only be passed to methods, like `filter`".to_string())
+            }
+        }
+    }
+
+    /// Calls a method of PAXEL's standard library, as the compiler's whitelist permits for each kind of receiver
+    fn call_method(
+        &self,
+        receiver: PaxelValue,
+        name: &str,
+        args: &[PaxelExpression],
+    ) -> Result<PaxelValue, String> {
+        let unknown_method = |receiver: &PaxelValue| {
+            Err(format!(
+                "no method `{}` taking {} arguments on {}",
+                name,
+                args.len(),
+                receiver.describe()
+            ))
+        };
+        let evaluate_args = || {
+            args.iter()
+                .map(|arg| self.evaluate(arg))
+                .collect::<Result<Vec<_>, String>>()
+        };
+        match receiver {
+            PaxelValue::Numeric(n) => {
+                let args = evaluate_args()?
+                    .into_iter()
+                    .map(|arg| arg.expect_numeric())
+                    .collect::<Result<Vec<_>, String>>()?;
+                let value = match (name, &args[..]) {
+                    ("abs", []) => paxel_std::abs(&n),
+                    ("floor", []) => paxel_std::floor(&n),
+                    ("ceil", []) => paxel_std::ceil(&n),
+                    ("round", []) => paxel_std::round(&n),
+                    ("sqrt", []) => paxel_std::sqrt(&n),
+                    ("pow", [exp]) => paxel_std::pow(&n, exp),
+                    ("min", [other]) => paxel_std::min(&n, other),
+                    ("max", [other]) => paxel_std::max(&n, other),
+                    ("clamp", [lower, upper]) => paxel_std::clamp(&n, lower, upper),
+                    _ => return unknown_method(&PaxelValue::Numeric(n)),
+                };
+                Ok(PaxelValue::Numeric(value))
+            }
+            PaxelValue::String(s) => {
+                let args = evaluate_args()?
+                    .into_iter()
+                    .map(|arg| arg.into_typed::<StringBox>())
+                    .collect::<Result<Vec<_>, String>>()?;
+                let value = match (name, &args[..]) {
+                    ("len", []) => PaxelValue::Numeric(paxel_std::len(&s)),
+                    ("is_empty", []) => PaxelValue::Bool(paxel_std::is_empty(&s)),
+                    ("to_uppercase", []) => PaxelValue::String(paxel_std::to_uppercase(&s)),
+                    ("to_lowercase", []) => PaxelValue::String(paxel_std::to_lowercase(&s)),
+                    ("trim", []) => PaxelValue::String(paxel_std::trim(&s)),
+                    ("contains", [pattern]) => PaxelValue::Bool(paxel_std::contains(&s, pattern)),
+                    ("starts_with", [pattern]) => {
+                        PaxelValue::Bool(paxel_std::starts_with(&s, pattern))
+                    }
+                    ("ends_with", [pattern]) => PaxelValue::Bool(paxel_std::ends_with(&s, pattern)),
+                    ("replace", [from, to]) => PaxelValue::String(paxel_std::replace(&s, from, to)),
+                    _ => return unknown_method(&PaxelValue::String(s)),
+                };
+                Ok(value)
+            }
+            receiver => {
+                let elements = match &receiver {
+                    PaxelValue::List(elements) => Some(elements.clone()),
+                    PaxelValue::Range(range) => Some(
+                        range
+                            .iter()
+                            .map(|i| PaxelValue::Numeric(Numeric::from(i)))
+                            .collect(),
+                    ),
+                    PaxelValue::Opaque(opaque) => self.reflection.get_elements(&**opaque),
+                    _ => None,
+                };
+                let elements = match elements {
+                    Some(elements) => elements,
+                    None => return unknown_method(&receiver),
+                };
+                match (name, args) {
+                    ("len", []) => Ok(PaxelValue::Numeric(paxel_std::len(&elements))),
+                    ("is_empty", []) => Ok(PaxelValue::Bool(paxel_std::is_empty(&elements))),
+                    ("rev", []) => Ok(PaxelValue::List(paxel_std::rev(&elements))),
+                    ("take", [_]) | ("skip", [_]) => {
+                        let n = evaluate_args()?.remove(0).expect_numeric()?;
+                        Ok(PaxelValue::List(if name == "take" {
+                            paxel_std::take(&elements, &n)
+                        } else {
+                            paxel_std::skip(&elements, &n)
+                        }))
+                    }
+                    ("filter", [lambda])
+                    | ("any", [lambda])
+                    | ("all", [lambda])
+                    | ("count", [lambda]) => {
+                        //evaluate the predicate up front, so that its errors can be returned
+                        let matches = elements
+                            .iter()
+                            .map(|element| self.call_lambda(lambda, element.clone())?.expect_bool())
+                            .collect::<Result<Vec<_>, String>>()?;
+                        Ok(match name {
+                            "filter" => PaxelValue::List(
+                                elements
+                                    .into_iter()
+                                    .zip(matches)
+                                    .filter(|(_, is_match)| *is_match)
+                                    .map(|(element, _)| element)
+                                    .collect(),
+                            ),
+                            "any" => PaxelValue::Bool(paxel_std::any(&matches, |m| *m)),
+                            "all" => PaxelValue::Bool(paxel_std::all(&matches, |m| *m)),
+                            _ => PaxelValue::Numeric(paxel_std::count(&matches, |m| *m)),
+                        })
+                    }
+                    _ => unknown_method(&receiver),
+                }
+            }
+        }
+    }
+
+    /// Evaluates the body of `lambda` with its single parameter bound to `arg`
+    fn call_lambda(&self, lambda: &PaxelExpression, arg: PaxelValue) -> Result<PaxelValue, String> {
+        match lambda {
+            PaxelExpression::Lambda(params, body) if params.len() == 1 => {
+                let mut locals = self.locals.clone();
+                locals.push((params[0].clone(), arg));
+                Evaluator {
+                    scope: self.scope,
+                    get_frame_properties: self.get_frame_properties,
+                    reflection: self.reflection,
+                    locals,
+                }
+                .evaluate(body)
+            }
+            PaxelExpression::Lambda(..) => {
+                Err("lambdas must take exactly one parameter, like `|x| x.visible`".to_string())
+            }
+            _ => Err("expected a lambda, like `|x| x.visible`".to_string()),
+        }
+    }
+
+    fn resolve_root(&self, root: &str) -> Result<PaxelValue, String> {
+        if let Some((_, value)) = self.locals.iter().rev().find(|(name, _)| name == root) {
+            return Ok(value.clone());
+        }
+        for (offset, frame) in self.scope.iter().enumerate() {
+            match frame {
+                PaxelScopeFrame::RepeatItem {
+                    elem,
+                    i,
+                    destructured,
+                } => {
+                    let is_i = i.as_deref() == Some(root);
+                    //the path of fields from the element to the value bound to `root`, if any
+                    let elem_path: Option<&[String]> = if elem == root {
+                        Some(&[])
+                    } else {
+                        destructured
+                            .iter()
+                            .find(|(name, _)| name == root)
+                            .map(|(_, path)| &path[..])
+                    };
+                    if elem_path.is_none() && !is_i {
+                        continue;
+                    }
+                    //RepeatItems are updated in place rather than through a PropertyInstance, so this read can't be tracked
+                    reactivity::notify_volatile_read();
+                    let properties = (self.get_frame_properties)(offset);
+                    let properties = &*(*properties).borrow();
+                    return match properties {
+                        PropertiesCoproduct::RepeatItem(_, index) if is_i => {
+                            Ok(PaxelValue::Numeric(Numeric::from(*index)))
+                        }
+                        PropertiesCoproduct::RepeatItem(datum, _) => {
+                            let elem = match &**datum {
+                                PropertiesCoproduct::isize(value) => {
+                                    PaxelValue::Numeric(Numeric::from(*value))
+                                }
+                                _ => PaxelValue::Datum(Rc::clone(datum)),
+                            };
+                            elem_path
+                                .unwrap_or_default()
+                                .iter()
+                                .try_fold(elem, |value, field| self.get_field(value, field))
+                        }
+                        _ => Err(format!("`{}` is not bound to a `for` element", root)),
+                    };
+                }
+                PaxelScopeFrame::Properties => {
+                    let properties = (self.get_frame_properties)(offset);
+                    let properties = &*(*properties).borrow();
+                    if let Some(value) = self.reflection.get_property(properties, root) {
+                        return Ok(value);
+                    }
+                }
+            }
+        }
+        Err(format!("symbol not found: `{}`", root))
+    }
+
+    fn get_field(&self, value: PaxelValue, name: &str) -> Result<PaxelValue, String> {
+        let field = match &value {
+            PaxelValue::Datum(datum) => self.reflection.get_property(datum, name),
+            PaxelValue::Opaque(opaque) => self.reflection.get_field(&**opaque, name),
+            PaxelValue::Tuple(elements) => name
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| elements.get(index).cloned()),
+            _ => None,
+        };
+        field.ok_or_else(|| format!("no field `{}` on {}", name, value.describe()))
+    }
+}
+
+fn scale_size(size: Size, factor: Numeric) -> Size {
+    match size {
+        Size::Pixels(pixels) => Size::Pixels(pixels * factor),
+        Size::Percent(percent) => Size::Percent(percent * factor),
+        Size::Combined(pixels, percent) => Size::Combined(pixels * factor, percent * factor),
+    }
+}
+
+fn scale_rotation(rotation: Rotation, factor: Numeric) -> Rotation {
+    match rotation {
+        Rotation::Radians(radians) => Rotation::Radians(radians * factor),
+        Rotation::Degrees(degrees) => Rotation::Degrees(degrees * factor),
+        Rotation::Percent(percent) => Rotation::Percent(percent * factor),
+    }
+}
+
+fn evaluate_binary(
+    operator: PaxelBinaryOperator,
+    lhs: PaxelValue,
+    rhs: PaxelValue,
+) -> Result<PaxelValue, String> {
+    use PaxelBinaryOperator::*;
+    use PaxelValue as V;
+    let value = match (operator, lhs, rhs) {
+        (Add, V::Numeric(a), V::Numeric(b)) => V::Numeric(a + b),
+        (Add, V::Size(a), V::Size(b)) => V::Size(a + b),
+        (Add, V::String(a), V::String(b)) => V::String(a + b),
+        (Sub, V::Numeric(a), V::Numeric(b)) => V::Numeric(a - b),
+        (Sub, V::Size(a), V::Size(b)) => V::Size(a + -b),
+        (Mul, V::Numeric(a), V::Numeric(b)) => V::Numeric(a * b),
+        (Mul, V::Size(a), V::Size(b)) => V::Size(a * b),
+        (Mul, V::Numeric(n), V::Size(s)) | (Mul, V::Size(s), V::Numeric(n)) => {
+            V::Size(scale_size(s, n))
+        }
+        (Mul, V::Numeric(n), V::Rotation(r)) | (Mul, V::Rotation(r), V::Numeric(n)) => {
+            V::Rotation(scale_rotation(r, n))
+        }
+        (Mul, V::Transform2D(a), V::Transform2D(b)) => V::Transform2D(a * b),
+        (Div, V::Numeric(a), V::Numeric(b)) => V::Numeric(a / b),
+        (Div, V::Size(s), V::Numeric(n)) => {
+            V::Size(scale_size(s, Numeric::from(1.0 / n.get_as_float())))
+        }
+        (Mod, V::Numeric(a), V::Numeric(b)) => V::Numeric(a % b),
+        (Exp, V::Numeric(a), V::Numeric(b)) => V::Numeric(Numeric::pow(a, b)),
+        (Eq, a, b) => V::Bool(values_equal(&a, &b)?),
+        (Neq, a, b) => V::Bool(!values_equal(&a, &b)?),
+        (Lt, V::Numeric(a), V::Numeric(b)) => V::Bool(a < b),
+        (Lte, V::Numeric(a), V::Numeric(b)) => V::Bool(a <= b),
+        (Gt, V::Numeric(a), V::Numeric(b)) => V::Bool(a > b),
+        (Gte, V::Numeric(a), V::Numeric(b)) => V::Bool(a >= b),
+        (operator, a, b) => {
+            return Err(format!(
+                "cannot apply `{}` to {} and {}",
+                operator.as_str(),
+                a.describe(),
+                b.describe()
+            ))
+        }
+    };
+    Ok(value)
+}
+
+fn values_equal(a: &PaxelValue, b: &PaxelValue) -> Result<bool, String> {
+    match (a, b) {
+        (PaxelValue::Numeric(a), PaxelValue::Numeric(b)) => Ok(a == b),
+        (PaxelValue::Bool(a), PaxelValue::Bool(b)) => Ok(a == b),
+        (PaxelValue::String(a), PaxelValue::String(b)) => Ok(a.string == b.string),
+        (PaxelValue::Range(a), PaxelValue::Range(b)) => Ok(a == b),
+        (PaxelValue::Tuple(a), PaxelValue::Tuple(b))
+        | (PaxelValue::List(a), PaxelValue::List(b)) => {
+            if a.len() != b.len() {
+                return Ok(false);
+            }
+            for (a, b) in a.iter().zip(b.iter()) {
+                if !values_equal(a, b)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        (a, b) => Err(format!(
+            "cannot compare {} and {}",
+            a.describe(),
+            b.describe()
+        )),
+    }
+}
+
+/// Functions over `pax_runtime_api` types, available even if they weren't used when the cartridge was built
+fn call_builtin_function(path: &str, args: Vec<PaxelValue>) -> Option<Result<PaxelValue, String>> {
+    let mut args = args.into_iter();
+    let mut next_arg = |index: usize| {
+        args.next()
+            .ok_or_else(|| format!("`{}` expects at least {} arguments", path, index + 1))
+    };
+    let result = match path {
+        "Transform2D::translate" | "Transform2D::scale" | "Transform2D::anchor" => {
+            let x = next_arg(0).and_then(|x| x.into_typed::<Size>());
+            let y = next_arg(1).and_then(|y| y.into_typed::<Size>());
+            x.and_then(|x| {
+                y.map(|y| {
+                    PaxelValue::Transform2D(match path {
+                        "Transform2D::translate" => Transform2D::translate(x, y),
+                        "Transform2D::scale" => Transform2D::scale(x, y),
+                        _ => Transform2D::anchor(x, y),
+                    })
+                })
+            })
+        }
+        "Transform2D::rotate" => next_arg(0)
+            .and_then(|z| z.into_typed::<Rotation>())
+            .map(|z| PaxelValue::Transform2D(Transform2D::rotate(z))),
+        "Size::Pixels" | "Size::Percent" | "Rotation::Radians" | "Rotation::Degrees"
+        | "Rotation::Percent" => next_arg(0)
+            .and_then(|n| n.expect_numeric())
+            .map(|n| match path {
+                "Size::Pixels" => PaxelValue::Size(Size::Pixels(n)),
+                "Size::Percent" => PaxelValue::Size(Size::Percent(n)),
+                "Rotation::Radians" => PaxelValue::Rotation(Rotation::Radians(n)),
+                "Rotation::Degrees" => PaxelValue::Rotation(Rotation::Degrees(n)),
+                _ => PaxelValue::Rotation(Rotation::Percent(n)),
+            }),
+        _ => return None,
+    };
+    Some(result)
+}
+
+thread_local! {
+    /// Parsed interpreted expressions, by vtable id
+    static EXPRESSIONS: RefCell<HashMap<usize, Rc<PaxelExpression>>> = RefCell::new(HashMap::new());
+}
+
+/// Register the PAXEL source for the expression with the given `vtable_id`, to be evaluated by `evaluate_registered`.
+/// Called by cartridges built with interpreted expressions; panics if `paxel` can't be parsed, since the
+/// compiler has already validated it.
+pub fn register_expression(vtable_id: usize, paxel: &str) {
+    try_register_expression(vtable_id, paxel)
+        .unwrap_or_else(|err| panic!("Failed to parse expression `{}`: {}", paxel, err));
+}
+
+/// Like `register_expression`, but for PAXEL that hasn't been validated by the compiler, e.g. a hot-reloaded template's
+pub fn try_register_expression(vtable_id: usize, paxel: &str) -> Result<(), String> {
+    let expression = parse(paxel)?;
+    EXPRESSIONS.with(|expressions| {
+        expressions
+            .borrow_mut()
+            .insert(vtable_id, Rc::new(expression))
+    });
+    Ok(())
+}
+
+/// Replace the expression with the given `vtable_id`, which will be re-evaluated on the next tick.
+/// Only expressions that were registered for interpretation may be swapped.
+pub fn hot_swap_expression(vtable_id: usize, paxel: &str) -> Result<(), String> {
+    let expression = parse(paxel)?;
+    EXPRESSIONS.with(|expressions| {
+        let mut expressions = expressions.borrow_mut();
+        match expressions.get_mut(&vtable_id) {
+            Some(registered) => {
+                *registered = Rc::new(expression);
+                Ok(())
+            }
+            None => Err(format!(
+                "expression {} is not interpreted; it can only be changed by rebuilding",
+                vtable_id
+            )),
+        }
+    })?;
+    reactivity::invalidate_expression(vtable_id);
+    Ok(())
+}
+
+/// Evaluate a parsed expression, resolving its symbols against `scope`, ordered from the current
+/// `StackFrame` outwards.  `get_frame_properties` returns the properties of the `StackFrame` at the given
+/// offset from the current one.
+pub fn evaluate(
+    expression: &PaxelExpression,
+    scope: &[PaxelScopeFrame],
+    get_frame_properties: &dyn Fn(usize) -> Rc<RefCell<PropertiesCoproduct>>,
+    reflection: &dyn PaxelReflection,
+) -> Result<PaxelValue, String> {
+    Evaluator {
+        scope,
+        get_frame_properties,
+        reflection,
+        locals: vec![],
+    }
+    .evaluate(expression)
+}
+
+/// Evaluate the expression registered with the given `vtable_id` against `stack_frame`
+pub fn evaluate_registered<R: 'static + RenderContext>(
+    vtable_id: usize,
+    scope: &[PaxelScopeFrame],
+    stack_frame: &Rc<RefCell<StackFrame<R>>>,
+    reflection: &dyn PaxelReflection,
+) -> Result<PaxelValue, String> {
+    let expression = EXPRESSIONS
+        .with(|expressions| expressions.borrow().get(&vtable_id).cloned())
+        .ok_or_else(|| format!("no expression registered with id {}", vtable_id))?;
+    let get_frame_properties = |offset: usize| {
+        let stack_frame = (**stack_frame).borrow();
+        match stack_frame.peek_nth(offset as isize) {
+            Some(ancestor) => (*ancestor).borrow().get_properties(),
+            None => stack_frame.get_properties(),
+        }
+    };
+    evaluate(&expression, scope, &get_frame_properties, reflection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Exposes `RepeatItem` indices, as the only properties known outside a cartridge
+    struct NoReflection;
+
+    impl PaxelReflection for NoReflection {
+        fn get_property(&self, _: &PropertiesCoproduct, _: &str) -> Option<PaxelValue> {
+            None
+        }
+        fn get_field(&self, _: &dyn Any, _: &str) -> Option<PaxelValue> {
+            None
+        }
+        fn get_elements(&self, _: &dyn Any) -> Option<Vec<PaxelValue>> {
+            None
+        }
+        fn call_function(&self, _: &str, _: Vec<PaxelValue>) -> Option<Result<PaxelValue, String>> {
+            None
+        }
+    }
+
+    fn evaluate_in_repeat(paxel: &str, elem: isize, i: usize) -> Result<PaxelValue, String> {
+        let properties = Rc::new(RefCell::new(PropertiesCoproduct::RepeatItem(
+            Rc::new(PropertiesCoproduct::isize(elem)),
+            i,
+        )));
+        let scope = [PaxelScopeFrame::RepeatItem {
+            elem: "elem".to_string(),
+            i: Some("i".to_string()),
+            destructured: vec![],
+        }];
+        evaluate(
+            &parse(paxel)?,
+            &scope,
+            &|_| Rc::clone(&properties),
+            &NoReflection,
+        )
+    }
+
+    #[test]
+    fn test_interpreter_evaluates_arithmetic_with_units() {
+        let value = evaluate_in_repeat("(i * 20 + elem)px", 5, 2).unwrap();
+        assert!(
+            matches!(value.into_typed::<Size>(), Ok(Size::Pixels(n)) if n.get_as_float() == 45.0)
+        );
+
+        let value = evaluate_in_repeat("(elem %% 2) == 0 ? \"even\" : \"odd\"", 5, 0).unwrap();
+        assert_eq!(value.into_typed::<String>().unwrap(), "odd");
+
+        let value = evaluate_in_repeat("Transform2D::rotate(i * 90deg)", 0, 1).unwrap();
+        assert!(value.into_typed::<Transform2D>().unwrap().rotate.is_some());
+
+        assert!(evaluate_in_repeat("self.missing + 1", 0, 0).is_err());
+        assert!(evaluate_in_repeat("i +", 0, 0).is_err());
+    }
+
+    #[test]
+    fn test_interpreter_converts_lists_and_keys() {
+        let value = evaluate_in_repeat("[elem, i * 2, 3]", 1, 1).unwrap();
+        assert_eq!(value.into_typed_vec::<isize>().unwrap(), vec![1, 2, 3]);
+
+        let value = evaluate_in_repeat("elem + i", 5, 2).unwrap();
+        assert_eq!(value.into_key().unwrap(), "7");
+        let value = evaluate_in_repeat("\"k\"", 0, 0).unwrap();
+        assert_eq!(value.into_key().unwrap(), "k");
+        assert!(evaluate_in_repeat("[elem]", 0, 0)
+            .unwrap()
+            .into_key()
+            .is_err());
+    }
+
+    #[test]
+    fn test_interpreter_evaluates_ranges() {
+        let value = evaluate_in_repeat("elem..=i step 2", 6, 0).unwrap();
+        let range = value.into_typed::<RepeatRange>().unwrap();
+        assert_eq!(range.iter().collect::<Vec<_>>(), vec![6, 4, 2, 0]);
+
+        let value = evaluate_in_repeat("0..elem", 3, 0).unwrap();
+        assert_eq!(value.into_typed::<RepeatRange>().unwrap(), (0..3).into());
+    }
+
+    #[test]
+    fn test_interpreter_evaluates_method_calls() {
+        let value = evaluate_in_repeat("elem.abs().max(i)", -5, 2).unwrap();
+        assert_eq!(value.into_typed::<isize>().unwrap(), 5);
+
+        let value = evaluate_in_repeat("elem.filter(|x| x > i)", 0, 0);
+        assert!(value.is_err());
+
+        let expression = parse("self.items.filter(|x| (x %% 2) == 0).rev().take(2)").unwrap();
+        let evaluator = Evaluator {
+            scope: &[],
+            get_frame_properties: &|_| Rc::new(RefCell::new(PropertiesCoproduct::None)),
+            reflection: &NoReflection,
+            locals: vec![(
+                "items".to_string(),
+                PaxelValue::Range(RepeatRange::new(0, 10, false, None)),
+            )],
+        };
+        let value = evaluator.evaluate(&expression).unwrap();
+        assert_eq!(value.into_typed_vec::<isize>().unwrap(), vec![8, 6]);
+
+        let expression = parse("self.items.any(|x| x.len() > 3)").unwrap();
+        let evaluator = Evaluator {
+            locals: vec![(
+                "items".to_string(),
+                PaxelValue::List(vec![
+                    PaxelValue::String(StringBox::from("pax")),
+                    PaxelValue::String(StringBox::from("paxel")),
+                ]),
+            )],
+            ..evaluator
+        };
+        let value = evaluator.evaluate(&expression).unwrap();
+        assert!(value.expect_bool().unwrap());
+    }
+}
@@ -7,10 +7,13 @@ pub mod conditional;
 pub mod declarative_macros;
 pub mod engine;
 pub mod expressions;
+pub mod hot_reload;
+pub mod interpreter;
 pub mod rendering;
 pub mod repeat;
 pub mod runtime;
 pub mod slot;
+pub mod timelines;
 
 pub use crate::component::*;
 pub use crate::conditional::*;
@@ -20,3 +23,4 @@ pub use crate::rendering::*;
 pub use crate::repeat::*;
 pub use crate::runtime::*;
 pub use crate::slot::*;
+pub use crate::timelines::*;
@@ -9,7 +9,9 @@ use pax_runtime_api::{Axis, CommonProperties, Transform2D};
 use piet::{Color, StrokeStyle};
 use piet_common::RenderContext;
 
-use pax_runtime_api::{ArgsScroll, Layer, Size};
+use pax_runtime_api::{
+    ArgsDropdownChange, ArgsScroll, ArgsSliderChange, ArgsTextboxChange, Layer, RepeatRange, Size,
+};
 
 use crate::{HandlerRegistry, InstanceRegistry, RenderTreeContext};
 
@@ -20,6 +22,10 @@ use pax_runtime_api::PropertyInstance;
 pub type RenderNodePtr<R> = Rc<RefCell<dyn RenderNode<R>>>;
 pub type RenderNodePtrList<R> = Rc<RefCell<Vec<RenderNodePtr<R>>>>;
 
+/// Computes a node's properties, e.g. by evaluating their expressions, given its `PropertiesCoproduct`
+pub type ComputePropertiesFn<R> =
+    Box<dyn FnMut(Rc<RefCell<PropertiesCoproduct>>, &mut RenderTreeContext<R>)>;
+
 pub struct ScrollerArgs {
     pub size_inner_pane: [Box<dyn PropertyInstance<f64>>; 2],
     pub axes_enabled: [Box<dyn PropertyInstance<bool>>; 2],
@@ -40,15 +46,18 @@ pub struct InstantiationArgs<R: 'static + RenderContext> {
     ///is encoded as a Vec<T> or as a Range<...>
     pub repeat_source_expression_vec:
         Option<Box<dyn PropertyInstance<Vec<Rc<PropertiesCoproduct>>>>>,
-    pub repeat_source_expression_range: Option<Box<dyn PropertyInstance<std::ops::Range<isize>>>>,
+    pub repeat_source_expression_range: Option<Box<dyn PropertyInstance<RepeatRange>>>,
+    ///used by Repeat — for keyed `for`, evaluated per item to give that item a stable identity
+    pub repeat_key_expression: Option<Box<dyn PropertyInstance<String>>>,
 
     ///used by Conditional
     pub conditional_boolean_expression: Option<Box<dyn PropertyInstance<bool>>>,
+    ///used by Conditional — the children of an `else` branch, which may itself be a nested Conditional (`else if`)
+    pub conditional_else_children: Option<RenderNodePtrList<R>>,
 
     ///used by Component instances, specifically to unwrap type-specific PropertiesCoproducts
     ///and recurse into descendant property computation
-    pub compute_properties_fn:
-        Option<Box<dyn FnMut(Rc<RefCell<PropertiesCoproduct>>, &mut RenderTreeContext<R>)>>,
+    pub compute_properties_fn: Option<ComputePropertiesFn<R>>,
 }
 
 #[derive(Copy, Clone)]
@@ -192,7 +201,7 @@ pub trait RenderNode<R: 'static + RenderContext> {
         //This doesn't preclude event handlers on Groups and size-None elements --
         //it just requires the event to "bubble".  otherwise, `Component A > Component B` will
         //never allow events to be bound to `B` — they will be vacuously intercepted by `A`
-        if let None = self.get_size() {
+        if self.get_size().is_none() {
             return false;
         }
 
@@ -223,6 +232,16 @@ pub trait RenderNode<R: 'static + RenderContext> {
         None
     }
 
+    /// Whether this node can take keyboard focus, by tabbing to it, clicking it, or with `RuntimeContext::focus`.
+    /// Defaults to the `focusable` common property, itself defaulting to `false`; nodes that are inherently
+    /// interactive, like form controls, override this to be focusable by default.
+    fn is_focusable(&self) -> bool {
+        self.get_common_properties()
+            .focusable
+            .as_ref()
+            .map_or(false, |focusable| *focusable.borrow().get())
+    }
+
     /// Returns the size of this node, or `None` if this node
     /// doesn't have a size (e.g. `Group`)
     fn get_size(&self) -> Option<(Size, Size)> {
@@ -358,6 +377,32 @@ pub trait RenderNode<R: 'static + RenderContext> {
         //no-op default implementation
     }
 
+    /// Invoked when the user moves the native slider rendered for `id_chain`, before `slider_change` handlers are
+    /// dispatched, so that this node can reflect the value the native slider already shows
+    fn handle_slider_change(&mut self, _id_chain: &[u32], _args_slider_change: &ArgsSliderChange) {
+        //no-op default implementation
+    }
+
+    /// Invoked when the user picks an option from the native dropdown rendered for `id_chain`, before
+    /// `dropdown_change` handlers are dispatched; see `handle_slider_change`
+    fn handle_dropdown_change(
+        &mut self,
+        _id_chain: &[u32],
+        _args_dropdown_change: &ArgsDropdownChange,
+    ) {
+        //no-op default implementation
+    }
+
+    /// Invoked when the user edits the native text box rendered for `id_chain`, before `textbox_change`
+    /// handlers are dispatched; see `handle_slider_change`
+    fn handle_textbox_change(
+        &mut self,
+        _id_chain: &[u32],
+        _args_textbox_change: &ArgsTextboxChange,
+    ) {
+        //no-op default implementation
+    }
+
     /// Returns the scroll offset from a Scroller component
     /// Used by the engine to transform its children
     fn get_scroll_offset(&mut self) -> (f64, f64) {
@@ -1,31 +1,207 @@
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::{
-    ComponentInstance, InstantiationArgs, RenderNode, RenderNodePtr, RenderNodePtrList,
-    RenderTreeContext,
+    ComponentInstance, InstanceRegistry, InstantiationArgs, RenderNode, RenderNodePtr,
+    RenderNodePtrList, RenderTreeContext,
 };
 use pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
-use pax_runtime_api::{CommonProperties, Layer, PropertyInstance, Size};
+use pax_runtime_api::{reactivity, CommonProperties, Layer, PropertyInstance, RepeatRange, Size};
 use piet_common::RenderContext;
 
 /// A special "control-flow" primitive associated with the `for` statement.
 /// Repeat allows for nodes to be rendered dynamically per data specified in `source_expression`.
 /// That is: for a `source_expression` of length `n`, `Repeat` will render its
 /// template `n` times, each with an embedded component context (`RepeatItem`)
-/// with an index `i` and a pointer to that relevant datum `source_expression[i]`
+/// with an index `i` and a pointer to that relevant datum `source_expression[i]`.
+///
+/// When a `key_expression` is provided (`for elem in self.data_list key elem.id`), Repeat
+/// reconciles its `RepeatItem`s by key: items whose keys persist are retained (and moved, if reordered),
+/// so only the items whose keys were added or removed are mounted or unmounted.  Keys should be unique:
+/// items sharing a key are told apart by their order among themselves (see `reconcile_keyed_children`.)
 pub struct RepeatInstance<R: 'static + RenderContext> {
     pub instance_id: u32,
     pub repeated_template: RenderNodePtrList<R>,
     pub source_expression_vec: Option<Box<dyn PropertyInstance<Vec<Rc<PropertiesCoproduct>>>>>,
-    pub source_expression_range: Option<Box<dyn PropertyInstance<std::ops::Range<isize>>>>,
+    pub source_expression_range: Option<Box<dyn PropertyInstance<RepeatRange>>>,
+    pub key_expression: Option<Box<dyn PropertyInstance<String>>>,
     pub active_children: RenderNodePtrList<R>,
     pub cleanup_children: RenderNodePtrList<R>,
     pub common_properties: CommonProperties,
-    /// Used for hacked dirty-checking, in the absence of our centralized dirty-checker
+    /// The most recently evaluated source, which stands until the source expression's dependencies change
     cached_old_value_vec: Option<Vec<Rc<PropertiesCoproduct>>>,
-    cached_old_value_range: Option<std::ops::Range<isize>>,
+    cached_old_value_range: Option<RepeatRange>,
     cached_old_bounds: (f64, f64),
+    /// Set initially and upon unmount, so that children are (re)built even if the source is unchanged
+    needs_rebuild: bool,
+    /// For non-keyed Repeat: the `RepeatItem` properties of each of `active_children`
+    active_children_properties: Vec<Rc<RefCell<PropertiesCoproduct>>>,
+    /// For keyed Repeat: the current `RepeatItem`s, by key
+    keyed_children: HashMap<String, KeyedRepeatItem<R>>,
+}
+
+struct KeyedRepeatItem<R: 'static + RenderContext> {
+    node: RenderNodePtr<R>,
+    /// The `RepeatItem` properties of `node`, updated in place when the item's datum or index changes
+    properties: Rc<RefCell<PropertiesCoproduct>>,
+}
+
+/// The evaluated source of a Repeat.  Ranges are kept as ranges, so that their elements are
+/// produced one at a time as `RepeatItem`s are built, rather than collected up front
+enum RepeatSource {
+    Vec(Vec<Rc<PropertiesCoproduct>>),
+    Range(RepeatRange),
+}
+
+impl RepeatSource {
+    fn iter(&self) -> Box<dyn Iterator<Item = Rc<PropertiesCoproduct>> + '_> {
+        match self {
+            RepeatSource::Vec(vec) => Box::new(vec.iter().map(Rc::clone)),
+            RepeatSource::Range(range) => Box::new(
+                range
+                    .iter()
+                    .map(|elem| Rc::new(PropertiesCoproduct::isize(elem))),
+            ),
+        }
+    }
+}
+
+impl<R: 'static + RenderContext> RepeatInstance<R> {
+    /// Wrap a `RepeatItem` in a `ComponentInstance`, which attaches the necessary data as stack frame context
+    fn instantiate_repeat_item(
+        &self,
+        instance_registry: &mut InstanceRegistry<R>,
+        rtc: &RenderTreeContext<R>,
+        forwarded_children: &RenderNodePtrList<R>,
+        properties: Rc<RefCell<PropertiesCoproduct>>,
+        is_keyed: bool,
+    ) -> RenderNodePtr<R> {
+        let instance_id = instance_registry.mint_id();
+        let common_properties = CommonProperties::default();
+
+        let render_node: RenderNodePtr<R> = Rc::new(RefCell::new(ComponentInstance {
+            instance_id,
+            children: Rc::clone(forwarded_children),
+            template: Rc::clone(&self.repeated_template),
+            common_properties,
+            properties,
+            timeline: None,
+            //keyed items are identified by their own (stable) instance id rather than their index
+            repeat_item_identity: is_keyed.then_some(instance_id),
+            handler_registry: None,
+            cleanup_children: Rc::new(RefCell::new(vec![])),
+            compute_properties_fn: Box::new(|_props, _rtc| {
+                //no-op since the Repeat RenderNode handles the necessary calc (see `RepeatInstance::compute_properties`)
+            }),
+        }));
+
+        instance_registry.register(instance_id, Rc::clone(&render_node));
+        instance_registry.mark_mounted(rtc.get_id_chain(instance_id));
+
+        // (&*render_node).borrow_mut().mount_recursive(rtc);
+
+        render_node
+    }
+
+    /// Evaluates `key_expression` for each datum, within a transient `RepeatItem` stack frame
+    fn compute_keys(&self, rtc: &mut RenderTreeContext<R>, source: &RepeatSource) -> Vec<String> {
+        let key_vtable_id = self.key_expression.as_ref().unwrap()._get_vtable_id();
+        source
+            .iter()
+            .enumerate()
+            .map(|(i, datum)| {
+                (*rtc.runtime).borrow_mut().push_stack_frame(
+                    Rc::new(RefCell::new(vec![])),
+                    Rc::new(RefCell::new(PropertiesCoproduct::RepeatItem(datum, i))),
+                    None,
+                    None,
+                );
+                //Keys are stringified where they're evaluated: compiled keys with `to_string()`, interpreted (e.g.
+                //hot-reloaded) ones by `PaxelValue::into_key`.  An item whose key can't be evaluated is keyed by
+                //its index instead, so unlike the others it is remounted, rather than moved, when reordered
+                let key = match rtc.compute_vtable_value_uncached(key_vtable_id) {
+                    Some(TypesCoproduct::String(key)) => key,
+                    _ => {
+                        pax_runtime_api::log(&format!(
+                            "Key of item {} in keyed `for` couldn't be evaluated; keying it by index",
+                            i
+                        ));
+                        format!("\u{0}{}", i)
+                    }
+                };
+                (*rtc.runtime).borrow_mut().pop_stack_frame();
+                key
+            })
+            .collect()
+    }
+
+    /// Keyed reconciliation: retain (and update in place) the items whose keys persist, mount items
+    /// for new keys, and unmount items whose keys were removed
+    fn reconcile_keyed_children(
+        &mut self,
+        rtc: &mut RenderTreeContext<R>,
+        source: &RepeatSource,
+        forwarded_children: RenderNodePtrList<R>,
+    ) {
+        let keys = self.compute_keys(rtc, source);
+
+        let instance_registry_rc = Rc::clone(&rtc.engine.instance_registry);
+        let mut instance_registry = (*instance_registry_rc).borrow_mut();
+        let mut previous_keyed_children = std::mem::take(&mut self.keyed_children);
+        let mut active_children = Vec::with_capacity(keys.len());
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+
+        for (i, (key, datum)) in keys.into_iter().zip(source.iter()).enumerate() {
+            //A duplicate key can't identify its item, so rather than crash on bad data, it's disambiguated by
+            //its number of prior occurrences: the items sharing a key keep their identities, in order, when moved
+            let occurrence = occurrences.entry(key.clone()).or_insert(0);
+            let key = if *occurrence > 0 {
+                pax_runtime_api::log(&format!(
+                    "Duplicate key `{}` in keyed `for`; keys must be unique",
+                    key
+                ));
+                format!("{}\u{0}{}", key, occurrence)
+            } else {
+                key
+            };
+            *occurrence += 1;
+
+            let keyed_item = if let Some(keyed_item) = previous_keyed_children.remove(&key) {
+                *(*keyed_item.properties).borrow_mut() = PropertiesCoproduct::RepeatItem(datum, i);
+                reactivity::notify_volatile_changed();
+                keyed_item
+            } else {
+                let properties = Rc::new(RefCell::new(PropertiesCoproduct::RepeatItem(datum, i)));
+                KeyedRepeatItem {
+                    node: self.instantiate_repeat_item(
+                        &mut instance_registry,
+                        rtc,
+                        &forwarded_children,
+                        Rc::clone(&properties),
+                        true,
+                    ),
+                    properties,
+                }
+            };
+
+            active_children.push(Rc::clone(&keyed_item.node));
+            self.keyed_children.insert(key, keyed_item);
+        }
+
+        previous_keyed_children
+            .into_values()
+            .for_each(|removed_item| {
+                let instance_id = (*removed_item.node).borrow().get_instance_id();
+                instance_registry.deregister(instance_id);
+                instance_registry.mark_for_unmount(instance_id);
+                (*self.cleanup_children)
+                    .borrow_mut()
+                    .push(removed_item.node);
+            });
+
+        self.active_children = Rc::new(RefCell::new(active_children));
+    }
 }
 
 impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
@@ -52,11 +228,15 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
             common_properties: args.common_properties,
             source_expression_vec: args.repeat_source_expression_vec,
             source_expression_range: args.repeat_source_expression_range,
+            key_expression: args.repeat_key_expression,
             active_children: Rc::new(RefCell::new(vec![])),
             cleanup_children: Rc::new(RefCell::new(vec![])),
             cached_old_value_vec: None,
             cached_old_value_range: None,
             cached_old_bounds: (0.0, 0.0),
+            needs_rebuild: true,
+            active_children_properties: vec![],
+            keyed_children: HashMap::new(),
         }));
 
         instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
@@ -64,63 +244,69 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
     }
 
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
-        let (is_dirty, normalized_vec_of_props) = if let Some(se) = &self.source_expression_vec {
+        let bounds_changed = rtc.bounds != self.cached_old_bounds;
+        self.cached_old_bounds = rtc.bounds;
+
+        //`compute_vtable_value` returns `None` when the source expression's dependencies are unchanged,
+        //in which case the previously evaluated source still stands
+        let (is_dirty, is_reevaluated, source) = if let Some(se) = &self.source_expression_vec {
             //Handle case where the source expression is a Vec<Property<T>>,
             // like `for elem in self.data_list`
-            let new_value = if let Some(tc) = rtc.compute_vtable_value(se._get_vtable_id().clone())
-            {
+            let new_value = rtc.compute_vtable_value(se._get_vtable_id()).map(|tc| {
                 if let TypesCoproduct::stdCOCOvecCOCOVecLABRstdCOCOrcCOCORcLABRPropertiesCoproductRABRRABR(vec) = tc { vec } else { unreachable!() }
-            } else {
-                se.get().clone()
-            };
-
-            //Major hack: will only consider a new vec dirty if its cardinality changes.
-            let is_dirty = {
-                rtc.bounds != self.cached_old_bounds
-                    || if self.cached_old_value_vec.is_none() {
-                        true
-                    } else {
-                        self.cached_old_value_vec.as_ref().unwrap().len() != new_value.len()
-                    }
-            };
-            self.cached_old_bounds = rtc.bounds;
+            });
+            let is_reevaluated = new_value.is_some();
+            let new_value = new_value
+                .or_else(|| self.cached_old_value_vec.clone())
+                .unwrap_or_else(|| se.get().clone());
+
+            //Children are only rebuilt when the cardinality changes; otherwise they are updated in place
+            let is_dirty = bounds_changed
+                || self.needs_rebuild
+                || self
+                    .cached_old_value_vec
+                    .as_ref()
+                    .map_or(true, |old_value| old_value.len() != new_value.len());
             self.cached_old_value_vec = Some(new_value.clone());
-            (is_dirty, new_value)
+            (is_dirty, is_reevaluated, RepeatSource::Vec(new_value))
         } else if let Some(se) = &self.source_expression_range {
             //Handle case where the source expression is a Range,
-            // like `for i in 0..5`
-            let new_value = if let Some(tc) = rtc.compute_vtable_value(se._get_vtable_id().clone())
-            {
-                if let TypesCoproduct::stdCOCOopsCOCORangeLABRisizeRABR(vec) = tc {
-                    vec
+            // like `for i in 0..5`, `for i in 5..=0` or `for i in 0..100 step 5`
+            let new_value = rtc.compute_vtable_value(se._get_vtable_id()).map(|tc| {
+                if let TypesCoproduct::RepeatRange(range) = tc {
+                    range
                 } else {
                     unreachable!()
                 }
-            } else {
-                unreachable!()
-            };
-
-            //Major hack: will only consider a new vec dirty if its cardinality changes.
-            let is_dirty = {
-                rtc.bounds != self.cached_old_bounds
-                    || if self.cached_old_value_range.is_none() {
-                        true
-                    } else {
-                        self.cached_old_value_range.as_ref().unwrap().len() != new_value.len()
-                    }
-            };
-            self.cached_old_bounds = rtc.bounds;
+            });
+            //A range that evaluates to the same bounds and step has nothing to update
+            let is_reevaluated =
+                new_value.is_some() && new_value.as_ref() != self.cached_old_value_range.as_ref();
+            let new_value = new_value
+                .or_else(|| self.cached_old_value_range.clone())
+                .unwrap_or_else(|| se.get().clone());
+
+            let is_dirty = bounds_changed
+                || self.needs_rebuild
+                || self
+                    .cached_old_value_range
+                    .as_ref()
+                    .map_or(true, |old_value| old_value.len() != new_value.len());
             self.cached_old_value_range = Some(new_value.clone());
-            let normalized_vec_of_props = new_value
-                .into_iter()
-                .map(|elem| Rc::new(PropertiesCoproduct::isize(elem)))
-                .collect();
-            (is_dirty, normalized_vec_of_props)
+            (is_dirty, is_reevaluated, RepeatSource::Range(new_value))
         } else {
             unreachable!()
         };
 
-        if is_dirty {
+        if self.key_expression.is_some() {
+            if is_reevaluated || self.needs_rebuild {
+                let forwarded_children = match (*rtc.runtime).borrow_mut().peek_stack_frame() {
+                    Some(frame) => Rc::clone(&(*frame.borrow()).get_unflattened_adoptees()),
+                    None => Rc::new(RefCell::new(vec![])),
+                };
+                self.reconcile_keyed_children(rtc, &source, forwarded_children);
+            }
+        } else if is_dirty {
             //Any stated children (repeat template members) of Repeat should be forwarded to the `RepeatItem`-wrapped `ComponentInstance`s
             //so that `Slot` works as expected
             let forwarded_children = match (*rtc.runtime).borrow_mut().peek_stack_frame() {
@@ -128,7 +314,8 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
                 None => Rc::new(RefCell::new(vec![])),
             };
 
-            let mut instance_registry = (*rtc.engine.instance_registry).borrow_mut();
+            let instance_registry_rc = Rc::clone(&rtc.engine.instance_registry);
+            let mut instance_registry = (*instance_registry_rc).borrow_mut();
 
             (*self.active_children)
                 .borrow_mut()
@@ -144,41 +331,37 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
             //reset children:
             //wrap source_expression into `RepeatItems`, which attach
             //the necessary data as stack frame context
+            self.active_children_properties = source
+                .iter()
+                .enumerate()
+                .map(|(i, datum)| Rc::new(RefCell::new(PropertiesCoproduct::RepeatItem(datum, i))))
+                .collect();
             self.active_children = Rc::new(RefCell::new(
-                normalized_vec_of_props
+                self.active_children_properties
                     .iter()
-                    .enumerate()
-                    .map(|(i, datum)| {
-                        let instance_id = instance_registry.mint_id();
-                        let common_properties = CommonProperties::default();
-
-                        let render_node: RenderNodePtr<R> =
-                            Rc::new(RefCell::new(ComponentInstance {
-                                instance_id,
-                                children: Rc::clone(&forwarded_children),
-                                template: Rc::clone(&self.repeated_template),
-                                common_properties,
-                                properties: Rc::new(RefCell::new(PropertiesCoproduct::RepeatItem(
-                                    Rc::clone(datum),
-                                    i,
-                                ))),
-                                timeline: None,
-                                handler_registry: None,
-                                compute_properties_fn: Box::new(|_props, _rtc| {
-                                    //no-op since the Repeat RenderNode handles the necessary calc (see `RepeatInstance::compute_properties`)
-                                }),
-                            }));
-
-                        instance_registry.register(instance_id, Rc::clone(&render_node));
-                        instance_registry.mark_mounted(rtc.get_id_chain(instance_id));
-
-                        // (&*render_node).borrow_mut().mount_recursive(rtc);
-
-                        render_node
+                    .map(|properties| {
+                        self.instantiate_repeat_item(
+                            &mut instance_registry,
+                            rtc,
+                            &forwarded_children,
+                            Rc::clone(properties),
+                            false,
+                        )
                     })
                     .collect(),
             ));
+        } else if is_reevaluated {
+            //Same cardinality: update each `RepeatItem`'s datum in place, retaining the mounted children
+            self.active_children_properties
+                .iter()
+                .zip(source.iter())
+                .enumerate()
+                .for_each(|(i, (properties, datum))| {
+                    *(**properties).borrow_mut() = PropertiesCoproduct::RepeatItem(datum, i);
+                });
+            reactivity::notify_volatile_changed();
         }
+        self.needs_rebuild = false;
 
         // pax_runtime_api::log(&format!("finished computing repeat properties, virt len: {}", (*self.virtual_children).borrow().len()));
     }
@@ -204,11 +387,156 @@ impl<R: 'static + RenderContext> RenderNode<R> for RepeatInstance<R> {
         Layer::DontCare
     }
 
-    fn handle_did_mount(&mut self, _rtc: &mut RenderTreeContext<R>, _z_index: u32) {
-        self.cached_old_value_range = None;
-        self.cached_old_value_vec = None;
+    fn handle_will_unmount(&mut self, _rtc: &mut RenderTreeContext<R>) {
+        //Children are unmounted along with this Repeat, so they're rebuilt if it's remounted.  (Not upon
+        //`did_mount`, which fires after the first `compute_properties` has already built them.)
+        self.needs_rebuild = true;
+        self.active_children = Rc::new(RefCell::new(vec![]));
+        self.active_children_properties.clear();
+        self.keyed_children.clear();
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::tests::{build_engine_with_expressions, get_value, set_value, tick, R};
+    use crate::{ExpressionFn, PaxEngine, PropertyExpression};
+
+    thread_local! {
+        /// The data of the keyed `for` under test, each keyed by its value
+        static DATA: RefCell<Vec<isize>> = RefCell::new(vec![]);
+    }
+
+    fn set_data(data: Vec<isize>) {
+        DATA.with(|d| *d.borrow_mut() = data);
+        set_value("repeat_data", get_value("repeat_data") + 1.0);
+    }
+
+    fn get_instance_ids(repeat: &Rc<RefCell<RepeatInstance<R>>>) -> Vec<u32> {
+        (*repeat.borrow().active_children)
+            .borrow()
+            .iter()
+            .map(|child| child.borrow().get_instance_id())
+            .collect()
+    }
+
+    fn build_keyed_repeat(
+        engine_repeat: &mut Option<Rc<RefCell<RepeatInstance<R>>>>,
+    ) -> PaxEngine<R> {
+        let mut expression_table: HashMap<usize, ExpressionFn<R>> = HashMap::new();
+        // for datum in data key datum
+        expression_table.insert(
+            0,
+            Box::new(|_| {
+                get_value("repeat_data");
+                let data = DATA.with(|d| d.borrow().clone());
+                TypesCoproduct::stdCOCOvecCOCOVecLABRstdCOCOrcCOCORcLABRPropertiesCoproductRABRRABR(
+                    data.into_iter()
+                        .map(|datum| Rc::new(PropertiesCoproduct::isize(datum)))
+                        .collect(),
+                )
+            }),
+        );
+        expression_table.insert(
+            1,
+            Box::new(
+                |ec| match &*ec.stack_frame.borrow().get_properties().borrow() {
+                    PropertiesCoproduct::RepeatItem(datum, _) => match &**datum {
+                        PropertiesCoproduct::isize(datum) => {
+                            TypesCoproduct::String(datum.to_string())
+                        }
+                        _ => unreachable!(),
+                    },
+                    _ => unreachable!(),
+                },
+            ),
+        );
+        build_engine_with_expressions(expression_table, |instance_registry| {
+            let instance_id = instance_registry.mint_id();
+            let repeat = Rc::new(RefCell::new(RepeatInstance {
+                instance_id,
+                repeated_template: Rc::new(RefCell::new(vec![])),
+                source_expression_vec: Some(Box::new(PropertyExpression::new(0))),
+                source_expression_range: None,
+                key_expression: Some(Box::new(PropertyExpression::new(1))),
+                active_children: Rc::new(RefCell::new(vec![])),
+                cleanup_children: Rc::new(RefCell::new(vec![])),
+                common_properties: CommonProperties::default(),
+                cached_old_value_vec: None,
+                cached_old_value_range: None,
+                cached_old_bounds: (0.0, 0.0),
+                needs_rebuild: true,
+                active_children_properties: vec![],
+                keyed_children: HashMap::new(),
+            }));
+            *engine_repeat = Some(Rc::clone(&repeat));
+            vec![repeat as RenderNodePtr<R>]
+        })
+    }
+
+    #[test]
+    fn test_keyed_items_keep_their_instances_when_moved() {
+        set_value("repeat_data", 0.0);
+        set_data(vec![1, 2, 3]);
+        let mut repeat = None;
+        let engine = &mut build_keyed_repeat(&mut repeat);
+        let repeat = repeat.unwrap();
+        tick(engine);
+        let [one, two, three] = get_instance_ids(&repeat)[..] else {
+            panic!()
+        };
+
+        //reorder
+        set_data(vec![3, 1, 2]);
+        tick(engine);
+        assert_eq!(get_instance_ids(&repeat), vec![three, one, two]);
+
+        //insert
+        set_data(vec![3, 1, 4, 2]);
+        tick(engine);
+        let ids = get_instance_ids(&repeat);
+        assert_eq!([ids[0], ids[1], ids[3]], [three, one, two]);
+        let four = ids[2];
+        assert!(![one, two, three].contains(&four));
+        assert!(engine.instance_registry.borrow().is_mounted(&vec![four]));
+
+        //remove
+        set_data(vec![4, 2]);
+        tick(engine);
+        assert_eq!(get_instance_ids(&repeat), vec![four, two]);
+        for removed in [one, three] {
+            let instance_registry = engine.instance_registry.borrow();
+            assert!(!instance_registry.is_registered(removed));
+            assert!(!instance_registry.is_mounted(&vec![removed]));
+        }
+    }
+
+    #[test]
+    fn test_items_with_duplicate_keys_keep_their_instances_in_order() {
+        set_value("repeat_data", 0.0);
+        set_data(vec![5, 5, 6]);
+        let mut repeat = None;
+        let engine = &mut build_keyed_repeat(&mut repeat);
+        let repeat = repeat.unwrap();
+        tick(engine);
+        let [first_five, second_five, six] = get_instance_ids(&repeat)[..] else {
+            panic!()
+        };
+
+        set_data(vec![6, 5, 5]);
+        tick(engine);
+        assert_eq!(
+            get_instance_ids(&repeat),
+            vec![six, first_five, second_five]
+        );
+
+        set_data(vec![5, 6]);
+        tick(engine);
+        assert_eq!(get_instance_ids(&repeat), vec![first_five, six]);
+        assert!(!engine.instance_registry.borrow().is_registered(second_five));
     }
 }
+
 /*
 lab journal, zb
 ---------------
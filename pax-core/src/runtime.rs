@@ -23,6 +23,12 @@ pub struct Runtime<R: 'static + RenderContext> {
     native_message_queue: VecDeque<pax_message::NativeMessage>,
 }
 
+impl<R: 'static + RenderContext> Default for Runtime<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<R: 'static + RenderContext> Runtime<R> {
     pub fn new() -> Self {
         Runtime {
@@ -40,10 +46,10 @@ impl<R: 'static + RenderContext> Runtime<R> {
         let mut indices: Vec<u32> = vec![];
 
         self.stack.iter().for_each(|frame_wrapped| {
-            if let PropertiesCoproduct::RepeatItem(_datum, i) =
-                &*(*(*(*frame_wrapped).borrow_mut()).properties).borrow()
-            {
-                indices.push(*i as u32)
+            let frame = (*frame_wrapped).borrow();
+            let properties = (*frame.properties).borrow();
+            if let PropertiesCoproduct::RepeatItem(_datum, i) = &*properties {
+                indices.push(frame.repeat_item_identity.unwrap_or(*i as u32))
             }
         });
         indices
@@ -61,7 +67,7 @@ impl<R: 'static + RenderContext> Runtime<R> {
     /// Return a pointer to the top StackFrame on the stack,
     /// without mutating the stack or consuming the value
     pub fn peek_stack_frame(&mut self) -> Option<Rc<RefCell<StackFrame<R>>>> {
-        if self.stack.len() > 0 {
+        if !self.stack.is_empty() {
             Some(Rc::clone(&self.stack[&self.stack.len() - 1]))
         } else {
             None
@@ -81,6 +87,7 @@ impl<R: 'static + RenderContext> Runtime<R> {
         flattened_adoptees: RenderNodePtrList<R>,
         properties: Rc<RefCell<PropertiesCoproduct>>,
         timeline: Option<Rc<RefCell<Timeline>>>,
+        repeat_item_identity: Option<u32>,
     ) {
         let parent = self.peek_stack_frame().as_ref().map(Rc::downgrade);
 
@@ -89,9 +96,16 @@ impl<R: 'static + RenderContext> Runtime<R> {
             properties,
             parent,
             timeline,
+            repeat_item_identity,
         ))));
     }
 
+    /// Push a frame that was previously pushed by `push_stack_frame` back onto the stack, e.g. to paint
+    /// a retained subtree without recomputing it
+    pub fn push_existing_stack_frame(&mut self, stack_frame: Rc<RefCell<StackFrame<R>>>) {
+        self.stack.push(stack_frame);
+    }
+
     pub fn push_clipping_stack_id(&mut self, id_chain: Vec<u32>) {
         self.clipping_stack.push(id_chain);
     }
@@ -138,10 +152,9 @@ impl<R: 'static + RenderContext> Runtime<R> {
             (*adoptee_borrowed.get_rendering_children())
                 .borrow()
                 .iter()
-                .map(|top_level_child_node| {
+                .flat_map(|top_level_child_node| {
                     Runtime::process__should_flatten__adoptees_recursive(top_level_child_node, rtc)
                 })
-                .flatten()
                 .collect()
         } else {
             vec![Rc::clone(adoptee)]
@@ -163,6 +176,8 @@ pub struct StackFrame<R: 'static + RenderContext> {
     properties: Rc<RefCell<PropertiesCoproduct>>,
     parent: Option<Weak<RefCell<StackFrame<R>>>>,
     timeline: Option<Rc<RefCell<Timeline>>>,
+    /// See `ComponentInstance::repeat_item_identity`
+    repeat_item_identity: Option<u32>,
 }
 
 impl<R: 'static + RenderContext> StackFrame<R> {
@@ -171,28 +186,38 @@ impl<R: 'static + RenderContext> StackFrame<R> {
         properties: Rc<RefCell<PropertiesCoproduct>>,
         parent: Option<Weak<RefCell<StackFrame<R>>>>,
         timeline: Option<Rc<RefCell<Timeline>>>,
+        repeat_item_identity: Option<u32>,
     ) -> Self {
         StackFrame {
             adoptees,
             properties,
             parent,
             timeline,
+            repeat_item_identity,
         }
     }
 
     pub fn get_timeline_playhead_position(&self) -> usize {
+        match self.get_timeline() {
+            Some(timeline) => (*timeline).borrow().playhead_position,
+            None => 0,
+        }
+    }
+
+    /// Returns this stack frame's timeline, or the nearest ancestor frame's timeline if this frame doesn't carry one
+    pub fn get_timeline(&self) -> Option<Rc<RefCell<Timeline>>> {
         match &self.timeline {
             None => {
                 //if this stackframe doesn't carry a timeline, then refer
                 //to the parent stackframe's timeline (and recurse)
                 match &self.parent {
-                    Some(parent_frame) => (*parent_frame.upgrade().unwrap())
-                        .borrow()
-                        .get_timeline_playhead_position(),
-                    None => 0,
+                    Some(parent_frame) => {
+                        (*parent_frame.upgrade().unwrap()).borrow().get_timeline()
+                    }
+                    None => None,
                 }
             }
-            Some(timeline) => (**timeline).borrow().playhead_position,
+            Some(timeline) => Some(Rc::clone(timeline)),
         }
     }
 
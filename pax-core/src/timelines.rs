@@ -1,97 +1,328 @@
-use pax_runtime_api::{Property, Tweenable};
-
-pub struct PropertyTimeline {
-    pub id: String,
-    pub starting_value: Box<dyn Property<f64>>,
-    pub timeline_segments: Vec<TimelineSegment>,
-    pub cached_evaluated_value: f64,
-    pub is_fresh: bool,
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use pax_runtime_api::reactivity::{self, PropertyId};
+use pax_runtime_api::{
+    EasingCurve, Interpolatable, Property, PropertyInstance, Timeline, TransitionManager,
+    TransitionQueueEntry,
+};
+
+/// A single keyframe of a `PropertyTimeline`: the property takes on `value` at `frame`,
+/// easing from the previous keyframe's value according to `curve`.
+pub struct Keyframe<T> {
+    pub frame: usize,
+    pub value: T,
+    /// Curve used to ease *into* this keyframe from the previous one; ignored for the first keyframe
+    pub curve: EasingCurve,
+    /// Optional name, registered with the timeline so that handlers may `seek_to_keyframe` by name
+    pub name: Option<String>,
+}
+
+impl<T> Keyframe<T> {
+    pub fn new(frame: usize, value: T, curve: EasingCurve) -> Self {
+        Keyframe {
+            frame,
+            value,
+            curve,
+            name: None,
+        }
+    }
+
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+}
+
+/// The Timeline-bound form of a Property: a series of keyframes evaluated against the
+/// playhead of a shared `Timeline`, interpolating between adjacent keyframes via `EasingCurve`.
+pub struct PropertyTimeline<T> {
+    id: PropertyId,
+    timeline: Rc<RefCell<Timeline>>,
+    /// Sorted by `frame`
+    keyframes: Vec<Keyframe<T>>,
+    cached_evaluated_value: T,
+    /// Playhead position as of the last evaluation, so that a paused timeline isn't re-evaluated (and re-set) each tick;
+    /// `None` to force re-evaluation
+    last_evaluated_playhead_position: Option<usize>,
+    transition_manager: TransitionManager<T>,
+    /// Whether to resume tracking the timeline once the transition queue empties, per `ease_to_default`
+    resume_after_transition: bool,
+}
+
+impl<T> From<PropertyTimeline<T>> for Box<dyn PropertyInstance<T>>
+where
+    T: Default + Clone + Interpolatable + 'static,
+{
+    fn from(val: PropertyTimeline<T>) -> Self {
+        Box::new(val)
+    }
+}
+
+impl<T: Clone + Interpolatable> PropertyTimeline<T> {
+    /// Bind `keyframes` to `timeline`, registering any named keyframes and extending the
+    /// timeline's `frame_count` to cover the last keyframe.
+    pub fn new(timeline: Rc<RefCell<Timeline>>, mut keyframes: Vec<Keyframe<T>>) -> Self {
+        assert!(
+            !keyframes.is_empty(),
+            "Timeline properties must define at least one Keyframe."
+        );
+        keyframes.sort_by_key(|keyframe| keyframe.frame);
+
+        let playhead_position = {
+            let mut timeline = (*timeline).borrow_mut();
+            for keyframe in keyframes.iter() {
+                timeline.register_keyframe(keyframe.name.as_deref(), keyframe.frame);
+            }
+            timeline.playhead_position
+        };
+
+        let cached_evaluated_value = Self::evaluate(&keyframes, playhead_position);
+        PropertyTimeline {
+            id: reactivity::mint_property_id(),
+            timeline,
+            keyframes,
+            cached_evaluated_value,
+            last_evaluated_playhead_position: Some(playhead_position),
+            transition_manager: TransitionManager::new(),
+            resume_after_transition: false,
+        }
+    }
+
+    pub fn get_timeline(&self) -> Rc<RefCell<Timeline>> {
+        Rc::clone(&self.timeline)
+    }
+
+    fn evaluate(keyframes: &[Keyframe<T>], playhead_position: usize) -> T {
+        let first = keyframes.first().unwrap();
+        if playhead_position <= first.frame {
+            return first.value.clone();
+        }
+
+        //Find the segment containing the playhead.
+        //NOTE: this linear scan could be replaced with a binary search for very long timelines
+        for segment in keyframes.windows(2) {
+            let (starting, ending) = (&segment[0], &segment[1]);
+            if playhead_position <= ending.frame {
+                let progress = if ending.frame == starting.frame {
+                    1.0
+                } else {
+                    (playhead_position - starting.frame) as f64
+                        / (ending.frame - starting.frame) as f64
+                };
+
+                //Note that the eased progress is NOT bound to [0,1], because some easing curves
+                //"hyperextend" their interpolation, e.g. `OutBack`
+                return ending
+                    .curve
+                    .interpolate(&starting.value, &ending.value, progress);
+            }
+        }
+
+        keyframes.last().unwrap().value.clone()
+    }
+}
+
+/// Sets `property` to its timeline's value, if it's bound to a `@timeline` whose playhead has moved.  Since a `@timeline`
+/// may animate any property, primitives call this for each of theirs before evaluating expressions.
+pub fn apply_timeline_value<T: Default + Clone>(property: &mut Property<T>) {
+    if let Some(new_value) = property._compute_timeline_value() {
+        property.set(new_value);
+    }
 }
 
-impl Property<f64> for PropertyTimeline {
-    fn get(&self) -> &f64 {
+impl<T> Drop for PropertyTimeline<T> {
+    fn drop(&mut self) {
+        reactivity::forget_property(self.id);
+    }
+}
+
+impl<T: Default + Clone + Interpolatable> PropertyInstance<T> for PropertyTimeline<T> {
+    fn get(&self) -> &T {
+        reactivity::notify_read(self.id);
         &self.cached_evaluated_value
     }
 
-    fn _get_vtable_id(&self) -> Option<u64> {
-        Some(self.id.as_str())
+    fn get_mut(&mut self) -> &mut T {
+        reactivity::notify_changed(self.id);
+        &mut self.cached_evaluated_value
+    }
+
+    fn _get_vtable_id(&self) -> Option<usize> {
+        None
+    }
+
+    fn _get_property_id(&self) -> PropertyId {
+        self.id
     }
 
-    fn set(&mut self, value: f64) {
+    /// Overrides the evaluated value until the playhead next moves
+    fn set(&mut self, value: T) {
+        reactivity::notify_changed(self.id);
         self.cached_evaluated_value = value;
     }
 
-    fn is_fresh(&self) -> bool {
-        self.is_fresh
+    fn _compute_timeline_value(&mut self) -> Option<T> {
+        let playhead_position = (*self.timeline).borrow().playhead_position;
+        if self.last_evaluated_playhead_position == Some(playhead_position) {
+            return None;
+        }
+        self.last_evaluated_playhead_position = Some(playhead_position);
+        Some(Self::evaluate(&self.keyframes, playhead_position))
     }
 
-    fn mark_fresh(&mut self) {
-        self.is_fresh = true;
+    //FUTURE: when trait fields land in Rust, DRY this implementation vs. other <T: PropertyInstance> implementations
+    fn ease_to(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+        reactivity::notify_changed(self.id);
+        self.resume_after_transition = false;
+        self.transition_manager.value = Some(self.get().clone());
+        let _ = &self.transition_manager.queue.clear();
+        let _ = &self
+            .transition_manager
+            .queue
+            .push_back(TransitionQueueEntry {
+                global_frame_started: None,
+                duration_frames,
+                curve,
+                starting_value: self.cached_evaluated_value.clone(),
+                ending_value: new_value,
+            });
     }
-}
 
+    fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+        reactivity::notify_changed(self.id);
+        self.resume_after_transition = false;
+        if self.transition_manager.value.is_none() {
+            //handle case where transition queue is empty -- a None value gets skipped, so populate it with Some
+            self.transition_manager.value = Some(self.get().clone());
+        }
+
+        let starting_value = if !self.transition_manager.queue.is_empty() {
+            self.transition_manager
+                .queue
+                .back()
+                .unwrap()
+                .ending_value
+                .clone()
+        } else {
+            self.cached_evaluated_value.clone()
+        };
+
+        self.transition_manager
+            .queue
+            .push_back(TransitionQueueEntry {
+                global_frame_started: None,
+                duration_frames,
+                curve,
+                starting_value,
+                ending_value: new_value,
+            });
+    }
 
-pub struct TimelineSegment {
-    pub curve_in: Box<dyn Tweenable>,
-    pub ending_value: Box<dyn Property<f64>>,
-    pub ending_frame_inclusive: usize,
+    fn _get_transition_manager(&mut self) -> Option<&mut TransitionManager<T>> {
+        if self.resume_after_transition && self.transition_manager.queue.is_empty() {
+            //hand control back to the timeline
+            self.resume_after_transition = false;
+            self.transition_manager.value = None;
+            self.last_evaluated_playhead_position = None;
+        }
+        if self.transition_manager.value.is_none() {
+            None
+        } else {
+            Some(&mut self.transition_manager)
+        }
+    }
+
+    fn to_default(&mut self) {
+        reactivity::notify_changed(self.id);
+        self.transition_manager.queue.clear();
+        self.transition_manager.value = None;
+        self.resume_after_transition = false;
+        let playhead_position = (*self.timeline).borrow().playhead_position;
+        self.cached_evaluated_value = Self::evaluate(&self.keyframes, playhead_position);
+        self.last_evaluated_playhead_position = Some(playhead_position);
+    }
+
+    /// Eases to the timeline's value as of the playhead's current position, then resumes tracking the timeline
+    fn ease_to_default(&mut self, duration_frames: u64, curve: EasingCurve) {
+        let playhead_position = (*self.timeline).borrow().playhead_position;
+        self.ease_to(
+            Self::evaluate(&self.keyframes, playhead_position),
+            duration_frames,
+            curve,
+        );
+        self.resume_after_transition = true;
+    }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_timeline_evaluates_segments() {
+        let timeline = Rc::new(RefCell::new(Timeline::new(0)));
+        let mut property = PropertyTimeline::new(
+            Rc::clone(&timeline),
+            vec![
+                Keyframe::new(10, 100.0, EasingCurve::Linear).named("end"),
+                Keyframe::new(0, 0.0, EasingCurve::Linear),
+            ],
+        );
+        assert_eq!(timeline.borrow().frame_count, 10);
+        assert_eq!(property._compute_timeline_value(), None);
+
+        timeline.borrow_mut().seek(5);
+        assert_eq!(property._compute_timeline_value(), Some(50.0));
+        //a paused playhead yields nothing new to set
+        assert_eq!(property._compute_timeline_value(), None);
+
+        assert!(timeline.borrow_mut().seek_to_keyframe("end"));
+        assert_eq!(property._compute_timeline_value(), Some(100.0));
+    }
+
+    #[test]
+    fn test_property_timeline_resumes_after_ease_to_default() {
+        let timeline = Rc::new(RefCell::new(Timeline::new(0)));
+        let mut property = PropertyTimeline::new(
+            Rc::clone(&timeline),
+            vec![
+                Keyframe::new(0, 0.0, EasingCurve::Linear),
+                Keyframe::new(10, 100.0, EasingCurve::Linear),
+            ],
+        );
+        timeline.borrow_mut().seek(5);
 
+        property.ease_to(20.0, 1, EasingCurve::Linear);
+        property.ease_to_default(1, EasingCurve::Linear);
+        let transition_manager = property._get_transition_manager().unwrap();
+        assert_eq!(transition_manager.queue.len(), 1);
+        assert_eq!(transition_manager.queue[0].ending_value, 50.0);
 
-//
-// pub struct PropertyTimeline {
-//     pub starting_value: Box<dyn Property<f64>>,
-//     pub timeline_segments: Vec<TimelineSegment>,
-//     pub cached_evaluated_value: f64,
-// }
-
-//FUTURE: create an Interpolatable trait that allows us
-//      to ease between values beyond f64 (e.g. a discrete interpolator
-// //      for ints or vecs; an interpolator for Colors)
-// impl ComputableProperty for PropertyTimeline {
-//
-//     fn compute_in_place(&mut self, rtc: &RenderTreeContext) {
-//         let timeline_playhead_position = rtc.timeline_playhead_position;
-//         let mut starting_frame : usize = 0;
-//         let mut starting_value = self.starting_value.get();
-//         let mut segments_iter = self.timeline_segments.iter().peekable();
-//         let mut active_segment = match segments_iter.next() {
-//             Some(seg) => seg,
-//             None => {panic!("Timeline properties must define at least one TimelineSegment.")}
-//         };
-//
-//         //Scan through our list of timeline segments to find our active segment
-//         //NOTE:  this lookup could be optimized to constant-time with something like
-//         //       a tree-map, or a "'''hashmap''' with ranges-of-frames for keys => pointers-to-segments for values"
-//         while timeline_playhead_position > active_segment.ending_frame_inclusive
-//             && segments_iter.peek().is_some()
-//         {
-//             starting_frame = active_segment.ending_frame_inclusive;
-//             starting_value = active_segment.ending_value.get();
-//             active_segment = segments_iter.next().unwrap();
-//         };
-//
-//         // Determine how far along the playhead is between starting_frame and
-//         // the current segment's ending_frame.  That ratio [0,100%] (capped)
-//         // is the number to pass into our easing curve
-//         let progress = (
-//             (timeline_playhead_position - starting_frame) as f64
-//             /
-//             (active_segment.ending_frame_inclusive - starting_frame) as f64
-//         ).min(1.0); //cap at 1.0 to satisfy domain expectations of easing functions [0,1]
-//
-//         let progress_eased = active_segment.curve_in.map(progress);
-//
-//         //the computed value is a function of the magnitude of difference
-//         //between val_last and val_next.  Keep in mind that progress_eased is NOT
-//         //bound to [0,1], because some easing curves can "hyperextend" their
-//         //interpolation, e.g. a standard elastic curve.  Such hyperextension, too,
-//         //is a function of the magnitude of the difference between val_last and val_next.
-//         let ending_value = active_segment.ending_value.get();
-//
-//         self.cached_evaluated_value = starting_value + (progress_eased * (ending_value - starting_value));
-//     }
-//
-//
-// }
+        //once the transition completes, the timeline takes back over
+        transition_manager.queue.clear();
+        assert!(property._get_transition_manager().is_none());
+
+        property.ease_to(20.0, 1, EasingCurve::Linear);
+        property.to_default();
+        assert!(property._get_transition_manager().is_none());
+        assert_eq!(*property.get(), 50.0);
+    }
+
+    #[test]
+    fn test_timeline_advances_once_per_tick_and_loops() {
+        let mut timeline = Timeline::new(2);
+        timeline.set_looping(true);
+        for frames_elapsed in [0, 0, 1, 1, 2] {
+            timeline.advance(frames_elapsed);
+        }
+        assert_eq!(timeline.playhead_position, 2);
+        timeline.advance(3);
+        assert_eq!(timeline.playhead_position, 0);
+
+        timeline.set_looping(false);
+        timeline.seek(2);
+        timeline.advance(4);
+        assert!(!timeline.is_playing);
+    }
+}
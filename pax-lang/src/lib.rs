@@ -1,3 +1,44 @@
+//! Root import entry-point for using Pax in a Rust program.
+//!
+//! Every method bound in a component's `@handlers` block must exist on that component;
+//! a binding to a missing method is rejected when the component is compiled:
+//!
+//! ```compile_fail,E0599
+//! use pax_lang::api::RuntimeContext;
+//! use pax_lang::*;
+//!
+//! #[derive(Pax)]
+//! #[inlined(
+//!     @handlers {
+//!         did_mount: handle_did_mount
+//!     }
+//! )]
+//! pub struct Example {}
+//!
+//! impl Example {
+//!     pub fn handle_will_render(&mut self, _ctx: RuntimeContext) {}
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ```
+//! use pax_lang::api::RuntimeContext;
+//! use pax_lang::*;
+//!
+//! #[derive(Pax)]
+//! #[inlined(
+//!     @handlers {
+//!         did_mount: handle_did_mount
+//!     }
+//! )]
+//! pub struct Example {}
+//!
+//! impl Example {
+//!     pub fn handle_did_mount(&mut self, _ctx: RuntimeContext) {}
+//! }
+//! # fn main() {}
+//! ```
+
 pub extern crate pax_macro;
 pub use pax_macro::*;
 
@@ -12,7 +12,7 @@ lazy_static! {
     static ref STRUCT_COMPLETIONS: RwLock<HashMap<String, CompletionItem>> = {
         let mut map = HashMap::new();
 
-        let components = ["Scroller", "Stacker", "Frame", "Group"];
+        let components = ["Scroller", "Stacker", "Frame", "Clip", "Group"];
 
         for component in &components {
             map.insert(
@@ -296,8 +296,15 @@ lazy_static! {
             ("double_click", "Set Double Click event handler"),
             ("context_menu", "Set Context Menu event handler"),
             ("wheel", "Set Wheel event handler"),
+            ("button_click", "Set Button Click event handler"),
+            ("slider_change", "Set Slider Change event handler"),
+            ("dropdown_change", "Set Dropdown Change event handler"),
+            ("textbox_change", "Set Textbox Change event handler"),
+            ("focus", "Set Focus event handler"),
+            ("blur", "Set Blur event handler"),
             ("will_render", "Set Will Render event handler"),
             ("did_mount", "Set Did Mount event handler"),
+            ("will_unmount", "Set Will Unmount event handler"),
         ];
 
         for (event, description) in &events {
@@ -309,6 +316,21 @@ lazy_static! {
                 ..Default::default()
             });
         }
+
+        // Every input event may also be handled during its capture phase, e.g. `@click_capture`
+        for (event, description) in events
+            .iter()
+            .filter(|(event, _)| !["will_render", "did_mount", "will_unmount"].contains(event))
+        {
+            let event = format!("{}_capture", event);
+            completions.push(CompletionItem {
+                label: event.clone(),
+                detail: Some(description.replace("event handler", "capture handler")),
+                kind: Some(CompletionItemKind::FIELD),
+                insert_text: Some(event),
+                ..Default::default()
+            });
+        }
         RwLock::new(completions)
     };
 }
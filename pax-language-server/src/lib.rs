@@ -526,6 +526,10 @@ impl Backend {
                                         "transform",
                                         "width",
                                         "height",
+                                        "focusable",
+                                        "tab_index",
+                                        "opacity",
+                                        "blend_mode",
                                     ];
 
                                     if let Some(struct_ident) = struct_name {
@@ -122,8 +122,12 @@ fn get_scoped_resolvable_types(t: &Type) -> (Vec<String>, String) {
     recurse_get_scoped_resolvable_types(t, &mut accum);
 
     //the recursion above was post-order, so we will assume
-    //the final element is root
-    let root_scoped_resolvable_type = accum.get(accum.len() - 1).unwrap().clone();
+    //the final element is root -- except for tuples, which are resolvable only as a whole, like `<(usize, Size)>`
+    let root_scoped_resolvable_type = if let Type::Tuple(_) = t {
+        format!("<{}>", t.to_token_stream().to_string().replace(" ", ""))
+    } else {
+        accum.get(accum.len() - 1).unwrap().clone()
+    };
 
     (accum, root_scoped_resolvable_type)
 }
@@ -282,6 +286,7 @@ fn pax_full_component(
         get_static_property_definitions_from_tokens(input_parsed.data);
     let template_dependencies =
         parsing::parse_pascal_identifiers_from_component_definition_string(&raw_pax);
+    let handler_methods = parsing::parse_handler_methods_from_component_definition_string(&raw_pax);
 
     // Load reexports.partial.rs if PAX_DIR is set
     let pax_dir: Option<&'static str> = option_env!("PAX_DIR");
@@ -299,6 +304,7 @@ fn pax_full_component(
             raw_pax,
             template_dependencies,
             reexports_snippet,
+            handler_methods,
         }),
         pascal_identifier,
         include_imports,
@@ -180,26 +180,44 @@ fn recurse_visit_tag_pairs_for_pascal_identifiers(
         Rule::statement_control_flow => {
             let matched_tag = any_tag_pair.into_inner().next().unwrap();
 
-            let n = match matched_tag.as_rule() {
-                Rule::statement_if => 1,
-                Rule::statement_for => 2,
-                Rule::statement_slot => 0,
+            if let Rule::statement_if = matched_tag.as_rule() {
+                //visit the `if` branch as well as any `else if` / `else` branches
+                matched_tag
+                    .into_inner()
+                    .for_each(|pair| match pair.as_rule() {
+                        Rule::inner_nodes => recurse_visit_inner_nodes_for_pascal_identifiers(
+                            pair,
+                            Rc::clone(&pascal_identifiers),
+                        ),
+                        Rule::statement_else_if | Rule::statement_else => {
+                            let inner_nodes = pair
+                                .into_inner()
+                                .find(|p| p.as_rule() == Rule::inner_nodes)
+                                .unwrap();
+                            recurse_visit_inner_nodes_for_pascal_identifiers(
+                                inner_nodes,
+                                Rc::clone(&pascal_identifiers),
+                            );
+                        }
+                        _ => {}
+                    });
+                return;
+            }
+
+            let prospective_inner_nodes = match matched_tag.as_rule() {
+                //inner_nodes is always the final pair of a `for`, following the optional `key`
+                Rule::statement_for => matched_tag.into_inner().last(),
+                Rule::statement_slot => matched_tag.into_inner().nth(0),
                 _ => {
                     unreachable!("Parsing error 944491032: {:?}", matched_tag.as_rule());
                 }
-            };
-
-            let prospective_inner_nodes = matched_tag.into_inner().nth(n).expect("WRONG nth");
+            }
+            .expect("WRONG nth");
             match prospective_inner_nodes.as_rule() {
-                Rule::inner_nodes => {
-                    let inner_nodes = prospective_inner_nodes;
-                    inner_nodes.into_inner().for_each(|sub_tag_pair| {
-                        recurse_visit_tag_pairs_for_pascal_identifiers(
-                            sub_tag_pair,
-                            Rc::clone(&pascal_identifiers),
-                        );
-                    })
-                }
+                Rule::inner_nodes => recurse_visit_inner_nodes_for_pascal_identifiers(
+                    prospective_inner_nodes,
+                    Rc::clone(&pascal_identifiers),
+                ),
                 Rule::expression_body => {
                     //This space intentionally left blank.
                     //e.g. for `slot` -- not necessary to worry about for PascalIdentifiers
@@ -217,3 +235,45 @@ fn recurse_visit_tag_pairs_for_pascal_identifiers(
         }
     }
 }
+
+fn recurse_visit_inner_nodes_for_pascal_identifiers(
+    inner_nodes: Pair<Rule>,
+    pascal_identifiers: Rc<RefCell<HashSet<String>>>,
+) {
+    inner_nodes.into_inner().for_each(|sub_tag_pair| {
+        recurse_visit_tag_pairs_for_pascal_identifiers(
+            sub_tag_pair,
+            Rc::clone(&pascal_identifiers),
+        );
+    })
+}
+
+/// The methods bound in a component's `@handlers` block, e.g. `handle_click` for `click: self.handle_click`
+pub fn parse_handler_methods_from_component_definition_string(pax: &str) -> Vec<String> {
+    let pax_component_definition = PaxMacroParser::parse(Rule::pax_component_definition, pax)
+        .expect(&format!("unsuccessful parse from {}", &pax))
+        .next()
+        .unwrap();
+
+    let mut methods = vec![];
+    pax_component_definition
+        .into_inner()
+        .filter(|pair| pair.as_rule() == Rule::handlers_block_declaration)
+        .flat_map(|pair| pair.into_inner())
+        .filter(|pair| pair.as_rule() == Rule::handlers_key_value_pair)
+        .for_each(|handlers_key_value_pair| {
+            let handlers_value = handlers_key_value_pair.into_inner().nth(1).unwrap();
+            let raw_values = handlers_value.into_inner().next().unwrap();
+            let literal_functions = match raw_values.as_rule() {
+                Rule::function_list => raw_values.into_inner().collect(),
+                _ => vec![raw_values],
+            };
+            for literal_function in literal_functions {
+                let method = literal_function.into_inner().next().unwrap().as_str();
+                if !methods.iter().any(|m| m == method) {
+                    methods.push(method.to_string());
+                }
+            }
+        });
+    methods
+}
@@ -27,6 +27,8 @@ pub struct ArgsFullComponent {
     pub is_main_component: bool,
     pub template_dependencies: Vec<String>,
     pub reexports_snippet: String,
+    /// The methods bound in `@handlers`, whose existence is asserted at compile time
+    pub handler_methods: Vec<String>,
 }
 
 #[derive(TemplateOnce)]
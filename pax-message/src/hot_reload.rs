@@ -0,0 +1,144 @@
+//! Messages sent by `pax run --hot-reload` to a running app, describing a component whose template
+//! has changed on disk.  The receiving engine rebuilds that component's template from the message,
+//! evaluating its expressions with the runtime PAXEL interpreter.
+
+/// A component's replacement template, along with everything needed to instantiate it
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ComponentTemplateReload {
+    /// e.g. `crate::hello_rgb::HelloRGB`
+    pub component_type_id: String,
+    /// Every expression bound in the new template.  Their ids are new, so that they don't collide with
+    /// expressions still bound in the running app.
+    pub expressions: Vec<InterpretedExpression>,
+    /// The top-level nodes of the new template
+    pub template: Vec<TemplateNodePatch>,
+    /// The component's own `@events`, as (event, method) pairs, e.g. `("did_mount", "handle_did_mount")`
+    pub handlers: Vec<(String, String)>,
+}
+
+/// An expression to be evaluated by the runtime interpreter
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct InterpretedExpression {
+    pub id: usize,
+    pub paxel: String,
+    /// The `TypesCoproduct` variant to which this expression's value is converted
+    pub pascalized_return_type: String,
+    /// The scopes visible to this expression, from the innermost outwards
+    pub scope_frames: Vec<InterpretedScopeFrame>,
+    pub kind: InterpretedExpressionKind,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum InterpretedExpressionKind {
+    /// An expression whose value is bound to a property
+    Value,
+    /// The source of a `for` over a `Vec<T>`, where the `String` is the `PropertiesCoproduct`
+    /// variant (type_id_escaped) of `T`
+    RepeatSourceIterable(String),
+    /// The key of a keyed `for`, stringified
+    RepeatKey,
+}
+
+/// One scope visible to an interpreted expression
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum InterpretedScopeFrame {
+    /// A component's scope, whose properties are resolved by name
+    Properties,
+    /// A `for` body's scope, binding e.g. `elem` and `i` in `for (elem, i) in ...`, along with any symbols bound by
+    /// destructuring each element and the path of fields to each, e.g. `("score", ["1"])` for `for ((name, score), i) in ...`
+    RepeatItem {
+        elem: String,
+        i: Option<String>,
+        destructured: Vec<(String, Vec<String>)>,
+    },
+}
+
+/// A node in a reloaded template
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum TemplateNodePatch {
+    /// An instance of a primitive or userland component, e.g. `<Rectangle fill={...} />`
+    Component {
+        type_id: String,
+        /// (property, expression id) pairs, e.g. `("fill", 12)`.  Literal values are sent as expressions, too.
+        settings: Vec<(String, usize)>,
+        /// (event, method) pairs, e.g. `("click", "handle_click")`, dispatched to the methods of the
+        /// component that owns the template
+        handlers: Vec<(String, String)>,
+        children: Vec<TemplateNodePatch>,
+    },
+    /// `for`, where `source` is the id of its source expression
+    Repeat {
+        source: usize,
+        is_source_range: bool,
+        key: Option<usize>,
+        children: Vec<TemplateNodePatch>,
+    },
+    /// `if`, where `condition` is the id of its condition expression
+    Conditional {
+        condition: usize,
+        children: Vec<TemplateNodePatch>,
+        else_children: Vec<TemplateNodePatch>,
+    },
+    /// `slot`, where `index` is the id of its index expression
+    Slot { index: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reloads_survive_the_trip_from_compiler_to_chassis() {
+        let reload = ComponentTemplateReload {
+            component_type_id: "crate::Example".to_string(),
+            expressions: vec![
+                InterpretedExpression {
+                    id: 7,
+                    paxel: "(elem.score * 10)px".to_string(),
+                    pascalized_return_type: "Size".to_string(),
+                    scope_frames: vec![
+                        InterpretedScopeFrame::RepeatItem {
+                            elem: "elem".to_string(),
+                            i: Some("i".to_string()),
+                            destructured: vec![("score".to_string(), vec!["1".to_string()])],
+                        },
+                        InterpretedScopeFrame::Properties,
+                    ],
+                    kind: InterpretedExpressionKind::Value,
+                },
+                InterpretedExpression {
+                    id: 8,
+                    paxel: "self.items".to_string(),
+                    pascalized_return_type: "stdCOCOvecCOCOVec".to_string(),
+                    scope_frames: vec![InterpretedScopeFrame::Properties],
+                    kind: InterpretedExpressionKind::RepeatSourceIterable(
+                        "crateCOCOItem".to_string(),
+                    ),
+                },
+            ],
+            template: vec![
+                TemplateNodePatch::Repeat {
+                    source: 8,
+                    is_source_range: false,
+                    key: Some(9),
+                    children: vec![TemplateNodePatch::Component {
+                        type_id: "pax_std::primitives::Rectangle".to_string(),
+                        settings: vec![("width".to_string(), 7)],
+                        handlers: vec![("click".to_string(), "handle_click".to_string())],
+                        children: vec![],
+                    }],
+                },
+                TemplateNodePatch::Conditional {
+                    condition: 10,
+                    children: vec![TemplateNodePatch::Slot { index: 11 }],
+                    else_children: vec![],
+                },
+            ],
+            handlers: vec![("did_mount".to_string(), "handle_did_mount".to_string())],
+        };
+
+        let json = serde_json::to_string(&reload).unwrap();
+        let received: ComponentTemplateReload = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{:?}", received), format!("{:?}", reload));
+    }
+}
@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate serde;
 
+pub mod hot_reload;
 pub mod reflection;
 
 //FUTURE: feature-flag, only for Web builds
@@ -12,7 +13,7 @@ use serde::Serialize;
 #[derive(Serialize)]
 pub enum NativeMessage {
     TextCreate(AnyCreatePatch),
-    TextUpdate(TextPatch),
+    TextUpdate(Box<TextPatch>),
     TextDelete(Vec<u32>), //node instance ID, "id_chain"
     FrameCreate(AnyCreatePatch),
     FrameUpdate(FramePatch),
@@ -21,7 +22,21 @@ pub enum NativeMessage {
     ScrollerUpdate(ScrollerPatch),
     ScrollerDelete(Vec<u32>),
     ImageLoad(ImagePatch),
-    LayerAdd(LayerAddPatch), //FUTURE: native form controls
+    LayerAdd(LayerAddPatch),
+    ButtonCreate(AnyCreatePatch),
+    ButtonUpdate(ButtonPatch),
+    ButtonDelete(Vec<u32>),
+    SliderCreate(AnyCreatePatch),
+    SliderUpdate(SliderPatch),
+    SliderDelete(Vec<u32>),
+    DropdownCreate(AnyCreatePatch),
+    DropdownUpdate(DropdownPatch),
+    DropdownDelete(Vec<u32>),
+    TextboxCreate(AnyCreatePatch),
+    TextboxUpdate(TextboxPatch),
+    TextboxDelete(Vec<u32>),
+    Focus(Vec<u32>), //the element addressed by this id_chain has taken keyboard focus, so its native counterpart (if any) should too
+    Blur(Vec<u32>),  //the element addressed by this id_chain has lost keyboard focus
 }
 
 #[derive(Deserialize)]
@@ -46,6 +61,12 @@ pub enum NativeInterrupt {
     ContextMenu(ContextMenuInterruptArgs),
     Image(ImageLoadInterruptArgs),
     AddedLayer(AddedLayerArgs),
+    FormButtonClick(FormButtonClickArgs),
+    FormSliderChange(FormSliderChangeArgs),
+    FormDropdownChange(FormDropdownChangeArgs),
+    FormTextboxChange(FormTextboxChangeArgs),
+    FormFocus(FormFocusArgs),
+    FormBlur(FormBlurArgs),
 }
 
 #[derive(Deserialize)]
@@ -238,6 +259,47 @@ pub struct ImageDataArgs {
     pub height: usize,
 }
 
+#[derive(Deserialize)]
+#[repr(C)]
+pub struct FormButtonClickArgs {
+    pub id_chain: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+#[repr(C)]
+pub struct FormSliderChangeArgs {
+    pub id_chain: Vec<u32>,
+    pub value: f64,
+}
+
+#[derive(Deserialize)]
+#[repr(C)]
+pub struct FormDropdownChangeArgs {
+    pub id_chain: Vec<u32>,
+    pub selected_index: usize,
+}
+
+#[derive(Deserialize)]
+#[repr(C)]
+pub struct FormTextboxChangeArgs {
+    pub id_chain: Vec<u32>,
+    pub text: String,
+}
+
+/// A native form control, addressed by `id_chain`, has received focus
+#[derive(Deserialize)]
+#[repr(C)]
+pub struct FormFocusArgs {
+    pub id_chain: Vec<u32>,
+}
+
+/// A native form control, addressed by `id_chain`, has lost focus
+#[derive(Deserialize)]
+#[repr(C)]
+pub struct FormBlurArgs {
+    pub id_chain: Vec<u32>,
+}
+
 #[repr(C)]
 pub struct InterruptBuffer {
     pub data_ptr: *const u8,
@@ -268,6 +330,10 @@ pub struct FramePatch {
     pub size_x: Option<f64>,
     pub size_y: Option<f64>,
     pub transform: Option<Vec<f64>>,
+    /// Accumulated from the frame and its ancestors
+    pub opacity: Option<f64>,
+    /// CSS name of the nearest `BlendMode`, e.g. `multiply`
+    pub blend_mode: Option<String>,
 }
 
 #[derive(Default, Serialize)]
@@ -280,6 +346,10 @@ pub struct TextPatch {
     pub size_y: Option<f64>,
     pub style: Option<TextStyleMessage>,
     pub style_link: Option<TextStyleMessage>,
+    /// Accumulated from the text and its ancestors
+    pub opacity: Option<f64>,
+    /// CSS name of the nearest `BlendMode`, e.g. `multiply`
+    pub blend_mode: Option<String>,
 }
 
 #[derive(Default, Serialize)]
@@ -301,6 +371,55 @@ pub struct ImagePatch {
     pub path: Option<String>,
 }
 
+#[derive(Default, Serialize)]
+#[repr(C)]
+pub struct ButtonPatch {
+    pub id_chain: Vec<u32>,
+    pub size_x: Option<f64>,
+    pub size_y: Option<f64>,
+    pub transform: Option<Vec<f64>>,
+    pub label: Option<String>,
+    pub disabled: Option<bool>,
+}
+
+#[derive(Default, Serialize)]
+#[repr(C)]
+pub struct SliderPatch {
+    pub id_chain: Vec<u32>,
+    pub size_x: Option<f64>,
+    pub size_y: Option<f64>,
+    pub transform: Option<Vec<f64>>,
+    pub value: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub step: Option<f64>,
+    pub disabled: Option<bool>,
+}
+
+#[derive(Default, Serialize)]
+#[repr(C)]
+pub struct DropdownPatch {
+    pub id_chain: Vec<u32>,
+    pub size_x: Option<f64>,
+    pub size_y: Option<f64>,
+    pub transform: Option<Vec<f64>>,
+    pub options: Option<Vec<String>>,
+    pub selected_index: Option<usize>,
+    pub disabled: Option<bool>,
+}
+
+#[derive(Default, Serialize)]
+#[repr(C)]
+pub struct TextboxPatch {
+    pub id_chain: Vec<u32>,
+    pub size_x: Option<f64>,
+    pub size_y: Option<f64>,
+    pub transform: Option<Vec<f64>>,
+    pub text: Option<String>,
+    pub placeholder: Option<String>,
+    pub disabled: Option<bool>,
+}
+
 #[derive(Serialize)]
 #[repr(C)]
 pub enum ColorVariantMessage {
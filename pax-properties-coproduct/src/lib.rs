@@ -1,5 +1,4 @@
 use std::cell::RefCell;
-use std::ops::Range;
 use std::rc::Rc;
 
 pub enum PropertiesCoproduct {
@@ -30,15 +29,15 @@ pub enum TypesCoproduct {
 
     #[allow(non_camel_case_types)]
     stdCOCOvecCOCOVecLABRstdCOCOrcCOCORcLABRPropertiesCoproductRABRRABR(Vec<Rc<PropertiesCoproduct>>),
-    #[allow(non_camel_case_types)]
-    stdCOCOopsCOCORangeLABRisizeRABR(Range<isize>),
+    RepeatRange(pax_runtime_api::RepeatRange),
     String(String),
     Transform2D(pax_runtime_api::Transform2D),
     SizePixels(pax_runtime_api::SizePixels),
     Size(pax_runtime_api::Size),
     Rotation(pax_runtime_api::Rotation),
     Numeric(pax_runtime_api::Numeric),
-    StringBox(pax_runtime_api::StringBox)
+    StringBox(pax_runtime_api::StringBox),
+    BlendMode(pax_runtime_api::BlendMode)
     //generated / userland
 }
 
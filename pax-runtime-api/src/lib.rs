@@ -1,8 +1,11 @@
 pub mod numeric;
+pub mod paxel_std;
+pub mod range;
+pub mod reactivity;
 
 use std::borrow::Borrow;
-use std::cell::RefCell;
-use std::collections::VecDeque;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
 use std::ffi::CString;
 use std::rc::Rc;
 
@@ -13,6 +16,8 @@ extern crate lazy_static;
 extern crate mut_static;
 
 pub use crate::numeric::Numeric;
+pub use crate::range::RepeatRange;
+use crate::reactivity::PropertyId;
 use mut_static::MutStatic;
 use pax_message::{ModifierKeyMessage, MouseButtonMessage, TouchMessage};
 
@@ -29,6 +34,9 @@ pub trait PropertyInstance<T: Default + Clone> {
     fn get(&self) -> &T;
     fn _get_vtable_id(&self) -> Option<usize>;
 
+    /// Used by engine to observe changes to this property, e.g. for `on_change` handlers
+    fn _get_property_id(&self) -> PropertyId;
+
     fn get_mut(&mut self) -> &mut T;
 
     fn set(&mut self, value: T);
@@ -45,9 +53,31 @@ pub trait PropertyInstance<T: Default + Clone> {
     /// transition will be the final value upon completion of the current transition queue.
     fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve);
 
-    //Wishlist:
-    // to_default: set back to default value
-    // ease_to_default: set back to default value via interpolation
+    /// Used by engine to evaluate timeline-bound properties at their timeline's current playhead position.
+    /// Returns `None` for properties that are not bound to a timeline, or whose timeline's playhead hasn't moved
+    /// since they were last evaluated.
+    fn _compute_timeline_value(&mut self) -> Option<T> {
+        None
+    }
+
+    /// Immediately reset to this property's declared default — the literal, expression or timeline it was declared with
+    /// in its template or settings — clearing the transition queue.  Expression- and timeline-bound properties
+    /// resume tracking their expression or timeline as of the next tick.
+    /// By default, for implementors that don't retain what they were declared with, resets to `T::default()`.
+    fn to_default(&mut self) {
+        if let Some(transition_manager) = self._get_transition_manager() {
+            transition_manager.queue.clear();
+            transition_manager.value = None;
+        }
+        self.set(T::default());
+    }
+
+    /// Clear the transition queue and transition from the current value to this property's declared default
+    /// (see `to_default`.)  Expression- and timeline-bound properties resume tracking their expression or timeline
+    /// once the transition completes.
+    fn ease_to_default(&mut self, duration_frames: u64, curve: EasingCurve) {
+        self.ease_to(T::default(), duration_frames, curve);
+    }
 }
 
 impl<T: Default + Clone + 'static> Default for Box<dyn PropertyInstance<T>> {
@@ -74,8 +104,109 @@ pub struct RuntimeContext {
     // pub bounds_viewport: (f64, f64)
     // /// The number of adoptees passed to the current component (used by Stacker for auto cell-count calc; might be extended/adjusted for other use-cases)
     // pub adoptee_count: usize,
-    // /// Current playhead position(s) for current component
-    //pub timeline_playhead_position: usize,
+    /// Current playhead position for the nearest ancestor component's timeline, or 0 if there is none
+    pub timeline_playhead_position: usize,
+    /// The nearest ancestor component's timeline, if any.  Handlers may use this to
+    /// `play`, `pause`, `seek` or `set_looping` the timeline that drives keyframed properties.
+    pub timeline: Option<Rc<RefCell<Timeline>>>,
+    /// The `id_chain` of the element this context belongs to, which `focus` and `blur` act upon
+    pub id_chain: Vec<u32>,
+    /// Focus changes requested by handlers, shared with (and drained by) the engine
+    pub focus_requests: Rc<RefCell<Vec<FocusRequest>>>,
+}
+
+impl RuntimeContext {
+    /// Move keyboard focus to this element, firing `blur` on the previously focused element and `focus` on this one.
+    /// Takes effect once the current handler returns.
+    pub fn focus(&self) {
+        self.request_focus(FocusRequest::Focus(self.id_chain.clone()));
+    }
+
+    /// Release keyboard focus from this element, if it is focused.  Takes effect once the current handler returns.
+    pub fn blur(&self) {
+        self.request_focus(FocusRequest::Blur(self.id_chain.clone()));
+    }
+
+    /// Move keyboard focus to the next focusable element in tab order, as if the user had pressed `Tab`
+    pub fn focus_next(&self) {
+        self.request_focus(FocusRequest::Next);
+    }
+
+    /// Move keyboard focus to the previous focusable element in tab order, as if the user had pressed `Shift+Tab`
+    pub fn focus_previous(&self) {
+        self.request_focus(FocusRequest::Previous);
+    }
+
+    fn request_focus(&self, request: FocusRequest) {
+        (*self.focus_requests).borrow_mut().push(request);
+    }
+}
+
+/// A change of keyboard focus requested from userland, e.g. with `RuntimeContext::focus`
+#[derive(Clone, Debug, PartialEq)]
+pub enum FocusRequest {
+    /// Focus the element addressed by this `id_chain`
+    Focus(Vec<u32>),
+    /// Blur the element addressed by this `id_chain`, if it is focused
+    Blur(Vec<u32>),
+    /// Focus the next focusable element in tab order
+    Next,
+    /// Focus the previous focusable element in tab order
+    Previous,
+}
+
+/// An event as delivered to handlers: its `Args*`, also reachable through `Deref` (e.g. `event.mouse.x`),
+/// along with control over how the event propagates through the render tree.
+///
+/// Like DOM events, events travel in two phases: first from the root down to the target, calling each node's
+/// capture handlers (e.g. `@click_capture`), then from the target back up to the root, calling each node's
+/// handlers (e.g. `@click`.)  Clones share their propagation state, so handlers may stash them.
+#[derive(Clone)]
+pub struct Event<T> {
+    pub args: T,
+    state: Rc<EventState>,
+}
+
+#[derive(Default)]
+struct EventState {
+    propagation_stopped: Cell<bool>,
+    default_prevented: Cell<bool>,
+}
+
+impl<T> Event<T> {
+    pub fn new(args: T) -> Self {
+        Self {
+            args,
+            state: Default::default(),
+        }
+    }
+
+    /// Stop this event from reaching any further nodes, once the handlers of the current node have run
+    pub fn stop_propagation(&self) {
+        self.state.propagation_stopped.set(true);
+    }
+
+    /// Suppress the chassis' default action for this event, e.g. native scrolling for a `wheel` in a `Scroller`,
+    /// or the browser's own menu for a `context_menu`.  The event still propagates; see `stop_propagation`.
+    pub fn prevent_default(&self) {
+        self.state.default_prevented.set(true);
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.state.propagation_stopped.get()
+    }
+
+    pub fn is_default_prevented(&self) -> bool {
+        self.state.default_prevented.get()
+    }
+}
+
+impl<T> Deref for Event<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.args
+    }
 }
 
 // Unified events
@@ -279,6 +410,38 @@ pub struct ArgsContextMenu {
     pub mouse: MouseEventArgs,
 }
 
+// Form Events
+
+/// User activates a `Button`, whether by clicking, tapping or with the keyboard.
+#[derive(Clone)]
+pub struct ArgsButtonClick {}
+
+/// User moves a `Slider` to a new `value`.
+#[derive(Clone)]
+pub struct ArgsSliderChange {
+    pub value: f64,
+}
+
+/// User picks the option at `selected_index` from a `Dropdown`.
+#[derive(Clone)]
+pub struct ArgsDropdownChange {
+    pub selected_index: usize,
+}
+
+/// User edits the contents of a `TextBox`, which now reads `text`.
+#[derive(Clone)]
+pub struct ArgsTextboxChange {
+    pub text: String,
+}
+
+/// A form control receives focus.
+#[derive(Clone)]
+pub struct ArgsFocus {}
+
+/// A form control loses focus.
+#[derive(Clone)]
+pub struct ArgsBlur {}
+
 /// A Size value that can be either a concrete pixel value
 /// or a percent of parent bounds.
 
@@ -380,6 +543,17 @@ pub struct CommonProperties {
     pub transform: Rc<RefCell<dyn PropertyInstance<Transform2D>>>,
     pub width: Rc<RefCell<dyn PropertyInstance<Size>>>,
     pub height: Rc<RefCell<dyn PropertyInstance<Size>>>,
+    /// Whether this element can take keyboard focus; see `RenderNode::is_focusable` for each element's default
+    pub focusable: Option<Rc<RefCell<dyn PropertyInstance<bool>>>>,
+    /// This element's position in tab order: focusable elements are visited in ascending `tab_index` (default 0),
+    /// then in template order.  A negative `tab_index` leaves an element out of tab order, though it may still
+    /// be focused by clicking it or with `RuntimeContext::focus`.
+    pub tab_index: Option<Rc<RefCell<dyn PropertyInstance<isize>>>>,
+    /// This element's opacity between 0.0 (transparent) and 1.0 (opaque, the default).  Applies to the element
+    /// and its whole subtree, compounding with the opacity of ancestors.
+    pub opacity: Option<Rc<RefCell<dyn PropertyInstance<f64>>>>,
+    /// How this element and its subtree are composited onto what is drawn beneath them; see `BlendMode`.
+    pub blend_mode: Option<Rc<RefCell<dyn PropertyInstance<BlendMode>>>>,
 }
 
 impl CommonProperties {
@@ -418,6 +592,10 @@ impl CommonProperties {
             ("transform".to_string(), "Transform2D".to_string()),
             ("width".to_string(), "Size".to_string()),
             ("height".to_string(), "Size".to_string()),
+            ("focusable".to_string(), "bool".to_string()),
+            ("tab_index".to_string(), "isize".to_string()),
+            ("opacity".to_string(), "f64".to_string()),
+            ("blend_mode".to_string(), "BlendMode".to_string()),
         ]
     }
 }
@@ -434,6 +612,10 @@ impl Default for CommonProperties {
             rotate: Default::default(),
             anchor_x: Default::default(),
             anchor_y: Default::default(),
+            focusable: Default::default(),
+            tab_index: Default::default(),
+            opacity: Default::default(),
+            blend_mode: Default::default(),
 
             width: Rc::new(RefCell::new(PropertyLiteral::new(Size::default()))),
             height: Rc::new(RefCell::new(PropertyLiteral::new(Size::default()))),
@@ -477,14 +659,14 @@ impl Neg for Rotation {
         }
     }
 }
-impl Into<Rotation> for Numeric {
-    fn into(self) -> Rotation {
-        Rotation::Radians(self)
+impl From<Numeric> for Rotation {
+    fn from(val: Numeric) -> Self {
+        Rotation::Radians(val)
     }
 }
-impl Into<Rotation> for Size {
-    fn into(self) -> Rotation {
-        if let Size::Percent(pix) = self {
+impl From<Size> for Rotation {
+    fn from(val: Size) -> Self {
+        if let Size::Percent(pix) = val {
             Rotation::Percent(pix)
         } else {
             panic!("Tried to coerce a pixel value into a rotation value; try `%` or `rad` instead of `px`.")
@@ -498,6 +680,61 @@ impl Default for Rotation {
     }
 }
 
+/// Describes how an element is composited onto the content beneath it, after the
+/// blend modes of CSS / the W3C Compositing spec.  Applied by `CommonProperties::blend_mode`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl BlendMode {
+    /// The name of this blend mode in CSS, e.g. for `mix-blend-mode` and `globalCompositeOperation`
+    pub fn css_name(&self) -> &'static str {
+        match self {
+            BlendMode::Normal => "normal",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Screen => "screen",
+            BlendMode::Overlay => "overlay",
+            BlendMode::Darken => "darken",
+            BlendMode::Lighten => "lighten",
+            BlendMode::ColorDodge => "color-dodge",
+            BlendMode::ColorBurn => "color-burn",
+            BlendMode::HardLight => "hard-light",
+            BlendMode::SoftLight => "soft-light",
+            BlendMode::Difference => "difference",
+            BlendMode::Exclusion => "exclusion",
+            BlendMode::Hue => "hue",
+            BlendMode::Saturation => "saturation",
+            BlendMode::Color => "color",
+            BlendMode::Luminosity => "luminosity",
+        }
+    }
+}
+
+impl Default for BlendMode {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+//blend modes are discrete, so they switch at the end of a transition rather than tweening
+impl Interpolatable for BlendMode {}
+
 impl Size {
     pub fn get_pixels(&self, parent: f64) -> f64 {
         match &self {
@@ -550,13 +787,31 @@ impl Interpolatable for Size {
     }
 }
 
+impl Interpolatable for Rotation {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        match (self, other) {
+            (Self::Radians(s), Self::Radians(o)) => {
+                Self::Radians(*s + ((*o - *s) * Numeric::from(t)))
+            }
+            (Self::Degrees(s), Self::Degrees(o)) => {
+                Self::Degrees(*s + ((*o - *s) * Numeric::from(t)))
+            }
+            (Self::Percent(s), Self::Percent(o)) => {
+                Self::Percent(*s + ((*o - *s) * Numeric::from(t)))
+            }
+            //mixed units: interpolate in radians
+            _ => Self::Radians(Numeric::from(
+                self.get_as_radians()
+                    .interpolate(&other.get_as_radians(), t),
+            )),
+        }
+    }
+}
+
 impl<T: Interpolatable> Interpolatable for Option<T> {
     fn interpolate(&self, other: &Self, t: f64) -> Self {
         match &self {
-            Self::Some(s) => match other {
-                Self::Some(o) => Some(s.interpolate(o, t)),
-                _ => None,
-            },
+            Self::Some(s) => other.as_ref().map(|o| s.interpolate(o, t)),
             Self::None => None,
         }
     }
@@ -609,6 +864,7 @@ impl PartialEq<SizePixels> for Numeric {
 pub enum PlatformSpecificLogger {
     Web(fn(&str)),
     MacOS(extern "C" fn(*const std::os::raw::c_char)),
+    Headless(fn(&str)),
 }
 
 pub struct Logger(PlatformSpecificLogger);
@@ -618,7 +874,12 @@ lazy_static! {
 }
 
 pub fn register_logger(logger: PlatformSpecificLogger) {
-    LOGGER.borrow().set(Logger(logger)).unwrap();
+    //Multiple engines may be instantiated in a single process (e.g. headless snapshot tests); the most recent registration wins
+    if LOGGER.borrow().is_set().unwrap() {
+        *LOGGER.borrow().write().unwrap() = Logger(logger);
+    } else {
+        LOGGER.borrow().set(Logger(logger)).unwrap();
+    }
 }
 
 /// Log to the appropriate native logging mechanism
@@ -631,6 +892,7 @@ pub fn log(msg: &str) {
             let msg = CString::new(msg).unwrap();
             (closure)(msg.as_ptr());
         }
+        PlatformSpecificLogger::Headless(closure) => closure(msg),
     }
 }
 
@@ -699,27 +961,31 @@ impl Mul for Transform2D {
 impl Transform2D {
     ///Scale coefficients (1.0 == 100%) over x-y plane
     pub fn scale(x: Size, y: Size) -> Self {
-        let mut ret = Transform2D::default();
-        ret.scale = Some([x, y]);
-        ret
+        Transform2D {
+            scale: Some([x, y]),
+            ..Default::default()
+        }
     }
     ///Rotation over z axis
     pub fn rotate(z: Rotation) -> Self {
-        let mut ret = Transform2D::default();
-        ret.rotate = Some(z);
-        ret
+        Transform2D {
+            rotate: Some(z),
+            ..Default::default()
+        }
     }
     ///Translation across x-y plane, pixels
     pub fn translate(x: Size, y: Size) -> Self {
-        let mut ret = Transform2D::default();
-        ret.translate = Some([x, y]);
-        ret
+        Transform2D {
+            translate: Some([x, y]),
+            ..Default::default()
+        }
     }
     ///Describe alignment of the (0,0) position of this element as it relates to its own bounding box
     pub fn anchor(x: Size, y: Size) -> Self {
-        let mut ret = Transform2D::default();
-        ret.anchor = Some([x, y]);
-        ret
+        Transform2D {
+            anchor: Some([x, y]),
+            ..Default::default()
+        }
     }
 
     pub fn default_wrapped() -> Rc<RefCell<dyn PropertyInstance<Self>>> {
@@ -732,6 +998,12 @@ pub struct TransitionManager<T> {
     pub value: Option<T>,
 }
 
+impl<T> Default for TransitionManager<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T> TransitionManager<T> {
     pub fn new() -> Self {
         Self {
@@ -743,33 +1015,48 @@ impl<T> TransitionManager<T> {
 
 /// The Literal form of a Property: a bare literal value with support for easing/interpolation
 pub struct PropertyLiteral<T> {
+    id: PropertyId,
     value: T,
+    /// The value this property was declared with, restored by `to_default`
+    default_value: T,
     transition_manager: TransitionManager<T>,
 }
 
-impl<T> Into<Box<dyn PropertyInstance<T>>> for PropertyLiteral<T>
+impl<T> From<PropertyLiteral<T>> for Box<dyn PropertyInstance<T>>
 where
     T: Default + Clone + 'static,
 {
-    fn into(self) -> Box<dyn PropertyInstance<T>> {
-        Box::new(self)
+    fn from(val: PropertyLiteral<T>) -> Self {
+        Box::new(val)
     }
 }
 
 impl<T: Clone> PropertyLiteral<T> {
     pub fn new(value: T) -> Self {
         PropertyLiteral {
+            id: reactivity::mint_property_id(),
+            default_value: value.clone(),
             value,
             transition_manager: TransitionManager::new(),
         }
     }
 }
+
+impl<T> Drop for PropertyLiteral<T> {
+    fn drop(&mut self) {
+        reactivity::forget_property(self.id);
+    }
+}
+
 impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
     fn get(&self) -> &T {
+        reactivity::notify_read(self.id);
         &self.value
     }
 
+    /// Conservatively treated as a change, since the caller may mutate the value in place
     fn get_mut(&mut self) -> &mut T {
+        reactivity::notify_changed(self.id);
         &mut self.value
     }
 
@@ -777,12 +1064,18 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
         None
     }
 
+    fn _get_property_id(&self) -> PropertyId {
+        self.id
+    }
+
     fn set(&mut self, value: T) {
+        reactivity::notify_changed(self.id);
         self.value = value;
     }
 
     //FUTURE: when trait fields land in Rust, DRY this implementation vs. other <T: PropertyInstance> implementations
     fn ease_to(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
+        reactivity::notify_changed(self.id);
         self.transition_manager.value = Some(self.get().clone());
         let _ = &self.transition_manager.queue.clear();
         let _ = &self
@@ -798,15 +1091,16 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
     }
 
     fn ease_to_later(&mut self, new_value: T, duration_frames: u64, curve: EasingCurve) {
-        if let None = self.transition_manager.value {
+        reactivity::notify_changed(self.id);
+        if self.transition_manager.value.is_none() {
             //handle case where transition queue is empty -- a None value gets skipped, so populate it with Some
             self.transition_manager.value = Some(self.get().clone());
         }
 
-        let starting_value = if self.transition_manager.queue.len() > 0 {
+        let starting_value = if !self.transition_manager.queue.is_empty() {
             self.transition_manager
                 .queue
-                .get(self.transition_manager.queue.len() - 1)
+                .back()
                 .unwrap()
                 .ending_value
                 .clone()
@@ -826,12 +1120,22 @@ impl<T: Default + Clone> PropertyInstance<T> for PropertyLiteral<T> {
     }
 
     fn _get_transition_manager(&mut self) -> Option<&mut TransitionManager<T>> {
-        if let None = self.transition_manager.value {
+        if self.transition_manager.value.is_none() {
             None
         } else {
             Some(&mut self.transition_manager)
         }
     }
+
+    fn to_default(&mut self) {
+        self.transition_manager.queue.clear();
+        self.transition_manager.value = None;
+        self.set(self.default_value.clone());
+    }
+
+    fn ease_to_default(&mut self, duration_frames: u64, curve: EasingCurve) {
+        self.ease_to(self.default_value.clone(), duration_frames, curve);
+    }
 }
 
 pub enum EasingCurve {
@@ -1004,10 +1308,107 @@ impl Interpolatable for i32 {
 
 impl Interpolatable for String {}
 
+/// Playhead state for a component's keyframe animations.  Timeline-bound properties
+/// (see `pax_core::PropertyTimeline`) share a `Timeline` and evaluate their keyframes against its `playhead_position`.
 pub struct Timeline {
     pub playhead_position: usize,
     pub frame_count: usize,
     pub is_playing: bool,
+    pub is_looping: bool,
+    /// Named keyframes, mapping to the frame at which each occurs; see `seek_to_keyframe`
+    pub keyframe_names: HashMap<String, usize>,
+    /// Global frame at which this timeline was last advanced, ensuring that a timeline
+    /// shared by multiple stack frames advances at most once per tick
+    last_advanced_frame: Option<usize>,
+    /// Changes to playback (e.g. `play`, `seek`) are reported as changes to this id, so that they wake an idle engine
+    id: PropertyId,
+}
+
+impl Drop for Timeline {
+    fn drop(&mut self) {
+        reactivity::forget_property(self.id);
+    }
+}
+
+impl Timeline {
+    /// Create a timeline that starts playing from frame 0, stopping at `frame_count`
+    pub fn new(frame_count: usize) -> Self {
+        Timeline {
+            playhead_position: 0,
+            frame_count,
+            is_playing: true,
+            is_looping: false,
+            keyframe_names: HashMap::new(),
+            last_advanced_frame: None,
+            id: reactivity::mint_property_id(),
+        }
+    }
+
+    pub fn play(&mut self) {
+        reactivity::notify_changed(self.id);
+        if !self.is_looping && self.playhead_position >= self.frame_count {
+            //replay from the beginning if we've already reached the end
+            self.playhead_position = 0;
+        }
+        self.is_playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        reactivity::notify_changed(self.id);
+        self.is_playing = false;
+    }
+
+    /// Move the playhead to `frame`, clamped to `[0, frame_count]`.  Does not affect whether the timeline is playing.
+    pub fn seek(&mut self, frame: usize) {
+        reactivity::notify_changed(self.id);
+        self.playhead_position = frame.min(self.frame_count);
+    }
+
+    /// Move the playhead to the frame of the keyframe registered as `name`, returning `false` if no such keyframe exists
+    pub fn seek_to_keyframe(&mut self, name: &str) -> bool {
+        if let Some(frame) = self.keyframe_names.get(name) {
+            let frame = *frame;
+            self.seek(frame);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn set_looping(&mut self, is_looping: bool) {
+        reactivity::notify_changed(self.id);
+        self.is_looping = is_looping;
+    }
+
+    /// Register a named keyframe, extending `frame_count` if `frame` lies beyond it
+    pub fn register_keyframe(&mut self, name: Option<&str>, frame: usize) {
+        self.frame_count = self.frame_count.max(frame);
+        if let Some(name) = name {
+            self.keyframe_names.insert(name.to_string(), frame);
+        }
+    }
+
+    /// Used by engine to advance the playhead by one frame per tick while playing.
+    /// Repeated calls during the same tick (`frames_elapsed`) are no-ops.
+    pub fn advance(&mut self, frames_elapsed: usize) {
+        let is_new_tick = match self.last_advanced_frame {
+            //the first tick renders frame 0
+            None => false,
+            Some(last_advanced_frame) => last_advanced_frame != frames_elapsed,
+        };
+        self.last_advanced_frame = Some(frames_elapsed);
+
+        if is_new_tick && self.is_playing {
+            if self.playhead_position < self.frame_count {
+                self.playhead_position += 1;
+            } else if self.is_looping {
+                self.playhead_position = 0;
+            }
+            if !self.is_looping && self.playhead_position >= self.frame_count {
+                self.is_playing = false;
+            }
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -1020,7 +1421,7 @@ pub enum Layer {
 
 /// Captures information about z-index during render node traversal
 /// Used for generating chassis side rendering architecture
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct ZIndex {
     z_index: u32,
     layer: Layer,
@@ -1048,10 +1449,8 @@ impl ZIndex {
         match layer {
             Layer::DontCare => {}
             _ => {
-                if self.layer != layer {
-                    if layer == Layer::Canvas || layer == Layer::Scroller {
-                        self.z_index += 1;
-                    }
+                if self.layer != layer && (layer == Layer::Canvas || layer == Layer::Scroller) {
+                    self.z_index += 1;
                 }
                 self.layer = layer.clone();
             }
@@ -1067,7 +1466,7 @@ impl ZIndex {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct StringBox {
     pub string: String,
 }
@@ -1076,16 +1475,14 @@ impl Add for StringBox {
     type Output = Self;
 
     fn add(mut self, rhs: Self) -> Self::Output {
-        self.string.push_str(&rhs.string.as_str());
+        self.string.push_str(rhs.string.as_str());
         self
     }
 }
 
-impl Default for StringBox {
-    fn default() -> Self {
-        Self {
-            string: String::new(),
-        }
+impl std::fmt::Display for StringBox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.string)
     }
 }
 
@@ -1113,6 +1510,88 @@ impl From<&String> for StringBox {
 
 impl From<StringBox> for String {
     fn from(value: StringBox) -> Self {
-        String::from(value.string)
+        value.string
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_transition(property: &mut dyn PropertyInstance<f64>) -> Option<(f64, f64)> {
+        let transition_manager = property._get_transition_manager()?;
+        let transition = transition_manager.queue.front()?;
+        Some((transition.starting_value, transition.ending_value))
+    }
+
+    /// A property that keeps nothing but its value, relying on the default `to_default` and `ease_to_default`
+    struct BareProperty {
+        value: f64,
+        transition_manager: TransitionManager<f64>,
+    }
+
+    impl PropertyInstance<f64> for BareProperty {
+        fn get(&self) -> &f64 {
+            &self.value
+        }
+        fn _get_vtable_id(&self) -> Option<usize> {
+            None
+        }
+        fn _get_property_id(&self) -> PropertyId {
+            unimplemented!()
+        }
+        fn get_mut(&mut self) -> &mut f64 {
+            &mut self.value
+        }
+        fn set(&mut self, value: f64) {
+            self.value = value;
+        }
+        fn _get_transition_manager(&mut self) -> Option<&mut TransitionManager<f64>> {
+            Some(&mut self.transition_manager)
+        }
+        fn ease_to(&mut self, new_value: f64, duration_frames: u64, curve: EasingCurve) {
+            self.transition_manager.queue.clear();
+            self.ease_to_later(new_value, duration_frames, curve);
+        }
+        fn ease_to_later(&mut self, new_value: f64, duration_frames: u64, curve: EasingCurve) {
+            self.transition_manager.value = Some(self.value);
+            self.transition_manager
+                .queue
+                .push_back(TransitionQueueEntry {
+                    global_frame_started: None,
+                    duration_frames,
+                    curve,
+                    starting_value: self.value,
+                    ending_value: new_value,
+                });
+        }
+    }
+
+    #[test]
+    fn test_literals_reset_and_ease_to_their_declared_value() {
+        let mut property = PropertyLiteral::new(1.0);
+        property.set(5.0);
+        property.ease_to_default(10, EasingCurve::Linear);
+        assert_eq!(get_transition(&mut property), Some((5.0, 1.0)));
+
+        property.ease_to(8.0, 10, EasingCurve::Linear);
+        property.to_default();
+        assert_eq!(*property.get(), 1.0);
+        assert!(property._get_transition_manager().is_none());
+    }
+
+    #[test]
+    fn test_default_to_default_falls_back_to_the_type_default() {
+        let mut property = BareProperty {
+            value: 5.0,
+            transition_manager: TransitionManager::new(),
+        };
+        property.ease_to_default(10, EasingCurve::Linear);
+        assert_eq!(get_transition(&mut property), Some((5.0, 0.0)));
+
+        property.to_default();
+        assert_eq!(*property.get(), 0.0);
+        assert!(property.transition_manager.queue.is_empty());
+        assert!(property.transition_manager.value.is_none());
     }
 }
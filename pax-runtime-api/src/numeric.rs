@@ -1,6 +1,7 @@
 use crate::Interpolatable;
 use std::cmp::Ordering;
 use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
 /// Numeric is a module that wraps numeric literals in Pax
@@ -17,6 +18,15 @@ impl Default for Numeric {
     }
 }
 
+impl Display for Numeric {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Numeric::Integer(i) => write!(f, "{}", i),
+            Numeric::Float(fl) => write!(f, "{}", fl),
+        }
+    }
+}
+
 impl Interpolatable for Numeric {
     fn interpolate(&self, other: &Self, t: f64) -> Self {
         Self::Float(self.get_as_float() + ((other.get_as_float() - self.get_as_float()) * t))
@@ -164,12 +174,12 @@ impl From<&i128> for Numeric {
 }
 impl From<&isize> for Numeric {
     fn from(value: &isize) -> Self {
-        Numeric::Integer(*value as isize)
+        Numeric::Integer(*value)
     }
 }
 impl From<&f64> for Numeric {
     fn from(value: &f64) -> Self {
-        Numeric::Float(*value as f64)
+        Numeric::Float(*value)
     }
 }
 
@@ -363,9 +373,7 @@ impl From<Numeric> for i128 {
 impl From<Numeric> for isize {
     fn from(value: Numeric) -> Self {
         match value {
-            Numeric::Integer(i) => isize::try_from(i).unwrap_or_else(|_| {
-                unreachable!("Conversion from Numeric to isize resulted in overflow.")
-            }),
+            Numeric::Integer(i) => i,
             Numeric::Float(f) => {
                 if (f >= isize::MIN as f64) && (f <= isize::MAX as f64) {
                     f as isize
@@ -561,45 +569,45 @@ mod tests {
 
     #[test]
     fn test_widen() {
-        let integer_a = Numeric::from(1 as usize);
-        let integer_b = Numeric::from(2 as usize);
+        let integer_a = Numeric::from(1_usize);
+        let integer_b = Numeric::from(2_usize);
         let float_a = Numeric::from(3.0);
         let float_b = Numeric::from(4.0);
 
-        assert_eq!(integer_a + integer_b, Numeric::Integer(3 as isize));
+        assert_eq!(integer_a + integer_b, Numeric::Integer(3_isize));
         assert_eq!(integer_a + float_a, Numeric::Float(4.0));
         assert_eq!(float_a + float_b, Numeric::Float(7.0));
     }
 
     #[test]
     fn test_cmp() {
-        let integer_a = Numeric::from(1 as usize);
-        let integer_b = Numeric::from(2 as usize);
+        let integer_a = Numeric::from(1_usize);
+        let integer_b = Numeric::from(2_usize);
         let float_a = Numeric::from(3.0);
         let float_b = Numeric::from(4.0);
         let float_c = Numeric::from(3.0000000001);
 
-        assert_eq!(integer_a < integer_b, true);
-        assert_eq!(integer_a > float_a, false);
-        assert_eq!(float_a <= float_b, true);
-        assert_eq!(float_b >= float_a, true);
-        assert_eq!(float_c > float_a, true)
+        assert!(integer_a < integer_b);
+        assert!(integer_a <= float_a);
+        assert!(float_a <= float_b);
+        assert!(float_b >= float_a);
+        assert!(float_c > float_a)
     }
 
     #[test]
     fn test_eq() {
-        let integer_a = Numeric::from(1 as usize);
-        let integer_b = Numeric::from(2 as usize);
-        let integer_c = Numeric::from(3 as usize);
+        let integer_a = Numeric::from(1_usize);
+        let integer_b = Numeric::from(2_usize);
+        let integer_c = Numeric::from(3_usize);
         let float_a = Numeric::from(3.0);
         let float_b = Numeric::from(4.0);
         let float_c = Numeric::from(3.0000000001);
 
-        assert_eq!(integer_a != integer_b, true);
-        assert_eq!(integer_b == integer_b, true);
-        assert_eq!(integer_c == float_a, true);
-        assert_eq!(integer_c != float_b, true);
-        assert_eq!(float_a != float_c, true);
+        assert!(integer_a != integer_b);
+        assert!(integer_b == integer_b);
+        assert!(integer_c == float_a);
+        assert!(integer_c != float_b);
+        assert!(float_a != float_c);
     }
 
     #[test]
@@ -618,8 +626,8 @@ mod tests {
         let epsilon_b = 0.001;
         let epsilon_c = 0.0001;
 
-        assert_eq!(Numeric::float_eq(a, b, epsilon_a), true);
-        assert_eq!(Numeric::float_eq(a, b, epsilon_b), true);
-        assert_eq!(Numeric::float_eq(a, b, epsilon_c), false);
+        assert!(Numeric::float_eq(a, b, epsilon_a));
+        assert!(Numeric::float_eq(a, b, epsilon_b));
+        assert!(!Numeric::float_eq(a, b, epsilon_c));
     }
 }
@@ -0,0 +1,224 @@
+//! PAXEL's standard library: the pure helpers that expressions may call as methods, like the `to_uppercase` in
+//! `self.name.to_uppercase()` or the `filter` in `for item in self.items.filter(|x| x.visible)`.
+//! Compiled expressions call these functions directly, passing the receiver as the first argument.
+
+use crate::{Numeric, StringBox};
+
+/// A value that PAXEL treats as a string
+pub trait PaxelStr {
+    fn as_paxel_str(&self) -> &str;
+}
+
+impl PaxelStr for String {
+    fn as_paxel_str(&self) -> &str {
+        self
+    }
+}
+
+impl PaxelStr for StringBox {
+    fn as_paxel_str(&self) -> &str {
+        &self.string
+    }
+}
+
+/// A value with a length: a string, measured in characters, or a collection
+pub trait PaxelLen {
+    fn paxel_len(&self) -> usize;
+}
+
+impl PaxelLen for String {
+    fn paxel_len(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl PaxelLen for StringBox {
+    fn paxel_len(&self) -> usize {
+        self.string.chars().count()
+    }
+}
+
+impl<T> PaxelLen for Vec<T> {
+    fn paxel_len(&self) -> usize {
+        self.len()
+    }
+}
+
+// Strings and collections
+
+pub fn len(value: &impl PaxelLen) -> Numeric {
+    Numeric::from(value.paxel_len())
+}
+
+pub fn is_empty(value: &impl PaxelLen) -> bool {
+    value.paxel_len() == 0
+}
+
+// Strings
+
+pub fn to_uppercase(string: &impl PaxelStr) -> StringBox {
+    StringBox::from(string.as_paxel_str().to_uppercase())
+}
+
+pub fn to_lowercase(string: &impl PaxelStr) -> StringBox {
+    StringBox::from(string.as_paxel_str().to_lowercase())
+}
+
+pub fn trim(string: &impl PaxelStr) -> StringBox {
+    StringBox::from(string.as_paxel_str().trim())
+}
+
+pub fn contains(string: &impl PaxelStr, pattern: &impl PaxelStr) -> bool {
+    string.as_paxel_str().contains(pattern.as_paxel_str())
+}
+
+pub fn starts_with(string: &impl PaxelStr, pattern: &impl PaxelStr) -> bool {
+    string.as_paxel_str().starts_with(pattern.as_paxel_str())
+}
+
+pub fn ends_with(string: &impl PaxelStr, pattern: &impl PaxelStr) -> bool {
+    string.as_paxel_str().ends_with(pattern.as_paxel_str())
+}
+
+pub fn replace(string: &impl PaxelStr, from: &impl PaxelStr, to: &impl PaxelStr) -> StringBox {
+    StringBox::from(
+        string
+            .as_paxel_str()
+            .replace(from.as_paxel_str(), to.as_paxel_str()),
+    )
+}
+
+// Math
+
+pub fn abs(n: &Numeric) -> Numeric {
+    match *n {
+        Numeric::Integer(i) => Numeric::Integer(i.abs()),
+        Numeric::Float(f) => Numeric::Float(f.abs()),
+    }
+}
+
+pub fn floor(n: &Numeric) -> Numeric {
+    match *n {
+        Numeric::Integer(i) => Numeric::Integer(i),
+        Numeric::Float(f) => Numeric::Float(f.floor()),
+    }
+}
+
+pub fn ceil(n: &Numeric) -> Numeric {
+    match *n {
+        Numeric::Integer(i) => Numeric::Integer(i),
+        Numeric::Float(f) => Numeric::Float(f.ceil()),
+    }
+}
+
+pub fn round(n: &Numeric) -> Numeric {
+    match *n {
+        Numeric::Integer(i) => Numeric::Integer(i),
+        Numeric::Float(f) => Numeric::Float(f.round()),
+    }
+}
+
+pub fn sqrt(n: &Numeric) -> Numeric {
+    Numeric::Float(n.get_as_float().sqrt())
+}
+
+pub fn pow(n: &Numeric, exp: &Numeric) -> Numeric {
+    Numeric::pow(*n, *exp)
+}
+
+pub fn min(a: &Numeric, b: &Numeric) -> Numeric {
+    if b < a {
+        *b
+    } else {
+        *a
+    }
+}
+
+pub fn max(a: &Numeric, b: &Numeric) -> Numeric {
+    if b > a {
+        *b
+    } else {
+        *a
+    }
+}
+
+pub fn clamp(n: &Numeric, lower: &Numeric, upper: &Numeric) -> Numeric {
+    min(&max(n, lower), upper)
+}
+
+// Collections
+
+pub fn filter<T: Clone>(items: &[T], predicate: impl Fn(&T) -> bool) -> Vec<T> {
+    items
+        .iter()
+        .filter(|item| predicate(item))
+        .cloned()
+        .collect()
+}
+
+pub fn any<T>(items: &[T], predicate: impl Fn(&T) -> bool) -> bool {
+    items.iter().any(predicate)
+}
+
+pub fn all<T>(items: &[T], predicate: impl Fn(&T) -> bool) -> bool {
+    items.iter().all(predicate)
+}
+
+pub fn count<T>(items: &[T], predicate: impl Fn(&T) -> bool) -> Numeric {
+    Numeric::from(items.iter().filter(|item| predicate(item)).count())
+}
+
+pub fn rev<T: Clone>(items: &[T]) -> Vec<T> {
+    items.iter().rev().cloned().collect()
+}
+
+pub fn take<T: Clone>(items: &[T], n: &Numeric) -> Vec<T> {
+    items
+        .iter()
+        .take(n.get_as_int().max(0) as usize)
+        .cloned()
+        .collect()
+}
+
+pub fn skip<T: Clone>(items: &[T], n: &Numeric) -> Vec<T> {
+    items
+        .iter()
+        .skip(n.get_as_int().max(0) as usize)
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::paxel_std;
+    use crate::{Numeric, StringBox};
+
+    #[test]
+    fn test_paxel_std() {
+        let name = StringBox::from(" Pax ");
+        assert_eq!(paxel_std::len(&name), Numeric::from(5));
+        assert_eq!(
+            paxel_std::to_uppercase(&paxel_std::trim(&name)).string,
+            "PAX"
+        );
+        assert!(paxel_std::contains(&name, &"ax".to_string()));
+
+        assert_eq!(paxel_std::abs(&Numeric::from(-3)), Numeric::from(3));
+        assert_eq!(
+            paxel_std::clamp(&Numeric::from(12.5), &Numeric::from(0), &Numeric::from(10)),
+            Numeric::from(10)
+        );
+
+        let items = vec![1, 2, 3, 4, 5];
+        assert_eq!(paxel_std::filter(&items, |x| x % 2 == 1), vec![1, 3, 5]);
+        assert_eq!(paxel_std::count(&items, |x| *x > 3), Numeric::from(2));
+        assert_eq!(
+            paxel_std::take(&paxel_std::rev(&items), &Numeric::from(2)),
+            vec![5, 4]
+        );
+        assert!(paxel_std::is_empty(&paxel_std::skip(
+            &items,
+            &Numeric::from(9)
+        )));
+    }
+}
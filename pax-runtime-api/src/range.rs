@@ -0,0 +1,128 @@
+use std::ops::{Range, RangeInclusive};
+
+/// RepeatRange is the runtime representation of a range literal used as a `for` source,
+/// e.g. `0..n`, `0..=n`, `10..0` or `0..100 step 5`.
+/// Direction is inferred from the bounds: when `end` is less than `start`, the range counts down.
+/// Elements are computed on demand, so arbitrarily large ranges can be iterated without
+/// materializing their elements.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepeatRange {
+    pub start: isize,
+    pub end: isize,
+    pub inclusive: bool,
+    /// Magnitude of the distance between consecutive elements; the sign is implied by direction.
+    pub step: usize,
+}
+
+impl Default for RepeatRange {
+    fn default() -> Self {
+        Self::new(0, 0, false, None)
+    }
+}
+
+impl RepeatRange {
+    /// Builds a range from `start` to `end`.  The sign of `step` is ignored; a step of zero
+    /// yields an empty range.
+    pub fn new(start: isize, end: isize, inclusive: bool, step: Option<isize>) -> Self {
+        RepeatRange {
+            start,
+            end,
+            inclusive,
+            step: step.map(|s| s.unsigned_abs()).unwrap_or(1),
+        }
+    }
+
+    pub fn is_descending(&self) -> bool {
+        self.end < self.start
+    }
+
+    pub fn len(&self) -> usize {
+        if self.step == 0 {
+            return 0;
+        }
+        let span = self.start.abs_diff(self.end);
+        if self.inclusive {
+            span / self.step + 1
+        } else if span == 0 {
+            0
+        } else {
+            (span - 1) / self.step + 1
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the `i`th element of this range, if any.
+    pub fn get(&self, i: usize) -> Option<isize> {
+        if i >= self.len() {
+            return None;
+        }
+        let offset = (i * self.step) as isize;
+        Some(if self.is_descending() {
+            self.start - offset
+        } else {
+            self.start + offset
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = isize> + '_ {
+        (0..self.len()).map(move |i| self.get(i).unwrap())
+    }
+}
+
+impl From<Range<isize>> for RepeatRange {
+    fn from(value: Range<isize>) -> Self {
+        RepeatRange::new(value.start, value.end, false, None)
+    }
+}
+
+impl From<RangeInclusive<isize>> for RepeatRange {
+    fn from(value: RangeInclusive<isize>) -> Self {
+        RepeatRange::new(*value.start(), *value.end(), true, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::range::RepeatRange;
+
+    #[test]
+    fn test_repeat_range_iteration() {
+        let collect = |r: RepeatRange| r.iter().collect::<Vec<isize>>();
+
+        assert_eq!(collect((0..4).into()), vec![0, 1, 2, 3]);
+        assert_eq!(collect((0..=4).into()), vec![0, 1, 2, 3, 4]);
+        assert_eq!(
+            collect(RepeatRange::new(4, 0, false, None)),
+            vec![4, 3, 2, 1]
+        );
+        assert_eq!(
+            collect(RepeatRange::new(4, 0, true, None)),
+            vec![4, 3, 2, 1, 0]
+        );
+        assert_eq!(collect(RepeatRange::new(0, 10, false, Some(5))), vec![0, 5]);
+        assert_eq!(
+            collect(RepeatRange::new(0, 10, true, Some(5))),
+            vec![0, 5, 10]
+        );
+        assert_eq!(
+            collect(RepeatRange::new(10, 0, false, Some(-3))),
+            vec![10, 7, 4, 1]
+        );
+        assert_eq!(
+            collect(RepeatRange::new(0, 10, false, Some(0))),
+            Vec::<isize>::new()
+        );
+        assert_eq!(
+            collect(RepeatRange::new(3, 3, false, None)),
+            Vec::<isize>::new()
+        );
+        assert_eq!(collect(RepeatRange::new(3, 3, true, None)), vec![3]);
+
+        let huge = RepeatRange::new(0, isize::MAX, false, Some(2));
+        assert_eq!(huge.len(), (isize::MAX as usize - 1) / 2 + 1);
+        assert_eq!(huge.get(10), Some(20));
+    }
+}
@@ -0,0 +1,226 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// Reactivity is the engine's centralized dirty-checker: every `PropertyInstance` is assigned a
+/// `PropertyId`, reports its reads via `notify_read` and its writes via `notify_changed`.
+/// While an expression is evaluated inside `track_dependencies`, the properties it reads are
+/// recorded, so that the engine can skip re-evaluating that expression until one of them changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PropertyId(u64);
+
+/// The set of properties read during a single evaluation, along with the revision at which
+/// that evaluation started.
+#[derive(Clone, Debug, Default)]
+pub struct Dependencies {
+    property_ids: Vec<PropertyId>,
+    /// Set when the evaluation read a value that is not backed by a `PropertyInstance`
+    /// (e.g. a `RepeatItem`'s `elem` or `i`), in which case it must always be re-evaluated
+    is_volatile: bool,
+    revision: u64,
+}
+
+impl Dependencies {
+    /// Whether any dependency has changed since the evaluation that produced these `Dependencies`
+    pub fn have_changed(&self) -> bool {
+        self.is_volatile || self.have_tracked_dependencies_changed()
+    }
+
+    /// Like `have_changed`, but disregarding volatility: only considers the dependencies that could be tracked
+    pub fn have_tracked_dependencies_changed(&self) -> bool {
+        CHANGED_AT.with(|changed_at| {
+            let changed_at = changed_at.borrow();
+            self.property_ids.iter().any(|id| {
+                changed_at
+                    .get(id)
+                    .map_or(false, |revision| *revision > self.revision)
+            })
+        })
+    }
+
+    /// Disregard the changes made up to now, e.g. those made over the course of the evaluation that read these
+    /// dependencies, when each is known to have preceded the reads of the property it changed
+    pub fn disregard_changes_until_now(&mut self) {
+        self.revision = current_revision();
+    }
+
+    pub fn is_volatile(&self) -> bool {
+        self.is_volatile
+    }
+
+    pub fn get_property_ids(&self) -> &Vec<PropertyId> {
+        &self.property_ids
+    }
+}
+
+thread_local! {
+    static NEXT_PROPERTY_ID: Cell<u64> = Cell::new(0);
+    /// Monotonically increasing counter, bumped on every change to any property
+    static REVISION: Cell<u64> = Cell::new(0);
+    /// The revision at which each property last changed.  Properties that have never changed are absent.
+    static CHANGED_AT: RefCell<HashMap<PropertyId, u64>> = RefCell::new(HashMap::new());
+    /// One entry per nested `track_dependencies` call
+    static TRACKING_STACK: RefCell<Vec<Dependencies>> = RefCell::new(Vec::new());
+    /// See `get_expression_property_id`
+    static EXPRESSION_PROPERTY_IDS: RefCell<HashMap<usize, PropertyId>> = RefCell::new(HashMap::new());
+    /// See `notify_volatile_changed`
+    static VOLATILE_PROPERTY_ID: PropertyId = mint_property_id();
+}
+
+pub fn mint_property_id() -> PropertyId {
+    NEXT_PROPERTY_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        PropertyId(id)
+    })
+}
+
+/// The current revision, which increases with every change to any property
+pub fn current_revision() -> u64 {
+    REVISION.with(|revision| revision.get())
+}
+
+/// Whether `id` has changed since `revision`, as returned by `current_revision`
+pub fn has_changed_since(id: PropertyId, revision: u64) -> bool {
+    CHANGED_AT.with(|changed_at| {
+        changed_at
+            .borrow()
+            .get(&id)
+            .map_or(false, |changed_at| *changed_at > revision)
+    })
+}
+
+/// Record a read of `id` against every evaluation currently being tracked
+pub fn notify_read(id: PropertyId) {
+    TRACKING_STACK.with(|stack| {
+        stack
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|dependencies| dependencies.property_ids.push(id));
+    });
+}
+
+/// Record reads of each of `dependencies`, as read by a previous evaluation, against every evaluation
+/// currently being tracked, e.g. when that evaluation's result is reused from a cache within one being tracked
+pub fn notify_read_all(dependencies: &Dependencies) {
+    if dependencies.is_volatile {
+        notify_volatile_read();
+    }
+    dependencies
+        .property_ids
+        .iter()
+        .for_each(|id| notify_read(*id));
+}
+
+/// Record a read of a value that cannot be tracked, marking the evaluations currently being
+/// tracked as volatile
+pub fn notify_volatile_read() {
+    VOLATILE_PROPERTY_ID.with(|id| notify_read(*id));
+    TRACKING_STACK.with(|stack| {
+        stack
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|dependencies| dependencies.is_volatile = true);
+    });
+}
+
+/// Record that the value of `id` has changed, dirtying every evaluation that depends on it
+pub fn notify_changed(id: PropertyId) {
+    let revision = REVISION.with(|revision| {
+        revision.set(revision.get() + 1);
+        revision.get()
+    });
+    CHANGED_AT.with(|changed_at| {
+        changed_at.borrow_mut().insert(id, revision);
+    });
+}
+
+/// Record that values read through `notify_volatile_read` may have changed, e.g. that a `RepeatItem` was updated
+/// in place.  Volatile evaluations are re-evaluated regardless, but this dirties the tracked dependencies of
+/// every evaluation that contains one, e.g. those of a subtree traversal
+pub fn notify_volatile_changed() {
+    VOLATILE_PROPERTY_ID.with(|id| notify_changed(*id));
+}
+
+/// The `PropertyId` standing in for the expression with the given `vtable_id` itself.  The engine records a read
+/// of this id whenever it evaluates the expression, so that `invalidate_expression` dirties every evaluation of it.
+pub fn get_expression_property_id(vtable_id: usize) -> PropertyId {
+    EXPRESSION_PROPERTY_IDS.with(|ids| {
+        *ids.borrow_mut()
+            .entry(vtable_id)
+            .or_insert_with(mint_property_id)
+    })
+}
+
+/// Force the expression with the given `vtable_id` to be re-evaluated on the next tick, even if none of the
+/// properties it reads have changed
+pub fn invalidate_expression(vtable_id: usize) {
+    notify_changed(get_expression_property_id(vtable_id));
+}
+
+/// Release the bookkeeping for a property that has been dropped
+pub fn forget_property(id: PropertyId) {
+    //`try_with`, because properties may be dropped while thread-locals are being torn down
+    let _ = CHANGED_AT.try_with(|changed_at| {
+        if let Ok(mut changed_at) = changed_at.try_borrow_mut() {
+            changed_at.remove(&id);
+        }
+    });
+}
+
+/// Evaluate `f`, returning its result along with the `Dependencies` read during its evaluation
+pub fn track_dependencies<T>(f: impl FnOnce() -> T) -> (T, Dependencies) {
+    let revision = current_revision();
+    TRACKING_STACK.with(|stack| {
+        stack.borrow_mut().push(Dependencies {
+            property_ids: vec![],
+            is_volatile: false,
+            revision,
+        })
+    });
+    let ret = f();
+    let mut dependencies = TRACKING_STACK
+        .with(|stack| stack.borrow_mut().pop())
+        .unwrap();
+    dependencies.property_ids.sort();
+    dependencies.property_ids.dedup();
+    (ret, dependencies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dependencies_dirtied_only_by_their_properties() {
+        let (a, b) = (mint_property_id(), mint_property_id());
+        let (_, dependencies) = track_dependencies(|| {
+            notify_read(a);
+            notify_read(a);
+        });
+        assert_eq!(dependencies.get_property_ids(), &vec![a]);
+        assert!(!dependencies.have_changed());
+
+        notify_changed(b);
+        assert!(!dependencies.have_changed());
+        notify_changed(a);
+        assert!(dependencies.have_changed());
+
+        let (_, dependencies) = track_dependencies(notify_volatile_read);
+        assert!(dependencies.have_changed());
+        assert!(!dependencies.have_tracked_dependencies_changed());
+        notify_volatile_changed();
+        assert!(dependencies.have_tracked_dependencies_changed());
+    }
+
+    #[test]
+    fn test_enclosing_evaluations_depend_on_reused_dependencies() {
+        let a = mint_property_id();
+        let (_, inner) = track_dependencies(|| notify_read(a));
+        let (_, outer) = track_dependencies(|| notify_read_all(&inner));
+        assert_eq!(outer.get_property_ids(), &vec![a]);
+        assert!(!outer.have_changed());
+
+        notify_changed(a);
+        assert!(outer.have_changed());
+    }
+}
@@ -0,0 +1,235 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
+use pax_core::{
+    apply_timeline_value, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+};
+use pax_message::{AnyCreatePatch, ButtonPatch};
+use pax_runtime_api::{CommonProperties, Layer, StringBox};
+use pax_std::forms::Button;
+use piet::RenderContext;
+
+/// A push button, rendered natively by each chassis.  Activating the native button raises a
+/// `FormButtonClick` interrupt, which is dispatched to this node's `button_click` handlers.
+pub struct ButtonInstance<R: 'static + RenderContext> {
+    pub handler_registry: Option<Rc<RefCell<HandlerRegistry<R>>>>,
+    pub instance_id: u32,
+    pub properties: Rc<RefCell<Button>>,
+    pub common_properties: CommonProperties,
+    //Cache of last-sent values per id_chain, for dirty-checking native patches; see `TextInstance`
+    last_patches: HashMap<Vec<u32>, ButtonPatch>,
+}
+
+impl<R: 'static + RenderContext> RenderNode<R> for ButtonInstance<R> {
+    fn get_common_properties(&self) -> &CommonProperties {
+        &self.common_properties
+    }
+
+    fn get_instance_id(&self) -> u32 {
+        self.instance_id
+    }
+
+    fn instantiate(args: InstantiationArgs<R>) -> Rc<RefCell<Self>>
+    where
+        Self: Sized,
+    {
+        let properties = unsafe_unwrap!(args.properties, PropertiesCoproduct, Button);
+
+        let mut instance_registry = (*args.instance_registry).borrow_mut();
+        let instance_id = instance_registry.mint_id();
+        let ret = Rc::new(RefCell::new(ButtonInstance {
+            instance_id,
+            properties: Rc::new(RefCell::new(properties)),
+            common_properties: args.common_properties,
+            handler_registry: args.handler_registry,
+            last_patches: Default::default(),
+        }));
+
+        instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
+        ret
+    }
+
+    fn get_rendering_children(&self) -> RenderNodePtrList<R> {
+        Rc::new(RefCell::new(vec![]))
+    }
+
+    fn get_handler_registry(&self) -> Option<Rc<RefCell<HandlerRegistry<R>>>> {
+        match &self.handler_registry {
+            Some(registry) => Some(Rc::clone(registry)),
+            _ => None,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        //Form controls are focusable unless disabled, or unless told otherwise with `focusable`
+        let focusable = self
+            .common_properties
+            .focusable
+            .as_ref()
+            .map_or(true, |focusable| *focusable.borrow().get());
+        focusable && !*self.properties.borrow().disabled.get()
+    }
+
+    fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
+        let properties = &mut *self.properties.as_ref().borrow_mut();
+
+        apply_timeline_value(&mut properties.label);
+        apply_timeline_value(&mut properties.disabled);
+
+        if let Some(label) = rtc.compute_vtable_value(properties.label._get_vtable_id()) {
+            let new_value = unsafe_unwrap!(label, TypesCoproduct, StringBox);
+            properties.label.set(new_value);
+        }
+
+        if let Some(disabled) = rtc.compute_vtable_value(properties.disabled._get_vtable_id()) {
+            let new_value = unsafe_unwrap!(disabled, TypesCoproduct, bool);
+            properties.disabled.set(new_value);
+        }
+
+        self.common_properties.compute_properties(rtc);
+    }
+
+    fn compute_native_patches(
+        &mut self,
+        rtc: &mut RenderTreeContext<R>,
+        computed_size: (f64, f64),
+        transform_coeffs: Vec<f64>,
+        _z_index: u32,
+        _subtree_depth: u32,
+    ) {
+        let mut new_message: ButtonPatch = Default::default();
+        new_message.id_chain = rtc.get_id_chain(self.instance_id);
+        let last_patch = self
+            .last_patches
+            .entry(new_message.id_chain.clone())
+            .or_insert_with(|| ButtonPatch {
+                id_chain: new_message.id_chain.clone(),
+                ..Default::default()
+            });
+        let mut has_any_updates = false;
+
+        let properties = &mut *self.properties.as_ref().borrow_mut();
+
+        let val = properties.label.get().string.clone();
+        if last_patch.label.as_ref() != Some(&val) {
+            new_message.label = Some(val.clone());
+            last_patch.label = Some(val);
+            has_any_updates = true;
+        }
+
+        let val = *properties.disabled.get();
+        if last_patch.disabled != Some(val) {
+            new_message.disabled = Some(val);
+            last_patch.disabled = Some(val);
+            has_any_updates = true;
+        }
+
+        let val = computed_size.0;
+        if last_patch.size_x != Some(val) {
+            new_message.size_x = Some(val);
+            last_patch.size_x = Some(val);
+            has_any_updates = true;
+        }
+
+        let val = computed_size.1;
+        if last_patch.size_y != Some(val) {
+            new_message.size_y = Some(val);
+            last_patch.size_y = Some(val);
+            has_any_updates = true;
+        }
+
+        if last_patch.transform.as_ref() != Some(&transform_coeffs) {
+            new_message.transform = Some(transform_coeffs.clone());
+            last_patch.transform = Some(transform_coeffs);
+            has_any_updates = true;
+        }
+
+        if has_any_updates {
+            (*rtc.engine.runtime)
+                .borrow_mut()
+                .enqueue_native_message(pax_message::NativeMessage::ButtonUpdate(new_message));
+        }
+    }
+
+    fn handle_render(&mut self, _rtc: &mut RenderTreeContext<R>, _rc: &mut R) {
+        //no-op -- only native rendering for form controls
+    }
+
+    fn handle_did_mount(&mut self, rtc: &mut RenderTreeContext<R>, z_index: u32) {
+        let id_chain = rtc.get_id_chain(self.instance_id);
+        let clipping_ids = (*rtc.runtime).borrow().get_current_clipping_ids();
+        let scroller_ids = (*rtc.runtime).borrow().get_current_scroller_ids();
+
+        (*rtc.engine.runtime).borrow_mut().enqueue_native_message(
+            pax_message::NativeMessage::ButtonCreate(AnyCreatePatch {
+                id_chain,
+                clipping_ids,
+                scroller_ids,
+                z_index,
+            }),
+        );
+    }
+
+    fn handle_will_unmount(&mut self, rtc: &mut RenderTreeContext<R>) {
+        let id_chain = rtc.get_id_chain(self.instance_id);
+        self.last_patches.remove(&id_chain);
+        (*rtc.engine.runtime)
+            .borrow_mut()
+            .enqueue_native_message(pax_message::NativeMessage::ButtonDelete(id_chain));
+    }
+
+    fn get_layer_type(&mut self) -> Layer {
+        Layer::Native
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{build_engine, clear_template, tick, R};
+    use pax_message::NativeMessage;
+
+    #[test]
+    fn test_button_patches_follow_its_mount_changes_and_unmount() {
+        let properties: Rc<RefCell<Button>> = Default::default();
+        properties.borrow_mut().label.set(StringBox::from("Save"));
+        let engine = &mut build_engine(|instance_registry| {
+            Rc::new(RefCell::new(ButtonInstance::<R> {
+                handler_registry: None,
+                instance_id: instance_registry.mint_id(),
+                properties: Rc::clone(&properties),
+                common_properties: Default::default(),
+                last_patches: Default::default(),
+            }))
+        });
+
+        let messages = tick(engine);
+        let id_chain = match &messages[..] {
+            [NativeMessage::ButtonCreate(create), NativeMessage::ButtonUpdate(patch)] => {
+                assert_eq!(patch.id_chain, create.id_chain);
+                assert_eq!(patch.label, Some("Save".to_string()));
+                assert_eq!(patch.disabled, Some(false));
+                create.id_chain.clone()
+            }
+            _ => panic!("expected a create and an update"),
+        };
+
+        properties.borrow_mut().label.set(StringBox::from("Saved"));
+        match &tick(engine)[..] {
+            [NativeMessage::ButtonUpdate(patch)] => {
+                assert_eq!(patch.label, Some("Saved".to_string()));
+                assert_eq!(patch.disabled, None);
+            }
+            _ => panic!("expected an update"),
+        }
+
+        clear_template(engine);
+        match &tick(engine)[..] {
+            [NativeMessage::ButtonDelete(deleted)] => assert_eq!(deleted, &id_chain),
+            _ => panic!("expected a delete"),
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use kurbo::{Point, Shape};
+use piet::RenderContext;
+
+use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
+use pax_core::{
+    apply_timeline_value, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext, TransformAndBounds,
+};
+use pax_runtime_api::{CommonProperties, Layer, Size};
+use pax_std::primitives::Clip;
+use pax_std::types::ClipShape;
+
+/// A primitive that clips its children to an arbitrary [`ClipShape`] — a (rounded) rectangle, an ellipse
+/// or a path — sized to its bounds.  Content outside of the shape is neither rendered nor hit by ray casting.
+///
+/// Unlike [`Frame`], a [`Clip`] masks only canvas content; native elements (e.g. `Text`) are not clipped.
+pub struct ClipInstance<R: 'static + RenderContext> {
+    pub instance_id: u32,
+    pub children: RenderNodePtrList<R>,
+    pub handler_registry: Option<Rc<RefCell<HandlerRegistry<R>>>>,
+    pub properties: Rc<RefCell<Clip>>,
+    pub common_properties: CommonProperties,
+}
+
+impl<R: 'static + RenderContext> RenderNode<R> for ClipInstance<R> {
+    fn get_handler_registry(&self) -> Option<Rc<RefCell<HandlerRegistry<R>>>> {
+        match &self.handler_registry {
+            Some(registry) => Some(Rc::clone(&registry)),
+            _ => None,
+        }
+    }
+
+    fn get_common_properties(&self) -> &CommonProperties {
+        &self.common_properties
+    }
+    fn get_instance_id(&self) -> u32 {
+        self.instance_id
+    }
+
+    fn instantiate(args: InstantiationArgs<R>) -> Rc<RefCell<Self>>
+    where
+        Self: Sized,
+    {
+        let properties = unsafe_unwrap!(args.properties, PropertiesCoproduct, Clip);
+        let mut instance_registry = args.instance_registry.borrow_mut();
+        let instance_id = instance_registry.mint_id();
+        let ret = Rc::new(RefCell::new(Self {
+            instance_id,
+            children: args.children.unwrap(), //Clip expects primitive_children, even if empty Vec
+            handler_registry: args.handler_registry,
+            properties: Rc::new(RefCell::new(properties)),
+            common_properties: args.common_properties,
+        }));
+
+        instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
+        ret
+    }
+
+    fn ray_cast_test(&self, ray: &(f64, f64), tab: &TransformAndBounds) -> bool {
+        let inverted_transform = tab.transform.inverse();
+        let transformed_ray = inverted_transform * Point { x: ray.0, y: ray.1 };
+
+        let properties = (*self.properties).borrow();
+        properties
+            .shape
+            .get()
+            .to_bez_path(tab.bounds)
+            .contains(transformed_ray)
+    }
+
+    fn get_clipping_bounds(&self) -> Option<(Size, Size)> {
+        self.get_size()
+    }
+
+    fn get_layer_type(&mut self) -> Layer {
+        Layer::DontCare
+    }
+
+    fn get_rendering_children(&self) -> RenderNodePtrList<R> {
+        Rc::clone(&self.children)
+    }
+
+    fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
+        let properties = &mut *self.properties.as_ref().borrow_mut();
+
+        apply_timeline_value(&mut properties.shape);
+
+        if let Some(shape) = rtc.compute_vtable_value(properties.shape._get_vtable_id()) {
+            let new_value = unsafe_unwrap!(shape, TypesCoproduct, ClipShape);
+            properties.shape.set(new_value);
+        }
+
+        self.common_properties.compute_properties(rtc);
+    }
+
+    fn handle_will_render(
+        &mut self,
+        rtc: &mut RenderTreeContext<R>,
+        rcs: &mut HashMap<std::string::String, R>,
+    ) {
+        let transform = rtc.transform_scroller_reset;
+        let bez_path = (*self.properties)
+            .borrow()
+            .shape
+            .get()
+            .to_bez_path(rtc.bounds);
+
+        let transformed_bez_path = transform * bez_path;
+        for (_key, rc) in rcs.iter_mut() {
+            rc.save().unwrap(); //our "save point" before clipping — restored to in the did_render
+            rc.clip(transformed_bez_path.clone());
+        }
+    }
+
+    fn handle_did_render(&mut self, _rtc: &mut RenderTreeContext<R>, rcs: &mut HashMap<String, R>) {
+        for (_key, rc) in rcs.iter_mut() {
+            //pop the clipping context from the stack
+            rc.restore().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::R;
+    use kurbo::Affine;
+
+    fn build_clip(shape: ClipShape) -> ClipInstance<R> {
+        let mut properties = Clip::default();
+        properties.shape.set(shape);
+        ClipInstance {
+            instance_id: 0,
+            children: Rc::new(RefCell::new(vec![])),
+            handler_registry: None,
+            properties: Rc::new(RefCell::new(properties)),
+            common_properties: Default::default(),
+        }
+    }
+
+    fn tab_at(x: f64, y: f64, bounds: (f64, f64)) -> TransformAndBounds {
+        TransformAndBounds {
+            transform: Affine::translate((x, y)),
+            bounds,
+            clipping_bounds: None,
+        }
+    }
+
+    #[test]
+    fn test_rounded_rectangle_clips_are_hit_within_their_corners() {
+        let clip = build_clip(ClipShape::rectangle(
+            20.into(),
+            20.into(),
+            20.into(),
+            20.into(),
+        ));
+        let tab = tab_at(10.0, 10.0, (100.0, 100.0));
+
+        assert!(clip.ray_cast_test(&(60.0, 60.0), &tab));
+        //along an edge, clear of the corners
+        assert!(clip.ray_cast_test(&(12.0, 60.0), &tab));
+        //within the bounds, but outside a rounded corner
+        assert!(!clip.ray_cast_test(&(12.0, 12.0), &tab));
+        assert!(!clip.ray_cast_test(&(108.0, 108.0), &tab));
+        //outside the bounds
+        assert!(!clip.ray_cast_test(&(5.0, 60.0), &tab));
+        assert!(!clip.ray_cast_test(&(60.0, 115.0), &tab));
+    }
+
+    #[test]
+    fn test_ellipse_clips_are_hit_only_within_the_ellipse() {
+        let clip = build_clip(ClipShape::ellipse());
+        let tab = tab_at(10.0, 10.0, (100.0, 50.0));
+
+        assert!(clip.ray_cast_test(&(60.0, 35.0), &tab));
+        assert!(clip.ray_cast_test(&(105.0, 35.0), &tab));
+        assert!(clip.ray_cast_test(&(60.0, 57.0), &tab));
+        //within the bounds, but outside the ellipse
+        assert!(!clip.ray_cast_test(&(15.0, 15.0), &tab));
+        assert!(!clip.ray_cast_test(&(105.0, 55.0), &tab));
+        //outside the bounds
+        assert!(!clip.ray_cast_test(&(60.0, 65.0), &tab));
+    }
+}
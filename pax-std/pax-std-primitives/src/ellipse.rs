@@ -3,11 +3,12 @@ use piet::RenderContext;
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, Color, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    apply_timeline_value, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_std::primitives::Ellipse;
-use pax_std::types::ColorVariant;
+use pax_std::types::effects::Effect;
+use pax_std::types::FillRule;
 
 use pax_runtime_api::CommonProperties;
 
@@ -65,6 +66,10 @@ impl<R: 'static + RenderContext> RenderNode<R> for EllipseInstance<R> {
 
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
+        apply_timeline_value(&mut properties.stroke);
+        apply_timeline_value(&mut properties.fill);
+        apply_timeline_value(&mut properties.effects);
+
         if let Some(stroke_width) =
             rtc.compute_vtable_value(properties.stroke.get().width._get_vtable_id())
         {
@@ -87,6 +92,11 @@ impl<R: 'static + RenderContext> RenderNode<R> for EllipseInstance<R> {
             let new_value = unsafe_unwrap!(fill, TypesCoproduct, pax_std::types::Color);
             properties.fill.set(new_value);
         }
+
+        if let Some(effects) = rtc.compute_vtable_value(properties.effects._get_vtable_id()) {
+            let new_value = unsafe_unwrap!(effects, TypesCoproduct, Vec<Effect>);
+            properties.effects.set(new_value);
+        }
     }
     fn handle_render(&mut self, rtc: &mut RenderTreeContext<R>, rc: &mut R) {
         let transform = rtc.transform_scroller_reset;
@@ -96,24 +106,27 @@ impl<R: 'static + RenderContext> RenderNode<R> for EllipseInstance<R> {
 
         let properties = (*self.properties).borrow();
 
-        let properties_color = properties.fill.get();
-        let _color = match properties_color.color_variant {
-            ColorVariant::Hlca(slice) => Color::hlca(slice[0], slice[1], slice[2], slice[3]),
-            ColorVariant::Hlc(slice) => Color::hlc(slice[0], slice[1], slice[2]),
-            ColorVariant::Rgba(slice) => Color::rgba(slice[0], slice[1], slice[2], slice[3]),
-            ColorVariant::Rgb(slice) => Color::rgb(slice[0], slice[1], slice[2]),
-        };
-
         let rect = Rect::from_points((0.0, 0.0), (width, height));
         let ellipse = KurboEllipse::from_rect(rect);
         let accuracy = 0.1;
         let bez_path = ellipse.to_path(accuracy);
 
-        let transformed_bez_path = transform * bez_path;
+        let effects = properties.effects.get();
+        Effect::render_drop_shadows(effects, rc, &bez_path, transform, &FillRule::NonZero);
+
+        let transformed_bez_path = transform * bez_path.clone();
         let duplicate_transformed_bez_path = transformed_bez_path.clone();
 
         let color = properties.fill.get().to_piet_color();
-        rc.fill(transformed_bez_path, &color);
+        Effect::fill_blurred(
+            rc,
+            &transformed_bez_path,
+            &color,
+            Effect::get_blur_radius(effects),
+            &FillRule::NonZero,
+        );
+
+        Effect::render_inner_shadows(effects, rc, &bez_path, transform);
 
         //hack to address "phantom stroke" bug on Web
         let width: f64 = *&properties.stroke.get().width.get().into();
@@ -126,6 +126,28 @@ impl<R: 'static + RenderContext> RenderNode<R> for FrameInstance<R> {
             has_any_updates = true;
         }
 
+        let val = rtc.opacity;
+        let is_new_value = match &last_patch.opacity {
+            Some(cached_value) => !val.eq(cached_value),
+            None => true,
+        };
+        if is_new_value {
+            new_message.opacity = Some(val);
+            last_patch.opacity = Some(val);
+            has_any_updates = true;
+        }
+
+        let val = rtc.blend_mode.css_name().to_string();
+        let is_new_value = match &last_patch.blend_mode {
+            Some(cached_value) => !val.eq(cached_value),
+            None => true,
+        };
+        if is_new_value {
+            new_message.blend_mode = Some(val.clone());
+            last_patch.blend_mode = Some(val);
+            has_any_updates = true;
+        }
+
         if has_any_updates {
             (*rtc.engine.runtime)
                 .borrow_mut()
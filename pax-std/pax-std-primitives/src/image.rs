@@ -4,8 +4,8 @@ use std::collections::HashMap;
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    apply_timeline_value, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_message::ImagePatch;
 use pax_runtime_api::CommonProperties;
@@ -63,6 +63,8 @@ impl<R: 'static + RenderContext> RenderNode<R> for ImageInstance<R> {
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
+        apply_timeline_value(&mut properties.path);
+
         if let Some(path) = rtc.compute_vtable_value(properties.path._get_vtable_id()) {
             let new_value = if let TypesCoproduct::String(v) = path {
                 v
@@ -1,3 +1,6 @@
+pub mod button;
+pub mod clip;
+pub mod dropdown;
 pub mod ellipse;
 pub mod frame;
 pub mod group;
@@ -5,4 +8,76 @@ pub mod image;
 pub mod path;
 pub mod rectangle;
 pub mod scroller;
+pub mod slider;
 pub mod text;
+pub mod text_box;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+    use std::sync::Mutex;
+
+    use pax_core::pax_properties_coproduct::PropertiesCoproduct;
+    use pax_core::{
+        ComponentInstance, HandlerRegistry, InstanceRegistry, InstantiationArgs, PaxEngine,
+        RenderNode, RenderNodePtr,
+    };
+    use pax_message::NativeMessage;
+    use pax_runtime_api::{CommonProperties, PlatformSpecificLogger};
+    use piet::NullRenderContext;
+
+    pub(crate) type R = NullRenderContext;
+
+    /// `register_logger` can't be raced, and tests run on parallel threads
+    static ENGINE_CONSTRUCTION: Mutex<()> = Mutex::new(());
+
+    /// Builds an engine whose main component's template is the single node built by `build_node`
+    pub(crate) fn build_engine(
+        build_node: impl FnOnce(&mut InstanceRegistry<R>) -> RenderNodePtr<R>,
+    ) -> PaxEngine<R> {
+        let instance_registry = Rc::new(RefCell::new(InstanceRegistry::new()));
+        let node = build_node(&mut instance_registry.borrow_mut());
+        let main_component = ComponentInstance::instantiate(InstantiationArgs {
+            common_properties: CommonProperties::default(),
+            properties: PropertiesCoproduct::None,
+            handler_registry: None,
+            instance_registry: Rc::clone(&instance_registry),
+            children: None,
+            component_template: Some(Rc::new(RefCell::new(vec![node]))),
+            scroller_args: None,
+            slot_index: None,
+            repeat_source_expression_vec: None,
+            repeat_source_expression_range: None,
+            repeat_key_expression: None,
+            conditional_boolean_expression: None,
+            conditional_else_children: None,
+            compute_properties_fn: Some(Box::new(|_, _| {})),
+        });
+        let _guard = ENGINE_CONSTRUCTION.lock().unwrap();
+        PaxEngine::new(
+            main_component,
+            HashMap::new(),
+            PlatformSpecificLogger::Headless(|_| {}),
+            (100.0, 100.0),
+            instance_registry,
+        )
+    }
+
+    /// Ticks with a single drawing context, returning the native messages enqueued
+    pub(crate) fn tick(engine: &mut PaxEngine<R>) -> Vec<NativeMessage> {
+        let mut rcs = HashMap::from([("0".to_string(), NullRenderContext::new())]);
+        engine.tick(&mut rcs)
+    }
+
+    /// Empties the main component's template, so that its node is unmounted on the next tick
+    pub(crate) fn clear_template(engine: &mut PaxEngine<R>) {
+        (*engine.main_component).borrow_mut().replace_template(
+            Rc::new(RefCell::new(vec![])),
+            Rc::new(RefCell::new(HandlerRegistry::default())),
+            &mut engine.instance_registry.borrow_mut(),
+        );
+        engine.invalidate();
+    }
+}
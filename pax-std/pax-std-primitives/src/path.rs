@@ -1,19 +1,19 @@
-use kurbo::BezPath;
 use piet::RenderContext;
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    apply_timeline_value, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_runtime_api::{CommonProperties, Size};
 use pax_std::primitives::Path;
-use pax_std::types::PathSegment;
+use pax_std::types::effects::Effect;
+use pax_std::types::{FillRule, PathSegment};
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
-/// A basic 2D vector path for arbitrary Bézier / line-segment chains
+/// A basic 2D vector path for arbitrary Bézier / arc / line-segment chains
 pub struct PathInstance<R: 'static + RenderContext> {
     pub handler_registry: Option<Rc<RefCell<HandlerRegistry<R>>>>,
     pub instance_id: u32,
@@ -67,6 +67,12 @@ impl<R: 'static + RenderContext> RenderNode<R> for PathInstance<R> {
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
+        apply_timeline_value(&mut properties.segments);
+        apply_timeline_value(&mut properties.stroke);
+        apply_timeline_value(&mut properties.fill);
+        apply_timeline_value(&mut properties.fill_rule);
+        apply_timeline_value(&mut properties.effects);
+
         if let Some(stroke_width) =
             rtc.compute_vtable_value(properties.stroke.get().width._get_vtable_id())
         {
@@ -87,11 +93,21 @@ impl<R: 'static + RenderContext> RenderNode<R> for PathInstance<R> {
             properties.fill.set(new_value);
         }
 
+        if let Some(fill_rule) = rtc.compute_vtable_value(properties.fill_rule._get_vtable_id()) {
+            let new_value = unsafe_unwrap!(fill_rule, TypesCoproduct, FillRule);
+            properties.fill_rule.set(new_value);
+        }
+
         if let Some(segments) = rtc.compute_vtable_value(properties.segments._get_vtable_id()) {
             let new_value = unsafe_unwrap!(segments, TypesCoproduct, Vec<PathSegment>);
             properties.segments.set(new_value);
         }
 
+        if let Some(effects) = rtc.compute_vtable_value(properties.effects._get_vtable_id()) {
+            let new_value = unsafe_unwrap!(effects, TypesCoproduct, Vec<Effect>);
+            properties.effects.set(new_value);
+        }
+
         self.common_properties.compute_properties(rtc);
     }
     fn handle_render(&mut self, rtc: &mut RenderTreeContext<R>, rc: &mut R) {
@@ -99,27 +115,25 @@ impl<R: 'static + RenderContext> RenderNode<R> for PathInstance<R> {
 
         let properties = (*self.properties).borrow();
 
-        let mut bez_path = BezPath::new();
-
-        for segment in properties.segments.get().iter() {
-            match segment {
-                PathSegment::Empty => { /* no-op */ }
-                PathSegment::LineSegment(data) => {
-                    bez_path.move_to(data.start);
-                    bez_path.line_to(data.end);
-                }
-                PathSegment::CurveSegment(data) => {
-                    bez_path.move_to(data.start);
-                    bez_path.quad_to(data.handle, data.end);
-                }
-            }
-        }
+        let bez_path = Path::to_bez_path(properties.segments.get());
+
+        let effects = properties.effects.get();
+        let fill_rule = properties.fill_rule.get();
+        Effect::render_drop_shadows(effects, rc, &bez_path, transform, fill_rule);
 
-        let transformed_bez_path = transform * bez_path;
+        let transformed_bez_path = transform * bez_path.clone();
         let duplicate_transformed_bez_path = transformed_bez_path.clone();
 
         let color = properties.fill.get().to_piet_color();
-        rc.fill(transformed_bez_path, &color);
+        Effect::fill_blurred(
+            rc,
+            &transformed_bez_path,
+            &color,
+            Effect::get_blur_radius(effects),
+            fill_rule,
+        );
+
+        Effect::render_inner_shadows(effects, rc, &bez_path, transform);
         rc.stroke(
             duplicate_transformed_bez_path,
             &properties.stroke.get().color.get().to_piet_color(),
@@ -3,11 +3,12 @@ use piet::{LinearGradient, RadialGradient, RenderContext};
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    apply_timeline_value, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_std::primitives::Rectangle;
-use pax_std::types::{Fill, RectangleCornerRadii};
+use pax_std::types::effects::Effect;
+use pax_std::types::{Fill, FillRule, RectangleCornerRadii};
 
 use pax_runtime_api::CommonProperties;
 
@@ -64,6 +65,11 @@ impl<R: 'static + RenderContext> RenderNode<R> for RectangleInstance<R> {
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
+        apply_timeline_value(&mut properties.stroke);
+        apply_timeline_value(&mut properties.fill);
+        apply_timeline_value(&mut properties.effects);
+        apply_timeline_value(&mut properties.corner_radii);
+
         if let Some(stroke_width) =
             rtc.compute_vtable_value(properties.stroke.get().width._get_vtable_id())
         {
@@ -126,6 +132,11 @@ impl<R: 'static + RenderContext> RenderNode<R> for RectangleInstance<R> {
             properties.corner_radii.set(new_value);
         }
 
+        if let Some(effects) = rtc.compute_vtable_value(properties.effects._get_vtable_id()) {
+            let new_value = unsafe_unwrap!(effects, TypesCoproduct, Vec<Effect>);
+            properties.effects.set(new_value);
+        }
+
         self.common_properties.compute_properties(rtc);
     }
 
@@ -141,12 +152,21 @@ impl<R: 'static + RenderContext> RenderNode<R> for RectangleInstance<R> {
 
         let bez_path = rect.to_path(0.1);
 
-        let transformed_bez_path = transform * bez_path;
+        let effects = properties.effects.get();
+        Effect::render_drop_shadows(effects, rc, &bez_path, transform, &FillRule::NonZero);
+
+        let transformed_bez_path = transform * bez_path.clone();
         let duplicate_transformed_bez_path = transformed_bez_path.clone();
 
         match properties.fill.get() {
             Fill::Solid(color) => {
-                rc.fill(transformed_bez_path, &color.to_piet_color());
+                Effect::fill_blurred(
+                    rc,
+                    &transformed_bez_path,
+                    &color.to_piet_color(),
+                    Effect::get_blur_radius(effects),
+                    &FillRule::NonZero,
+                );
             }
             Fill::LinearGradient(linear) => {
                 let linear_gradient = LinearGradient::new(
@@ -167,6 +187,8 @@ impl<R: 'static + RenderContext> RenderNode<R> for RectangleInstance<R> {
             }
         }
 
+        Effect::render_inner_shadows(effects, rc, &bez_path, transform);
+
         //hack to address "phantom stroke" bug on Web
         let width: f64 = *&properties.stroke.get().width.get().into();
         if width > f64::EPSILON {
@@ -9,8 +9,8 @@ use piet::RenderContext;
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    apply_timeline_value, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_message::{AnyCreatePatch, ScrollerPatch};
 use pax_runtime_api::{
@@ -259,6 +259,11 @@ impl<R: 'static + RenderContext> RenderNode<R> for ScrollerInstance<R> {
 
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
+        apply_timeline_value(&mut properties.size_inner_pane_x);
+        apply_timeline_value(&mut properties.size_inner_pane_y);
+        apply_timeline_value(&mut properties.scroll_enabled_x);
+        apply_timeline_value(&mut properties.scroll_enabled_y);
+
         if let Some(new_size) =
             rtc.compute_vtable_value(properties.size_inner_pane_x._get_vtable_id())
         {
@@ -2,8 +2,8 @@ use std::cell::RefCell;
 
 use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
 use pax_core::{
-    unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable, RenderNode,
-    RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+    apply_timeline_value, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
 };
 use pax_message::{AnyCreatePatch, TextPatch};
 use pax_runtime_api::{CommonProperties, Layer, SizePixels, StringBox};
@@ -63,6 +63,10 @@ impl<R: 'static + RenderContext> RenderNode<R> for TextInstance<R> {
     fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
         let properties = &mut *self.properties.as_ref().borrow_mut();
 
+        apply_timeline_value(&mut properties.text);
+        apply_timeline_value(&mut properties.style);
+        apply_timeline_value(&mut properties.style_link);
+
         if let Some(text) = rtc.compute_vtable_value(properties.text._get_vtable_id()) {
             let new_value = unsafe_unwrap!(text, TypesCoproduct, StringBox);
             properties.text.set(new_value);
@@ -268,10 +272,32 @@ impl<R: 'static + RenderContext> RenderNode<R> for TextInstance<R> {
             has_any_updates = true;
         }
 
+        let val = rtc.opacity;
+        let is_new_value = match &last_patch.opacity {
+            Some(cached_value) => !val.eq(cached_value),
+            None => true,
+        };
+        if is_new_value {
+            new_message.opacity = Some(val);
+            last_patch.opacity = Some(val);
+            has_any_updates = true;
+        }
+
+        let val = rtc.blend_mode.css_name().to_string();
+        let is_new_value = match &last_patch.blend_mode {
+            Some(cached_value) => !val.eq(cached_value),
+            None => true,
+        };
+        if is_new_value {
+            new_message.blend_mode = Some(val.clone());
+            last_patch.blend_mode = Some(val);
+            has_any_updates = true;
+        }
+
         if has_any_updates {
-            (*rtc.engine.runtime)
-                .borrow_mut()
-                .enqueue_native_message(pax_message::NativeMessage::TextUpdate(new_message));
+            (*rtc.engine.runtime).borrow_mut().enqueue_native_message(
+                pax_message::NativeMessage::TextUpdate(Box::new(new_message)),
+            );
         }
     }
 
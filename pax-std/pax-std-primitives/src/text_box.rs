@@ -0,0 +1,267 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use pax_core::pax_properties_coproduct::{PropertiesCoproduct, TypesCoproduct};
+use pax_core::{
+    apply_timeline_value, unsafe_unwrap, HandlerRegistry, InstantiationArgs, PropertiesComputable,
+    RenderNode, RenderNodePtr, RenderNodePtrList, RenderTreeContext,
+};
+use pax_message::{AnyCreatePatch, TextboxPatch};
+use pax_runtime_api::{ArgsTextboxChange, CommonProperties, Layer, StringBox};
+use pax_std::forms::TextBox;
+use piet::RenderContext;
+
+/// A single-line text input, rendered natively by each chassis.  Editing the native input raises
+/// `FormTextboxChange` interrupts, which are dispatched to this node's `textbox_change` handlers.
+pub struct TextBoxInstance<R: 'static + RenderContext> {
+    pub handler_registry: Option<Rc<RefCell<HandlerRegistry<R>>>>,
+    pub instance_id: u32,
+    pub properties: Rc<RefCell<TextBox>>,
+    pub common_properties: CommonProperties,
+    //Cache of last-sent values per id_chain, for dirty-checking native patches; see `TextInstance`
+    last_patches: HashMap<Vec<u32>, TextboxPatch>,
+}
+
+impl<R: 'static + RenderContext> RenderNode<R> for TextBoxInstance<R> {
+    fn get_common_properties(&self) -> &CommonProperties {
+        &self.common_properties
+    }
+
+    fn get_instance_id(&self) -> u32 {
+        self.instance_id
+    }
+
+    fn instantiate(args: InstantiationArgs<R>) -> Rc<RefCell<Self>>
+    where
+        Self: Sized,
+    {
+        let properties = unsafe_unwrap!(args.properties, PropertiesCoproduct, TextBox);
+
+        let mut instance_registry = (*args.instance_registry).borrow_mut();
+        let instance_id = instance_registry.mint_id();
+        let ret = Rc::new(RefCell::new(TextBoxInstance {
+            instance_id,
+            properties: Rc::new(RefCell::new(properties)),
+            common_properties: args.common_properties,
+            handler_registry: args.handler_registry,
+            last_patches: Default::default(),
+        }));
+
+        instance_registry.register(instance_id, Rc::clone(&ret) as RenderNodePtr<R>);
+        ret
+    }
+
+    fn get_rendering_children(&self) -> RenderNodePtrList<R> {
+        Rc::new(RefCell::new(vec![]))
+    }
+
+    fn get_handler_registry(&self) -> Option<Rc<RefCell<HandlerRegistry<R>>>> {
+        match &self.handler_registry {
+            Some(registry) => Some(Rc::clone(registry)),
+            _ => None,
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        //Form controls are focusable unless disabled, or unless told otherwise with `focusable`
+        let focusable = self
+            .common_properties
+            .focusable
+            .as_ref()
+            .map_or(true, |focusable| *focusable.borrow().get());
+        focusable && !*self.properties.borrow().disabled.get()
+    }
+
+    fn compute_properties(&mut self, rtc: &mut RenderTreeContext<R>) {
+        let properties = &mut *self.properties.as_ref().borrow_mut();
+
+        apply_timeline_value(&mut properties.text);
+        apply_timeline_value(&mut properties.placeholder);
+        apply_timeline_value(&mut properties.disabled);
+
+        if let Some(text) = rtc.compute_vtable_value(properties.text._get_vtable_id()) {
+            let new_value = unsafe_unwrap!(text, TypesCoproduct, StringBox);
+            properties.text.set(new_value);
+        }
+
+        if let Some(placeholder) = rtc.compute_vtable_value(properties.placeholder._get_vtable_id())
+        {
+            let new_value = unsafe_unwrap!(placeholder, TypesCoproduct, StringBox);
+            properties.placeholder.set(new_value);
+        }
+
+        if let Some(disabled) = rtc.compute_vtable_value(properties.disabled._get_vtable_id()) {
+            let new_value = unsafe_unwrap!(disabled, TypesCoproduct, bool);
+            properties.disabled.set(new_value);
+        }
+
+        self.common_properties.compute_properties(rtc);
+    }
+
+    fn compute_native_patches(
+        &mut self,
+        rtc: &mut RenderTreeContext<R>,
+        computed_size: (f64, f64),
+        transform_coeffs: Vec<f64>,
+        _z_index: u32,
+        _subtree_depth: u32,
+    ) {
+        let mut new_message: TextboxPatch = Default::default();
+        new_message.id_chain = rtc.get_id_chain(self.instance_id);
+        let last_patch = self
+            .last_patches
+            .entry(new_message.id_chain.clone())
+            .or_insert_with(|| TextboxPatch {
+                id_chain: new_message.id_chain.clone(),
+                ..Default::default()
+            });
+        let mut has_any_updates = false;
+
+        let properties = &mut *self.properties.as_ref().borrow_mut();
+
+        let val = properties.text.get().string.clone();
+        if last_patch.text.as_ref() != Some(&val) {
+            new_message.text = Some(val.clone());
+            last_patch.text = Some(val);
+            has_any_updates = true;
+        }
+
+        let val = properties.placeholder.get().string.clone();
+        if last_patch.placeholder.as_ref() != Some(&val) {
+            new_message.placeholder = Some(val.clone());
+            last_patch.placeholder = Some(val);
+            has_any_updates = true;
+        }
+
+        let val = *properties.disabled.get();
+        if last_patch.disabled != Some(val) {
+            new_message.disabled = Some(val);
+            last_patch.disabled = Some(val);
+            has_any_updates = true;
+        }
+
+        let val = computed_size.0;
+        if last_patch.size_x != Some(val) {
+            new_message.size_x = Some(val);
+            last_patch.size_x = Some(val);
+            has_any_updates = true;
+        }
+
+        let val = computed_size.1;
+        if last_patch.size_y != Some(val) {
+            new_message.size_y = Some(val);
+            last_patch.size_y = Some(val);
+            has_any_updates = true;
+        }
+
+        if last_patch.transform.as_ref() != Some(&transform_coeffs) {
+            new_message.transform = Some(transform_coeffs.clone());
+            last_patch.transform = Some(transform_coeffs);
+            has_any_updates = true;
+        }
+
+        if has_any_updates {
+            (*rtc.engine.runtime)
+                .borrow_mut()
+                .enqueue_native_message(pax_message::NativeMessage::TextboxUpdate(new_message));
+        }
+    }
+
+    fn handle_textbox_change(&mut self, id_chain: &[u32], args_textbox_change: &ArgsTextboxChange) {
+        //The native text box already shows the new text; see `SliderInstance::handle_slider_change`
+        if let Some(last_patch) = self.last_patches.get_mut(id_chain) {
+            last_patch.text = Some(args_textbox_change.text.clone());
+        }
+        self.properties.borrow_mut().text.set(StringBox {
+            string: args_textbox_change.text.clone(),
+        });
+    }
+
+    fn handle_render(&mut self, _rtc: &mut RenderTreeContext<R>, _rc: &mut R) {
+        //no-op -- only native rendering for form controls
+    }
+
+    fn handle_did_mount(&mut self, rtc: &mut RenderTreeContext<R>, z_index: u32) {
+        let id_chain = rtc.get_id_chain(self.instance_id);
+        let clipping_ids = (*rtc.runtime).borrow().get_current_clipping_ids();
+        let scroller_ids = (*rtc.runtime).borrow().get_current_scroller_ids();
+
+        (*rtc.engine.runtime).borrow_mut().enqueue_native_message(
+            pax_message::NativeMessage::TextboxCreate(AnyCreatePatch {
+                id_chain,
+                clipping_ids,
+                scroller_ids,
+                z_index,
+            }),
+        );
+    }
+
+    fn handle_will_unmount(&mut self, rtc: &mut RenderTreeContext<R>) {
+        let id_chain = rtc.get_id_chain(self.instance_id);
+        self.last_patches.remove(&id_chain);
+        (*rtc.engine.runtime)
+            .borrow_mut()
+            .enqueue_native_message(pax_message::NativeMessage::TextboxDelete(id_chain));
+    }
+
+    fn get_layer_type(&mut self) -> Layer {
+        Layer::Native
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{build_engine, clear_template, tick, R};
+    use pax_message::NativeMessage;
+
+    #[test]
+    fn test_text_box_patches_follow_its_mount_changes_and_unmount() {
+        let properties: Rc<RefCell<TextBox>> = Default::default();
+        let engine = &mut build_engine(|instance_registry| {
+            Rc::new(RefCell::new(TextBoxInstance::<R> {
+                handler_registry: None,
+                instance_id: instance_registry.mint_id(),
+                properties: Rc::clone(&properties),
+                common_properties: Default::default(),
+                last_patches: Default::default(),
+            }))
+        });
+
+        let messages = tick(engine);
+        let id_chain = match &messages[..] {
+            [NativeMessage::TextboxCreate(create), NativeMessage::TextboxUpdate(patch)] => {
+                assert_eq!(patch.id_chain, create.id_chain);
+                assert_eq!(patch.text, Some("".to_string()));
+                create.id_chain.clone()
+            }
+            _ => panic!("expected a create and an update"),
+        };
+
+        properties
+            .borrow_mut()
+            .placeholder
+            .set(StringBox::from("Name"));
+        match &tick(engine)[..] {
+            [NativeMessage::TextboxUpdate(patch)] => {
+                assert_eq!(patch.placeholder, Some("Name".to_string()));
+                assert_eq!(patch.text, None);
+            }
+            _ => panic!("expected an update"),
+        }
+
+        let node = engine.get_repeat_expanded_node(&id_chain).unwrap();
+        node.dispatch_textbox_change(ArgsTextboxChange {
+            text: "Ada".to_string(),
+        });
+        assert_eq!(properties.borrow().text.get().string, "Ada");
+        assert!(tick(engine).is_empty());
+
+        clear_template(engine);
+        match &tick(engine)[..] {
+            [NativeMessage::TextboxDelete(deleted)] => assert_eq!(deleted, &id_chain),
+            _ => panic!("expected a delete"),
+        }
+    }
+}
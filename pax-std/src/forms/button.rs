@@ -0,0 +1,11 @@
+use pax_lang::*;
+use pax_runtime_api::StringBox;
+
+/// A native push button, which fires `button_click` when activated
+#[derive(Pax)]
+#[custom(Imports)]
+#[primitive("pax_std_primitives::button::ButtonInstance")]
+pub struct Button {
+    pub label: Property<StringBox>,
+    pub disabled: Property<bool>,
+}
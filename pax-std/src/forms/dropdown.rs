@@ -0,0 +1,12 @@
+use pax_lang::*;
+use pax_runtime_api::StringBox;
+
+/// A native select box, which fires `dropdown_change` with the index of the picked option
+#[derive(Pax)]
+#[custom(Imports)]
+#[primitive("pax_std_primitives::dropdown::DropdownInstance")]
+pub struct Dropdown {
+    pub options: Property<Vec<StringBox>>,
+    pub selected_index: Property<usize>,
+    pub disabled: Property<bool>,
+}
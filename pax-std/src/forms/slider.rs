@@ -0,0 +1,26 @@
+use pax_lang::api::PropertyLiteral;
+use pax_lang::*;
+
+/// A native range input, which fires `slider_change` as its handle is dragged between `min` and `max`
+#[derive(Pax)]
+#[custom(Imports, Default)]
+#[primitive("pax_std_primitives::slider::SliderInstance")]
+pub struct Slider {
+    pub value: Property<f64>,
+    pub min: Property<f64>,
+    pub max: Property<f64>,
+    pub step: Property<f64>,
+    pub disabled: Property<bool>,
+}
+
+impl Default for Slider {
+    fn default() -> Self {
+        Self {
+            value: Box::new(PropertyLiteral::new(0.0)),
+            min: Box::new(PropertyLiteral::new(0.0)),
+            max: Box::new(PropertyLiteral::new(100.0)),
+            step: Box::new(PropertyLiteral::new(1.0)),
+            disabled: Default::default(),
+        }
+    }
+}
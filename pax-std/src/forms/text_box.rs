@@ -0,0 +1,12 @@
+use pax_lang::*;
+use pax_runtime_api::StringBox;
+
+/// A native single-line text input, which fires `textbox_change` as it is edited
+#[derive(Pax)]
+#[custom(Imports)]
+#[primitive("pax_std_primitives::text_box::TextBoxInstance")]
+pub struct TextBox {
+    pub text: Property<StringBox>,
+    pub placeholder: Property<StringBox>,
+    pub disabled: Property<bool>,
+}
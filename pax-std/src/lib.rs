@@ -7,11 +7,25 @@ pub mod components {
     pub use super::stacker::*;
 }
 
+/// Native form controls, rendered by each chassis with its platform's own inputs
+pub mod forms {
+    pub mod button;
+    pub mod dropdown;
+    pub mod slider;
+    pub mod text_box;
+
+    pub use button::Button;
+    pub use dropdown::Dropdown;
+    pub use slider::Slider;
+    pub use text_box::TextBox;
+}
+
 pub mod primitives {
     use pax_lang::Pax;
     use pax_runtime_api::Size;
     use pax_runtime_api::StringBox;
 
+    use crate::types::effects::Effect;
     use crate::types::text::TextStyle;
     use crate::types::PathSegment;
 
@@ -19,6 +33,15 @@ pub mod primitives {
     #[primitive("pax_std_primitives::frame::FrameInstance")]
     pub struct Frame {}
 
+    /// Like [`Frame`], except that it clips its children to an arbitrary `shape`, both when rendering
+    /// and when hit testing.  Clipping applies only to canvas content, not to native elements like `Text`.
+    #[derive(Pax)]
+    #[custom(Imports)]
+    #[primitive("pax_std_primitives::clip::ClipInstance")]
+    pub struct Clip {
+        pub shape: pax_lang::Property<crate::types::ClipShape>,
+    }
+
     #[derive(Pax)]
     #[custom(Imports)]
     #[primitive("pax_std_primitives::group::GroupInstance")]
@@ -41,6 +64,7 @@ pub mod primitives {
         pub stroke: pax_lang::Property<crate::types::Stroke>,
         pub fill: pax_lang::Property<crate::types::Fill>,
         pub corner_radii: pax_lang::Property<crate::types::RectangleCornerRadii>,
+        pub effects: pax_lang::Property<Vec<Effect>>,
     }
 
     #[derive(Pax)]
@@ -49,6 +73,7 @@ pub mod primitives {
     pub struct Ellipse {
         pub stroke: pax_lang::Property<crate::types::Stroke>,
         pub fill: pax_lang::Property<crate::types::Color>,
+        pub effects: pax_lang::Property<Vec<Effect>>,
     }
 
     #[derive(Pax)]
@@ -58,6 +83,8 @@ pub mod primitives {
         pub segments: pax_lang::Property<Vec<PathSegment>>,
         pub stroke: pax_lang::Property<crate::types::Stroke>,
         pub fill: pax_lang::Property<crate::types::Color>,
+        pub fill_rule: pax_lang::Property<crate::types::FillRule>,
+        pub effects: pax_lang::Property<Vec<Effect>>,
     }
 
     #[derive(Pax)]
@@ -138,8 +138,11 @@ impl Stacker {
                 used_space += cell_space[i];
                 ret
             })
-            .collect();
+            .collect::<Vec<StackerCell>>();
 
-        self._cell_specs.set(new_cell_specs);
+        //Only set when changed, so that an otherwise-idle engine isn't kept awake
+        if *self._cell_specs.get() != new_cell_specs {
+            self._cell_specs.set(new_cell_specs);
+        }
     }
 }
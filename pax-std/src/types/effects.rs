@@ -0,0 +1,334 @@
+use crate::types::{Color, FillRule};
+use kurbo::{Affine, BezPath, Shape};
+use pax_lang::api::{Interpolatable, Numeric};
+use pax_lang::*;
+use piet::RenderContext;
+
+/// How many strokes are layered to fade out a blurred edge, since piet offers no general-purpose blur
+const BLUR_STEPS: usize = 8;
+
+/// A visual effect applied to a shape, for example `effects=[Effect::drop_shadow(0, 4, 8, 0, Color::rgba(0, 0, 0, 0.25))]`.
+/// Drop shadows are drawn beneath the shape, inner shadows atop its fill, and blurs soften the fill itself.
+#[derive(Pax)]
+#[custom(Default, Imports, Interpolatable)]
+pub enum Effect {
+    DropShadow(Shadow),
+    InnerShadow(Shadow),
+    /// Softens the edge of a shape's (solid) fill over the given radius, in pixels
+    GaussianBlur(f64),
+}
+
+/// Describes a shadow after CSS's `box-shadow`: offset, blurred and spread, all in pixels
+#[derive(Pax)]
+#[custom(Imports, Interpolatable)]
+pub struct Shadow {
+    pub offset_x: f64,
+    pub offset_y: f64,
+    /// The distance over which the shadow's edge fades out
+    pub blur_radius: f64,
+    /// Grows a drop shadow beyond the shape's outline, or an inner shadow further into the shape
+    pub spread: f64,
+    pub color: Color,
+}
+
+impl Default for Effect {
+    fn default() -> Self {
+        Self::GaussianBlur(0.0)
+    }
+}
+
+impl Interpolatable for Shadow {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        Self {
+            offset_x: self.offset_x.interpolate(&other.offset_x, t),
+            offset_y: self.offset_y.interpolate(&other.offset_y, t),
+            blur_radius: self.blur_radius.interpolate(&other.blur_radius, t),
+            spread: self.spread.interpolate(&other.spread, t),
+            color: self.color.interpolate(&other.color, t),
+        }
+    }
+}
+
+impl Interpolatable for Effect {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        match (self, other) {
+            (Self::DropShadow(s), Self::DropShadow(o)) => Self::DropShadow(s.interpolate(o, t)),
+            (Self::InnerShadow(s), Self::InnerShadow(o)) => Self::InnerShadow(s.interpolate(o, t)),
+            (Self::GaussianBlur(s), Self::GaussianBlur(o)) => {
+                Self::GaussianBlur(s.interpolate(o, t))
+            }
+            //different kinds of effect can't be tweened, so hold the first
+            _ => self.clone(),
+        }
+    }
+}
+
+impl Effect {
+    pub fn drop_shadow(
+        offset_x: Numeric,
+        offset_y: Numeric,
+        blur_radius: Numeric,
+        spread: Numeric,
+        color: Color,
+    ) -> Self {
+        Self::DropShadow(Shadow::new(offset_x, offset_y, blur_radius, spread, color))
+    }
+
+    pub fn inner_shadow(
+        offset_x: Numeric,
+        offset_y: Numeric,
+        blur_radius: Numeric,
+        spread: Numeric,
+        color: Color,
+    ) -> Self {
+        Self::InnerShadow(Shadow::new(offset_x, offset_y, blur_radius, spread, color))
+    }
+
+    pub fn blur(radius: Numeric) -> Self {
+        Self::GaussianBlur(radius.get_as_float())
+    }
+
+    /// The total radius of the `GaussianBlur`s among `effects`, with which to fill the shape itself
+    pub fn get_blur_radius(effects: &[Effect]) -> f64 {
+        effects
+            .iter()
+            .map(|effect| match effect {
+                Effect::GaussianBlur(radius) => radius.max(0.0),
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    /// Draws the drop shadows among `effects`, cast by `path` (in the shape's own coordinates) as transformed by `transform`
+    /// and filled per `fill_rule`.  Call before filling the shape.
+    pub fn render_drop_shadows<R: RenderContext>(
+        effects: &[Effect],
+        rc: &mut R,
+        path: &BezPath,
+        transform: Affine,
+        fill_rule: &FillRule,
+    ) {
+        for effect in effects {
+            if let Effect::DropShadow(shadow) = effect {
+                let caster = transform * shadow.offset(path);
+                fill_feathered(
+                    rc,
+                    &caster,
+                    &shadow.color.to_piet_color(),
+                    shadow.blur_radius,
+                    shadow.spread,
+                    matches!(fill_rule, FillRule::EvenOdd),
+                );
+            }
+        }
+    }
+
+    /// Draws the inner shadows among `effects` within `path` (in the shape's own coordinates) as transformed by `transform`.
+    /// Call after filling the shape.
+    pub fn render_inner_shadows<R: RenderContext>(
+        effects: &[Effect],
+        rc: &mut R,
+        path: &BezPath,
+        transform: Affine,
+    ) {
+        for effect in effects {
+            if let Effect::InnerShadow(shadow) = effect {
+                let shape = transform * path.clone();
+                let offset_shape = transform * shadow.offset(path);
+
+                //an inner shadow is cast by everything outside the offset shape, seen through the shape itself.
+                //The margin keeps the outer edge of that region (and its feathering) clear of the shape.
+                let margin = shadow.blur_radius.max(0.0) + shadow.spread.max(0.0) + 1.0;
+                let mut caster = shape
+                    .bounding_box()
+                    .union(offset_shape.bounding_box())
+                    .inflate(margin, margin)
+                    .to_path(0.1);
+                caster.extend(offset_shape);
+
+                rc.save().unwrap();
+                rc.clip(shape);
+                fill_feathered(
+                    rc,
+                    &caster,
+                    &shadow.color.to_piet_color(),
+                    shadow.blur_radius,
+                    shadow.spread,
+                    true,
+                );
+                rc.restore().unwrap();
+            }
+        }
+    }
+
+    /// Fills `path` with `color` per `fill_rule`, fading its edge out over `blur_radius` (see `get_blur_radius`)
+    pub fn fill_blurred<R: RenderContext>(
+        rc: &mut R,
+        path: &BezPath,
+        color: &piet::Color,
+        blur_radius: f64,
+        fill_rule: &FillRule,
+    ) {
+        fill_feathered(
+            rc,
+            path,
+            color,
+            blur_radius,
+            0.0,
+            matches!(fill_rule, FillRule::EvenOdd),
+        );
+    }
+}
+
+impl Shadow {
+    pub fn new(
+        offset_x: Numeric,
+        offset_y: Numeric,
+        blur_radius: Numeric,
+        spread: Numeric,
+        color: Color,
+    ) -> Self {
+        Self {
+            offset_x: offset_x.get_as_float(),
+            offset_y: offset_y.get_as_float(),
+            blur_radius: blur_radius.get_as_float(),
+            spread: spread.get_as_float(),
+            color,
+        }
+    }
+
+    fn offset(&self, path: &BezPath) -> BezPath {
+        Affine::translate((self.offset_x, self.offset_y)) * path.clone()
+    }
+}
+
+/// Fills `path`, grown by `spread`, with its edge fading out over `blur_radius`.  The fade is approximated by layering
+/// translucent strokes of decreasing width around the path, which darkens the inside of the edge slightly
+/// for translucent colors.
+fn fill_feathered<R: RenderContext>(
+    rc: &mut R,
+    path: &BezPath,
+    color: &piet::Color,
+    blur_radius: f64,
+    spread: f64,
+    even_odd: bool,
+) {
+    if even_odd {
+        rc.fill_even_odd(path, color);
+    } else {
+        rc.fill(path, color);
+    }
+    if spread > f64::EPSILON {
+        rc.stroke(path, color, spread * 2.0);
+    }
+    if blur_radius > f64::EPSILON {
+        let (r, g, b, a) = color.as_rgba();
+        let layer_color = piet::Color::rgba(r, g, b, a / BLUR_STEPS as f64);
+        for step in 1..=BLUR_STEPS {
+            let extent = spread.max(0.0) + blur_radius * step as f64 / BLUR_STEPS as f64;
+            rc.stroke(path, &layer_color, extent * 2.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shadow_of(effect: &Effect) -> &Shadow {
+        match effect {
+            Effect::DropShadow(shadow) | Effect::InnerShadow(shadow) => shadow,
+            Effect::GaussianBlur(_) => panic!("expected a shadow"),
+        }
+    }
+
+    #[test]
+    fn test_constructors_take_numerics() {
+        let shadow = Effect::drop_shadow(
+            1.into(),
+            2.5.into(),
+            3.into(),
+            4.into(),
+            Color::rgba(0.into(), 0.into(), 0.into(), 0.5.into()),
+        );
+        let shadow = shadow_of(&shadow);
+        assert_eq!(
+            (
+                shadow.offset_x,
+                shadow.offset_y,
+                shadow.blur_radius,
+                shadow.spread
+            ),
+            (1.0, 2.5, 3.0, 4.0)
+        );
+        assert!(matches!(
+            Effect::inner_shadow(0.into(), 0.into(), 0.into(), 0.into(), Color::default()),
+            Effect::InnerShadow(_)
+        ));
+        assert!(matches!(Effect::blur(2.into()), Effect::GaussianBlur(radius) if radius == 2.0));
+    }
+
+    #[test]
+    fn test_blur_radius_sums_blurs_and_ignores_shadows() {
+        let effects = vec![
+            Effect::blur(2.into()),
+            Effect::drop_shadow(0.into(), 0.into(), 8.into(), 0.into(), Color::default()),
+            Effect::blur(3.into()),
+            //negative radii don't sharpen
+            Effect::blur((-4).into()),
+        ];
+        assert_eq!(Effect::get_blur_radius(&effects), 5.0);
+        assert_eq!(Effect::get_blur_radius(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_effects_of_the_same_kind_interpolate() {
+        let from = Effect::drop_shadow(
+            0.into(),
+            0.into(),
+            0.into(),
+            0.into(),
+            Color::rgba(0.into(), 0.into(), 0.into(), 0.into()),
+        );
+        let to = Effect::drop_shadow(
+            10.into(),
+            20.into(),
+            4.into(),
+            2.into(),
+            Color::rgba(1.into(), 1.into(), 1.into(), 1.into()),
+        );
+        let halfway = from.interpolate(&to, 0.5);
+        let shadow = shadow_of(&halfway);
+        assert!(matches!(halfway, Effect::DropShadow(_)));
+        assert_eq!(
+            (
+                shadow.offset_x,
+                shadow.offset_y,
+                shadow.blur_radius,
+                shadow.spread
+            ),
+            (5.0, 10.0, 2.0, 1.0)
+        );
+        let (r, g, b, a) = shadow.color.to_piet_color().as_rgba();
+        for channel in [r, g, b, a] {
+            assert!((channel - 0.5).abs() < 0.01);
+        }
+
+        let blurred = Effect::blur(0.into()).interpolate(&Effect::blur(8.into()), 0.25);
+        assert!(matches!(blurred, Effect::GaussianBlur(radius) if radius == 2.0));
+    }
+
+    #[test]
+    fn test_effects_of_different_kinds_hold_the_first() {
+        let blur = Effect::blur(4.into());
+        let shadow = Effect::inner_shadow(1.into(), 1.into(), 1.into(), 1.into(), Color::default());
+        assert!(matches!(
+            blur.interpolate(&shadow, 0.9),
+            Effect::GaussianBlur(radius) if radius == 4.0
+        ));
+        assert!(matches!(
+            shadow.interpolate(&blur, 0.9),
+            Effect::InnerShadow(_)
+        ));
+    }
+}
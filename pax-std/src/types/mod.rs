@@ -1,10 +1,13 @@
+pub mod effects;
 pub mod text;
 
 use crate::primitives::Path;
-use kurbo::{Point, RoundedRectRadii};
+use kurbo::{
+    Arc, BezPath, Ellipse, PathEl, Point, Rect, RoundedRect, RoundedRectRadii, Shape, SvgArc, Vec2,
+};
 use pax_lang::api::numeric::Numeric;
 pub use pax_lang::api::Size;
-use pax_lang::api::{PropertyLiteral, SizePixels};
+use pax_lang::api::{Interpolatable, PropertyLiteral, SizePixels, StringBox};
 use pax_lang::*;
 use pax_message::ColorVariantMessage;
 use piet::UnitPoint;
@@ -25,7 +28,7 @@ impl Default for Stroke {
     }
 }
 
-#[derive(Pax)]
+#[derive(Pax, PartialEq)]
 #[custom(Imports)]
 pub struct StackerCell {
     pub x_px: f64,
@@ -142,7 +145,7 @@ impl Fill {
 }
 
 #[derive(Pax)]
-#[custom(Default, Imports)]
+#[custom(Default, Imports, Interpolatable)]
 pub struct Color {
     pub color_variant: ColorVariant,
 }
@@ -202,6 +205,22 @@ impl Default for Color {
         }
     }
 }
+//colors are tweened channel-wise in RGBA, whatever their variants
+impl Interpolatable for Color {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        let (r0, g0, b0, a0) = self.to_piet_color().as_rgba();
+        let (r1, g1, b1, a1) = other.to_piet_color().as_rgba();
+        Self {
+            color_variant: ColorVariant::Rgba([
+                r0.interpolate(&r1, t),
+                g0.interpolate(&g1, t),
+                b0.interpolate(&b1, t),
+                a0.interpolate(&a1, t),
+            ]),
+        }
+    }
+}
+
 impl Into<ColorVariantMessage> for &Color {
     fn into(self) -> ColorVariantMessage {
         match self.color_variant {
@@ -259,6 +278,9 @@ impl Default for ColorVariant {
     }
 }
 
+/// One step in drawing a `Path`.  `MoveTo` begins a subpath, from whose end each of `LineTo`, `QuadTo`, `CubicTo` and
+/// `ArcTo` continues, until `Close` joins it back to its start.  `LineSegment` and `CurveSegment` carry their own
+/// start points, continuing the current subpath only when they start where it ends.
 #[derive(Pax)]
 #[custom(Imports)]
 pub enum PathSegment {
@@ -266,6 +288,12 @@ pub enum PathSegment {
     Empty,
     LineSegment(LineSegmentData),
     CurveSegment(CurveSegmentData),
+    MoveTo(Point),
+    LineTo(Point),
+    QuadTo(QuadToData),
+    CubicTo(CubicToData),
+    ArcTo(ArcToData),
+    Close,
 }
 
 #[derive(Pax)]
@@ -283,7 +311,156 @@ pub struct CurveSegmentData {
     pub end: Point,
 }
 
+#[derive(Pax)]
+#[custom(Imports)]
+pub struct QuadToData {
+    pub handle: Point,
+    pub end: Point,
+}
+
+#[derive(Pax)]
+#[custom(Imports)]
+pub struct CubicToData {
+    pub handle_1: Point,
+    pub handle_2: Point,
+    pub end: Point,
+}
+
+/// An elliptical arc, parameterized as by SVG's `A` command
+#[derive(Pax)]
+#[custom(Imports)]
+pub struct ArcToData {
+    pub radius_x: f64,
+    pub radius_y: f64,
+    /// The rotation of the ellipse's x-axis, in degrees
+    pub x_rotation: f64,
+    /// Whether to take the longer of the two arcs between the current point and `end`
+    pub large_arc: bool,
+    /// Whether the arc turns clockwise (in screen coordinates) from the current point to `end`
+    pub sweep: bool,
+    pub end: Point,
+}
+
+/// How a `Path` decides which areas its subpaths enclose, where they overlap or nest, as SVG's `fill-rule`
+#[derive(Pax)]
+#[custom(Imports)]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
 impl Path {
+    /// Builds the geometry described by `segments`, e.g. for rendering or hit testing
+    pub fn to_bez_path(segments: &[PathSegment]) -> BezPath {
+        let mut bez_path = BezPath::new();
+        //where the open subpath ends, if there is one
+        let mut open_end: Option<Point> = None;
+        let mut subpath_start = Point::ZERO;
+        //where segments without start points of their own start, even after a subpath is closed
+        let mut pen = Point::ZERO;
+
+        for segment in segments {
+            let start = match segment {
+                PathSegment::Empty | PathSegment::MoveTo(_) | PathSegment::Close => None,
+                PathSegment::LineSegment(data) => Some(data.start),
+                PathSegment::CurveSegment(data) => Some(data.start),
+                _ => Some(pen),
+            };
+            if let Some(start) = start {
+                if open_end != Some(start) {
+                    bez_path.move_to(start);
+                    subpath_start = start;
+                }
+            }
+
+            let end = match segment {
+                PathSegment::Empty => continue,
+                PathSegment::LineSegment(data) => {
+                    bez_path.line_to(data.end);
+                    data.end
+                }
+                PathSegment::CurveSegment(data) => {
+                    bez_path.quad_to(data.handle, data.end);
+                    data.end
+                }
+                PathSegment::MoveTo(point) => {
+                    bez_path.move_to(*point);
+                    subpath_start = *point;
+                    *point
+                }
+                PathSegment::LineTo(end) => {
+                    bez_path.line_to(*end);
+                    *end
+                }
+                PathSegment::QuadTo(data) => {
+                    bez_path.quad_to(data.handle, data.end);
+                    data.end
+                }
+                PathSegment::CubicTo(data) => {
+                    bez_path.curve_to(data.handle_1, data.handle_2, data.end);
+                    data.end
+                }
+                PathSegment::ArcTo(data) => {
+                    let svg_arc = SvgArc {
+                        from: pen,
+                        to: data.end,
+                        radii: Vec2::new(data.radius_x, data.radius_y),
+                        x_rotation: data.x_rotation.to_radians(),
+                        large_arc: data.large_arc,
+                        sweep: data.sweep,
+                    };
+                    //as in SVG, an arc without radii (or without length) is a straight line
+                    match Arc::from_svg_arc(&svg_arc) {
+                        Some(arc) => arc.to_cubic_beziers(0.1, |handle_1, handle_2, end| {
+                            bez_path.curve_to(handle_1, handle_2, end)
+                        }),
+                        None => bez_path.line_to(data.end),
+                    }
+                    data.end
+                }
+                PathSegment::Close => {
+                    if open_end.take().is_some() {
+                        bez_path.close_path();
+                    }
+                    pen = subpath_start;
+                    continue;
+                }
+            };
+            open_end = Some(end);
+            pen = end;
+        }
+        bez_path
+    }
+
+    /// Parses SVG path data, like `M0 0 L10 10 Z`, into segments.  Arcs are approximated by cubic curves.
+    /// Invalid path data is logged and yields no segments, since it may come from e.g. user input at runtime.
+    /// Templates' `Path::svg("...")` calls with literal path data are instead parsed by the compiler.
+    pub fn svg(data: StringBox) -> Vec<PathSegment> {
+        let bez_path = match BezPath::from_svg(&data.string) {
+            Ok(bez_path) => bez_path,
+            Err(err) => {
+                pax_lang::log(&format!("Invalid SVG path data `{}`: {}", data.string, err));
+                return vec![];
+            }
+        };
+        bez_path
+            .elements()
+            .iter()
+            .map(|el| match *el {
+                PathEl::MoveTo(point) => PathSegment::MoveTo(point),
+                PathEl::LineTo(end) => PathSegment::LineTo(end),
+                PathEl::QuadTo(handle, end) => PathSegment::QuadTo(QuadToData { handle, end }),
+                PathEl::CurveTo(handle_1, handle_2, end) => PathSegment::CubicTo(CubicToData {
+                    handle_1,
+                    handle_2,
+                    end,
+                }),
+                PathEl::ClosePath => PathSegment::Close,
+            })
+            .collect()
+    }
+
     pub fn start() -> Vec<PathSegment> {
         let start: Vec<PathSegment> = Vec::new();
         start
@@ -317,6 +494,70 @@ impl Path {
         path.push(PathSegment::CurveSegment(curve_seg_data));
         path
     }
+
+    /// Begins a new subpath at `point`
+    pub fn move_to(mut path: Vec<PathSegment>, point: (f64, f64)) -> Vec<PathSegment> {
+        path.push(PathSegment::MoveTo(Point::from(point)));
+        path
+    }
+
+    /// Continues the current subpath with a straight line to `end`
+    pub fn line(mut path: Vec<PathSegment>, end: (f64, f64)) -> Vec<PathSegment> {
+        path.push(PathSegment::LineTo(Point::from(end)));
+        path
+    }
+
+    pub fn quad_to(
+        mut path: Vec<PathSegment>,
+        handle: (f64, f64),
+        end: (f64, f64),
+    ) -> Vec<PathSegment> {
+        path.push(PathSegment::QuadTo(QuadToData {
+            handle: Point::from(handle),
+            end: Point::from(end),
+        }));
+        path
+    }
+
+    pub fn cubic_to(
+        mut path: Vec<PathSegment>,
+        handle_1: (f64, f64),
+        handle_2: (f64, f64),
+        end: (f64, f64),
+    ) -> Vec<PathSegment> {
+        path.push(PathSegment::CubicTo(CubicToData {
+            handle_1: Point::from(handle_1),
+            handle_2: Point::from(handle_2),
+            end: Point::from(end),
+        }));
+        path
+    }
+
+    /// Continues the current subpath with an elliptical arc to `end`; see [`ArcToData`]
+    pub fn arc_to(
+        mut path: Vec<PathSegment>,
+        radii: (f64, f64),
+        x_rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        end: (f64, f64),
+    ) -> Vec<PathSegment> {
+        path.push(PathSegment::ArcTo(ArcToData {
+            radius_x: radii.0,
+            radius_y: radii.1,
+            x_rotation,
+            large_arc,
+            sweep,
+            end: Point::from(end),
+        }));
+        path
+    }
+
+    /// Joins the current subpath back to its start
+    pub fn close(mut path: Vec<PathSegment>) -> Vec<PathSegment> {
+        path.push(PathSegment::Close);
+        path
+    }
 }
 
 #[derive(Pax)]
@@ -354,3 +595,88 @@ impl RectangleCornerRadii {
         }
     }
 }
+
+/// The geometry to which a `Clip` confines its children, sized to the `Clip`'s bounds
+#[derive(Pax)]
+#[custom(Default, Imports)]
+pub enum ClipShape {
+    Rectangle(RectangleCornerRadii),
+    Ellipse,
+    /// Segments in the `Clip`'s own coordinates, as for `Path`
+    Path(Vec<PathSegment>),
+}
+
+impl Default for ClipShape {
+    fn default() -> Self {
+        Self::Rectangle(RectangleCornerRadii::radii(
+            0.0.into(),
+            0.0.into(),
+            0.0.into(),
+            0.0.into(),
+        ))
+    }
+}
+
+impl ClipShape {
+    pub fn rectangle(
+        top_left: Numeric,
+        top_right: Numeric,
+        bottom_right: Numeric,
+        bottom_left: Numeric,
+    ) -> Self {
+        Self::Rectangle(RectangleCornerRadii::radii(
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        ))
+    }
+
+    pub fn ellipse() -> Self {
+        Self::Ellipse
+    }
+
+    pub fn path(segments: Vec<PathSegment>) -> Self {
+        Self::Path(segments)
+    }
+
+    /// Builds this shape's geometry within `bounds`, before transformation
+    pub fn to_bez_path(&self, bounds: (f64, f64)) -> BezPath {
+        let rect = Rect::from_points((0.0, 0.0), bounds);
+        match self {
+            ClipShape::Rectangle(corner_radii) => {
+                RoundedRect::from_rect(rect, corner_radii).to_path(0.1)
+            }
+            ClipShape::Ellipse => Ellipse::from_rect(rect).to_path(0.1),
+            ClipShape::Path(segments) => Path::to_bez_path(segments),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clip_shapes_fill_their_bounds() {
+        let bounds = (100.0, 50.0);
+
+        let square = ClipShape::default().to_bez_path(bounds);
+        assert!(square.contains(Point::new(1.0, 1.0)));
+        assert!(square.contains(Point::new(99.0, 49.0)));
+        assert!(!square.contains(Point::new(101.0, 25.0)));
+
+        let rounded =
+            ClipShape::rectangle(10.into(), 10.into(), 10.into(), 10.into()).to_bez_path(bounds);
+        assert!(rounded.contains(Point::new(50.0, 25.0)));
+        assert!(rounded.contains(Point::new(1.0, 25.0)));
+        assert!(!rounded.contains(Point::new(1.0, 1.0)));
+        assert!(!rounded.contains(Point::new(99.0, 49.0)));
+
+        let ellipse = ClipShape::ellipse().to_bez_path(bounds);
+        assert!(ellipse.contains(Point::new(50.0, 25.0)));
+        assert!(ellipse.contains(Point::new(98.0, 25.0)));
+        assert!(!ellipse.contains(Point::new(5.0, 5.0)));
+        assert!(!ellipse.contains(Point::new(50.0, 51.0)));
+    }
+}